@@ -0,0 +1,806 @@
+// Convert-all batches for the editor.
+// - Prompts for an optional extension filter, then plans an export for
+//   every editable media file in the browser's directory, or for the marked
+//   files when any are marked.
+// - Probes the files on a background thread and lists the planned outputs
+//   and commands for review as they come in, so files can be left out
+//   before anything runs. Planning puts the editor form back untouched.
+// - Snapshots the current export settings as a preset and applies it to
+//   each file in turn, reusing the review's probes.
+// - Starts the next export as each ffmpeg run ends and tracks the totals.
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+use crate::{
+    media::{
+        default_output_name, enforce_output_extension, is_editable_media_file,
+        output_path_without_numbered_suffix, resolve_output_path,
+    },
+    model::TextInput,
+};
+
+use super::{
+    App, editor::LaunchError, files::MediaProbe, keyframes::KeyframeWait, presets::ExportPreset,
+    remaining_seconds,
+};
+
+/// Extension filter being typed before a batch starts.
+pub(super) struct BatchPrompt {
+    input: TextInput,
+    /// Leave out files whose output already exists, so a re-run only
+    /// converts new files.
+    skip_existing: bool,
+}
+
+/// Planned exports waiting for the user to confirm, with the settings they
+/// were planned with.
+pub(super) struct BatchReview {
+    preset: ExportPreset,
+    items: Vec<BatchPlanItem>,
+    cursor: usize,
+    skipped: usize,
+    /// Set once Enter has shown the low free-space warning; the next Enter
+    /// starts the batch anyway.
+    low_space_shown: bool,
+    /// Probes still coming in, by item index.
+    probes: Option<Receiver<(usize, MediaProbe)>>,
+}
+
+/// One file in the review: its planned output and commands, or why the
+/// form can't export it.
+struct BatchPlanItem {
+    path: PathBuf,
+    name: String,
+    /// `None` until the file's probe is in.
+    plan: Option<Result<(PathBuf, Vec<String>), String>>,
+    probe: Option<MediaProbe>,
+    included: bool,
+}
+
+impl BatchPlanItem {
+    fn planned(&self) -> bool {
+        matches!(self.plan, Some(Ok(_)))
+    }
+}
+
+/// A review row for rendering.
+pub struct BatchReviewRow<'a> {
+    pub name: &'a str,
+    pub included: bool,
+    /// Still waiting for ffprobe.
+    pub probing: bool,
+    pub output: Option<&'a Path>,
+    pub commands: &'a [String],
+    pub error: Option<&'a str>,
+}
+
+/// Files still to export with the settings captured when the batch started.
+pub(super) struct EditorBatch {
+    preset: ExportPreset,
+    pending: VecDeque<PathBuf>,
+    /// The review's probes for the pending files.
+    probes: HashMap<PathBuf, MediaProbe>,
+    total: usize,
+    finished: usize,
+    failed: Vec<String>,
+    /// Files left out up front because their output already existed.
+    skipped: usize,
+    current: Option<String>,
+    cancelled: bool,
+    /// Started past the low free-space warning, so exports skip the check.
+    ignore_free_space: bool,
+    /// Why the batch stopped early for lack of free space.
+    low_space: Option<String>,
+    started_at: Instant,
+}
+
+/// How starting one batch file went.
+enum BatchStep {
+    Started,
+    /// Counted as failed; the next file can go.
+    Failed,
+    /// Out of free space, or the batch is gone.
+    Stopped,
+}
+
+/// Batch counters for rendering.
+pub struct BatchProgress<'a> {
+    pub finished: usize,
+    pub total: usize,
+    pub failed: usize,
+    pub current: Option<&'a str>,
+}
+
+impl EditorBatch {
+    /// The queued file that is `path` or lies under it, if any.
+    pub(super) fn queued_under(&self, path: &Path) -> Option<&Path> {
+        self.pending
+            .iter()
+            .map(PathBuf::as_path)
+            .find(|pending| pending.starts_with(path))
+    }
+}
+
+impl App {
+    pub fn start_batch_prompt(&mut self) {
+        if self.editor_batch.is_some() {
+            self.status_message =
+                "A batch is already running. x in the ffmpeg output stops it.".to_string();
+            return;
+        }
+        let mut input = TextInput::new("");
+        input.focus_append();
+        self.batch_prompt = Some(BatchPrompt {
+            input,
+            skip_existing: true,
+        });
+    }
+
+    pub fn has_batch_prompt(&self) -> bool {
+        self.batch_prompt.is_some()
+    }
+
+    pub fn batch_prompt_input(&self) -> Option<&TextInput> {
+        self.batch_prompt.as_ref().map(|prompt| &prompt.input)
+    }
+
+    pub fn batch_prompt_skips_existing(&self) -> bool {
+        self.batch_prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.skip_existing)
+    }
+
+    pub fn toggle_batch_prompt_skip_existing(&mut self) {
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.skip_existing = !prompt.skip_existing;
+        }
+    }
+
+    /// How many files the typed filter would queue and how many of those
+    /// are skipped as already converted.
+    pub fn batch_prompt_match_counts(&self) -> (usize, usize) {
+        self.batch_prompt
+            .as_ref()
+            .map(|prompt| {
+                let (files, skipped) = self.batch_prompt_files(prompt);
+                (files.len(), skipped)
+            })
+            .unwrap_or((0, 0))
+    }
+
+    fn batch_prompt_files(&self, prompt: &BatchPrompt) -> (Vec<PathBuf>, usize) {
+        let skip_existing_for = prompt.skip_existing.then_some(self.output_format);
+        let output_dir = self.editor_defaults.output_dir.as_deref();
+        batch_files(
+            &self.cwd,
+            &self.marked,
+            prompt.input.value(),
+            skip_existing_for,
+            output_dir,
+        )
+    }
+
+    pub fn push_batch_prompt_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.input.insert(ch);
+        }
+    }
+
+    pub fn backspace_batch_prompt(&mut self) {
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.input.backspace();
+        }
+    }
+
+    pub fn move_batch_prompt_cursor_left(&mut self) {
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.input.move_left();
+        }
+    }
+
+    pub fn move_batch_prompt_cursor_right(&mut self) {
+        if let Some(prompt) = self.batch_prompt.as_mut() {
+            prompt.input.move_right();
+        }
+    }
+
+    pub fn cancel_batch_prompt(&mut self) {
+        self.batch_prompt = None;
+    }
+
+    /// Plans an export for every matching file and opens the review.
+    pub fn confirm_batch_prompt(&mut self) {
+        let Some(prompt) = self.batch_prompt.take() else {
+            return;
+        };
+        let (files, skipped) = self.batch_prompt_files(&prompt);
+        if files.is_empty() {
+            self.status_message = if skipped > 0 {
+                format!("All {skipped} matching file(s) are already converted.")
+            } else {
+                if self.marked.is_empty() {
+                    "No matching media files in this directory.".to_string()
+                } else {
+                    "No matching media files among the marked ones.".to_string()
+                }
+            };
+            return;
+        }
+
+        self.commit_time_offset();
+        let preset = self.current_export_preset("batch".to_string());
+        let items = files
+            .iter()
+            .map(|path| BatchPlanItem {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string()),
+                path: path.clone(),
+                plan: None,
+                probe: None,
+                included: false,
+            })
+            .collect::<Vec<_>>();
+
+        // Replacing the review drops the receiver, which stops this thread
+        // at its next send.
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (index, path) in files.iter().enumerate() {
+                if tx.send((index, MediaProbe::read(path))).is_err() {
+                    break;
+                }
+            }
+        });
+        self.status_message = format!("Probing {} file(s) for the batch.", items.len());
+        self.batch_review = Some(BatchReview {
+            preset,
+            items,
+            cursor: 0,
+            skipped,
+            low_space_shown: false,
+            probes: Some(rx),
+        });
+    }
+
+    /// Plans the review's files as their probes arrive. Returns whether a
+    /// row changed.
+    pub(super) fn pump_batch_review_probes(&mut self) -> bool {
+        let Some(rx) = self
+            .batch_review
+            .as_mut()
+            .and_then(|review| review.probes.take())
+        else {
+            return false;
+        };
+        let mut arrived = false;
+        let mut done = false;
+        loop {
+            match rx.try_recv() {
+                Ok((index, probe)) => {
+                    self.plan_batch_review_item(index, probe);
+                    arrived = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+        let Some(review) = self.batch_review.as_mut() else {
+            return arrived;
+        };
+        if !done {
+            review.probes = Some(rx);
+            return arrived;
+        }
+
+        let planned = review.items.iter().filter(|item| item.planned()).count();
+        let failed = review.items.len() - planned;
+        self.status_message = format!(
+            "Planned {planned} export(s){}. Review them before starting.",
+            if failed > 0 {
+                format!(", {failed} can't export")
+            } else {
+                String::new()
+            }
+        );
+        true
+    }
+
+    /// Builds one file's command from its probe and the batch preset, then
+    /// puts the form back as the user left it.
+    fn plan_batch_review_item(&mut self, index: usize, probe: MediaProbe) {
+        let Some(review) = self.batch_review.as_ref() else {
+            return;
+        };
+        let Some(path) = review.items.get(index).map(|item| item.path.clone()) else {
+            return;
+        };
+        let preset = review.preset.clone();
+
+        let form = self.editor_form();
+        self.load_media(path, probe.clone());
+        self.apply_export_preset(&preset);
+        let plan = self.planned_editor_export();
+        self.restore_editor_form(form);
+
+        if let Some(item) = self
+            .batch_review
+            .as_mut()
+            .and_then(|review| review.items.get_mut(index))
+        {
+            item.included = plan.is_ok();
+            item.plan = Some(plan);
+            item.probe = Some(probe);
+        }
+    }
+
+    pub fn has_batch_review(&self) -> bool {
+        self.batch_review.is_some()
+    }
+
+    /// Planned rows and the highlighted index, for rendering.
+    pub fn batch_review(&self) -> Option<(usize, Vec<BatchReviewRow<'_>>)> {
+        let review = self.batch_review.as_ref()?;
+        let rows = review
+            .items
+            .iter()
+            .map(|item| match &item.plan {
+                Some(Ok((output, commands))) => BatchReviewRow {
+                    name: &item.name,
+                    included: item.included,
+                    probing: false,
+                    output: Some(output.as_path()),
+                    commands: commands.as_slice(),
+                    error: None,
+                },
+                Some(Err(message)) => BatchReviewRow {
+                    name: &item.name,
+                    included: false,
+                    probing: false,
+                    output: None,
+                    commands: &[],
+                    error: Some(message),
+                },
+                None => BatchReviewRow {
+                    name: &item.name,
+                    included: false,
+                    probing: true,
+                    output: None,
+                    commands: &[],
+                    error: None,
+                },
+            })
+            .collect();
+        Some((review.cursor, rows))
+    }
+
+    pub fn select_next_batch_review_item(&mut self) {
+        if let Some(review) = self.batch_review.as_mut()
+            && review.cursor + 1 < review.items.len()
+        {
+            review.cursor += 1;
+        }
+    }
+
+    pub fn select_previous_batch_review_item(&mut self) {
+        if let Some(review) = self.batch_review.as_mut() {
+            review.cursor = review.cursor.saturating_sub(1);
+        }
+    }
+
+    /// Includes or excludes the highlighted file. Files that can't export,
+    /// or aren't probed yet, stay excluded.
+    pub fn toggle_batch_review_item(&mut self) {
+        if let Some(review) = self.batch_review.as_mut()
+            && let Some(item) = review.items.get_mut(review.cursor)
+            && item.planned()
+        {
+            item.included = !item.included;
+        }
+    }
+
+    /// Includes every exportable file, or excludes them all when they
+    /// already are.
+    pub fn toggle_all_batch_review_items(&mut self) {
+        if let Some(review) = self.batch_review.as_mut() {
+            let include = !review
+                .items
+                .iter()
+                .filter(|item| item.planned())
+                .all(|item| item.included);
+            for item in review.items.iter_mut().filter(|item| item.planned()) {
+                item.included = include;
+            }
+        }
+    }
+
+    pub fn cancel_batch_review(&mut self) {
+        if self.batch_review.take().is_some() {
+            self.status_message = "Batch not started.".to_string();
+        }
+    }
+
+    /// Queues the included files and starts the first export.
+    pub fn confirm_batch_review(&mut self) {
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to convert files.".to_string();
+            return;
+        }
+        if let Some(review) = self.batch_review.as_ref()
+            && review.probes.is_some()
+        {
+            let probing = review
+                .items
+                .iter()
+                .filter(|item| item.plan.is_none())
+                .count();
+            self.status_message =
+                format!("Still probing {probing} file(s). Start once the review is complete.");
+            return;
+        }
+        let Some(mut review) = self.batch_review.take() else {
+            return;
+        };
+        let ignore_free_space = review.low_space_shown;
+        if !ignore_free_space && let Some(warning) = self.batch_low_space_warning(&review) {
+            self.status_message =
+                format!("Low disk space: {warning}. Press Enter again to start anyway.");
+            review.low_space_shown = true;
+            self.batch_review = Some(review);
+            return;
+        }
+        let mut probes = HashMap::new();
+        let mut files = VecDeque::new();
+        for item in review.items.into_iter().filter(|item| item.included) {
+            if let Some(probe) = item.probe {
+                probes.insert(item.path.clone(), probe);
+            }
+            files.push_back(item.path);
+        }
+        if files.is_empty() {
+            self.status_message = "No files included. Batch not started.".to_string();
+            return;
+        }
+
+        self.editor_batch = Some(EditorBatch {
+            preset: review.preset,
+            total: files.len(),
+            pending: files,
+            probes,
+            finished: 0,
+            failed: Vec::new(),
+            skipped: review.skipped,
+            current: None,
+            cancelled: false,
+            ignore_free_space,
+            low_space: None,
+            started_at: Instant::now(),
+        });
+        if let Some(summary) = self.start_next_batch_file() {
+            self.status_message = summary;
+        }
+    }
+
+    pub fn editor_batch_progress(&self) -> Option<BatchProgress<'_>> {
+        self.editor_batch.as_ref().map(|batch| BatchProgress {
+            finished: batch.finished,
+            total: batch.total,
+            failed: batch.failed.len(),
+            current: batch.current.as_deref(),
+        })
+    }
+
+    /// Overall percent across the batch, counting the running file's share.
+    pub fn editor_batch_percent(&self) -> Option<u8> {
+        let batch = self.editor_batch.as_ref()?;
+        let current = f64::from(self.editor_progress_percent().unwrap_or(0)) / 100.0;
+        let done = batch.finished as f64
+            + if batch.current.is_some() {
+                current
+            } else {
+                0.0
+            };
+        Some((done / batch.total.max(1) as f64 * 100.0).min(100.0) as u8)
+    }
+
+    /// Seconds until the whole batch is done, at the pace so far.
+    pub fn editor_batch_eta_seconds(&self) -> Option<u64> {
+        let batch = self.editor_batch.as_ref()?;
+        remaining_seconds(batch.started_at, self.editor_batch_percent())
+    }
+
+    pub(super) fn editor_batch_running(&self) -> bool {
+        self.editor_batch.is_some()
+    }
+
+    /// Stops the batch after the running export; called when it is cancelled.
+    pub(super) fn cancel_editor_batch(&mut self) {
+        if let Some(batch) = self.editor_batch.as_mut() {
+            batch.cancelled = true;
+        }
+    }
+
+    /// Records how the running file ended and starts the next one. Returns
+    /// the batch summary once the queue is empty.
+    pub(super) fn advance_editor_batch(&mut self, succeeded: bool) -> Option<String> {
+        let batch = self.editor_batch.as_mut()?;
+        if let Some(name) = batch.current.take() {
+            batch.finished += 1;
+            if !succeeded {
+                batch.failed.push(name);
+            }
+        }
+        if batch.cancelled {
+            batch.pending.clear();
+        }
+        self.start_next_batch_file()
+    }
+
+    /// Loads files off the queue until one starts exporting. Files whose
+    /// export can't be built or started count as failed. Returns the batch
+    /// summary when the queue runs out instead.
+    fn start_next_batch_file(&mut self) -> Option<String> {
+        loop {
+            let batch = self.editor_batch.as_mut()?;
+            let Some(path) = batch.pending.pop_front() else {
+                return self.finish_editor_batch();
+            };
+            let preset = batch.preset.clone();
+            let probe = batch.probes.remove(&path);
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+
+            match probe {
+                Some(probe) => self.load_media(path.clone(), probe),
+                None => self.select_media(path.clone()),
+            }
+            self.apply_export_preset(&preset);
+            if self.keyframe_scan_pending() {
+                self.keyframe_wait = Some(KeyframeWait::Batch {
+                    path,
+                    name,
+                    commands: self.unsnapped_editor_commands(),
+                });
+                return None;
+            }
+            match self.launch_batch_file(path, name) {
+                BatchStep::Started => return None,
+                BatchStep::Failed => {}
+                BatchStep::Stopped => return self.finish_editor_batch(),
+            }
+        }
+    }
+
+    /// Continues the batch once the keyframe scan its file waited for is in.
+    pub(super) fn resume_batch_file(&mut self, path: PathBuf, name: String) {
+        let summary = match self.launch_batch_file(path, name) {
+            BatchStep::Started => None,
+            BatchStep::Failed => self.start_next_batch_file(),
+            BatchStep::Stopped => self.finish_editor_batch(),
+        };
+        if let Some(summary) = summary {
+            self.status_message = summary;
+        }
+    }
+
+    /// Queues `path` again and starts over from loading it, after the form
+    /// was edited while the file waited for its keyframe scan.
+    pub(super) fn restart_batch_file(&mut self, path: PathBuf) {
+        if let Some(batch) = self.editor_batch.as_mut() {
+            batch.pending.push_front(path);
+        }
+        if let Some(summary) = self.start_next_batch_file() {
+            self.status_message = summary;
+        }
+    }
+
+    /// Stops the batch while `path`, its next file, waits to start.
+    pub(super) fn stop_batch_before(&mut self, path: PathBuf) {
+        if let Some(batch) = self.editor_batch.as_mut() {
+            batch.pending.push_front(path);
+            batch.cancelled = true;
+        }
+        if let Some(summary) = self.finish_editor_batch() {
+            self.status_message = summary;
+        }
+    }
+
+    /// Starts the export for the batch file loaded into the form.
+    fn launch_batch_file(&mut self, path: PathBuf, name: String) -> BatchStep {
+        let Some(batch) = self.editor_batch.as_ref() else {
+            return BatchStep::Stopped;
+        };
+        let note = format!(" (batch {}/{})", batch.finished + 1, batch.total);
+        match self.launch_editor_export_now(&note, batch.ignore_free_space) {
+            Ok(()) => {}
+            Err(LaunchError::Build(message)) => {
+                if let Some(batch) = self.editor_batch.as_mut() {
+                    batch.finished += 1;
+                    batch.failed.push(format!("{name} ({message})"));
+                }
+                return BatchStep::Failed;
+            }
+            Err(LaunchError::LowSpace(warning)) => {
+                if let Some(batch) = self.editor_batch.as_mut() {
+                    batch.pending.push_front(path);
+                    batch.low_space = Some(warning);
+                }
+                return BatchStep::Stopped;
+            }
+        }
+        let started = self.running_editor.is_some();
+        let Some(batch) = self.editor_batch.as_mut() else {
+            return BatchStep::Stopped;
+        };
+        if started {
+            batch.current = Some(name);
+            BatchStep::Started
+        } else {
+            batch.finished += 1;
+            batch.failed.push(name);
+            BatchStep::Failed
+        }
+    }
+
+    /// The free-space warning for the folder the first included export
+    /// writes to, before counting any export's size.
+    fn batch_low_space_warning(&self, review: &BatchReview) -> Option<String> {
+        let output = review
+            .items
+            .iter()
+            .filter(|item| item.included)
+            .find_map(|item| item.plan.as_ref()?.as_ref().ok())
+            .map(|(output, _)| output)?;
+        self.low_space_warning(output.parent()?, None)
+    }
+
+    fn finish_editor_batch(&mut self) -> Option<String> {
+        let batch = self.editor_batch.take()?;
+        let succeeded = batch.finished - batch.failed.len();
+        let mut summary = if let Some(warning) = &batch.low_space {
+            format!(
+                "Batch stopped for low disk space after {} of {} file(s) ({warning}): {succeeded} converted, {} not started",
+                batch.finished,
+                batch.total,
+                batch.pending.len()
+            )
+        } else if batch.cancelled {
+            format!(
+                "Batch cancelled after {} of {} file(s): {succeeded} converted",
+                batch.finished, batch.total
+            )
+        } else {
+            format!(
+                "Batch done: {succeeded} of {} file(s) converted",
+                batch.total
+            )
+        };
+        if batch.skipped > 0 {
+            summary.push_str(&format!(", {} skipped (already converted)", batch.skipped));
+        }
+        if !batch.failed.is_empty() {
+            summary.push_str(&format!(", failed: {}", batch.failed.join(", ")));
+        }
+        Some(summary)
+    }
+}
+
+/// Media files a batch would queue, plus how many were left out because an
+/// output already exists. Takes the `marked` files in mark order, or the
+/// files in `dir` sorted by name when nothing is marked. `filter` lists
+/// extensions separated by spaces or commas (`mp4 mkv`, `.mov`); empty
+/// matches every editable media file. `skip_existing_for` is the target
+/// format when converted files should be skipped; outputs are looked for
+/// in `output_dir` when one is configured, otherwise next to each file.
+fn batch_files(
+    dir: &Path,
+    marked: &[PathBuf],
+    filter: &str,
+    skip_existing_for: Option<&str>,
+    output_dir: Option<&Path>,
+) -> (Vec<PathBuf>, usize) {
+    let extensions = filter
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .map(|ext| ext.trim_start_matches('.').to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect::<Vec<_>>();
+    let all_files = if marked.is_empty() {
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            return (Vec::new(), 0);
+        };
+        let mut all_files = dir_files(read_dir);
+        all_files.sort();
+        all_files
+    } else {
+        marked.to_vec()
+    };
+    let existing_outputs = output_dir
+        .filter(|output_dir| !marked.is_empty() || *output_dir != dir)
+        .map(|output_dir| fs::read_dir(output_dir).map(dir_files).unwrap_or_default());
+    // Marked files can come from several folders; each is checked against
+    // its own folder's files.
+    let mut folder_files = HashMap::<PathBuf, Vec<PathBuf>>::new();
+
+    let candidates = all_files.iter().filter(|path| {
+        is_editable_media_file(path)
+            && (extensions.is_empty()
+                || path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+                    .is_some_and(|ext| extensions.contains(&ext)))
+    });
+    let mut files = Vec::new();
+    let mut skipped = 0;
+    for path in candidates {
+        let siblings = match existing_outputs.as_deref() {
+            Some(outputs) => outputs,
+            None if marked.is_empty() => &all_files,
+            None => {
+                let folder = path
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .to_path_buf();
+                folder_files.entry(folder).or_insert_with_key(|folder| {
+                    fs::read_dir(folder).map(dir_files).unwrap_or_default()
+                })
+            }
+        };
+        if let Some(format) = skip_existing_for
+            && has_existing_output(path, format, output_dir, siblings)
+        {
+            skipped += 1;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    (files, skipped)
+}
+
+/// Whether another file in the folder already has the output name this
+/// input would get: the same stem with the target extension, with or
+/// without the `(N)` suffix added for collisions. Earlier outputs match
+/// their source this way, so re-running a batch leaves both alone.
+fn has_existing_output(
+    input: &Path,
+    format: &str,
+    output_dir: Option<&Path>,
+    siblings: &[PathBuf],
+) -> bool {
+    let name = enforce_output_extension(&default_output_name(input), format);
+    let target = match output_dir {
+        Some(output_dir) => output_dir.join(name),
+        None => resolve_output_path(input, &name),
+    };
+    let target = output_path_without_numbered_suffix(&target);
+    siblings
+        .iter()
+        .any(|path| path != input && output_path_without_numbered_suffix(path) == target)
+}
+
+fn dir_files(read_dir: fs::ReadDir) -> Vec<PathBuf> {
+    read_dir
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| entry.path())
+        .collect()
+}
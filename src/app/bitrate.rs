@@ -0,0 +1,120 @@
+// Bitrate-over-time analysis for the editor.
+// - Scans packet sizes with ffprobe on a background thread.
+// - Keeps the per-second series for the selected video so the editor can chart it.
+use std::{
+    io,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::media::probe_bitrate_per_second;
+
+use super::App;
+
+/// Per-second bitrate of one file, in kbit/s.
+pub(crate) struct BitrateGraph {
+    pub(crate) path: PathBuf,
+    pub(crate) kbps_per_second: Vec<u32>,
+}
+
+impl BitrateGraph {
+    /// Highest one-second bitrate and the second it occurs at.
+    pub(crate) fn peak(&self) -> Option<(usize, u32)> {
+        self.kbps_per_second
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by_key(|(_, kbps)| *kbps)
+    }
+
+    pub(crate) fn average_kbps(&self) -> u32 {
+        if self.kbps_per_second.is_empty() {
+            return 0;
+        }
+        let total: u64 = self
+            .kbps_per_second
+            .iter()
+            .map(|kbps| u64::from(*kbps))
+            .sum();
+        (total / self.kbps_per_second.len() as u64) as u32
+    }
+}
+
+pub(super) struct RunningBitrateProbe {
+    path: PathBuf,
+    rx: Receiver<io::Result<Vec<u32>>>,
+}
+
+impl App {
+    /// Starts a packet scan of the editor's video. The result replaces any
+    /// earlier graph once it arrives.
+    pub fn analyze_bitrate_for_selected_video(&mut self) {
+        let Some(path) = self.selected_video.clone() else {
+            self.status_message = "Select a video before analyzing bitrate.".to_string();
+            return;
+        };
+        if self.running_bitrate_probe.is_some() {
+            self.status_message = "Bitrate analysis is already running.".to_string();
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let probe_path = path.clone();
+        thread::spawn(move || {
+            let _ = tx.send(probe_bitrate_per_second(&probe_path));
+        });
+        self.running_bitrate_probe = Some(RunningBitrateProbe { path, rx });
+        self.status_message = "Analyzing bitrate over time...".to_string();
+    }
+
+    pub(super) fn try_finish_bitrate_probe(&mut self) {
+        let Some(running) = &self.running_bitrate_probe else {
+            return;
+        };
+        let result = match running.rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                Err(io::Error::other("bitrate probe thread stopped"))
+            }
+        };
+        let Some(running) = self.running_bitrate_probe.take() else {
+            return;
+        };
+
+        match result {
+            Ok(kbps_per_second) if kbps_per_second.is_empty() => {
+                self.status_message = "Bitrate analysis found no packets.".to_string();
+            }
+            Ok(kbps_per_second) => {
+                let graph = BitrateGraph {
+                    path: running.path,
+                    kbps_per_second,
+                };
+                self.status_message = match graph.peak() {
+                    Some((second, kbps)) => format!(
+                        "Bitrate analysis done: avg {} kbps, peak {kbps} kbps at {second}s.",
+                        graph.average_kbps()
+                    ),
+                    None => "Bitrate analysis done.".to_string(),
+                };
+                self.bitrate_graph = Some(graph);
+            }
+            Err(err) => {
+                self.status_message = format!("Bitrate analysis failed: {err}");
+            }
+        }
+    }
+
+    /// Graph for the video currently loaded in the editor, if analyzed.
+    pub fn bitrate_graph(&self) -> Option<&BitrateGraph> {
+        self.bitrate_graph
+            .as_ref()
+            .filter(|graph| self.selected_video.as_ref() == Some(&graph.path))
+    }
+
+    pub fn bitrate_analysis_running(&self) -> bool {
+        self.running_bitrate_probe.is_some()
+    }
+}
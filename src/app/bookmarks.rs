@@ -0,0 +1,222 @@
+// Bookmarked directories for the file browser.
+// - `F` bookmarks the current directory, or removes it when it already is.
+// - `` ` `` opens a picker of the bookmarks; `g1`-`g9` jump straight to one.
+// - Persists in the `[bookmarks]` table of config.toml as `1 = "path"`,
+//   `2 = ...` in the order they are numbered, so they travel with the W
+//   export. A `bookmarks.txt` left by older versions is moved there once.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    App,
+    config::{Config, save_config_table, toml_string},
+    config_dir,
+    quick_jump::{expand_home_dir, tilde_path},
+};
+
+const BOOKMARKS_TABLE: &str = "bookmarks";
+const LEGACY_BOOKMARKS_FILE_NAME: &str = "bookmarks.txt";
+
+/// Popup state: the highlighted bookmark.
+pub(super) struct BookmarkPicker {
+    cursor: usize,
+}
+
+/// A bookmark row for rendering.
+pub struct BookmarkRow {
+    pub path: String,
+    pub missing: bool,
+}
+
+/// Bookmarks from config.toml, ordered by their numbers. Without any,
+/// moves over the ones in `bookmarks.txt`.
+pub(super) fn load_bookmarks(config: &Config) -> io::Result<Vec<PathBuf>> {
+    let mut numbered: Vec<(u64, PathBuf)> = config
+        .table(BOOKMARKS_TABLE)
+        .filter_map(|(key, value)| Some((key.parse().ok()?, expand_home_dir(&value.as_text()))))
+        .collect();
+    if numbered.is_empty() {
+        return migrate_legacy_bookmarks();
+    }
+    numbered.sort_by_key(|(number, _)| *number);
+    Ok(numbered.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Reads `bookmarks.txt` (one path per line), saves its bookmarks to
+/// config.toml, and removes it. A missing file means none.
+fn migrate_legacy_bookmarks() -> io::Result<Vec<PathBuf>> {
+    let Some(path) = config_dir().map(|dir| dir.join(LEGACY_BOOKMARKS_FILE_NAME)) else {
+        return Ok(Vec::new());
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let bookmarks: Vec<PathBuf> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(PathBuf::from)
+        .collect();
+    save_bookmarks(&bookmarks)?;
+    fs::remove_file(&path)?;
+    Ok(bookmarks)
+}
+
+/// `[bookmarks]` lines for config.toml and the W export.
+pub(super) fn bookmark_lines(bookmarks: &[PathBuf]) -> Vec<String> {
+    bookmarks
+        .iter()
+        .enumerate()
+        .map(|(index, path)| format!("{} = {}", index + 1, toml_string(&path.to_string_lossy())))
+        .collect()
+}
+
+fn save_bookmarks(bookmarks: &[PathBuf]) -> io::Result<()> {
+    save_config_table(BOOKMARKS_TABLE, &bookmark_lines(bookmarks))
+}
+
+impl App {
+    /// Bookmarks the current directory, or removes its bookmark.
+    pub fn toggle_cwd_bookmark(&mut self) {
+        let cwd = self.cwd.clone();
+        let name = tilde_path(&cwd);
+        let message = match self.bookmark_number(&cwd) {
+            Some(number) => {
+                self.bookmarks.remove(number - 1);
+                format!("Removed bookmark {number}: {name}")
+            }
+            None => {
+                self.bookmarks.push(cwd);
+                format!("Bookmarked {name} as {}.", self.bookmarks.len())
+            }
+        };
+        self.status_message = match save_bookmarks(&self.bookmarks) {
+            Ok(()) => message,
+            Err(err) => format!("{message} Failed to save bookmarks: {err}"),
+        };
+    }
+
+    pub fn open_bookmark_picker(&mut self) {
+        let cursor = self
+            .bookmark_number(&self.cwd)
+            .map_or(0, |number| number - 1);
+        self.bookmark_picker = Some(BookmarkPicker { cursor });
+    }
+
+    pub fn has_bookmark_picker(&self) -> bool {
+        self.bookmark_picker.is_some()
+    }
+
+    pub fn cancel_bookmark_picker(&mut self) {
+        self.bookmark_picker = None;
+    }
+
+    /// Bookmarks and the highlighted one, for rendering.
+    pub fn bookmark_picker(&self) -> Option<(usize, Vec<BookmarkRow>)> {
+        let picker = self.bookmark_picker.as_ref()?;
+        let rows = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| BookmarkRow {
+                path: tilde_path(bookmark),
+                missing: !bookmark.is_dir(),
+            })
+            .collect();
+        Some((picker.cursor, rows))
+    }
+
+    pub fn select_next_bookmark(&mut self) {
+        let count = self.bookmarks.len();
+        if let Some(picker) = self.bookmark_picker.as_mut()
+            && picker.cursor + 1 < count
+        {
+            picker.cursor += 1;
+        }
+    }
+
+    pub fn select_previous_bookmark(&mut self) {
+        if let Some(picker) = self.bookmark_picker.as_mut() {
+            picker.cursor = picker.cursor.saturating_sub(1);
+        }
+    }
+
+    /// Moves the highlighted bookmark one place, changing its number.
+    pub fn move_selected_bookmark(&mut self, down: bool) {
+        let Some(picker) = self.bookmark_picker.as_mut() else {
+            return;
+        };
+        let from = picker.cursor;
+        let to = if down { from + 1 } else { from.wrapping_sub(1) };
+        if to >= self.bookmarks.len() {
+            return;
+        }
+        self.bookmarks.swap(from, to);
+        picker.cursor = to;
+        if let Err(err) = save_bookmarks(&self.bookmarks) {
+            self.status_message = format!("Failed to save bookmarks: {err}");
+        }
+    }
+
+    pub fn delete_selected_bookmark(&mut self) {
+        let Some(picker) = self.bookmark_picker.as_mut() else {
+            return;
+        };
+        if picker.cursor >= self.bookmarks.len() {
+            return;
+        }
+        let removed = self.bookmarks.remove(picker.cursor);
+        picker.cursor = picker.cursor.min(self.bookmarks.len().saturating_sub(1));
+        let message = format!("Removed bookmark: {}", tilde_path(&removed));
+        self.status_message = match save_bookmarks(&self.bookmarks) {
+            Ok(()) => message,
+            Err(err) => format!("{message}. Failed to save bookmarks: {err}"),
+        };
+    }
+
+    pub fn open_selected_bookmark(&mut self) -> io::Result<()> {
+        match self.bookmark_picker.as_ref() {
+            Some(picker) => self.open_bookmark(picker.cursor),
+            None => Ok(()),
+        }
+    }
+
+    /// Opens the bookmark shown as `number` (1-based), for the picker's
+    /// digits and `g1`-`g9`.
+    pub fn open_bookmark_number(&mut self, number: usize) -> io::Result<()> {
+        if number == 0 || number > self.bookmarks.len() {
+            self.status_message = format!("No bookmark {number}. ` lists the bookmarks.");
+            return Ok(());
+        }
+        self.open_bookmark(number - 1)
+    }
+
+    fn open_bookmark(&mut self, index: usize) -> io::Result<()> {
+        let Some(target) = self.bookmarks.get(index).cloned() else {
+            return Ok(());
+        };
+        self.bookmark_picker = None;
+        if !target.is_dir() {
+            self.status_message = format!(
+                "Bookmark {} no longer exists: {}",
+                index + 1,
+                target.display()
+            );
+            return Ok(());
+        }
+        self.change_dir(target)?;
+        self.status_message = format!("Opened bookmark {}: {}", index + 1, tilde_path(&self.cwd));
+        Ok(())
+    }
+
+    /// The number `dir` is bookmarked under, if it is.
+    pub fn bookmark_number(&self, dir: &Path) -> Option<usize> {
+        self.bookmarks
+            .iter()
+            .position(|bookmark| bookmark == dir)
+            .map(|index| index + 1)
+    }
+}
@@ -0,0 +1,93 @@
+// Breadcrumb navigation for the file browser.
+// - Splits the current directory into segments shown in the Files title.
+// - `b` picks an ancestor with h/l and jumps to it in one step, selecting
+//   the folder it came from.
+use std::{
+    ffi::OsString,
+    io,
+    path::{Component, Path},
+};
+
+use super::App;
+
+/// Path segments from the root down, e.g. `/`, `home`, `me`, `clips`.
+pub fn breadcrumb_segments(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().into_owned()),
+            Component::RootDir => Some(std::path::MAIN_SEPARATOR.to_string()),
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            Component::CurDir | Component::ParentDir => None,
+        })
+        .collect()
+}
+
+impl App {
+    /// Selects the ancestor `levels_up` directories above the current one,
+    /// capped at the root.
+    pub fn start_breadcrumb(&mut self, levels_up: usize) {
+        if self.showing_stdin_listing {
+            self.status_message = "Leave the stdin listing (-) to use the breadcrumb.".to_string();
+            return;
+        }
+        let max = self.breadcrumb_max_levels();
+        if max == 0 {
+            self.status_message = "Already at the top directory.".to_string();
+            return;
+        }
+        self.breadcrumb = Some(levels_up.clamp(1, max));
+    }
+
+    fn breadcrumb_max_levels(&self) -> usize {
+        self.cwd.ancestors().count().saturating_sub(1)
+    }
+
+    /// The selected ancestor, counted in levels above the current directory.
+    pub fn breadcrumb_levels_up(&self) -> Option<usize> {
+        self.breadcrumb
+    }
+
+    pub fn breadcrumb_up(&mut self) {
+        let max = self.breadcrumb_max_levels();
+        if let Some(levels_up) = self.breadcrumb.as_mut() {
+            *levels_up = (*levels_up + 1).min(max);
+        }
+    }
+
+    pub fn breadcrumb_down(&mut self) {
+        if let Some(levels_up) = self.breadcrumb.as_mut() {
+            *levels_up = levels_up.saturating_sub(1).max(1);
+        }
+    }
+
+    pub fn cancel_breadcrumb(&mut self) {
+        self.breadcrumb = None;
+    }
+
+    /// Opens the selected ancestor with the folder on the way back selected.
+    pub fn confirm_breadcrumb(&mut self) -> io::Result<()> {
+        let Some(levels_up) = self.breadcrumb.take() else {
+            return Ok(());
+        };
+        let Some(target) = self.cwd.ancestors().nth(levels_up).map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        let came_from: Option<OsString> = self
+            .cwd
+            .ancestors()
+            .nth(levels_up - 1)
+            .and_then(Path::file_name)
+            .map(OsString::from);
+        self.change_dir(target.clone())?;
+        if let Some(name) = came_from
+            && let Some(index) = self
+                .entries
+                .iter()
+                .position(|entry| entry.is_dir && entry.path.file_name() == Some(name.as_os_str()))
+        {
+            self.selected = index;
+        }
+        self.status_message = format!("Opened {}", target.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Release notes built into the binary.
+// - `CHANGELOG.md` is embedded at compile time; each `## <version>` heading
+//   starts a release, newest first.
+// - After an upgrade the releases newer than the last version run show once
+//   as "what's new". `last_version` in the config dir remembers the version.
+// - `V` opens the whole history at any time.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{App, config_dir, state_lock::write_state_file};
+
+const CHANGELOG: &str = include_str!("../../CHANGELOG.md");
+const LAST_VERSION_FILE_NAME: &str = "last_version";
+
+/// One `## <version>` section of the changelog.
+pub struct Release {
+    pub version: &'static str,
+    pub notes: Vec<&'static str>,
+}
+
+/// The open popup: every release, or only the new ones after an upgrade.
+pub(super) struct ChangelogScreen {
+    releases: Vec<Release>,
+    whats_new: bool,
+    scroll: usize,
+}
+
+/// The popup for rendering.
+pub struct ChangelogView<'a> {
+    pub releases: &'a [Release],
+    /// Only the releases since the last version run.
+    pub whats_new: bool,
+    pub scroll: usize,
+}
+
+/// The embedded changelog's releases, newest first.
+fn releases() -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    for line in CHANGELOG.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            let version = heading.split_whitespace().next().unwrap_or(heading);
+            releases.push(Release {
+                version,
+                notes: Vec::new(),
+            });
+        } else if let Some(release) = releases.last_mut() {
+            release.notes.push(line);
+        }
+    }
+    // Blank lines around the notes only pad the popup.
+    for release in &mut releases {
+        while release
+            .notes
+            .first()
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            release.notes.remove(0);
+        }
+        while release
+            .notes
+            .last()
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            release.notes.pop();
+        }
+    }
+    releases
+}
+
+/// `1.10.0` sorts after `1.9.2`; parts that aren't numbers count as 0.
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn last_version_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(LAST_VERSION_FILE_NAME))
+}
+
+/// The version rt last started as, `None` before any was recorded.
+fn last_version(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let version = contents.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Records the running version so its notes aren't shown again.
+pub(super) fn record_version() {
+    if let Some(path) = last_version_path() {
+        // Failing only means the notes show again next launch.
+        let _ = write_state_file(&path, &format!("{}\n", env!("CARGO_PKG_VERSION")));
+    }
+}
+
+/// Releases newer than the last version run. Installs from before versions
+/// were recorded get the current release's notes; without a config dir
+/// nothing could be remembered, so nothing is shown.
+pub(super) fn unseen_releases() -> Vec<Release> {
+    let Some(path) = last_version_path() else {
+        return Vec::new();
+    };
+    let current = env!("CARGO_PKG_VERSION");
+    let last = last_version(&path);
+    if last.as_deref() == Some(current) {
+        return Vec::new();
+    }
+    let since = last.map(|version| version_parts(&version));
+    releases()
+        .into_iter()
+        .filter(|release| version_parts(release.version) <= version_parts(current))
+        .filter(|release| match since.as_ref() {
+            Some(since) => version_parts(release.version) > *since,
+            None => release.version == current,
+        })
+        .collect()
+}
+
+impl App {
+    /// Shows the notes for releases since the last version run, once.
+    pub(super) fn open_whats_new(&mut self) {
+        let releases = unseen_releases();
+        record_version();
+        if !releases.is_empty() {
+            self.changelog = Some(ChangelogScreen {
+                releases,
+                whats_new: true,
+                scroll: 0,
+            });
+        }
+    }
+
+    /// Opens the full version history.
+    pub fn open_changelog(&mut self) {
+        self.changelog = Some(ChangelogScreen {
+            releases: releases(),
+            whats_new: false,
+            scroll: 0,
+        });
+    }
+
+    pub fn changelog(&self) -> Option<ChangelogView<'_>> {
+        self.changelog.as_ref().map(|screen| ChangelogView {
+            releases: &screen.releases,
+            whats_new: screen.whats_new,
+            scroll: screen.scroll,
+        })
+    }
+
+    /// Scrolls by one line of notes.
+    pub fn scroll_changelog(&mut self, down: bool) {
+        if let Some(screen) = self.changelog.as_mut() {
+            let lines = screen
+                .releases
+                .iter()
+                .map(|release| release.notes.len() + 2)
+                .sum::<usize>();
+            screen.scroll = if down {
+                (screen.scroll + 1).min(lines.saturating_sub(1))
+            } else {
+                screen.scroll.saturating_sub(1)
+            };
+        }
+    }
+
+    pub fn close_changelog(&mut self) {
+        self.changelog = None;
+    }
+}
@@ -0,0 +1,324 @@
+// Cleanup assistant for old exports.
+// - Prompts for a number of days or a glob: a number finds numbered-suffix
+//   exports (`clip(2).mp4`, ...) older than that many days, a glob finds any
+//   file whose name matches it.
+// - Lists the matches with their sizes and their total, so files can be
+//   left out before the rest go to the trash, where `u` can undo it.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    media::{format_bytes, is_editable_media_file, output_path_without_numbered_suffix},
+    model::TextInput,
+};
+
+use super::{App, trash::in_rt_trash};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Age or glob being typed before the cleanup lists its matches.
+pub(super) struct CleanupPrompt {
+    input: TextInput,
+}
+
+/// Matched files waiting for the user to confirm the delete.
+pub(super) struct CleanupReview {
+    items: Vec<CleanupItem>,
+    cursor: usize,
+}
+
+struct CleanupItem {
+    path: PathBuf,
+    name: String,
+    size: u64,
+    included: bool,
+}
+
+/// A review row for rendering.
+pub struct CleanupReviewRow<'a> {
+    pub name: &'a str,
+    pub size: u64,
+    pub included: bool,
+}
+
+enum CleanupCriteria<'a> {
+    OlderThanDays(u64),
+    Glob(&'a str),
+}
+
+impl<'a> CleanupCriteria<'a> {
+    /// Empty input means numbered-suffix exports of any age.
+    fn parse(input: &'a str) -> Self {
+        let input = input.trim();
+        if input.is_empty() {
+            return Self::OlderThanDays(0);
+        }
+        match input.parse::<u64>() {
+            Ok(days) => Self::OlderThanDays(days),
+            Err(_) => Self::Glob(input),
+        }
+    }
+
+    fn matches(&self, path: &Path, name: &str, metadata: &fs::Metadata, now: SystemTime) -> bool {
+        match self {
+            Self::OlderThanDays(days) => {
+                let age = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .unwrap_or_default();
+                is_numbered_export(path)
+                    && age >= Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY))
+            }
+            Self::Glob(pattern) => glob_matches(pattern, name),
+        }
+    }
+}
+
+/// Media whose name carries the `(N)` suffix added when an export would
+/// have overwritten an existing file.
+fn is_numbered_export(path: &Path) -> bool {
+    is_editable_media_file(path) && output_path_without_numbered_suffix(path) != path
+}
+
+/// `*` matches any run of characters and `?` any one; the rest compares
+/// case-insensitively.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // Where the last `*` was and how much of the name it has taken.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+/// Files in `dir` that meet the criteria, sorted by name.
+fn cleanup_files(dir: &Path, input: &str) -> Vec<CleanupItem> {
+    let criteria = CleanupCriteria::parse(input);
+    let now = SystemTime::now();
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut items = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            criteria
+                .matches(&path, &name, &metadata, now)
+                .then_some(CleanupItem {
+                    path,
+                    name,
+                    size: metadata.len(),
+                    included: true,
+                })
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| a.name.cmp(&b.name));
+    items
+}
+
+impl App {
+    pub fn start_cleanup_prompt(&mut self) {
+        let mut input = TextInput::new("30");
+        input.focus_append();
+        self.cleanup_prompt = Some(CleanupPrompt { input });
+    }
+
+    pub fn has_cleanup_prompt(&self) -> bool {
+        self.cleanup_prompt.is_some()
+    }
+
+    pub fn cleanup_prompt_input(&self) -> Option<&TextInput> {
+        self.cleanup_prompt.as_ref().map(|prompt| &prompt.input)
+    }
+
+    /// How many files the typed criteria match and their total size.
+    pub fn cleanup_prompt_matches(&self) -> (usize, u64) {
+        self.cleanup_prompt
+            .as_ref()
+            .map(|prompt| {
+                let items = cleanup_files(&self.cwd, prompt.input.value());
+                (items.len(), items.iter().map(|item| item.size).sum())
+            })
+            .unwrap_or((0, 0))
+    }
+
+    pub fn push_cleanup_prompt_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(prompt) = self.cleanup_prompt.as_mut() {
+            prompt.input.insert(ch);
+        }
+    }
+
+    pub fn backspace_cleanup_prompt(&mut self) {
+        if let Some(prompt) = self.cleanup_prompt.as_mut() {
+            prompt.input.backspace();
+        }
+    }
+
+    pub fn move_cleanup_prompt_cursor_left(&mut self) {
+        if let Some(prompt) = self.cleanup_prompt.as_mut() {
+            prompt.input.move_left();
+        }
+    }
+
+    pub fn move_cleanup_prompt_cursor_right(&mut self) {
+        if let Some(prompt) = self.cleanup_prompt.as_mut() {
+            prompt.input.move_right();
+        }
+    }
+
+    pub fn cancel_cleanup_prompt(&mut self) {
+        self.cleanup_prompt = None;
+    }
+
+    /// Lists the matching files for review.
+    pub fn confirm_cleanup_prompt(&mut self) {
+        let Some(prompt) = self.cleanup_prompt.take() else {
+            return;
+        };
+        let items = cleanup_files(&self.cwd, prompt.input.value());
+        if items.is_empty() {
+            self.status_message = "No matching files to clean up in this directory.".to_string();
+            return;
+        }
+        self.cleanup_review = Some(CleanupReview { items, cursor: 0 });
+    }
+
+    pub fn has_cleanup_review(&self) -> bool {
+        self.cleanup_review.is_some()
+    }
+
+    /// Matched rows and the highlighted index, for rendering.
+    pub fn cleanup_review(&self) -> Option<(usize, Vec<CleanupReviewRow<'_>>)> {
+        let review = self.cleanup_review.as_ref()?;
+        let rows = review
+            .items
+            .iter()
+            .map(|item| CleanupReviewRow {
+                name: &item.name,
+                size: item.size,
+                included: item.included,
+            })
+            .collect();
+        Some((review.cursor, rows))
+    }
+
+    pub fn select_next_cleanup_review_item(&mut self) {
+        if let Some(review) = self.cleanup_review.as_mut()
+            && review.cursor + 1 < review.items.len()
+        {
+            review.cursor += 1;
+        }
+    }
+
+    pub fn select_previous_cleanup_review_item(&mut self) {
+        if let Some(review) = self.cleanup_review.as_mut() {
+            review.cursor = review.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn toggle_cleanup_review_item(&mut self) {
+        if let Some(review) = self.cleanup_review.as_mut()
+            && let Some(item) = review.items.get_mut(review.cursor)
+        {
+            item.included = !item.included;
+        }
+    }
+
+    /// Includes every file, or excludes them all when they already are.
+    pub fn toggle_all_cleanup_review_items(&mut self) {
+        if let Some(review) = self.cleanup_review.as_mut() {
+            let include = !review.items.iter().all(|item| item.included);
+            for item in &mut review.items {
+                item.included = include;
+            }
+        }
+    }
+
+    pub fn cancel_cleanup_review(&mut self) {
+        if self.cleanup_review.take().is_some() {
+            self.status_message = "Nothing deleted.".to_string();
+        }
+    }
+
+    /// Moves the included files to the trash and refreshes the browser.
+    pub fn confirm_cleanup_review(&mut self) {
+        let Some(review) = self.cleanup_review.take() else {
+            return;
+        };
+        let mut failed = Vec::new();
+        let mut free = Vec::new();
+        for item in review.items.into_iter().filter(|item| item.included) {
+            // A batch or join may have started on the file since it was listed.
+            match self.job_using_path(&item.path) {
+                Some(usage) => failed.push(format!("{}: {usage}", item.name)),
+                None => free.push(item),
+            }
+        }
+        let paths: Vec<PathBuf> = free.iter().map(|item| item.path.clone()).collect();
+        let (trashed, trash_failures) = self.trash_paths(&paths);
+        failed.extend(trash_failures);
+        let mut deleted = 0;
+        let mut bytes = 0;
+        for item in free.iter().filter(|item| trashed.contains(&item.path)) {
+            self.clear_selected_video_if_matches(&item.path);
+            self.marked.retain(|path| *path != item.path);
+            deleted += 1;
+            bytes += item.size;
+        }
+
+        let mut message = if !paths.is_empty() && paths.iter().all(|path| in_rt_trash(path)) {
+            format!(
+                "Deleted {deleted} file(s), {}, for good",
+                format_bytes(bytes)
+            )
+        } else {
+            let mut message = format!(
+                "Moved {deleted} file(s), {}, to trash. u undoes it",
+                format_bytes(bytes)
+            );
+            if let Some(note) = self.rt_trash_note() {
+                message.push_str(&format!(". {note}"));
+            }
+            message
+        };
+        if !failed.is_empty() {
+            message.push_str(&format!(". {} failed: {}", failed.len(), failed.join(", ")));
+        }
+        if let Err(err) = self.reload() {
+            message.push_str(&format!(". Failed to refresh browser: {err}"));
+        }
+        self.status_message = message;
+    }
+}
@@ -0,0 +1,375 @@
+// Concat tab: join several files into one.
+// - Keeps an ordered list of clips added from the browser.
+// - Uses the concat demuxer with stream copy when every clip matches.
+// - Falls back to a re-encode through the concat filter when they don't.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::media::{
+    VideoStats, is_video_file, next_available_output_path, probe_video_stats, probe_video_times,
+    supports_faststart,
+};
+
+use super::{App, FfmpegJob, FfmpegJobKind, ffmpeg::ffmpeg_command_line};
+
+/// Audio bitrate for re-encoded joins, matching the editor's default.
+const CONCAT_AUDIO_BITRATE_KBPS: u32 = 192;
+
+/// One clip in the join list, probed when it was added.
+pub(crate) struct ConcatItem {
+    pub(crate) path: PathBuf,
+    pub(crate) name: String,
+    pub(crate) duration_seconds: f64,
+    pub(crate) stats: Option<VideoStats>,
+}
+
+impl ConcatItem {
+    fn probe(path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let duration_seconds = probe_video_times(&path)
+            .map(|(_, _, bounds)| (bounds.end_seconds - bounds.start_seconds).max(0.0))
+            .unwrap_or(0.0);
+        let stats = probe_video_stats(&path).ok();
+        Self {
+            path,
+            name,
+            duration_seconds,
+            stats,
+        }
+    }
+
+    fn has_audio(&self) -> bool {
+        self.stats
+            .as_ref()
+            .is_some_and(|stats| stats.audio_codec != "n/a")
+    }
+
+    /// Codec and frame parameters that must match for a stream-copy join.
+    fn copy_signature(&self) -> Option<(String, String, String)> {
+        let stats = self.stats.as_ref()?;
+        Some((
+            stats.video_codec.clone(),
+            stats.resolution.clone(),
+            stats.audio_codec.clone(),
+        ))
+    }
+}
+
+/// How the current list would be joined.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConcatMode {
+    StreamCopy,
+    Reencode,
+}
+
+impl App {
+    /// Appends the marked files (or the selected file when nothing is
+    /// marked) to the concat list, skipping ones already on it.
+    pub fn add_to_concat_list(&mut self) {
+        let candidates = if self.marked.is_empty() {
+            match self.selected_entry() {
+                Some(entry) if !entry.is_dir => vec![entry.path.clone()],
+                _ => {
+                    self.status_message = "Select or mark video files to join.".to_string();
+                    return;
+                }
+            }
+        } else {
+            self.marked.clone()
+        };
+
+        let mut added = 0;
+        let mut skipped = 0;
+        for path in candidates {
+            if !is_video_file(&path) || self.concat_items.iter().any(|item| item.path == path) {
+                skipped += 1;
+                continue;
+            }
+            self.concat_items.push(ConcatItem::probe(path));
+            added += 1;
+        }
+
+        self.status_message = format!(
+            "Added {added} file(s) to concat list ({} total){}",
+            self.concat_items.len(),
+            if skipped > 0 {
+                format!(", skipped {skipped} non-video or duplicate")
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    pub fn concat_items(&self) -> &[ConcatItem] {
+        &self.concat_items
+    }
+
+    pub fn concat_cursor(&self) -> usize {
+        self.concat_cursor
+    }
+
+    pub fn select_next_concat_item(&mut self) {
+        if self.concat_cursor + 1 < self.concat_items.len() {
+            self.concat_cursor += 1;
+        }
+    }
+
+    pub fn select_previous_concat_item(&mut self) {
+        self.concat_cursor = self.concat_cursor.saturating_sub(1);
+    }
+
+    pub fn select_first_concat_item(&mut self) {
+        self.concat_cursor = 0;
+    }
+
+    pub fn select_last_concat_item(&mut self) {
+        self.concat_cursor = self.concat_items.len().saturating_sub(1);
+    }
+
+    /// Swaps the selected clip with its neighbour, keeping it selected.
+    pub fn move_concat_item(&mut self, down: bool) {
+        let index = self.concat_cursor;
+        let target = if down {
+            index + 1
+        } else {
+            index.wrapping_sub(1)
+        };
+        if target < self.concat_items.len() && index < self.concat_items.len() {
+            self.concat_items.swap(index, target);
+            self.concat_cursor = target;
+        }
+    }
+
+    pub fn remove_selected_concat_item(&mut self) {
+        if self.concat_cursor >= self.concat_items.len() {
+            return;
+        }
+        let item = self.concat_items.remove(self.concat_cursor);
+        self.concat_cursor = self
+            .concat_cursor
+            .min(self.concat_items.len().saturating_sub(1));
+        self.status_message = format!("Removed {} from concat list.", item.name);
+    }
+
+    pub fn clear_concat_list(&mut self) {
+        self.concat_items.clear();
+        self.concat_cursor = 0;
+        self.status_message = "Cleared concat list.".to_string();
+    }
+
+    /// Stream copy only works when every clip shares codecs and frame size.
+    pub fn concat_mode(&self) -> ConcatMode {
+        let mut signatures = self.concat_items.iter().map(ConcatItem::copy_signature);
+        let Some(Some(first)) = signatures.next() else {
+            return ConcatMode::Reencode;
+        };
+        if signatures.all(|signature| signature.as_ref() == Some(&first)) {
+            ConcatMode::StreamCopy
+        } else {
+            ConcatMode::Reencode
+        }
+    }
+
+    pub fn concat_total_seconds(&self) -> f64 {
+        self.concat_items
+            .iter()
+            .map(|item| item.duration_seconds)
+            .sum()
+    }
+
+    /// Joins the list into `concat.<ext>` in the browser's directory.
+    pub fn run_concat(&mut self) {
+        if self.concat_items.len() < 2 {
+            self.status_message = "Add at least two files to join.".to_string();
+            return;
+        }
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to join files.".to_string();
+            return;
+        }
+
+        let mode = self.concat_mode();
+        let extension = match mode {
+            ConcatMode::StreamCopy => self.concat_items[0]
+                .path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_else(|| "mkv".to_string()),
+            ConcatMode::Reencode => "mp4".to_string(),
+        };
+        let output_path = next_available_output_path(&self.cwd.join(format!("concat.{extension}")));
+
+        let (args, note) = match mode {
+            ConcatMode::StreamCopy => {
+                let list_path = concat_list_path();
+                if let Err(err) = write_concat_list(&list_path, &self.concat_items) {
+                    self.status_message = format!("Failed to write concat list: {err}");
+                    return;
+                }
+                (
+                    concat_copy_args(&list_path, &output_path),
+                    " (concat, stream copy)",
+                )
+            }
+            ConcatMode::Reencode => (
+                concat_reencode_args(&self.concat_items, &output_path),
+                " (concat, re-encode)",
+            ),
+        };
+
+        let job = FfmpegJob {
+            kind: FfmpegJobKind::Concat,
+            command_line: ffmpeg_command_line(&args),
+            input_path: self.concat_items[0].path.clone(),
+            args,
+            output_path,
+            progress_only: self.ffmpeg_progress_only,
+            duration_seconds: self.concat_total_seconds(),
+            follow_up: None,
+            file_time: None,
+        };
+        self.launch_editor_export(job, note);
+        if self.running_editor.is_none() {
+            remove_concat_list();
+        }
+    }
+}
+
+/// Per-process list file so two instances never share one.
+fn concat_list_path() -> PathBuf {
+    env::temp_dir().join(format!("rt-concat-{}.txt", process::id()))
+}
+
+/// Drops the list file once the join is over, whether it finished, failed
+/// or was cancelled. Re-encoded joins never write one, so a missing file is
+/// fine.
+pub(super) fn remove_concat_list() {
+    let _ = fs::remove_file(concat_list_path());
+}
+
+/// Writes the concat demuxer's `file '...'` list. Single quotes inside a
+/// path close the quote, add an escaped quote, and reopen it.
+fn write_concat_list(list_path: &Path, items: &[ConcatItem]) -> io::Result<()> {
+    let mut contents = String::from("ffconcat version 1.0\n");
+    for item in items {
+        let absolute = fs::canonicalize(&item.path).unwrap_or_else(|_| item.path.clone());
+        let quoted = absolute.display().to_string().replace('\'', "'\\''");
+        contents.push_str(&format!("file '{quoted}'\n"));
+    }
+    fs::write(list_path, contents)
+}
+
+fn concat_copy_args(list_path: &Path, output_path: &Path) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "warning".to_string(),
+        "-f".to_string(),
+        "concat".to_string(),
+        "-safe".to_string(),
+        "0".to_string(),
+        "-i".to_string(),
+        list_path.display().to_string(),
+        "-map".to_string(),
+        "0".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+    ];
+    if supports_faststart(output_path) {
+        args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+    }
+    args.push(output_path.display().to_string());
+    args
+}
+
+/// Scales and pads every clip to the first clip's frame size so the
+/// concat filter accepts them. Audio joins only when every clip has some.
+fn concat_reencode_args(items: &[ConcatItem], output_path: &Path) -> Vec<String> {
+    let first_stats = items[0].stats.as_ref();
+    let (width, height) = first_stats
+        .and_then(|stats| stats.width.zip(stats.height))
+        .unwrap_or((1920, 1080));
+    let fps = first_stats
+        .and_then(|stats| stats.fps.parse::<f64>().ok())
+        .filter(|fps| fps.is_finite() && *fps > 0.0);
+    let with_audio = items.iter().all(ConcatItem::has_audio);
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "warning".to_string(),
+    ];
+    for item in items {
+        args.extend(["-i".to_string(), item.path.display().to_string()]);
+    }
+
+    let mut graph = Vec::new();
+    let mut concat_inputs = String::new();
+    for index in 0..items.len() {
+        let mut video = format!(
+            "[{index}:v:0]scale={width}:{height}:force_original_aspect_ratio=decrease,pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1"
+        );
+        if let Some(fps) = fps {
+            video.push_str(&format!(",fps={fps}"));
+        }
+        video.push_str(&format!("[v{index}]"));
+        graph.push(video);
+        concat_inputs.push_str(&format!("[v{index}]"));
+        if with_audio {
+            graph.push(format!(
+                "[{index}:a:0]aresample=48000,aformat=sample_fmts=fltp:channel_layouts=stereo[a{index}]"
+            ));
+            concat_inputs.push_str(&format!("[a{index}]"));
+        }
+    }
+    let audio_streams = u8::from(with_audio);
+    let outputs = if with_audio { "[v][a]" } else { "[v]" };
+    graph.push(format!(
+        "{concat_inputs}concat=n={}:v=1:a={audio_streams}{outputs}",
+        items.len()
+    ));
+
+    args.extend([
+        "-filter_complex".to_string(),
+        graph.join(";"),
+        "-map".to_string(),
+        "[v]".to_string(),
+    ]);
+    if with_audio {
+        args.extend([
+            "-map".to_string(),
+            "[a]".to_string(),
+            "-c:a".to_string(),
+            "aac".to_string(),
+            "-b:a".to_string(),
+            format!("{CONCAT_AUDIO_BITRATE_KBPS}k"),
+        ]);
+    }
+    args.extend([
+        "-c:v".to_string(),
+        "libx264".to_string(),
+        "-preset".to_string(),
+        "veryfast".to_string(),
+        "-crf".to_string(),
+        "20".to_string(),
+        "-pix_fmt".to_string(),
+        "yuv420p".to_string(),
+        "-movflags".to_string(),
+        "+faststart".to_string(),
+        output_path.display().to_string(),
+    ]);
+    args
+}
@@ -0,0 +1,381 @@
+// User configuration from `config.toml` in the config dir.
+// - Reads a small TOML subset: `[table]` headers and `key = value` lines
+//   with strings, numbers, and booleans.
+// - Supplies startup defaults for the editor form, the downloader, and the
+//   event loop; command-line flags override them.
+// - rt only writes back the `[bookmarks]` table, keeping everything else
+//   in the file as it was.
+use std::{fs, io, path::PathBuf, time::Duration};
+
+use crate::{media::VIDEO_CODECS, model::PerfSettings};
+
+use super::{
+    config_dir, downloader_defaults::DownloaderDefaults, editor::parse_output_fps,
+    pane_split::PaneSplit, quick_jump::expand_home_dir, state_lock::update_state_file,
+};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConfigValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl ConfigValue {
+    fn parse(raw: &str) -> Result<Self, String> {
+        if let Some(quoted) = raw.strip_prefix('"') {
+            return parse_basic_string(quoted).map(Self::String);
+        }
+        if let Some(quoted) = raw.strip_prefix('\'') {
+            let (value, rest) = quoted
+                .split_once('\'')
+                .ok_or_else(|| "unterminated string".to_string())?;
+            expect_line_end(rest)?;
+            return Ok(Self::String(value.to_string()));
+        }
+        let value = raw.split('#').next().unwrap_or("").trim();
+        match value {
+            "true" => return Ok(Self::Bool(true)),
+            "false" => return Ok(Self::Bool(false)),
+            _ => {}
+        }
+        let digits = value.replace('_', "");
+        if let Ok(integer) = digits.parse::<i64>() {
+            return Ok(Self::Integer(integer));
+        }
+        if let Ok(float) = digits.parse::<f64>() {
+            return Ok(Self::Float(float));
+        }
+        Err(format!("unsupported value `{value}`"))
+    }
+
+    /// The value as text, so `fps = 30` and `fps = "29.97"` both work.
+    pub(crate) fn as_text(&self) -> String {
+        match self {
+            Self::String(value) => value.clone(),
+            Self::Integer(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+            Self::Bool(value) => value.to_string(),
+        }
+    }
+}
+
+/// A `"..."` string after its opening quote, with the usual escapes.
+fn parse_basic_string(quoted: &str) -> Result<String, String> {
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                expect_line_end(chars.as_str())?;
+                return Ok(value);
+            }
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                other => {
+                    return Err(format!(
+                        "unsupported escape `\\{}`",
+                        other.map(String::from).unwrap_or_default()
+                    ));
+                }
+            },
+            _ => value.push(ch),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+/// A TOML basic string, escaping what the config reader unescapes.
+pub(super) fn toml_string(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn expect_line_end(rest: &str) -> Result<(), String> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected `{rest}` after value"))
+    }
+}
+
+/// Every `key = value` in the file, with the table it appeared under.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Config {
+    entries: Vec<(String, String, ConfigValue)>,
+}
+
+impl Config {
+    /// A missing file is an empty config.
+    pub(super) fn load() -> io::Result<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        Self::parse(&contents).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {message}", path.display()),
+            )
+        })
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        let mut table = String::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line_error = |message: String| format!("line {}: {message}", index + 1);
+            if let Some(header) = line.strip_prefix('[') {
+                let (name, rest) = header
+                    .split_once(']')
+                    .ok_or_else(|| line_error("unterminated table header".to_string()))?;
+                expect_line_end(rest).map_err(line_error)?;
+                table = name.trim().to_string();
+                continue;
+            }
+            let (key, raw) = line
+                .split_once('=')
+                .ok_or_else(|| line_error("expected `key = value`".to_string()))?;
+            let key = key.trim().trim_matches('"').to_string();
+            let value = ConfigValue::parse(raw.trim()).map_err(line_error)?;
+            config
+                .entries
+                .retain(|(t, k, _)| !(*t == table && *k == key));
+            config.entries.push((table.clone(), key, value));
+        }
+        Ok(config)
+    }
+
+    pub(crate) fn value(&self, table: &str, key: &str) -> Option<&ConfigValue> {
+        self.entries
+            .iter()
+            .find(|(t, k, _)| t == table && k == key)
+            .map(|(_, _, value)| value)
+    }
+
+    /// Keys and values of one table, in file order.
+    pub(crate) fn table<'a>(
+        &'a self,
+        table: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a ConfigValue)> {
+        self.entries
+            .iter()
+            .filter(move |(t, _, _)| t == table)
+            .map(|(_, key, value)| (key.as_str(), value))
+    }
+
+    pub(crate) fn string(&self, table: &str, key: &str) -> Option<String> {
+        self.value(table, key).map(ConfigValue::as_text)
+    }
+
+    pub(crate) fn bool(&self, table: &str, key: &str) -> Option<bool> {
+        match self.value(table, key)? {
+            ConfigValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn integer(&self, table: &str, key: &str) -> Option<i64> {
+        match self.value(table, key)? {
+            ConfigValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Integers and floats alike, so `scale = 2` and `scale = 0.5` both work.
+    pub(crate) fn number(&self, table: &str, key: &str) -> Option<f64> {
+        match self.value(table, key)? {
+            ConfigValue::Integer(value) => Some(*value as f64),
+            ConfigValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE_NAME))
+}
+
+/// Rewrites one table of `config.toml` to `lines`, leaving the rest of the
+/// file as the user wrote it. No lines drops the table.
+pub(super) fn save_config_table(table: &str, lines: &[String]) -> io::Result<()> {
+    let Some(path) = config_path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no config directory (set HOME or XDG_CONFIG_HOME)",
+        ));
+    };
+    update_state_file(&path, |current| {
+        replace_table(current.as_deref().unwrap_or(""), table, lines)
+    })
+}
+
+/// `contents` with `table`'s header and keys swapped for `lines`, appended
+/// at the end when the table isn't there yet.
+fn replace_table(contents: &str, table: &str, lines: &[String]) -> String {
+    let section = if lines.is_empty() {
+        Vec::new()
+    } else {
+        let mut section = vec![format!("[{table}]")];
+        section.extend(lines.iter().cloned());
+        section
+    };
+    let header_name = |line: &str| {
+        let header = line.trim().strip_prefix('[')?;
+        Some(header.split_once(']')?.0.trim().to_string())
+    };
+    let mut out: Vec<String> = Vec::new();
+    let mut in_table = false;
+    let mut replaced = false;
+    for line in contents.lines() {
+        if let Some(name) = header_name(line) {
+            in_table = name == table;
+            if in_table {
+                if !replaced {
+                    out.extend(section.iter().cloned());
+                    replaced = true;
+                }
+                continue;
+            }
+        }
+        // The table's own keys go; comments and blank lines in it stay.
+        let trimmed = line.trim();
+        if !in_table || trimmed.is_empty() || trimmed.starts_with('#') {
+            out.push(line.to_string());
+        }
+    }
+    if !replaced && !section.is_empty() {
+        if out.last().is_some_and(|line| !line.trim().is_empty()) {
+            out.push(String::new());
+        }
+        out.extend(section);
+    }
+    let mut text = out.join("\n");
+    text.push('\n');
+    text
+}
+
+impl PaneSplit {
+    /// The `[layout]` table over the split saved in `layout.ini`; unset
+    /// keys keep the saved value.
+    pub(super) fn from_config(config: &Config, saved: Self) -> Self {
+        Self::new(
+            config
+                .integer("layout", "files_percent")
+                .unwrap_or(i64::from(saved.files_percent)),
+            config
+                .integer("layout", "output_percent")
+                .unwrap_or(i64::from(saved.output_percent)),
+        )
+    }
+}
+
+impl PerfSettings {
+    /// The `[perf]` table: `profile` picks the base values and the other
+    /// keys replace single ones. Unknown profiles and zeros are ignored.
+    pub(super) fn from_config(config: &Config) -> Self {
+        let positive = |key: &str| {
+            config
+                .integer("perf", key)
+                .and_then(|value| u64::try_from(value).ok())
+                .filter(|value| *value > 0)
+        };
+        let mut perf = config
+            .string("perf", "profile")
+            .and_then(|name| Self::from_profile(name.trim()))
+            .unwrap_or_default()
+            .with_overrides(
+                positive("poll_ms"),
+                config
+                    .integer("perf", "spinner_ms")
+                    .and_then(|ms| u64::try_from(ms).ok()),
+                positive("pump_batch").and_then(|batch| usize::try_from(batch).ok()),
+            );
+        if let Some(ms) = positive("dir_poll_ms") {
+            perf.dir_poll_interval = Duration::from_millis(ms);
+        }
+        perf
+    }
+}
+
+/// The `[editor]` table, checked and ready for the form.
+#[derive(Debug, Clone, Default)]
+pub(super) struct EditorDefaults {
+    /// Exports go here instead of next to the source.
+    pub(super) output_dir: Option<PathBuf>,
+    pub(super) codec: Option<&'static str>,
+    pub(super) gpu: Option<bool>,
+    /// Replaces the source's frame rate as the FPS default.
+    pub(super) fps: Option<String>,
+    /// Replaces the source's bitrate as the Bitrate default.
+    pub(super) bitrate_kbps: Option<u32>,
+}
+
+impl EditorDefaults {
+    /// Values that don't fit a field are left out.
+    pub(super) fn from_config(config: &Config) -> Self {
+        Self {
+            output_dir: config
+                .string("editor", "output_dir")
+                .filter(|dir| !dir.trim().is_empty())
+                .map(|dir| expand_home_dir(dir.trim())),
+            codec: config.string("editor", "codec").and_then(|codec| {
+                VIDEO_CODECS
+                    .iter()
+                    .copied()
+                    .find(|known| known.eq_ignore_ascii_case(codec.trim()))
+            }),
+            gpu: config.bool("editor", "gpu"),
+            fps: config
+                .string("editor", "fps")
+                .and_then(|fps| parse_output_fps(&fps)),
+            bitrate_kbps: config
+                .integer("editor", "bitrate_kbps")
+                .and_then(|kbps| u32::try_from(kbps).ok())
+                .filter(|kbps| *kbps > 0),
+        }
+    }
+}
+
+impl DownloaderDefaults {
+    /// The `[downloader]` table over the toggles saved in `downloader.ini`;
+    /// unset keys keep the saved value.
+    pub(super) fn from_config(config: &Config, saved: Self) -> Self {
+        Self {
+            audio_only: config
+                .bool("downloader", "audio_only")
+                .unwrap_or(saved.audio_only),
+            sponsorblock: config
+                .bool("downloader", "sponsorblock")
+                .unwrap_or(saved.sponsorblock),
+            subtitles: config
+                .bool("downloader", "subtitles")
+                .unwrap_or(saved.subtitles),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Writes the settings rt is running with to a shareable file.
+// - `config.toml` merged with the defaults, the saved toggles and layout,
+//   and command-line flags, so a teammate can drop it in as their own
+//   `config.toml`.
+// - Every table is written out in full: all theme roles and the keys of
+//   every action, not just the ones that were overridden, and the
+//   bookmarks.
+// - Written to `rt-config.toml` in the current directory.
+use std::fs;
+
+use crate::media::{VIDEO_CODECS, next_available_output_path};
+
+use super::{App, bookmarks::bookmark_lines, config::toml_string, free_space::BYTES_PER_GB};
+
+const EXPORT_FILE_NAME: &str = "rt-config.toml";
+
+impl App {
+    /// The effective configuration as `config.toml` text.
+    fn effective_config(&self) -> String {
+        let defaults = &self.editor_defaults;
+        let mut lines = vec![
+            "# rt settings: config.toml merged with the defaults and saved toggles.".to_string(),
+            "# Copy to ~/.config/rt/config.toml to use them.".to_string(),
+            String::new(),
+            "[editor]".to_string(),
+        ];
+        lines.push(match defaults.output_dir.as_ref() {
+            Some(dir) => format!("output_dir = {}", toml_string(&dir.to_string_lossy())),
+            None => "# output_dir unset: exports go next to the source".to_string(),
+        });
+        let codec = defaults.codec.unwrap_or(VIDEO_CODECS[0]);
+        lines.push(format!("codec = {}", toml_string(codec)));
+        lines.push(format!("gpu = {}", defaults.gpu.unwrap_or(true)));
+        lines.push(match defaults.fps.as_ref() {
+            Some(fps) => format!("fps = {}", toml_string(fps)),
+            None => "# fps unset: the source's frame rate".to_string(),
+        });
+        lines.push(match defaults.bitrate_kbps {
+            Some(kbps) => format!("bitrate_kbps = {kbps}"),
+            None => "# bitrate_kbps unset: the source's bitrate".to_string(),
+        });
+        lines.extend([
+            format!("confirm_minutes = {}", self.export_confirm_minutes),
+            format!("confirm_mb = {}", self.export_confirm_megabytes),
+            format!("after_export = {}", toml_string(self.after_export.name())),
+        ]);
+
+        lines.extend([
+            String::new(),
+            "[downloader]".to_string(),
+            format!("audio_only = {}", self.downloader_audio_only),
+            format!("sponsorblock = {}", self.downloader_sponsorblock),
+            format!("subtitles = {}", self.downloader_subtitles),
+            format!("strip_tracking = {}", self.downloader_strip_tracking),
+            String::new(),
+            "[layout]".to_string(),
+            format!("files_percent = {}", self.pane_split.files_percent),
+            format!("output_percent = {}", self.pane_split.output_percent),
+            String::new(),
+            "[jobs]".to_string(),
+            format!(
+                "min_free_gb = {}",
+                self.min_free_bytes as f64 / BYTES_PER_GB
+            ),
+            String::new(),
+            "[ui]".to_string(),
+            format!("screen_reader = {}", self.screen_reader),
+            format!("auto_refresh = {}", self.auto_refresh),
+            format!("log_page_scale = {}", self.log_page_scale),
+            String::new(),
+            "[perf]".to_string(),
+            format!("poll_ms = {}", self.perf.poll_interval.as_millis()),
+            format!("spinner_ms = {}", self.perf.spinner_interval.as_millis()),
+            format!("pump_batch = {}", self.perf.pump_batch),
+            format!("dir_poll_ms = {}", self.perf.dir_poll_interval.as_millis()),
+            String::new(),
+            "[theme]".to_string(),
+        ]);
+        lines.extend(
+            self.theme
+                .roles()
+                .iter()
+                .map(|(role, color)| format!("{role} = {}", toml_string(&color.to_string()))),
+        );
+
+        lines.extend([String::new(), "[keys]".to_string()]);
+        lines.extend(
+            self.keymap
+                .entries()
+                .map(|(action, keys)| format!("{action} = {}", toml_string(&keys))),
+        );
+
+        lines.extend([String::new(), "[bookmarks]".to_string()]);
+        lines.extend(bookmark_lines(&self.bookmarks));
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// Writes the effective configuration and keymap into the browser's
+    /// directory and selects the file.
+    pub fn export_effective_config(&mut self) {
+        let path = next_available_output_path(&self.cwd.join(EXPORT_FILE_NAME));
+        if let Err(err) = fs::write(&path, self.effective_config()) {
+            self.status_message = format!("Failed to write config export: {err}");
+            return;
+        }
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.status_message = format!("Wrote {name} with the current settings and keys.");
+        if let Err(err) = self.refresh_file_browser_after_save(&path) {
+            self.status_message = format!("Wrote {name}, but failed to refresh browser: {err}");
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Contact sheet generation for the file browser.
+// - Builds an ffmpeg tile-filter job for the selected video.
+// - Writes a timestamped thumbnail grid next to the source file.
+use std::path::{Path, PathBuf};
+
+use crate::media::{is_video_file, next_available_output_path, probe_video_times};
+
+use super::{App, FfmpegJob, FfmpegJobKind, ffmpeg::ffmpeg_command_line};
+
+const CONTACT_SHEET_COLUMNS: u32 = 4;
+const CONTACT_SHEET_ROWS: u32 = 4;
+const CONTACT_SHEET_TILE_WIDTH: u32 = 320;
+
+impl App {
+    /// Runs ffmpeg to write `<name>_sheet.jpg` for the selected video: a grid
+    /// of evenly spaced frames, each stamped with its timestamp.
+    pub fn create_contact_sheet_for_selected(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return;
+        };
+        if entry.is_dir || !is_video_file(&entry.path) {
+            self.status_message = format!("Contact sheets need a video file: {}", entry.name);
+            return;
+        }
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to create contact sheets."
+                    .to_string();
+            return;
+        }
+
+        let duration_seconds = match probe_video_times(&entry.path) {
+            Ok((_, _, bounds)) => (bounds.end_seconds - bounds.start_seconds).max(0.0),
+            Err(err) => {
+                self.status_message = format!("Failed to read duration of {}: {err}", entry.name);
+                return;
+            }
+        };
+        if duration_seconds < 1.0 {
+            self.status_message = format!("{} is too short for a contact sheet.", entry.name);
+            return;
+        }
+
+        let output_path = next_available_output_path(&contact_sheet_path(&entry.path));
+        let args = contact_sheet_args(&entry.path, &output_path, duration_seconds);
+        let job = FfmpegJob {
+            kind: FfmpegJobKind::ContactSheet,
+            command_line: ffmpeg_command_line(&args),
+            input_path: entry.path,
+            args,
+            output_path,
+            progress_only: self.ffmpeg_progress_only,
+            duration_seconds,
+            follow_up: None,
+            file_time: None,
+        };
+        self.launch_editor_export(job, " (contact sheet)");
+    }
+}
+
+fn contact_sheet_path(input_path: &Path) -> PathBuf {
+    let stem = input_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "video".to_string());
+    input_path.with_file_name(format!("{stem}_sheet.jpg"))
+}
+
+/// Only keyframes are decoded, which keeps long videos fast; each tile shows
+/// the keyframe closest to its evenly spaced slot.
+fn contact_sheet_args(input_path: &Path, output_path: &Path, duration_seconds: f64) -> Vec<String> {
+    let tiles = CONTACT_SHEET_COLUMNS * CONTACT_SHEET_ROWS;
+    let filters = [
+        format!("fps={tiles}/{duration_seconds}"),
+        format!("scale={CONTACT_SHEET_TILE_WIDTH}:-2"),
+        "drawtext=text='%{pts\\:hms}':x=6:y=h-th-6:fontsize=16:fontcolor=white:box=1:boxcolor=black@0.6:boxborderw=4".to_string(),
+        format!("tile={CONTACT_SHEET_COLUMNS}x{CONTACT_SHEET_ROWS}:padding=4:margin=4"),
+    ];
+
+    vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "warning".to_string(),
+        "-skip_frame".to_string(),
+        "nokey".to_string(),
+        "-i".to_string(),
+        input_path.display().to_string(),
+        "-an".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-vf".to_string(),
+        filters.join(","),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        "-q:v".to_string(),
+        "3".to_string(),
+        output_path.display().to_string(),
+    ]
+}
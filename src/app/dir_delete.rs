@@ -0,0 +1,196 @@
+// Deleting a folder from the browser.
+// - Counts what the folder holds so the confirmation and the status line
+//   can say how much goes with it. The count runs on a background thread,
+//   so a huge folder or a slow mount doesn't hold up the prompt; it shows
+//   the running totals until the walk is done.
+// - A folder is only deleted after its name (or `yes`) is typed, since one
+//   stray `y` would take everything under it.
+use std::{
+    fs,
+    path::Path,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::{media::format_bytes, model::TextInput};
+
+use super::App;
+
+/// What a folder holds, counted before it is deleted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirContents {
+    pub files: usize,
+    pub dirs: usize,
+    pub bytes: u64,
+}
+
+impl DirContents {
+    /// Walks `dir` without following symlinks. Entries that can't be read
+    /// are left out of the counts. `report` gets the totals so far every
+    /// `REPORT_EVERY` entries and returns false to stop the walk.
+    fn count(dir: &Path, mut report: impl FnMut(&Self) -> bool) -> Self {
+        const REPORT_EVERY: usize = 500;
+        let mut contents = Self::default();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(metadata) = entry.path().symlink_metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    contents.dirs += 1;
+                    pending.push(entry.path());
+                } else {
+                    contents.files += 1;
+                    contents.bytes += metadata.len();
+                }
+                if (contents.files + contents.dirs).is_multiple_of(REPORT_EVERY)
+                    && !report(&contents)
+                {
+                    return contents;
+                }
+            }
+        }
+        contents
+    }
+
+    /// e.g. `12 file(s) in 3 folder(s), 1.20 GB`.
+    pub fn summary(&self) -> String {
+        if self.files == 0 && self.dirs == 0 {
+            return "empty".to_string();
+        }
+        let mut summary = format!("{} file(s)", self.files);
+        if self.dirs > 0 {
+            summary.push_str(&format!(" in {} folder(s)", self.dirs));
+        }
+        summary.push_str(&format!(", {}", format_bytes(self.bytes)));
+        summary
+    }
+}
+
+/// The typed confirmation for deleting a folder.
+pub(super) struct DirDelete {
+    /// The totals so far while `counting`, then the whole folder's.
+    contents: DirContents,
+    /// Running totals from the count's thread; it ends with the final ones.
+    counting: Option<Receiver<DirContents>>,
+    pub(super) input: TextInput,
+}
+
+impl DirDelete {
+    /// Starts counting `dir` in the background.
+    pub(super) fn new(dir: &Path) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let dir = dir.to_path_buf();
+        thread::spawn(move || {
+            // A closed prompt drops the receiver, which ends the walk.
+            let contents = DirContents::count(&dir, |so_far| tx.send(*so_far).is_ok());
+            let _ = tx.send(contents);
+        });
+        Self {
+            contents: DirContents::default(),
+            counting: Some(rx),
+            input: TextInput::new(""),
+        }
+    }
+
+    /// e.g. `12 file(s) in 3 folder(s), 1.20 GB`, or the totals so far
+    /// while counting.
+    pub(super) fn summary(&self) -> String {
+        if self.counting.is_none() {
+            self.contents.summary()
+        } else if self.contents.files == 0 && self.contents.dirs == 0 {
+            "counting...".to_string()
+        } else {
+            format!("counting... {} so far", self.contents.summary())
+        }
+    }
+
+    /// The folder's full count, once the walk is done.
+    pub(super) fn counted_summary(&self) -> Option<String> {
+        self.counting.is_none().then(|| self.contents.summary())
+    }
+
+    /// Takes in the count's latest totals. Returns whether they changed.
+    fn pump_count(&mut self) -> bool {
+        let Some(rx) = self.counting.as_ref() else {
+            return false;
+        };
+        let mut changed = false;
+        loop {
+            match rx.try_recv() {
+                Ok(contents) => {
+                    self.contents = contents;
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.counting = None;
+                    changed = true;
+                    break;
+                }
+            }
+        }
+        changed
+    }
+
+    /// The folder's name or `yes`, ignoring surrounding spaces.
+    pub(super) fn confirmed(&self, name: &str) -> bool {
+        let typed = self.input.value().trim();
+        typed == name || typed.eq_ignore_ascii_case("yes")
+    }
+}
+
+impl App {
+    /// The folder's contents, as far as they are counted, and the typed
+    /// confirmation, while a folder delete waits for one.
+    pub fn pending_dir_delete(&self) -> Option<(String, &TextInput)> {
+        self.pending_delete
+            .as_ref()
+            .and_then(|pending| pending.dir.as_ref())
+            .map(|dir| (dir.summary(), &dir.input))
+    }
+
+    /// Updates the pending folder delete with its count's progress, for
+    /// the tick. Returns whether the prompt needs a redraw.
+    pub(super) fn pump_dir_delete_count(&mut self) -> bool {
+        self.pending_dir_delete_mut()
+            .is_some_and(|dir| dir.pump_count())
+    }
+
+    fn pending_dir_delete_mut(&mut self) -> Option<&mut DirDelete> {
+        self.pending_delete
+            .as_mut()
+            .and_then(|pending| pending.dir.as_mut())
+    }
+
+    pub fn push_dir_delete_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(dir) = self.pending_dir_delete_mut() {
+            dir.input.insert(ch);
+        }
+    }
+
+    pub fn backspace_dir_delete(&mut self) {
+        if let Some(dir) = self.pending_dir_delete_mut() {
+            dir.input.backspace();
+        }
+    }
+
+    pub fn move_dir_delete_cursor_left(&mut self) {
+        if let Some(dir) = self.pending_dir_delete_mut() {
+            dir.input.move_left();
+        }
+    }
+
+    pub fn move_dir_delete_cursor_right(&mut self) {
+        if let Some(dir) = self.pending_dir_delete_mut() {
+            dir.input.move_right();
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// Refreshing the browser when its folder changes on disk.
+// - Polls the folder on the perf profile's clock instead of subscribing to
+//   inotify, so it behaves the same on every platform and network mount.
+// - A change is any name, size, or modified time that differs from the last
+//   listing, so a growing `.part` download updates its size column too.
+// - The folder is read on a background thread, and a changed listing is
+//   shown as read there, so a slow mount never stalls the UI.
+// - Turned off with `[ui] auto_refresh = false`; `r` still reloads.
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+use crate::model::FileEntry;
+
+use super::{App, files::read_entries};
+
+/// A background read of the folder: its entries and their signature.
+type DirRead = io::Result<(Vec<FileEntry>, u64)>;
+
+/// What the folder looked like when it was last listed.
+pub(super) struct DirWatch {
+    dir: PathBuf,
+    signature: u64,
+    last_poll: Instant,
+    pending: Option<Receiver<DirRead>>,
+}
+
+impl DirWatch {
+    pub(super) fn new(dir: &Path, entries: &[FileEntry]) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            signature: entries_signature(entries),
+            last_poll: Instant::now(),
+            pending: None,
+        }
+    }
+
+    /// Records `entries` as the listing of `dir` just shown. A read still
+    /// running is older than it, so its result is dropped.
+    pub(super) fn record(&mut self, dir: &Path, entries: &[FileEntry]) {
+        self.dir = dir.to_path_buf();
+        self.signature = entries_signature(entries);
+        self.pending = None;
+    }
+}
+
+/// A hash of every entry's name, size, and modified time.
+fn entries_signature(entries: &[FileEntry]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for entry in entries {
+        entry.name.hash(&mut hasher);
+        entry.size_bytes.hash(&mut hasher);
+        entry.modified.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl App {
+    /// Starts a background read of the folder once the poll interval is
+    /// up, and shows its listing when it differs from the last one.
+    /// Returns whether the listing changed.
+    pub(super) fn poll_dir_watch(&mut self) -> bool {
+        if !self.auto_refresh || self.showing_stdin_listing {
+            self.dir_watch.pending = None;
+            return false;
+        }
+        if let Some(rx) = self.dir_watch.pending.as_ref() {
+            let read = match rx.try_recv() {
+                Ok(read) => read,
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    self.dir_watch.pending = None;
+                    return false;
+                }
+            };
+            self.dir_watch.pending = None;
+            let Ok((entries, signature)) = read else {
+                return false;
+            };
+            if self.dir_watch.dir != self.cwd || signature == self.dir_watch.signature {
+                return false;
+            }
+            self.show_reloaded_entries(entries);
+            return true;
+        }
+        if self.dir_watch.last_poll.elapsed() < self.perf.dir_poll_interval {
+            return false;
+        }
+        self.dir_watch.last_poll = Instant::now();
+        let dir = self.cwd.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let read = read_entries(&dir).map(|entries| {
+                let signature = entries_signature(&entries);
+                (entries, signature)
+            });
+            let _ = tx.send(read);
+        });
+        self.dir_watch.pending = Some(rx);
+        false
+    }
+}
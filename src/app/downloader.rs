@@ -10,19 +10,26 @@ use std::{
     collections::HashSet,
     io::{self, BufReader, Read},
     path::{Path, PathBuf},
-    process::{Command, ExitStatus, Stdio},
-    sync::mpsc::{self, TryRecvError},
+    process::{Command, ExitStatus, Output, Stdio},
+    sync::{
+        Arc,
+        atomic::{self, AtomicBool},
+        mpsc::{self, TryRecvError},
+    },
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    media::{next_available_output_path, shell_quote},
-    model::DownloaderStep,
+    media::{is_editable_media_file, next_available_output_path, progress_percent, shell_quote},
+    model::{DownloaderStep, PageSize},
 };
 
 use super::{
-    App, DownloaderEvent, DownloaderProbeResult, DownloaderQualityChoice, DownloaderStream,
-    RunningDownloader, RunningDownloaderProbe,
+    App, CachedDownloaderProbe, DownloaderEvent, DownloaderProbeMode, DownloaderProbeResult,
+    DownloaderQualityChoice, DownloaderStream, RunningDownloader, RunningDownloaderProbe,
+    downloader_url::{check_downloader_url, downloader_local_path},
+    job_logs::run_transcript,
 };
 
 const QUALITY_ID_WIDTH: usize = 7;
@@ -32,6 +39,8 @@ const QUALITY_FPS_WIDTH: usize = 6;
 const QUALITY_SIZE_WIDTH: usize = 10;
 const QUALITY_AUD_WIDTH: usize = 5;
 const DOWNLOADER_BASE_OPTION_COUNT: usize = 3;
+/// How often a probe thread checks whether it was cancelled.
+const PROBE_CANCEL_POLL: Duration = Duration::from_millis(50);
 
 impl App {
     pub fn downloader_step(&self) -> DownloaderStep {
@@ -117,6 +126,18 @@ impl App {
         self.running_downloader_probe.is_some()
     }
 
+    /// A re-probe after an option toggle, while the old list stays shown.
+    pub fn downloader_is_refreshing_qualities(&self) -> bool {
+        self.running_downloader_probe
+            .as_ref()
+            .is_some_and(|running| running.refresh)
+    }
+
+    /// Whether the shown list holds audio formats rather than video ones.
+    pub fn downloader_quality_list_is_audio(&self) -> bool {
+        self.downloader_probe_mode.audio_only
+    }
+
     pub fn downloader_accepts_text_input(&self) -> bool {
         self.downloader_step == DownloaderStep::UrlInput && self.running_downloader_probe.is_none()
     }
@@ -177,11 +198,17 @@ impl App {
                     super::input::byte_index_for_char(&self.downloader_url, remove_char_index + 1);
                 self.downloader_url.replace_range(start..end, "");
                 self.downloader_url_cursor -= 1;
+                self.downloader_url_error = None;
             }
             DownloaderStep::QualitySelect => {
+                // Keep the URL so Enter brings the cached list straight back.
+                let url = std::mem::take(&mut self.downloader_url);
                 self.return_to_downloader_url_input();
+                self.downloader_url_cursor = url.chars().count();
+                self.downloader_url = url;
                 self.status_message =
-                    "Returned to URL input. Enter a URL to fetch qualities.".to_string();
+                    "Returned to URL input. Enter reuses the fetched qualities, Ctrl+r probes again."
+                        .to_string();
             }
         }
     }
@@ -195,17 +222,14 @@ impl App {
                 );
                 self.downloader_url.insert(byte_index, ch);
                 self.downloader_url_cursor += 1;
+                self.downloader_url_error = None;
             }
             DownloaderStep::QualitySelect => match ch {
-                'j' => {
-                    if self.downloader_quality_list_focused() {
-                        self.select_next_downloader_quality();
-                    }
+                'j' if self.downloader_quality_list_focused() => {
+                    self.select_next_downloader_quality();
                 }
-                'k' => {
-                    if self.downloader_quality_list_focused() {
-                        self.select_previous_downloader_quality();
-                    }
+                'k' if self.downloader_quality_list_focused() => {
+                    self.select_previous_downloader_quality();
                 }
                 _ => {}
             },
@@ -228,6 +252,22 @@ impl App {
         }
     }
 
+    pub fn select_first_downloader_quality(&mut self) {
+        if self.downloader_step == DownloaderStep::QualitySelect
+            && self.downloader_quality_list_focused()
+        {
+            self.downloader_quality_index = 0;
+        }
+    }
+
+    pub fn select_last_downloader_quality(&mut self) {
+        if self.downloader_step == DownloaderStep::QualitySelect
+            && self.downloader_quality_list_focused()
+        {
+            self.downloader_quality_index = self.downloader_quality_choices.len().saturating_sub(1);
+        }
+    }
+
     pub fn next_downloader_option_focus(&mut self) {
         if self.downloader_step != DownloaderStep::QualitySelect {
             return;
@@ -280,12 +320,20 @@ impl App {
         self.downloader_output.scroll_up();
     }
 
-    pub fn page_downloader_output_down(&mut self) {
-        self.downloader_output.page_down();
+    pub fn page_downloader_output_down(&mut self, size: PageSize) {
+        self.downloader_output.page_down(size, self.log_page_scale);
+    }
+
+    pub fn page_downloader_output_up(&mut self, size: PageSize) {
+        self.downloader_output.page_up(size, self.log_page_scale);
+    }
+
+    pub fn scroll_downloader_output_to_top(&mut self) {
+        self.downloader_output.scroll_to_top();
     }
 
-    pub fn page_downloader_output_up(&mut self) {
-        self.downloader_output.page_up();
+    pub fn scroll_downloader_output_to_bottom(&mut self) {
+        self.downloader_output.scroll_to_bottom();
     }
 
     pub fn cancel_downloader(&mut self) {
@@ -314,6 +362,27 @@ impl App {
         }
     }
 
+    /// Esc or `x`: stops the yt-dlp probe. A first probe goes back to the
+    /// URL, which stays filled in; a refresh keeps the previous list.
+    pub fn cancel_downloader_probe(&mut self) {
+        let Some(running) = self.running_downloader_probe.take() else {
+            return;
+        };
+        running.cancel.store(true, atomic::Ordering::Relaxed);
+        self.downloader_output
+            .append_line("Quality probe cancelled by user.".to_string());
+        if running.refresh {
+            self.downloader_probe_mode = running.mode;
+            self.status_message =
+                "Quality refresh cancelled (showing the previous list).".to_string();
+        } else {
+            self.downloader_step = DownloaderStep::UrlInput;
+            self.downloader_option_focus = None;
+            self.status_message =
+                "Quality probe cancelled. Edit the URL and press Enter to retry.".to_string();
+        }
+    }
+
     pub fn run_downloader_download(&mut self) {
         if self.running_downloader.is_some() {
             self.status_message =
@@ -337,15 +406,28 @@ impl App {
         }
         let playlist_supported = url_has_playlist_param(&url_input);
         let download_playlist = playlist_supported && self.downloader_playlist;
+        let selected_quality = self.selected_downloader_quality();
+        // The estimate covers one video, so a playlist only checks what's free.
+        let projected = selected_quality.size_bytes.filter(|_| !download_playlist);
+        // The warning shows once per URL; Enter again downloads anyway.
+        if self.downloader_low_space_shown_for.as_deref() != Some(url_input.as_str())
+            && let Some(warning) = self.low_space_warning(&self.cwd, projected)
+        {
+            self.status_message =
+                format!("Low disk space: {warning}. Press Enter again to download anyway.");
+            self.downloader_low_space_shown_for = Some(url_input);
+            return;
+        }
+        self.downloader_low_space_shown_for = None;
         let target_url = if download_playlist {
             url_input.clone()
         } else {
             normalize_downloader_target_url(&url_input)
         };
 
-        let selected_quality = self.selected_downloader_quality();
         let effective_selector = self.effective_downloader_selector(&selected_quality.selector);
         let mut output_args = Vec::new();
+        let mut predicted_output = None;
         let output_label = if download_playlist {
             output_args.extend([
                 "-P".to_string(),
@@ -370,7 +452,9 @@ impl App {
                 }
             };
             output_args.extend(["-o".to_string(), output_path.display().to_string()]);
-            output_path.display().to_string()
+            let label = output_path.display().to_string();
+            predicted_output = Some(output_path);
+            label
         };
 
         let mut downloader_args = vec![
@@ -414,13 +498,16 @@ impl App {
                 .join(" ")
         );
 
-        match self.start_downloader_job(command_line.clone(), downloader_args) {
+        match self.start_downloader_job(command_line.clone(), downloader_args, predicted_output) {
             Ok(()) => {
                 self.status_message = format!(
                     "Running Downloader ({}) -> {}",
                     self.downloader_run_mode_label(&selected_quality.label),
                     output_label
                 );
+                if let Some(note) = self.take_free_space_unread_note() {
+                    self.downloader_output.append_line(note);
+                }
             }
             Err(err) => {
                 self.downloader_output.replace_with_command_error(
@@ -451,14 +538,41 @@ impl App {
             return;
         };
 
-        let command_line = self
-            .running_downloader_probe
-            .take()
-            .map(|running| running.command_line)
-            .unwrap_or_else(|| "yt-dlp --no-playlist -F".to_string());
+        let Some(running) = self.running_downloader_probe.take() else {
+            return;
+        };
+        if let DownloaderProbeResult::Success { choices, title } = &result {
+            self.downloader_probe_cache.insert(
+                (running.target_url, running.mode),
+                CachedDownloaderProbe {
+                    choices: choices.clone(),
+                    title: title.clone(),
+                },
+            );
+        }
+        self.apply_downloader_probe_result(
+            result,
+            running.mode,
+            running.refresh,
+            &running.command_line,
+        );
+    }
+
+    fn apply_downloader_probe_result(
+        &mut self,
+        result: DownloaderProbeResult,
+        mode: DownloaderProbeMode,
+        refresh: bool,
+        command_line: &str,
+    ) {
+        if refresh {
+            self.finish_downloader_quality_refresh(result, mode, command_line);
+            return;
+        }
 
         match result {
             DownloaderProbeResult::Success { choices, title } => {
+                self.downloader_probe_mode = mode;
                 self.downloader_quality_choices = choices;
                 self.downloader_video_title = title;
                 self.downloader_quality_index = 0;
@@ -470,50 +584,243 @@ impl App {
                     "Loaded {total} video quality options. Use Up/Down (or j/k), then Enter to download."
                 );
                 self.downloader_output
-                    .begin_stream(&command_line, "Video quality options loaded.");
+                    .begin_stream(command_line, "Video quality options loaded.");
                 self.downloader_output
                     .append_line(format!("Detected {total} video quality options."));
             }
             DownloaderProbeResult::Failed { error } => {
                 self.return_to_downloader_url_input();
                 self.downloader_output
-                    .replace_with_command_error(&command_line, &error);
+                    .replace_with_command_error(command_line, &error);
                 self.status_message = error;
             }
         }
     }
 
+    /// Applies a re-probe. Failures keep the previous list so the quality
+    /// step stays usable.
+    fn finish_downloader_quality_refresh(
+        &mut self,
+        result: DownloaderProbeResult,
+        mode: DownloaderProbeMode,
+        command_line: &str,
+    ) {
+        if self.downloader_step != DownloaderStep::QualitySelect {
+            return;
+        }
+        match result {
+            DownloaderProbeResult::Success { choices, title } => {
+                self.downloader_quality_choices = choices;
+                if title.is_some() {
+                    self.downloader_video_title = title;
+                }
+                self.downloader_quality_index = 0;
+                self.downloader_probe_mode = mode;
+
+                let (_, total) = self.downloader_quality_position();
+                let kind = if mode.audio_only { "audio" } else { "video" };
+                self.status_message = format!("Refreshed: {total} {kind} quality options.");
+                self.downloader_output
+                    .begin_stream(command_line, "Quality options refreshed.");
+                self.downloader_output
+                    .append_line(format!("Detected {total} {kind} quality options."));
+            }
+            DownloaderProbeResult::Failed { error } => {
+                self.downloader_output
+                    .replace_with_command_error(command_line, &error);
+                self.status_message = format!("{error} (showing the previous list)");
+            }
+        }
+    }
+
     fn fetch_downloader_qualities(&mut self) {
+        if let Some(path) = downloader_local_path(&self.downloader_url, &self.cwd) {
+            self.open_downloader_local_path(path);
+            return;
+        }
         if !self.downloader_available() {
             self.status_message =
                 "Downloader requires yt-dlp in PATH. Install it to enable downloads.".to_string();
             return;
         }
 
-        let url_input = self.downloader_url.trim().to_string();
-        if url_input.is_empty() {
+        if self.downloader_url.trim().is_empty() {
             self.status_message = "Enter a URL before fetching quality options.".to_string();
             return;
         }
-        let target_url = normalize_downloader_target_url(&url_input);
-
-        let command_line = format!("yt-dlp --no-playlist -F {}", shell_quote(&target_url));
-        let (tx, rx) = mpsc::channel();
+        let checked =
+            match check_downloader_url(&self.downloader_url, self.downloader_strip_tracking) {
+                Ok(checked) => checked,
+                Err(err) => {
+                    self.status_message = format!("Invalid URL: {err}");
+                    self.downloader_url_error = Some(err);
+                    return;
+                }
+            };
+        if checked.url != self.downloader_url {
+            self.downloader_url_cursor = checked.url.chars().count();
+            self.downloader_url = checked.url;
+        }
         self.downloader_video_title = None;
         self.downloader_playlist = false;
+        if self.start_downloader_probe(false) {
+            self.status_message = "Fetching available downloader qualities...".to_string();
+        }
+        if !checked.removed_params.is_empty() {
+            self.status_message.push_str(&format!(
+                " Removed tracking parameters: {}.",
+                checked.removed_params.join(", ")
+            ));
+        }
+    }
+
+    /// A local path in the URL field: folders open in the browser and
+    /// media files in the editor, where they can be converted or remuxed.
+    fn open_downloader_local_path(&mut self, path: PathBuf) {
+        let shown = path.display().to_string();
+        if !path.exists() {
+            self.status_message = format!("No such file: {shown}");
+            self.downloader_url_error = Some(format!("`{shown}` does not exist."));
+            return;
+        }
+        if path.is_dir() {
+            match self.change_dir(path) {
+                Ok(()) => self.status_message = format!("Opened folder in the browser: {shown}"),
+                Err(err) => self.status_message = format!("Failed to open {shown}: {err}"),
+            }
+            return;
+        }
+        if !is_editable_media_file(&path) {
+            self.status_message = format!("Not a supported media file: {shown}");
+            self.downloader_url_error =
+                Some("Local files must be audio or video to open in the editor.".to_string());
+            return;
+        }
+
+        // Show the file in the browser too, so Enter there reloads it. An
+        // unreadable folder only costs that; the editor still opens.
+        if let Some(parent) = path.parent()
+            && parent != self.cwd
+        {
+            let _ = self.change_dir(parent.to_path_buf());
+        }
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == path) {
+            self.selected = index;
+        }
+        self.select_media(path);
+        self.status_message =
+            format!("Opened local file in the editor: {shown}. Convert or remux it from there.");
+    }
+
+    /// Why the last Enter rejected the URL, until the URL is edited.
+    pub fn downloader_url_error(&self) -> Option<&str> {
+        self.downloader_url_error.as_deref()
+    }
+
+    /// Ctrl+r: probes the URL again instead of using the cached list.
+    pub fn reprobe_downloader_qualities(&mut self) {
+        if self.running_downloader.is_some() || self.running_downloader_probe.is_some() {
+            return;
+        }
+        let mode = self.current_downloader_probe_mode();
+        let key = (self.downloader_probe_target_url(mode), mode);
+        self.downloader_probe_cache.remove(&key);
+        match self.downloader_step {
+            DownloaderStep::UrlInput => self.fetch_downloader_qualities(),
+            DownloaderStep::QualitySelect if self.downloader_available() => {
+                self.start_downloader_probe(true);
+                self.status_message = "Refreshing quality options...".to_string();
+            }
+            DownloaderStep::QualitySelect => {}
+        }
+    }
+
+    fn current_downloader_probe_mode(&self) -> DownloaderProbeMode {
+        DownloaderProbeMode {
+            audio_only: self.downloader_audio_only,
+            playlist: self.downloader_playlist_enabled(),
+        }
+    }
+
+    fn downloader_probe_target_url(&self, mode: DownloaderProbeMode) -> String {
+        let url_input = self.downloader_url.trim().to_string();
+        if mode.playlist {
+            url_input
+        } else {
+            normalize_downloader_target_url(&url_input)
+        }
+    }
+
+    /// Starts a probe thread, or applies this session's cached result for
+    /// the same URL and options right away. Returns whether a probe started.
+    fn start_downloader_probe(&mut self, refresh: bool) -> bool {
+        // A probe still running for the previous options is stopped; its
+        // result would be discarded anyway.
+        if let Some(previous) = self.running_downloader_probe.take() {
+            previous.cancel.store(true, atomic::Ordering::Relaxed);
+        }
+        let mode = self.current_downloader_probe_mode();
+        let target_url = self.downloader_probe_target_url(mode);
+
+        let command_line = format!(
+            "yt-dlp {} -F {}",
+            probe_playlist_args(mode).join(" "),
+            shell_quote(&target_url)
+        );
+        if let Some(cached) = self
+            .downloader_probe_cache
+            .get(&(target_url.clone(), mode))
+            .cloned()
+        {
+            let result = DownloaderProbeResult::Success {
+                choices: cached.choices,
+                title: cached.title,
+            };
+            self.apply_downloader_probe_result(result, mode, refresh, &command_line);
+            self.downloader_output
+                .append_line("Reused this session's probe; Ctrl+r probes again.".to_string());
+            return false;
+        }
+
+        let thread_url = target_url.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let thread_cancel = Arc::clone(&cancel);
+        let (tx, rx) = mpsc::channel();
         thread::spawn(move || {
-            let result = probe_downloader_qualities(&target_url);
+            let result = probe_downloader_qualities(&thread_url, mode, &thread_cancel);
             let _ = tx.send(result);
         });
 
         self.running_downloader_probe = Some(RunningDownloaderProbe {
             rx,
             command_line: command_line.clone(),
+            target_url,
+            mode,
+            refresh,
+            cancel,
         });
         self.downloader_spinner_frame = 0;
-        self.downloader_output
-            .begin_stream(&command_line, "Fetching quality options...");
-        self.status_message = "Fetching available downloader qualities...".to_string();
+        let note = if refresh {
+            "Refreshing quality options..."
+        } else {
+            "Fetching quality options..."
+        };
+        self.downloader_output.begin_stream(&command_line, note);
+        true
+    }
+
+    /// Re-probes when an option toggle no longer matches the shown list.
+    fn refresh_downloader_qualities_if_stale(&mut self) {
+        if self.downloader_step != DownloaderStep::QualitySelect
+            || !self.downloader_available()
+            || self.current_downloader_probe_mode() == self.downloader_probe_mode
+        {
+            return;
+        }
+        if self.start_downloader_probe(true) {
+            self.status_message
+                .push_str(" Refreshing quality options...");
+        }
     }
 
     fn toggle_downloader_audio_only(&mut self) {
@@ -522,6 +829,8 @@ impl App {
             "Downloader option: audio-only {}.",
             on_off(self.downloader_audio_only)
         );
+        self.save_downloader_defaults();
+        self.refresh_downloader_qualities_if_stale();
     }
 
     fn toggle_downloader_sponsorblock(&mut self) {
@@ -530,6 +839,7 @@ impl App {
             "Downloader option: SponsorBlock {}.",
             on_off(self.downloader_sponsorblock)
         );
+        self.save_downloader_defaults();
     }
 
     fn toggle_downloader_subtitles(&mut self) {
@@ -538,6 +848,7 @@ impl App {
             "Downloader option: subtitles {}.",
             on_off(self.downloader_subtitles)
         );
+        self.save_downloader_defaults();
     }
 
     fn toggle_downloader_playlist(&mut self) {
@@ -553,10 +864,11 @@ impl App {
             "Downloader option: playlist {}.",
             on_off(self.downloader_playlist)
         );
+        self.refresh_downloader_qualities_if_stale();
     }
 
     fn effective_downloader_selector(&self, selected_selector: &str) -> String {
-        if self.downloader_audio_only {
+        if self.downloader_audio_only && !self.downloader_probe_mode.audio_only {
             "bestaudio/best".to_string()
         } else {
             selected_selector.to_string()
@@ -589,6 +901,7 @@ impl App {
         &mut self,
         command_line: String,
         downloader_args: Vec<String>,
+        predicted_output: Option<PathBuf>,
     ) -> io::Result<()> {
         let mut child = Command::new("yt-dlp")
             .args(&downloader_args)
@@ -617,6 +930,11 @@ impl App {
             child,
             rx,
             command_line,
+            progress_percent: None,
+            eta_seconds: None,
+            output_file: predicted_output,
+            output_dir: self.cwd.clone(),
+            started_at: Instant::now(),
             stdout_raw: Vec::new(),
             stderr_raw: Vec::new(),
             stdout_pending: Vec::new(),
@@ -658,8 +976,12 @@ impl App {
     pub(super) fn pump_running_downloader_events(&mut self) {
         let mut streamed_lines = Vec::new();
 
+        let batch = self.perf.pump_batch;
         if let Some(running) = self.running_downloader.as_mut() {
-            while let Ok(event) = running.rx.try_recv() {
+            for _ in 0..batch {
+                let Ok(event) = running.rx.try_recv() else {
+                    break;
+                };
                 match event {
                     DownloaderEvent::Chunk { stream, data } => {
                         let lines = consume_stream_chunk(running, stream, &data);
@@ -727,27 +1049,51 @@ impl App {
         }
 
         let command_line = running.command_line;
-        let _stdout_raw = running.stdout_raw;
+        let stdout_raw = running.stdout_raw;
         let stderr_raw = running.stderr_raw;
+        let output_file = running.output_file;
 
         if status.success() {
+            if let Some(path) = output_file.as_ref().filter(|path| path.is_file()) {
+                self.last_output = Some(path.clone());
+            }
             if let Err(err) = self.reload() {
                 self.status_message =
                     format!("Downloader completed, but browser refresh failed: {err}");
             } else {
                 self.status_message = "Downloader completed successfully.".to_string();
             }
+            let downloaded = output_file.as_deref().filter(|path| path.is_file());
+            self.record_download_stats(downloaded, running.started_at.elapsed());
         } else {
             let stderr = String::from_utf8_lossy(&stderr_raw);
             let detail = stderr
                 .lines()
                 .map(str::trim)
-                .filter(|line| !line.is_empty())
-                .next_back()
+                .rfind(|line| !line.is_empty())
                 .unwrap_or("unknown yt-dlp error");
             self.status_message = format!("Downloader failed: {detail}");
         }
 
+        let job_log = run_transcript(
+            "yt-dlp",
+            &command_line,
+            status.code(),
+            &stdout_raw,
+            &stderr_raw,
+            None,
+        )
+        .and_then(|entry| self.write_job_log(output_file.as_deref(), &entry));
+        match job_log {
+            Ok(Some(path)) => self
+                .status_message
+                .push_str(&format!(" (log: {})", path.display())),
+            Ok(None) => {}
+            Err(err) => self
+                .status_message
+                .push_str(&format!(" (log write failed: {err})")),
+        }
+
         self.append_downloader_output_line(format!(
             "Downloader finished with exit code: {} ({command_line})",
             status.code().unwrap_or(-1)
@@ -755,6 +1101,17 @@ impl App {
     }
 
     fn append_downloader_stream_line(&mut self, stream: DownloaderStream, line: String) {
+        if let Some(running) = self.running_downloader.as_mut()
+            && let Some(percent) = parse_downloader_progress_percent(&line)
+        {
+            running.progress_percent = Some(percent);
+            running.eta_seconds = parse_downloader_eta_seconds(&line);
+        }
+        if let Some(running) = self.running_downloader.as_mut()
+            && let Some(path) = parse_downloader_output_file(&line)
+        {
+            running.output_file = Some(path);
+        }
         let prefix = match stream {
             DownloaderStream::Stdout => "stdout",
             DownloaderStream::Stderr => "stderr",
@@ -773,6 +1130,7 @@ impl App {
         self.downloader_playlist = false;
         self.downloader_url.clear();
         self.downloader_url_cursor = 0;
+        self.downloader_url_error = None;
     }
 
     fn downloader_option_count(&self) -> usize {
@@ -784,15 +1142,80 @@ fn default_downloader_quality_choice() -> DownloaderQualityChoice {
     DownloaderQualityChoice {
         selector: "bestvideo+bestaudio/best".to_string(),
         label: format_quality_columns("AUTO", "auto", "best", "--", "--", "auto", "video"),
+        size_bytes: None,
     }
 }
 
-fn probe_downloader_qualities(url: &str) -> DownloaderProbeResult {
-    let title = probe_downloader_title(url);
-    let output = match Command::new("yt-dlp")
-        .args(["--no-playlist", "-F", url])
-        .output()
-    {
+fn default_downloader_audio_choice() -> DownloaderQualityChoice {
+    DownloaderQualityChoice {
+        selector: "bestaudio/best".to_string(),
+        label: format_quality_columns("AUTO", "auto", "audio", "--", "--", "best", "audio"),
+        size_bytes: None,
+    }
+}
+
+/// Playlist probes list the first entry's formats, which the rest of a
+/// playlist almost always shares.
+fn probe_playlist_args(mode: DownloaderProbeMode) -> Vec<&'static str> {
+    if mode.playlist {
+        vec!["--yes-playlist", "--playlist-items", "1"]
+    } else {
+        vec!["--no-playlist"]
+    }
+}
+
+/// Runs a probe command to completion, killing it once `cancel` is set.
+fn probe_output(command: &mut Command, cancel: &AtomicBool) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain both pipes so a chatty probe can't block on a full buffer.
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut bytes = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut bytes);
+            }
+            bytes
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = drain(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+    let status = loop {
+        if cancel.load(atomic::Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "probe cancelled",
+            ));
+        }
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        thread::sleep(PROBE_CANCEL_POLL);
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn probe_downloader_qualities(
+    url: &str,
+    mode: DownloaderProbeMode,
+    cancel: &AtomicBool,
+) -> DownloaderProbeResult {
+    let title = probe_downloader_title(url, mode, cancel);
+    let output = match probe_output(
+        Command::new("yt-dlp")
+            .args(probe_playlist_args(mode))
+            .args(["-F", url]),
+        cancel,
+    ) {
         Ok(output) => output,
         Err(err) => {
             return DownloaderProbeResult::Failed {
@@ -806,8 +1229,7 @@ fn probe_downloader_qualities(url: &str) -> DownloaderProbeResult {
         let detail = stderr
             .lines()
             .map(str::trim)
-            .filter(|line| !line.is_empty())
-            .next_back()
+            .rfind(|line| !line.is_empty())
             .unwrap_or("yt-dlp failed to fetch formats");
         return DownloaderProbeResult::Failed {
             error: format!("Format probe failed: {detail}"),
@@ -815,26 +1237,32 @@ fn probe_downloader_qualities(url: &str) -> DownloaderProbeResult {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut choices = parse_quality_choices_from_format_list(&stdout);
-    if choices.is_empty() {
-        choices.push(default_downloader_quality_choice());
-    }
+    let choices = if mode.audio_only {
+        parse_audio_choices_from_format_list(&stdout)
+    } else {
+        parse_quality_choices_from_format_list(&stdout)
+    };
 
     DownloaderProbeResult::Success { choices, title }
 }
 
-fn probe_downloader_title(url: &str) -> Option<String> {
-    let output = Command::new("yt-dlp")
-        .args([
-            "--no-playlist",
-            "--skip-download",
-            "--print",
-            "title",
-            "--no-warnings",
-            url,
-        ])
-        .output()
-        .ok()?;
+fn probe_downloader_title(
+    url: &str,
+    mode: DownloaderProbeMode,
+    cancel: &AtomicBool,
+) -> Option<String> {
+    let field = if mode.playlist {
+        "playlist_title"
+    } else {
+        "title"
+    };
+    let output = probe_output(
+        Command::new("yt-dlp")
+            .args(probe_playlist_args(mode))
+            .args(["--skip-download", "--print", field, "--no-warnings", url]),
+        cancel,
+    )
+    .ok()?;
     if !output.status.success() {
         return None;
     }
@@ -842,8 +1270,7 @@ fn probe_downloader_title(url: &str) -> Option<String> {
     String::from_utf8_lossy(&output.stdout)
         .lines()
         .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .next_back()
+        .rfind(|line| !line.is_empty())
         .map(str::to_string)
 }
 
@@ -851,6 +1278,7 @@ fn parse_quality_choices_from_format_list(output: &str) -> Vec<DownloaderQuality
     let mut seen = HashSet::new();
     seen.insert("bestvideo+bestaudio/best".to_string());
     let mut candidates = Vec::new();
+    let best_audio_bytes = best_audio_size_bytes(output);
 
     let mut in_table = false;
     for line in output.lines() {
@@ -893,10 +1321,16 @@ fn parse_quality_choices_from_format_list(output: &str) -> Vec<DownloaderQuality
         };
 
         let fps = extract_fps_token(trimmed).unwrap_or_else(|| "--".to_string());
-        let size = extract_size_token(trimmed).unwrap_or_else(|| "--".to_string());
-        let size_bytes = parse_size_bytes(&size);
+        let mut size = extract_size_token(trimmed).unwrap_or_else(|| "--".to_string());
+        let mut size_bytes = parse_size_bytes(&size);
         let video_only = trimmed.contains("video only");
-        let has_audio = !video_only;
+        // Video-only picks download `+bestaudio` too, so show what lands on disk.
+        if video_only && let (Some(video_bytes), Some(audio_bytes)) = (size_bytes, best_audio_bytes)
+        {
+            let total = video_bytes + audio_bytes;
+            size = format_size_token(total);
+            size_bytes = Some(total);
+        }
         let selector = if video_only {
             format!("{format_id}+bestaudio/best")
         } else {
@@ -915,11 +1349,11 @@ fn parse_quality_choices_from_format_list(output: &str) -> Vec<DownloaderQuality
                     &resolution,
                     &fps,
                     &size,
-                    if has_audio { "yes" } else { "no" },
+                    if video_only { "+best" } else { "yes" },
                     if video_only { "video" } else { "muxed" },
                 ),
+                size_bytes,
             },
-            size_bytes,
             original_index: candidates.len(),
         });
 
@@ -935,6 +1369,44 @@ fn parse_quality_choices_from_format_list(output: &str) -> Vec<DownloaderQuality
     choices
 }
 
+/// Audio-only rows of `yt-dlp -F`, for the audio-only download mode.
+fn parse_audio_choices_from_format_list(output: &str) -> Vec<DownloaderQualityChoice> {
+    let mut candidates = Vec::new();
+    let mut in_table = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !in_table {
+            in_table = trimmed.contains("ID") && trimmed.contains("EXT");
+            continue;
+        }
+        if !trimmed.contains("audio only") {
+            continue;
+        }
+        let mut parts = trimmed.split_whitespace();
+        let (Some(format_id), Some(ext)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if !is_format_id_token(format_id) {
+            continue;
+        }
+        let size = extract_size_token(trimmed).unwrap_or_else(|| "--".to_string());
+        candidates.push(QualityCandidate {
+            choice: DownloaderQualityChoice {
+                selector: format_id.to_string(),
+                label: format_quality_columns(format_id, ext, "audio", "--", &size, "yes", "audio"),
+                size_bytes: parse_size_bytes(&size),
+            },
+            original_index: candidates.len(),
+        });
+    }
+
+    candidates.sort_by(compare_quality_candidates);
+
+    let mut choices = vec![default_downloader_audio_choice()];
+    choices.extend(candidates.into_iter().map(|entry| entry.choice));
+    choices
+}
+
 fn format_quality_columns(
     id: &str,
     ext: &str,
@@ -1010,6 +1482,28 @@ fn extract_size_token(line: &str) -> Option<String> {
     })
 }
 
+/// Size of the largest audio-only format, standing in for what
+/// `bestaudio` will fetch.
+fn best_audio_size_bytes(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .filter(|line| line.contains("audio only"))
+        .filter_map(extract_size_token)
+        .filter_map(|size| parse_size_bytes(&size))
+        .max()
+}
+
+/// Formats bytes like yt-dlp's size column, with `~` for an estimate.
+fn format_size_token(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= 1024.0 * MIB {
+        format!("~{:.2}GiB", bytes / (1024.0 * MIB))
+    } else {
+        format!("~{:.2}MiB", bytes / MIB)
+    }
+}
+
 fn parse_size_bytes(size_token: &str) -> Option<u64> {
     let normalized = size_token.trim().trim_start_matches('~');
     if normalized.is_empty() || normalized == "--" {
@@ -1038,12 +1532,11 @@ fn parse_size_bytes(size_token: &str) -> Option<u64> {
 #[derive(Debug)]
 struct QualityCandidate {
     choice: DownloaderQualityChoice,
-    size_bytes: Option<u64>,
     original_index: usize,
 }
 
 fn compare_quality_candidates(left: &QualityCandidate, right: &QualityCandidate) -> Ordering {
-    match (left.size_bytes, right.size_bytes) {
+    match (left.choice.size_bytes, right.choice.size_bytes) {
         (Some(a), Some(b)) => a
             .cmp(&b)
             .then_with(|| left.original_index.cmp(&right.original_index)),
@@ -1182,19 +1675,13 @@ fn resolve_downloader_output_path(
         let detail = stderr
             .lines()
             .map(str::trim)
-            .filter(|line| !line.is_empty())
-            .next_back()
+            .rfind(|line| !line.is_empty())
             .unwrap_or("yt-dlp failed to compute output filename");
         return Err(io::Error::other(detail.to_string()));
     }
 
     let stdout = String::from_utf8_lossy(&probe_output.stdout);
-    let Some(filename_line) = stdout
-        .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .next_back()
-    else {
+    let Some(filename_line) = stdout.lines().map(str::trim).rfind(|line| !line.is_empty()) else {
         return Err(io::Error::other(
             "yt-dlp did not return a predicted output filename",
         ));
@@ -1210,6 +1697,42 @@ fn resolve_downloader_output_path(
     Ok(next_available_output_path(&absolute_predicted))
 }
 
+// yt-dlp --newline progress rows look like "[download]  42.3% of ~10.00MiB at ...".
+fn parse_downloader_progress_percent(line: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix("[download]")?;
+    let token = rest.split_whitespace().next()?;
+    let value = token.strip_suffix('%')?.parse::<f64>().ok()?;
+    progress_percent(value, 100.0)
+}
+
+/// The `ETA 01:23` (or `ETA 1:02:03`) at the end of a progress row.
+fn parse_downloader_eta_seconds(line: &str) -> Option<u64> {
+    let mut tokens = line.split_whitespace();
+    tokens.find(|token| *token == "ETA")?;
+    tokens.next()?.split(':').try_fold(0, |total: u64, part| {
+        Some(total * 60 + part.parse::<u64>().ok()?)
+    })
+}
+
+/// Extracts the file yt-dlp reports writing. Later stages (merge, audio
+/// extraction) print the final name, so the last match wins.
+fn parse_downloader_output_file(line: &str) -> Option<PathBuf> {
+    let line = line.trim();
+    let path = if let Some(rest) = line.strip_prefix("[Merger] Merging formats into ") {
+        rest.trim_matches('"')
+    } else if let Some((_, rest)) = line.split_once("] Destination: ") {
+        if line.starts_with("[download]") || line.starts_with("[ExtractAudio]") {
+            rest
+        } else {
+            return None;
+        }
+    } else {
+        line.strip_prefix("[download] ")?
+            .strip_suffix(" has already been downloaded")?
+    };
+    Some(PathBuf::from(path))
+}
+
 fn url_has_playlist_param(url: &str) -> bool {
     let trimmed = url.trim();
     let Some((_, query)) = trimmed.split_once('?') else {
@@ -1245,7 +1768,11 @@ fn normalize_downloader_target_url(url: &str) -> String {
         .split('&')
         .filter(|pair| !pair.is_empty())
         .filter(|pair| {
-            let key = pair.split('=').next().unwrap_or_default().to_ascii_lowercase();
+            let key = pair
+                .split('=')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
             !matches!(key.as_str(), "list" | "index" | "pp")
         })
         .collect::<Vec<_>>();
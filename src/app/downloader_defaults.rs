@@ -0,0 +1,82 @@
+// Downloader option defaults kept between launches.
+// - Stores the audio-only, SponsorBlock, and subtitles toggles in the config dir.
+// - Saved on every toggle, so the last choice becomes the next default.
+// - A key set under `[downloader]` in `config.toml` wins over the saved
+//   toggle, so an imported config takes effect.
+use std::{fs, io, path::PathBuf};
+
+use super::{App, config_dir, state_lock::write_state_file};
+
+const DOWNLOADER_DEFAULTS_FILE_NAME: &str = "downloader.ini";
+
+#[derive(Clone, Default)]
+pub(super) struct DownloaderDefaults {
+    pub(super) audio_only: bool,
+    pub(super) sponsorblock: bool,
+    pub(super) subtitles: bool,
+}
+
+impl DownloaderDefaults {
+    /// The saved toggles; a missing file or key leaves it off.
+    pub(super) fn load() -> io::Result<Self> {
+        let mut defaults = Self::default();
+        let Some(path) = defaults_path() else {
+            return Ok(defaults);
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(defaults),
+            Err(err) => return Err(err),
+        };
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let enabled = matches!(value.trim(), "true" | "on" | "yes" | "1");
+            match key.trim() {
+                "audio_only" => defaults.audio_only = enabled,
+                "sponsorblock" => defaults.sponsorblock = enabled,
+                "subtitles" => defaults.subtitles = enabled,
+                _ => {}
+            }
+        }
+        Ok(defaults)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = defaults_path() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no config directory (set HOME or XDG_CONFIG_HOME)",
+            ));
+        };
+        let contents = format!(
+            "# rt downloader defaults, updated when the options are toggled\naudio_only = {}\nsponsorblock = {}\nsubtitles = {}\n",
+            self.audio_only, self.sponsorblock, self.subtitles
+        );
+        write_state_file(&path, &contents)
+    }
+}
+
+fn defaults_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(DOWNLOADER_DEFAULTS_FILE_NAME))
+}
+
+impl App {
+    /// Persists the current toggles; a failure is appended to the status.
+    pub(super) fn save_downloader_defaults(&mut self) {
+        let defaults = DownloaderDefaults {
+            audio_only: self.downloader_audio_only,
+            sponsorblock: self.downloader_sponsorblock,
+            subtitles: self.downloader_subtitles,
+        };
+        if let Err(err) = defaults.save() {
+            self.status_message
+                .push_str(&format!(" (not saved as default: {err})"));
+        }
+    }
+}
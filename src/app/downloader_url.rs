@@ -0,0 +1,148 @@
+// URL checks for the downloader's first step.
+// - Rejects text that can't be a web address before yt-dlp is spawned, so
+//   a typo shows up at once under the URL instead of after a slow probe.
+// - Adds `https://` to bare addresses like `youtu.be/abc`.
+// - Drops tracking parameters (`si`, `utm_*`, ...) unless
+//   `strip_tracking = false` is set under `[downloader]` in `config.toml`.
+// - Recognizes local paths (`/...`, `~/...`, `./...`, `file://...`, or a name
+//   in the browser's folder) so they open in the editor instead of yt-dlp.
+use std::path::{Path, PathBuf};
+
+use super::quick_jump::expand_home_dir;
+
+/// Query parameters that only track where a link was shared from.
+const TRACKING_PARAMS: [&str; 5] = ["si", "fbclid", "gclid", "igshid", "mc_cid"];
+
+/// A URL that passed the checks, with any parameters that were removed.
+pub(super) struct CheckedUrl {
+    pub(super) url: String,
+    pub(super) removed_params: Vec<String>,
+}
+
+/// The path `input` names when it is a local file rather than a link.
+pub(super) fn downloader_local_path(input: &str, cwd: &Path) -> Option<PathBuf> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if let Some(path) = input.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
+    if ["/", "~/", "./", "../"]
+        .iter()
+        .any(|prefix| input.starts_with(prefix))
+        || input == "~"
+    {
+        return Some(expand_home_dir(input));
+    }
+    let relative = cwd.join(input);
+    (!input.contains("://") && relative.exists()).then_some(relative)
+}
+
+/// Checks the scheme and host of `input` and tidies it for probing.
+pub(super) fn check_downloader_url(
+    input: &str,
+    strip_tracking: bool,
+) -> Result<CheckedUrl, String> {
+    let input = input.trim();
+    if input.chars().any(char::is_whitespace) {
+        return Err("URL contains spaces. Paste a single link.".to_string());
+    }
+
+    let url = match input.split_once("://") {
+        Some((scheme, _)) => {
+            let scheme = scheme.to_ascii_lowercase();
+            if scheme != "http" && scheme != "https" {
+                return Err(format!(
+                    "Unsupported scheme `{scheme}://`. Use an http:// or https:// link."
+                ));
+            }
+            input.to_string()
+        }
+        None if ["http:", "https:"]
+            .iter()
+            .any(|scheme| input.to_ascii_lowercase().starts_with(scheme)) =>
+        {
+            return Err("Missing `//` after the scheme (e.g. https://...).".to_string());
+        }
+        None => format!("https://{input}"),
+    };
+
+    let (_, rest) = url.split_once("://").unwrap_or(("", &url));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default();
+    if host.is_empty() {
+        return Err("URL has no host.".to_string());
+    }
+    if !host
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || ch == '.' || ch == '-')
+    {
+        return Err(format!("`{host}` is not a valid host name."));
+    }
+    let labels = host.split('.').collect::<Vec<_>>();
+    if labels.iter().any(|label| label.is_empty()) {
+        return Err(format!("`{host}` has an empty part between dots."));
+    }
+    if labels.len() < 2 && host != "localhost" {
+        return Err(format!("`{host}` is missing a domain ending such as .com."));
+    }
+
+    if !strip_tracking {
+        return Ok(CheckedUrl {
+            url,
+            removed_params: Vec::new(),
+        });
+    }
+    Ok(strip_tracking_params(&url))
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+fn strip_tracking_params(url: &str) -> CheckedUrl {
+    let (without_fragment, fragment) = match url.split_once('#') {
+        Some((head, tail)) => (head, Some(tail)),
+        None => (url, None),
+    };
+    let Some((base, query)) = without_fragment.split_once('?') else {
+        return CheckedUrl {
+            url: url.to_string(),
+            removed_params: Vec::new(),
+        };
+    };
+
+    let mut removed_params = Vec::new();
+    let kept_params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            if is_tracking_param(key) {
+                removed_params.push(key.to_string());
+                false
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut cleaned = if kept_params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{base}?{}", kept_params.join("&"))
+    };
+    if let Some(fragment) = fragment
+        && !fragment.is_empty()
+    {
+        cleaned.push('#');
+        cleaned.push_str(fragment);
+    }
+    CheckedUrl {
+        url: cleaned,
+        removed_params,
+    }
+}
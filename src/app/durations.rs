@@ -0,0 +1,120 @@
+// Duration badges for media files in the browser.
+// - Probes each audio and video file in the listing with ffprobe on a
+//   background thread, one at a time, after the listing changes.
+// - Remembers results for the session, so revisiting a folder shows its
+//   badges at once. A file whose size or modification time changed since
+//   is probed again.
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::SystemTime,
+};
+
+use crate::{
+    media::{is_editable_media_file, probe_video_times},
+    model::FileEntry,
+};
+
+use super::App;
+
+/// A file as it was when probed: path, size, and modification time.
+type DurationKey = (PathBuf, Option<u64>, Option<SystemTime>);
+
+fn duration_key(entry: &FileEntry) -> DurationKey {
+    (entry.path.clone(), entry.size_bytes, entry.modified)
+}
+
+pub(super) struct EntryDurations {
+    /// Seconds per probed file; `None` when ffprobe couldn't read it.
+    known: HashMap<DurationKey, Option<f64>>,
+    running: Option<Receiver<(DurationKey, Option<f64>)>>,
+    /// The listing changed since the last probe started.
+    stale: bool,
+}
+
+impl Default for EntryDurations {
+    fn default() -> Self {
+        Self {
+            known: HashMap::new(),
+            running: None,
+            stale: true,
+        }
+    }
+}
+
+impl App {
+    /// The probed length of a media entry, once known.
+    pub fn entry_duration(&self, entry: &FileEntry) -> Option<f64> {
+        self.entry_durations
+            .known
+            .get(&duration_key(entry))
+            .copied()
+            .flatten()
+    }
+
+    /// Asks for the current listing to be probed on the next tick.
+    pub(super) fn mark_entry_durations_stale(&mut self) {
+        self.entry_durations.stale = true;
+    }
+
+    /// Starts probing a changed listing and collects finished results.
+    /// Returns whether a badge arrived.
+    pub(super) fn pump_entry_durations(&mut self) -> bool {
+        if self.entry_durations.stale {
+            self.entry_durations.stale = false;
+            self.start_entry_duration_probes();
+        }
+        let Some(rx) = self.entry_durations.running.as_ref() else {
+            return false;
+        };
+        let mut arrived = false;
+        loop {
+            match rx.try_recv() {
+                Ok((key, seconds)) => {
+                    self.entry_durations.known.insert(key, seconds);
+                    arrived = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.entry_durations.running = None;
+                    break;
+                }
+            }
+        }
+        arrived
+    }
+
+    /// Replacing the receiver stops the previous thread at its next send.
+    fn start_entry_duration_probes(&mut self) {
+        self.entry_durations.running = None;
+        if !self.ffmpeg_available {
+            return;
+        }
+        let keys = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.is_dir && is_editable_media_file(&entry.path))
+            .map(duration_key)
+            .filter(|key| !self.entry_durations.known.contains_key(key))
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for key in keys {
+                let seconds = probe_video_times(&key.0)
+                    .ok()
+                    .map(|(_, _, bounds)| bounds.end_seconds - bounds.start_seconds)
+                    .filter(|seconds| *seconds > 0.0);
+                if tx.send((key, seconds)).is_err() {
+                    break;
+                }
+            }
+        });
+        self.entry_durations.running = Some(rx);
+    }
+}
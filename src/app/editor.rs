@@ -2,245 +2,1150 @@
 // - Validates time range, format-specific options, and required output fields.
 // - Translates current form state into ffmpeg CLI arguments.
 // - Starts ffmpeg jobs and reports launch/validation errors back to the UI.
+use std::path::PathBuf;
+
 use crate::{
     media::{
-        enforce_output_extension, next_available_output_path, resolve_output_path,
-        scaled_resolution_for_percent, shell_quote,
+        MAX_CAPTION_SIZE, MIN_CAPTION_SIZE, MediaStream, StreamKind, amerge_stereo_filters,
+        atempo_filters, caption_filter, channel_filter, codec_fits_container, denoise_filter,
+        enforce_output_extension, export_file_time, format_bytes, image_sequence_paths,
+        loudnorm_analysis_filter, loudnorm_filter, nearest_keyframe, next_available_output_path,
+        parse_color_adjust, parse_crop_input, resolve_output_path, scaled_resolution_for_percent,
+        segments_filter_graph, sharpen_filter, split_shell_words, stream_input_args,
+        supports_faststart, video_encoder_candidates, video_encoder_speed_args, waveform_filter,
     },
-    model::TimeInput,
+    model::{AudioTrack, InputField, TimeInput},
 };
 
-use super::App;
+use super::{
+    App, FfmpegJob, FfmpegJobKind, LoudnormFollowUp, PendingExport,
+    clipboard::copy_to_clipboard,
+    ffmpeg::ffmpeg_command_line,
+    keyframes::KeyframeWait,
+    segments::{segments_span, segments_total_seconds},
+};
 
-impl App {
-    pub fn run_editor_export(&mut self) {
-        if self.running_editor.is_some() {
-            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
-            return;
-        }
-        if !self.ffmpeg_available() {
-            self.status_message =
-                "ffmpeg was not found in PATH. Install ffmpeg to enable editing and export."
-                    .to_string();
-            return;
-        }
+/// Why a batch export didn't start.
+pub(super) enum LaunchError {
+    /// The form can't build an export for the file.
+    Build(String),
+    /// The export would leave less than the configured free space.
+    LowSpace(String),
+}
 
+/// A fully assembled editor export, ready to confirm or launch.
+struct EditorExport {
+    job: FfmpegJob,
+    /// Output name with the format's extension, before collision numbering.
+    output_name: String,
+    /// False for frame sequences, whose name stays the typed pattern.
+    sync_output_name: bool,
+    output_duration_seconds: f64,
+    estimated_bytes: Option<u64>,
+    /// One-line summary for the large-export confirmation.
+    summary: String,
+    status_note: String,
+}
+
+impl App {
+    /// Assembles the export job for the current form without touching any
+    /// state. A stream copy snaps to the keyframes in the start's hint,
+    /// which `run_editor_export` waits for.
+    fn build_editor_export(&self) -> Result<EditorExport, String> {
         let Some(input_path) = self.selected_video.clone() else {
-            self.status_message = "No video selected. Choose one in the left pane.".to_string();
-            return;
+            return Err("No video selected. Choose one in the left pane.".to_string());
         };
 
-        if !self.start_time.has_valid_minute_second_range()
-            || !self.end_time.has_valid_minute_second_range()
-        {
-            self.status_message = "Minutes and seconds must be between 00 and 59.".to_string();
-            return;
-        }
-
-        let start_seconds = self.start_time.to_seconds();
-        let end_seconds = self.end_time.to_seconds();
-        let start = self.start_time.to_ffmpeg_timestamp();
+        // Listed segments replace the Start/End range; the cut starts at the
+        // earliest one and the trims are relative to it.
+        let (start_seconds, end_seconds, clip_duration) = if self.segments.is_empty() {
+            let (start_seconds, end_seconds) = self.validated_time_range()?;
+            (start_seconds, end_seconds, end_seconds - start_seconds)
+        } else {
+            let (first, last) = segments_span(&self.segments);
+            (first, last, segments_total_seconds(&self.segments))
+        };
+        let start = TimeInput::from_seconds(start_seconds).to_ffmpeg_timestamp();
         let output = self.output_name.trim();
-
-        if let Some(bounds) = self.selected_video_bounds {
-            if start_seconds < bounds.start_seconds {
-                self.status_message = format!(
-                    "Start time must be >= {}.",
-                    TimeInput::from_seconds(bounds.start_seconds as f64).to_ffmpeg_timestamp()
-                );
-                return;
-            }
-            if start_seconds >= bounds.end_seconds {
-                self.status_message = format!(
-                    "Start time must be < {}.",
-                    TimeInput::from_seconds(bounds.end_seconds as f64).to_ffmpeg_timestamp()
-                );
-                return;
-            }
-            if end_seconds > bounds.end_seconds {
-                self.status_message = format!(
-                    "End time must be <= {}.",
-                    TimeInput::from_seconds(bounds.end_seconds as f64).to_ffmpeg_timestamp()
-                );
-                return;
-            }
+        if output.is_empty() {
+            return Err("Output file name is required.".to_string());
         }
 
-        if end_seconds <= start_seconds {
-            self.status_message = "End time must be greater than start time.".to_string();
-            return;
-        }
+        let extra_args = split_shell_words(self.ffmpeg_extra_args.value())
+            .map_err(|message| format!("Extra args: {message}."))?;
 
-        let clip_duration = end_seconds - start_seconds;
-        if output.is_empty() {
-            self.status_message = "Output file name is required.".to_string();
-            return;
+        // Stream copy ignores every re-encode option, so skip validating them.
+        let stream_copy = self.stream_copy_active();
+        let segmented = !self.segments.is_empty();
+        if segmented && !self.bitrate_enabled() {
+            return Err("Segments need a video format such as mp4 or mkv.".to_string());
         }
-
-        let parsed_output_fps = if self.video_options_enabled() {
+        if segmented && self.effective_audio_track() == AudioTrack::Mix {
+            return Err(
+                "Segments join one audio track. Pick a track instead of mix all.".to_string(),
+            );
+        }
+        let parsed_output_fps = if self.editor_input_enabled(InputField::Fps) {
             let output_fps = self.output_fps.trim().to_string();
             let Some(parsed_output_fps) = parse_output_fps(&output_fps) else {
-                self.status_message = "FPS must be a number greater than 0.".to_string();
-                return;
+                return Err("FPS must be a number greater than 0.".to_string());
             };
             Some(parsed_output_fps)
         } else {
             None
         };
-        let parsed_output_bitrate_kbps = if self.bitrate_enabled() {
+        let target_size_mb =
+            if self.editor_input_enabled(InputField::TargetSize) && self.target_size_set() {
+                let Some(target_size_mb) = parse_target_size_mb(self.output_target_size_mb.value())
+                else {
+                    return Err("Target size must be a number of MB greater than 0.".to_string());
+                };
+                Some(target_size_mb)
+            } else {
+                None
+            };
+        let parsed_output_bitrate_kbps = if self.editor_input_enabled(InputField::Bitrate) {
             let output_bitrate = self.output_bitrate_kbps.trim().to_string();
             let Some(parsed_output_bitrate_kbps) = parse_output_bitrate_kbps(&output_bitrate)
             else {
-                self.status_message = "Bitrate must be a whole number greater than 0.".to_string();
-                return;
+                return Err("Bitrate must be a whole number greater than 0.".to_string());
             };
             Some(parsed_output_bitrate_kbps)
         } else {
             None
         };
-        let scale_percent = if self.video_options_enabled() {
+        let scale_percent = if self.editor_input_enabled(InputField::ScalePercent) {
             let Some(scale_percent) = parse_output_scale_percent(&self.output_scale_percent) else {
-                self.status_message =
-                    "Scale percent must be a whole number between 1 and 100.".to_string();
-                return;
+                return Err("Scale percent must be a whole number between 1 and 100.".to_string());
             };
             scale_percent
         } else {
             100
         };
+        let source_resolution = self
+            .selected_video_stats
+            .as_ref()
+            .and_then(|stats| stats.width.zip(stats.height));
+        let crop = if self.editor_input_enabled(InputField::Crop) {
+            parse_crop_input(self.output_crop.value(), source_resolution)?
+        } else {
+            None
+        };
 
-        let output_name = enforce_output_extension(output, self.output_format);
-        self.output_name = output_name.clone();
-        self.output_cursor = self.output_cursor.min(self.output_name.chars().count());
+        let caption = if self.editor_input_enabled(InputField::Caption)
+            && !self.output_caption.value().trim().is_empty()
+        {
+            let Some(font_size) = parse_caption_size(self.caption_size.value()) else {
+                return Err(format!(
+                    "Caption size must be a whole number between {MIN_CAPTION_SIZE} and {MAX_CAPTION_SIZE}."
+                ));
+            };
+            Some(caption_filter(
+                self.output_caption.value().trim(),
+                self.caption_position,
+                font_size,
+                self.caption_outline,
+            ))
+        } else {
+            None
+        };
+
+        let max_width = if self.animated_image_output_selected() {
+            parse_max_width(self.output_max_width.value())?
+        } else {
+            None
+        };
 
-        let requested_output_path = resolve_output_path(&input_path, &output_name);
-        let output_path = next_available_output_path(&requested_output_path);
-        self.sync_output_name_with_path(&output_name, &output_path);
-        self.status_message = format!("Running ffmpeg -> {}", output_path.display());
+        let speed = if !self.editor_input_enabled(InputField::Speed) {
+            1.0
+        } else {
+            let Some(speed) = parse_speed(self.output_speed.value()) else {
+                return Err(format!(
+                    "Speed must be a number between {MIN_SPEED} and {MAX_SPEED}."
+                ));
+            };
+            speed
+        };
+
+        let color = if self.color_adjust && self.editor_input_enabled(InputField::Color) {
+            parse_color_adjust(
+                self.color_brightness.value(),
+                self.color_contrast.value(),
+                self.color_saturation.value(),
+                self.color_gamma.value(),
+            )?
+            .eq_filter()
+        } else {
+            None
+        };
+
+        let Some(stats_period) = parse_stats_period(self.ffmpeg_stats_period.value()) else {
+            return Err("Stats period must be a number of seconds greater than 0.".to_string());
+        };
+        let log_level = effective_log_level(self.ffmpeg_log_level, self.ffmpeg_progress_only);
+
+        if stream_copy && self.effective_audio_track() == AudioTrack::Mix {
+            return Err("Mixing audio tracks needs re-encoding. Turn off stream copy.".to_string());
+        }
+
+        let subtitle_codec_args = if self.editor_input_enabled(InputField::Streams) {
+            self.subtitle_codec_args()?
+        } else {
+            Vec::new()
+        };
+
+        let mut seek_start = start;
+        let mut clip_duration_seconds = clip_duration;
+        let mut status_note = None;
+        if stream_copy {
+            // Copied streams can only be cut on keyframes, so start on one
+            // instead of letting players show a frozen or black lead-in.
+            let target = start_seconds;
+            let keyframes = match self.keyframe_hint() {
+                Some(hint) => hint
+                    .keyframes
+                    .clone()
+                    .ok_or_else(|| "ffprobe failed".to_string()),
+                None => Err("still scanning".to_string()),
+            };
+            match keyframes {
+                Ok(keyframes) => {
+                    if let Some(keyframe) = nearest_keyframe(&keyframes, target) {
+                        if keyframe >= end_seconds {
+                            return Err("Nearest keyframe is past the end time. Extend the range or turn off stream copy.".to_string());
+                        }
+                        seek_start = format!("{keyframe:.3}");
+                        clip_duration_seconds = end_seconds - keyframe;
+                        status_note =
+                            Some(format!(" (start snapped to keyframe at {keyframe:.3}s)"));
+                    } else {
+                        status_note =
+                            Some(" (no keyframe found near start, not snapped)".to_string());
+                    }
+                }
+                Err(err) => {
+                    status_note = Some(format!(" (keyframe scan failed: {err}, not snapped)"));
+                }
+            }
+        }
+
+        // -t limits the output, which speed changes stretch or shrink.
+        let output_duration_seconds = clip_duration_seconds / speed;
+
+        let output_name = enforce_output_extension(output, self.output_format);
+        let requested_output_path =
+            resolve_output_path(&self.output_anchor(&input_path), &output_name);
+        // A sequence writes `name_%05d.ext`; the job reports its first frame.
+        let (output_path, output_target) = if self.frame_sequence_selected() {
+            let (pattern, first_frame) = image_sequence_paths(&requested_output_path);
+            (first_frame, pattern)
+        } else {
+            let output_path = next_available_output_path(&requested_output_path);
+            (output_path.clone(), output_path)
+        };
 
         let mut ffmpeg_args = vec![
             "-y".to_string(),
             "-hide_banner".to_string(),
+            "-loglevel".to_string(),
+            log_level.to_string(),
+            "-stats_period".to_string(),
+            stats_period.clone(),
+        ];
+        ffmpeg_args.extend(stream_input_args(&input_path));
+        ffmpeg_args.extend([
             "-ss".to_string(),
-            start.clone(),
+            seek_start.clone(),
             "-i".to_string(),
             input_path.display().to_string(),
             "-t".to_string(),
-            clip_duration.to_string(),
-            "-sn".to_string(),
+            output_duration_seconds.to_string(),
             "-dn".to_string(),
             "-fflags".to_string(),
             "+genpts".to_string(),
             "-avoid_negative_ts".to_string(),
             "make_zero".to_string(),
-        ];
+        ]);
+        if subtitle_codec_args.is_empty() {
+            ffmpeg_args.push("-sn".to_string());
+        }
         let mut filters = Vec::new();
+        // Deinterlace before anything resizes the fields.
+        if self.deinterlace && self.editor_input_enabled(InputField::Deinterlace) {
+            filters.push("bwdif=mode=send_frame".to_string());
+        }
+        if let Some(crop) = crop {
+            filters.push(crop.to_filter());
+        }
+        // Denoise at source size; sharpen after scaling so it works on the
+        // output pixels.
+        if self.editor_input_enabled(InputField::Denoise)
+            && let Some(denoise) = denoise_filter(self.denoise_level)
+        {
+            filters.push(denoise);
+        }
+        if let Some(color) = color {
+            filters.push(color);
+        }
         if self.video_options_enabled() && scale_percent != 100 {
-            let scale_filter = if let Some(stats) = self.selected_video_stats.as_ref() {
-                if let (Some(width), Some(height)) = (stats.width, stats.height) {
-                    let (scaled_width, scaled_height) =
-                        scaled_resolution_for_percent(width, height, scale_percent);
-                    format!("scale={scaled_width}:{scaled_height}")
-                } else {
-                    format!(
-                        "scale=trunc(iw*{scale_percent}/100/2)*2:trunc(ih*{scale_percent}/100/2)*2"
-                    )
-                }
+            // Scale is applied after crop, so size it from the cropped frame.
+            let scale_source = crop
+                .map(|crop| (crop.width, crop.height))
+                .or(source_resolution);
+            let scale_filter = if let Some((width, height)) = scale_source {
+                let (scaled_width, scaled_height) =
+                    scaled_resolution_for_percent(width, height, scale_percent);
+                format!("scale={scaled_width}:{scaled_height}")
             } else {
                 format!("scale=trunc(iw*{scale_percent}/100/2)*2:trunc(ih*{scale_percent}/100/2)*2")
             };
             filters.push(scale_filter);
         }
+        if self.editor_input_enabled(InputField::Sharpen)
+            && let Some(sharpen) = sharpen_filter(self.sharpen_level)
+        {
+            filters.push(sharpen);
+        }
+        if let Some(caption) = caption {
+            filters.push(caption);
+        }
+        if speed != 1.0 && self.video_options_enabled() {
+            filters.push(format!("setpts=PTS/{speed}"));
+        }
 
+        // What the confirm prompt reports for long or large exports.
+        let mut codec_summary = self.output_format.to_string();
+        let mut estimated_kbps = None;
+        let mut audio_encoded = false;
+        let mut audio_filters = if speed != 1.0 {
+            atempo_filters(speed)
+        } else {
+            Vec::new()
+        };
+        if self.editor_input_enabled(InputField::Channels)
+            && let Some(pan) = channel_filter(self.audio_channels)
+        {
+            audio_filters.push(pan);
+        }
         if self.audio_only_output_selected() {
             let (audio_codec, audio_args) = match self.output_format {
-                "mp3" => ("libmp3lame", vec!["-b:a".to_string(), "192k".to_string()]),
-                "m4a" => ("aac", vec!["-b:a".to_string(), "192k".to_string()]),
+                "mp3" => (
+                    "libmp3lame",
+                    vec!["-b:a".to_string(), format!("{AUDIO_BITRATE_KBPS}k")],
+                ),
+                "m4a" => (
+                    "aac",
+                    vec!["-b:a".to_string(), format!("{AUDIO_BITRATE_KBPS}k")],
+                ),
                 "wav" => ("pcm_s16le", Vec::new()),
                 "flac" => ("flac", Vec::new()),
-                _ => ("aac", vec!["-b:a".to_string(), "192k".to_string()]),
+                _ => (
+                    "aac",
+                    vec!["-b:a".to_string(), format!("{AUDIO_BITRATE_KBPS}k")],
+                ),
             };
+            ffmpeg_args.extend(self.audio_only_stream_maps());
             ffmpeg_args.extend([
-                "-map".to_string(),
-                "0:a:0?".to_string(),
                 "-vn".to_string(),
                 "-c:a".to_string(),
                 audio_codec.to_string(),
             ]);
             ffmpeg_args.extend(audio_args);
-        } else if self.output_format == "gif" {
+            audio_encoded = true;
+            codec_summary = audio_codec.to_string();
+            estimated_kbps = Some(match self.output_format {
+                "wav" => 1_411,
+                "flac" => 900,
+                _ => AUDIO_BITRATE_KBPS,
+            });
+        } else if self.still_image_output_selected() {
+            if self.frame_sequence {
+                let Some(parsed_output_fps) = parsed_output_fps else {
+                    return Err("FPS must be a number greater than 0.".to_string());
+                };
+                filters.push(format!("fps={parsed_output_fps}"));
+                codec_summary =
+                    format!("{} sequence at {parsed_output_fps} fps", self.output_format);
+            } else {
+                ffmpeg_args.extend(["-frames:v".to_string(), "1".to_string()]);
+                codec_summary = format!("single {} frame", self.output_format);
+            }
+            ffmpeg_args.extend(["-map".to_string(), "0:v:0".to_string(), "-an".to_string()]);
+            if self.output_format == "jpg" {
+                ffmpeg_args.extend(["-q:v".to_string(), "2".to_string()]);
+            }
+        } else if self.animated_image_output_selected() {
             let Some(parsed_output_fps) = parsed_output_fps else {
-                self.status_message = "FPS must be a number greater than 0.".to_string();
-                return;
+                return Err("FPS must be a number greater than 0.".to_string());
             };
             filters.push(format!("fps={parsed_output_fps}"));
-            ffmpeg_args.extend([
-                "-map".to_string(),
-                "0:v:0?".to_string(),
-                "-an".to_string(),
-                "-loop".to_string(),
-                "0".to_string(),
-            ]);
+            if let Some(max_width) = max_width {
+                filters.push(format!("scale=min(iw\\,{max_width}):-2:flags=lanczos"));
+            }
+            if self.output_format == "webp" {
+                let encoder = if self.ffmpeg_encoder_available("libwebp_anim") {
+                    "libwebp_anim"
+                } else {
+                    "libwebp"
+                };
+                ffmpeg_args.extend([
+                    "-map".to_string(),
+                    "0:v:0?".to_string(),
+                    "-c:v".to_string(),
+                    encoder.to_string(),
+                    "-lossless".to_string(),
+                    "0".to_string(),
+                    "-q:v".to_string(),
+                    "75".to_string(),
+                    "-compression_level".to_string(),
+                    "4".to_string(),
+                ]);
+            } else if self.output_format == "apng" {
+                ffmpeg_args.extend([
+                    "-map".to_string(),
+                    "0:v:0?".to_string(),
+                    "-c:v".to_string(),
+                    "apng".to_string(),
+                    "-pred".to_string(),
+                    "mixed".to_string(),
+                    "-plays".to_string(),
+                    "0".to_string(),
+                    "-f".to_string(),
+                    "apng".to_string(),
+                ]);
+            } else if self.gif_palette {
+                // Build a palette from the clip itself, then map frames onto it.
+                // Far smaller and cleaner than ffmpeg's generic 256-color palette.
+                let graph = format!(
+                    "[0:v:0]{},split[frames][source];[source]palettegen=stats_mode=diff[palette];[frames][palette]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle[gif]",
+                    filters.join(",")
+                );
+                filters.clear();
+                ffmpeg_args.extend([
+                    "-filter_complex".to_string(),
+                    graph,
+                    "-map".to_string(),
+                    "[gif]".to_string(),
+                ]);
+            } else {
+                ffmpeg_args.extend(["-map".to_string(), "0:v:0?".to_string()]);
+            }
+            ffmpeg_args.push("-an".to_string());
+            if self.output_format != "apng" {
+                ffmpeg_args.extend(["-loop".to_string(), "0".to_string()]);
+            }
+        } else if stream_copy {
+            codec_summary = "stream copy".to_string();
+            estimated_kbps = self.source_bitrate_kbps();
+            ffmpeg_args.extend(self.container_stream_maps());
+            if self.remove_audio {
+                ffmpeg_args.push("-an".to_string());
+            }
+            ffmpeg_args.extend(["-c".to_string(), "copy".to_string()]);
+            if supports_faststart(&output_path) {
+                ffmpeg_args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+            }
+            // Copy everything else, but let text subtitles change format.
+            ffmpeg_args.extend(subtitle_codec_args);
         } else {
             let Some(parsed_output_fps) = parsed_output_fps else {
-                self.status_message = "FPS must be a number greater than 0.".to_string();
-                return;
+                return Err("FPS must be a number greater than 0.".to_string());
             };
-            let Some(parsed_output_bitrate_kbps) = parsed_output_bitrate_kbps else {
-                self.status_message = "Bitrate must be a whole number greater than 0.".to_string();
-                return;
+            if !codec_fits_container(self.output_video_codec, self.output_format) {
+                return Err(format!(
+                    "{} can't be written to {}. Pick mp4 or mkv, or another codec.",
+                    self.output_video_codec, self.output_format
+                ));
+            }
+            let (video_encoder, fell_back) = self.resolved_video_encoder();
+            if fell_back {
+                status_note = Some(format!(
+                    " ({} encoder unavailable, using {video_encoder})",
+                    self.output_video_codec
+                ));
+            }
+            let audio_tracks = if self.waveform_active() {
+                if video_encoder == "copy" {
+                    return Err(
+                        "Waveform video needs a codec to encode with, not copy.".to_string()
+                    );
+                }
+                let audio_map = self.waveform_audio_map()?;
+                let Some(waveform) = waveform_filter(self.waveform_style, &parsed_output_fps)
+                else {
+                    return Err("Unknown waveform style.".to_string());
+                };
+                ffmpeg_args.extend([
+                    "-filter_complex".to_string(),
+                    format!("[{audio_map}]{waveform}[wave]"),
+                    "-map".to_string(),
+                    "[wave]".to_string(),
+                    "-map".to_string(),
+                    audio_map,
+                ]);
+                1
+            } else if segmented {
+                if video_encoder == "copy" {
+                    return Err(
+                        "Segments are re-encoded. Pick a codec other than copy.".to_string()
+                    );
+                }
+                usize::from(self.segment_audio_map().is_some())
+            } else {
+                ffmpeg_args.extend(self.container_stream_maps());
+                self.mapped_audio_track_count()
+            };
+            ffmpeg_args.extend(["-c:v".to_string(), video_encoder.to_string()]);
+            codec_summary = format!("{} ({video_encoder})", self.output_video_codec);
+            if self.waveform_active() {
+                codec_summary.push_str(&format!(", {} from audio", self.waveform_style));
+            }
+            let audio_kbps = AUDIO_BITRATE_KBPS * audio_tracks as u32;
+            let parsed_output_bitrate_kbps = match (target_size_mb, parsed_output_bitrate_kbps) {
+                (Some(target_size_mb), _) => {
+                    let Some(video_kbps) = target_video_bitrate_kbps(
+                        target_size_mb,
+                        output_duration_seconds,
+                        audio_kbps,
+                    ) else {
+                        return Err(format!(
+                            "{target_size_mb} MB is too small for this clip: {audio_kbps} kbps audio leaves no room for video."
+                        ));
+                    };
+                    codec_summary.push_str(&format!(", {video_kbps} kbps for {target_size_mb} MB"));
+                    video_kbps
+                }
+                (None, Some(parsed_output_bitrate_kbps)) => parsed_output_bitrate_kbps,
+                (None, None) => {
+                    return Err("Bitrate must be a whole number greater than 0.".to_string());
+                }
             };
-            let (video_encoder, preset) = if self.use_gpu_encoding {
-                ("h264_nvenc", "p4")
+            estimated_kbps = if video_encoder == "copy" {
+                self.source_bitrate_kbps()
             } else {
-                ("libx264", "veryfast")
+                Some(parsed_output_bitrate_kbps + audio_kbps)
             };
-            ffmpeg_args.extend([
-                "-map".to_string(),
-                "0:v:0?".to_string(),
-                "-c:v".to_string(),
-                video_encoder.to_string(),
-                "-preset".to_string(),
-                preset.to_string(),
-                "-b:v".to_string(),
-                format!("{parsed_output_bitrate_kbps}k"),
-                "-pix_fmt".to_string(),
-                "yuv420p".to_string(),
-                "-r".to_string(),
-                parsed_output_fps,
-            ]);
-            if self.remove_audio {
-                ffmpeg_args.push("-an".to_string());
+            if video_encoder == "copy" {
+                if !filters.is_empty() {
+                    return Err(
+                        "Codec copy cannot be combined with video filters, speed, or a caption."
+                            .to_string(),
+                    );
+                }
             } else {
+                ffmpeg_args.extend(video_encoder_speed_args(video_encoder));
+                // Duplicates or drops frames to an even rate, which keeps
+                // variable-rate recordings in sync in other editors.
+                if self.constant_fps && self.editor_input_enabled(InputField::ConstantFps) {
+                    ffmpeg_args.extend(["-vsync".to_string(), "cfr".to_string()]);
+                }
+                ffmpeg_args.extend(["-b:v".to_string(), format!("{parsed_output_bitrate_kbps}k")]);
+                // A single pass only aims at the average; capping the peak
+                // rate keeps a hard scene from blowing the size budget.
+                if target_size_mb.is_some() {
+                    ffmpeg_args.extend([
+                        "-maxrate".to_string(),
+                        format!("{parsed_output_bitrate_kbps}k"),
+                        "-bufsize".to_string(),
+                        format!("{}k", parsed_output_bitrate_kbps * 2),
+                    ]);
+                }
+                ffmpeg_args.extend([
+                    "-pix_fmt".to_string(),
+                    "yuv420p".to_string(),
+                    "-r".to_string(),
+                    parsed_output_fps,
+                ]);
+            }
+            // Apple players only recognize HEVC in MP4/MOV with the hvc1 tag.
+            if matches!(video_encoder, "libx265" | "hevc_nvenc")
+                && matches!(self.output_format, "mp4" | "mov")
+            {
+                ffmpeg_args.extend(["-tag:v".to_string(), "hvc1".to_string()]);
+            }
+            if self.remove_audio && !self.waveform_active() {
+                ffmpeg_args.push("-an".to_string());
+            } else if audio_tracks > 0 {
+                audio_encoded = true;
                 ffmpeg_args.extend([
-                    "-map".to_string(),
-                    "0:a:0?".to_string(),
                     "-c:a".to_string(),
                     "aac".to_string(),
                     "-b:a".to_string(),
-                    "192k".to_string(),
+                    format!("{AUDIO_BITRATE_KBPS}k"),
                 ]);
             }
-            ffmpeg_args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+            ffmpeg_args.extend(subtitle_codec_args);
+            if supports_faststart(&output_path) {
+                ffmpeg_args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+            }
         }
-        if !filters.is_empty() {
+        // Segments run the video filters inside their own graph below.
+        if !filters.is_empty() && !segmented {
             ffmpeg_args.extend(["-vf".to_string(), filters.join(",")]);
         }
+        let normalize = audio_encoded
+            && self.normalize_audio
+            && self.editor_input_enabled(InputField::Normalize);
+        // Mixed tracks run through -filter_complex, so the other audio
+        // filters join that chain instead of a separate -af.
+        let audio_mix = if audio_encoded && !segmented {
+            self.audio_mix_graph()
+        } else {
+            None
+        };
+        let graph_pads = match &audio_mix {
+            Some((inputs, mix_filters)) => {
+                audio_filters.splice(0..0, mix_filters.iter().cloned());
+                (inputs.clone(), format!("[{MIXED_AUDIO_PAD}]"))
+            }
+            None => (String::new(), String::new()),
+        };
+        let filters_before_loudnorm = audio_filters.clone();
+        if normalize {
+            // Single-pass form; a two-pass export rewrites it once measured.
+            audio_filters.push(loudnorm_filter(None));
+        }
+        let mut audio_filter_arg_index = None;
+        if segmented {
+            let audio_map = if audio_encoded {
+                self.segment_audio_map()
+            } else {
+                None
+            };
+            let relative_segments = self
+                .segments
+                .iter()
+                .map(|(start, end)| (start - start_seconds, end - start_seconds))
+                .collect::<Vec<_>>();
+            ffmpeg_args.extend([
+                "-filter_complex".to_string(),
+                segments_filter_graph(
+                    &relative_segments,
+                    audio_map.as_deref(),
+                    &filters,
+                    &audio_filters,
+                ),
+                "-map".to_string(),
+                "[vout]".to_string(),
+            ]);
+            if audio_map.is_some() {
+                ffmpeg_args.extend(["-map".to_string(), "[aout]".to_string()]);
+            }
+        } else if audio_encoded && !audio_filters.is_empty() {
+            let (inputs, outputs) = &graph_pads;
+            let chain = format!("{inputs}{}{outputs}", audio_filters.join(","));
+            if audio_mix.is_some() {
+                ffmpeg_args.extend(["-filter_complex".to_string(), chain]);
+                audio_filter_arg_index = Some(ffmpeg_args.len() - 1);
+                ffmpeg_args.extend(["-map".to_string(), outputs.clone()]);
+            } else {
+                ffmpeg_args.extend(["-af".to_string(), chain]);
+                audio_filter_arg_index = Some(ffmpeg_args.len() - 1);
+            }
+        }
 
-        ffmpeg_args.push(output_path.display().to_string());
+        ffmpeg_args.extend([
+            "-map_metadata".to_string(),
+            if self.keep_metadata { "0" } else { "-1" }.to_string(),
+        ]);
 
-        let command_line = format!(
-            "ffmpeg {}",
-            ffmpeg_args
-                .iter()
-                .map(|arg| shell_quote(arg))
-                .collect::<Vec<_>>()
-                .join(" ")
+        // Extra args go last so they can override anything generated above.
+        ffmpeg_args.extend(extra_args);
+        ffmpeg_args.push(output_target.display().to_string());
+
+        let mut job = FfmpegJob {
+            kind: FfmpegJobKind::Clip,
+            command_line: ffmpeg_command_line(&ffmpeg_args),
+            input_path: input_path.clone(),
+            args: ffmpeg_args,
+            output_path,
+            progress_only: self.ffmpeg_progress_only,
+            duration_seconds: output_duration_seconds,
+            follow_up: None,
+            file_time: if self.editor_input_enabled(InputField::FileTime) {
+                export_file_time(self.file_time_mode, &input_path, start_seconds)
+            } else {
+                None
+            },
+        };
+        if normalize
+            && self.normalize_two_pass
+            && let Some(filter_arg_index) = audio_filter_arg_index
+        {
+            let mut analysis_filters = filters_before_loudnorm.clone();
+            analysis_filters.push(loudnorm_analysis_filter());
+            let (inputs, outputs) = &graph_pads;
+            let analysis_chain = format!("{inputs}{}{outputs}", analysis_filters.join(","));
+            let analysis_audio_args = if audio_mix.is_some() {
+                vec![
+                    "-filter_complex".to_string(),
+                    analysis_chain,
+                    "-map".to_string(),
+                    outputs.clone(),
+                ]
+            } else {
+                vec![
+                    "-map".to_string(),
+                    self.analysis_audio_map(),
+                    "-af".to_string(),
+                    analysis_chain,
+                ]
+            };
+            // loudnorm prints its measurements at info level.
+            let mut analysis_args = vec![
+                "-y".to_string(),
+                "-hide_banner".to_string(),
+                "-loglevel".to_string(),
+                "info".to_string(),
+                "-stats_period".to_string(),
+                stats_period,
+            ];
+            analysis_args.extend(stream_input_args(&input_path));
+            analysis_args.extend([
+                "-ss".to_string(),
+                seek_start,
+                "-i".to_string(),
+                input_path.display().to_string(),
+                "-t".to_string(),
+                output_duration_seconds.to_string(),
+                "-vn".to_string(),
+                "-sn".to_string(),
+                "-dn".to_string(),
+            ]);
+            analysis_args.extend(analysis_audio_args);
+            analysis_args.extend(["-f".to_string(), "null".to_string(), "-".to_string()]);
+            job = FfmpegJob {
+                kind: FfmpegJobKind::Clip,
+                command_line: ffmpeg_command_line(&analysis_args),
+                input_path: input_path.clone(),
+                args: analysis_args,
+                output_path: job.output_path.clone(),
+                progress_only: job.progress_only,
+                duration_seconds: output_duration_seconds,
+                follow_up: Some(Box::new(LoudnormFollowUp {
+                    job,
+                    filter_arg_index,
+                    filters_before: filters_before_loudnorm,
+                    graph_pads,
+                })),
+                file_time: None,
+            };
+            codec_summary.push_str(", two-pass loudnorm");
+        }
+        let mut status_note = status_note.unwrap_or_default();
+        if job.follow_up.is_some() {
+            status_note.push_str(" (pass 1/2: measuring loudness)");
+        }
+        let estimated_bytes = estimated_kbps
+            .map(|kbps| (kbps as f64 * 1_000.0 / 8.0 * output_duration_seconds) as u64);
+        let size = estimated_bytes
+            .map(|bytes| format!("~{}", format_bytes(bytes)))
+            .unwrap_or_else(|| "size unknown".to_string());
+        let summary = format!(
+            "{} | {codec_summary} | {size} | {}",
+            TimeInput::from_seconds(output_duration_seconds).to_ffmpeg_timestamp(),
+            job.output_path.display()
         );
+        Ok(EditorExport {
+            job,
+            output_name,
+            sync_output_name: !self.frame_sequence_selected(),
+            output_duration_seconds,
+            estimated_bytes,
+            summary,
+            status_note,
+        })
+    }
+
+    /// Start and End in seconds, checked against each other and the file.
+    pub(super) fn validated_time_range(&self) -> Result<(f64, f64), String> {
+        if !self.start_time.has_valid_minute_second_range()
+            || !self.end_time.has_valid_minute_second_range()
+        {
+            return Err("Minutes and seconds must be between 00 and 59.".to_string());
+        }
+
+        let start_seconds = self.start_time.to_seconds_f64();
+        let end_seconds = self.end_time.to_seconds_f64();
+        if let Some(bounds) = self.selected_video_bounds {
+            let (first, last) = (bounds.start_seconds, bounds.end_seconds);
+            if start_seconds < first {
+                return Err(format!(
+                    "Start time must be >= {}.",
+                    TimeInput::from_seconds(bounds.start_seconds).to_ffmpeg_timestamp()
+                ));
+            }
+            if start_seconds >= last {
+                return Err(format!(
+                    "Start time must be < {}.",
+                    TimeInput::from_seconds(bounds.end_seconds).to_ffmpeg_timestamp()
+                ));
+            }
+            if end_seconds > last {
+                return Err(format!(
+                    "End time must be <= {}.",
+                    TimeInput::from_seconds(bounds.end_seconds).to_ffmpeg_timestamp()
+                ));
+            }
+        }
+
+        if end_seconds <= start_seconds {
+            return Err("End time must be greater than start time.".to_string());
+        }
+        Ok((start_seconds, end_seconds))
+    }
+
+    pub fn run_editor_export(&mut self) {
+        self.commit_time_offset();
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to enable editing and export."
+                    .to_string();
+            return;
+        }
+
+        if self.keyframe_scan_pending() {
+            self.keyframe_wait = Some(KeyframeWait::Export {
+                commands: self.unsnapped_editor_commands(),
+            });
+            self.status_message =
+                "Scanning keyframes near the start. The copy starts when it's done; Esc cancels."
+                    .to_string();
+            return;
+        }
+
+        let export = match self.prepare_editor_export() {
+            Ok(export) => export,
+            Err(message) => {
+                self.status_message = message;
+                return;
+            }
+        };
+
+        let low_space = self.export_low_space(&export);
+        if low_space.is_some()
+            || self
+                .export_needs_confirmation(export.output_duration_seconds, export.estimated_bytes)
+        {
+            self.status_message = if low_space.is_some() {
+                "Low disk space. Confirm to start ffmpeg anyway.".to_string()
+            } else {
+                "Large export. Confirm to start ffmpeg.".to_string()
+            };
+            self.pending_export = Some(PendingExport {
+                summary: export.summary,
+                job: export.job,
+                status_note: export.status_note,
+                low_space,
+            });
+            return;
+        }
+
+        self.launch_editor_export(export.job, &export.status_note);
+    }
+
+    /// Launches the form's export without the large-export confirmation,
+    /// for batches. `note` is appended to the status line. Unless
+    /// `ignore_free_space`, an export that would leave too little free
+    /// space isn't started and `LaunchError::LowSpace` says why.
+    pub(super) fn launch_editor_export_now(
+        &mut self,
+        note: &str,
+        ignore_free_space: bool,
+    ) -> Result<(), LaunchError> {
+        let export = self.prepare_editor_export().map_err(LaunchError::Build)?;
+        if !ignore_free_space && let Some(warning) = self.export_low_space(&export) {
+            return Err(LaunchError::LowSpace(warning));
+        }
+        self.launch_editor_export(export.job, &format!("{}{note}", export.status_note));
+        Ok(())
+    }
+
+    /// The free-space warning for writing `export`, if any.
+    fn export_low_space(&self, export: &EditorExport) -> Option<String> {
+        let dir = export.job.output_path.parent()?;
+        self.low_space_warning(dir, export.estimated_bytes)
+    }
+
+    /// Builds the export and writes the final output name back into the
+    /// form.
+    fn prepare_editor_export(&mut self) -> Result<EditorExport, String> {
+        let mut export = self.build_editor_export()?;
+        if export.sync_output_name {
+            self.sync_output_name_with_path(&export.output_name, &export.job.output_path);
+        } else {
+            self.output_name = std::mem::take(&mut export.output_name);
+            self.output_cursor = self.output_cursor.min(self.output_name.chars().count());
+        }
+        Ok(export)
+    }
+
+    /// The command Enter would run, or why the form can't export yet. A
+    /// two-pass export lists both passes.
+    pub fn editor_command_preview(&self) -> Result<Vec<String>, String> {
+        self.planned_editor_export().map(|(_, commands)| commands)
+    }
+
+    /// Output path and commands the form would run.
+    pub(super) fn planned_editor_export(&self) -> Result<(PathBuf, Vec<String>), String> {
+        let export = self.build_editor_export()?;
+        let mut commands = vec![export.job.command_line];
+        if let Some(follow_up) = export.job.follow_up {
+            commands.push(follow_up.job.command_line);
+        }
+        Ok((export.job.output_path, commands))
+    }
+
+    /// Copies the assembled command (both passes, one per line) for use
+    /// outside rt.
+    pub fn copy_editor_command(&mut self) {
+        let commands = match self.editor_command_preview() {
+            Ok(commands) => commands,
+            Err(message) => {
+                self.status_message = format!("Nothing to copy: {message}");
+                return;
+            }
+        };
+        self.status_message = match copy_to_clipboard(&commands.join("\n")) {
+            Ok(method) => format!("Copied ffmpeg command via {method}."),
+            Err(err) => format!("Failed to copy ffmpeg command: {err}"),
+        };
+    }
+
+    pub fn has_pending_export(&self) -> bool {
+        self.pending_export.is_some()
+    }
+
+    pub fn pending_export_summary(&self) -> Option<&str> {
+        self.pending_export
+            .as_ref()
+            .map(|pending| pending.summary.as_str())
+    }
+
+    /// The free-space warning the pending export was held back for.
+    pub fn pending_export_low_space(&self) -> Option<&str> {
+        self.pending_export
+            .as_ref()
+            .and_then(|pending| pending.low_space.as_deref())
+    }
+
+    pub fn cancel_pending_export(&mut self) {
+        if self.pending_export.take().is_some() {
+            self.status_message = "Export not started.".to_string();
+        }
+    }
+
+    pub fn confirm_pending_export(&mut self) {
+        let Some(pending) = self.pending_export.take() else {
+            return;
+        };
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        self.launch_editor_export(pending.job, &pending.status_note);
+    }
+
+    /// Sets the duration (minutes) and estimated size (MB) at or above which
+    /// exports ask for confirmation first. 0 disables a threshold.
+    /// Thresholds left as `None` keep the `config.toml` value.
+    pub fn set_export_confirm_thresholds(&mut self, minutes: Option<f64>, megabytes: Option<u64>) {
+        if let Some(minutes) = minutes {
+            self.export_confirm_minutes = minutes;
+        }
+        if let Some(megabytes) = megabytes {
+            self.export_confirm_megabytes = megabytes;
+        }
+    }
+
+    fn export_needs_confirmation(
+        &self,
+        duration_seconds: f64,
+        estimated_bytes: Option<u64>,
+    ) -> bool {
+        let long = self.export_confirm_minutes > 0.0
+            && duration_seconds >= self.export_confirm_minutes * 60.0;
+        let large = self.export_confirm_megabytes > 0
+            && estimated_bytes
+                .is_some_and(|bytes| bytes >= self.export_confirm_megabytes * 1_000_000);
+        long || large
+    }
+
+    fn source_bitrate_kbps(&self) -> Option<u32> {
+        self.selected_video_stats
+            .as_ref()
+            .and_then(|stats| stats.bitrate_kbps)
+    }
+
+    pub fn audio_streams(&self) -> Vec<&MediaStream> {
+        self.media_streams
+            .iter()
+            .filter(|stream| stream.kind == StreamKind::Audio)
+            .collect()
+    }
+
+    /// The Audio track choice, or `Auto` while the field doesn't apply.
+    fn effective_audio_track(&self) -> AudioTrack {
+        if self.editor_input_enabled(InputField::AudioTrack) {
+            self.audio_track
+        } else {
+            AudioTrack::Auto
+        }
+    }
+
+    /// Streams ticked in the Streams field, skipping audio when it is removed
+    /// or chosen by the Audio track field instead.
+    fn mapped_streams(&self) -> impl Iterator<Item = &MediaStream> {
+        let skip_audio = self.remove_audio || self.effective_audio_track() != AudioTrack::Auto;
+        self.media_streams
+            .iter()
+            .zip(&self.stream_selected)
+            .filter(move |(stream, selected)| {
+                **selected && !(skip_audio && stream.kind == StreamKind::Audio)
+            })
+            .map(|(stream, _)| stream)
+    }
+
+    pub(super) fn chosen_audio_stream(&self) -> Option<&MediaStream> {
+        match self.effective_audio_track() {
+            AudioTrack::Track(index) => self.audio_streams().get(index).copied(),
+            AudioTrack::Auto | AudioTrack::Mix => None,
+        }
+    }
+
+    fn audio_only_stream_maps(&self) -> Vec<String> {
+        match self.effective_audio_track() {
+            AudioTrack::Auto => vec!["-map".to_string(), "0:a:0?".to_string()],
+            AudioTrack::Track(_) => self
+                .chosen_audio_stream()
+                .map(|stream| vec!["-map".to_string(), stream.map_arg()])
+                .unwrap_or_default(),
+            // Mapped from the filter graph output instead.
+            AudioTrack::Mix => Vec::new(),
+        }
+    }
+
+    /// `[0:a][0:b]` input pads and the merge filters when mixing tracks.
+    fn audio_mix_graph(&self) -> Option<(String, Vec<String>)> {
+        if self.effective_audio_track() != AudioTrack::Mix {
+            return None;
+        }
+        let tracks = self.audio_streams();
+        let inputs = tracks
+            .iter()
+            .map(|stream| format!("[{}]", stream.map_arg()))
+            .collect::<String>();
+        let channels = tracks
+            .iter()
+            .map(|stream| stream.channels.unwrap_or(2))
+            .collect::<Vec<_>>();
+        Some((inputs, amerge_stereo_filters(&channels)))
+    }
 
-        match self.start_ffmpeg_job(command_line.clone(), ffmpeg_args, output_path.clone()) {
+    /// `-map` args for video container outputs. Falls back to the first
+    /// video and audio stream when ffprobe could not list the streams.
+    fn container_stream_maps(&self) -> Vec<String> {
+        if self.media_streams.is_empty() {
+            let mut maps = vec!["-map".to_string(), "0:v:0?".to_string()];
+            if !self.remove_audio {
+                maps.extend(["-map".to_string(), "0:a:0?".to_string()]);
+            }
+            return maps;
+        }
+        let mut maps = self
+            .mapped_streams()
+            .flat_map(|stream| ["-map".to_string(), stream.map_arg()])
+            .collect::<Vec<_>>();
+        if let Some(stream) = self.chosen_audio_stream() {
+            maps.extend(["-map".to_string(), stream.map_arg()]);
+        }
+        maps
+    }
+
+    fn mapped_audio_track_count(&self) -> usize {
+        if self.remove_audio {
+            return 0;
+        }
+        if self.media_streams.is_empty() {
+            return 1;
+        }
+        match self.effective_audio_track() {
+            AudioTrack::Auto => self
+                .mapped_streams()
+                .filter(|stream| stream.kind == StreamKind::Audio)
+                .count(),
+            AudioTrack::Track(_) | AudioTrack::Mix => 1,
+        }
+    }
+
+    /// The single track a waveform is drawn from and muxed with.
+    fn waveform_audio_map(&self) -> Result<String, String> {
+        match self.effective_audio_track() {
+            AudioTrack::Mix => {
+                Err("Waveform needs one audio track. Pick a track instead of mix all.".to_string())
+            }
+            _ => Ok(self
+                .chosen_audio_stream()
+                .or_else(|| self.audio_streams().first().copied())
+                .map(MediaStream::map_arg)
+                .unwrap_or_else(|| "0:a:0".to_string())),
+        }
+    }
+
+    /// Loudness is measured on the chosen track, else the first mapped one.
+    fn analysis_audio_map(&self) -> String {
+        self.chosen_audio_stream()
+            .or_else(|| {
+                self.mapped_streams()
+                    .find(|stream| stream.kind == StreamKind::Audio)
+            })
+            .map(MediaStream::map_arg)
+            .unwrap_or_else(|| "0:a:0?".to_string())
+    }
+
+    /// Per-stream `-c:s:N` args for the mapped subtitles. MP4/MOV only hold
+    /// `mov_text`, and MKV can't hold `mov_text`, so text tracks are converted
+    /// and everything else is copied.
+    fn subtitle_codec_args(&self) -> Result<Vec<String>, String> {
+        let mp4_family = matches!(self.output_format, "mp4" | "mov");
+        let mut args = Vec::new();
+        for (output_index, stream) in self
+            .mapped_streams()
+            .filter(|stream| stream.kind == StreamKind::Subtitle)
+            .enumerate()
+        {
+            let codec = if mp4_family {
+                if stream.is_bitmap_subtitle() {
+                    return Err(format!(
+                        "Subtitle #{} ({}) is a bitmap format that {} can't hold. Use mkv or untick it.",
+                        stream.index, stream.codec, self.output_format
+                    ));
+                }
+                "mov_text"
+            } else if stream.codec == "mov_text" {
+                "srt"
+            } else {
+                "copy"
+            };
+            args.extend([format!("-c:s:{output_index}"), codec.to_string()]);
+        }
+        Ok(args)
+    }
+
+    pub(super) fn launch_editor_export(&mut self, job: FfmpegJob, status_note: &str) {
+        let command_line = job.command_line.clone();
+        let output_path = job.output_path.clone();
+        match self.start_ffmpeg_job(job) {
             Ok(()) => {
-                self.status_message = format!("Running ffmpeg -> {}", output_path.display());
+                self.status_message =
+                    format!("Running ffmpeg -> {}{status_note}", output_path.display());
+                if let Some(note) = self.take_free_space_unread_note() {
+                    self.ffmpeg_output.append_line(note);
+                }
             }
             Err(err) => {
                 self.ffmpeg_output.replace_with_command_error(
@@ -254,6 +1159,7 @@ impl App {
                     &[],
                     &[],
                     Some(&err.to_string()),
+                    Some(&output_path),
                 ) {
                     Ok(log_path) => {
                         self.status_message = format!(
@@ -271,6 +1177,56 @@ impl App {
     }
 }
 
+impl App {
+    /// Video bitrate the target size works out to for the current range,
+    /// speed, and audio tracks, for the form preview.
+    pub fn target_size_video_kbps(&self) -> Option<u32> {
+        let target_size_mb = parse_target_size_mb(self.output_target_size_mb.value())?;
+        let clip_seconds = if self.segments.is_empty() {
+            self.end_time.to_seconds_f64() - self.start_time.to_seconds_f64()
+        } else {
+            segments_total_seconds(&self.segments)
+        };
+        if clip_seconds <= 0.0 {
+            return None;
+        }
+        let speed = parse_speed(self.output_speed.value()).unwrap_or(1.0);
+        let audio_kbps = AUDIO_BITRATE_KBPS * self.mapped_audio_track_count() as u32;
+        target_video_bitrate_kbps(target_size_mb, clip_seconds / speed, audio_kbps)
+    }
+
+    /// Picks the first locally available encoder for the selected codec.
+    /// Returns `(encoder, fell_back)`; when nothing matches, falls back to
+    /// H.264 so exports still work on minimal ffmpeg builds.
+    pub fn resolved_video_encoder(&self) -> (&'static str, bool) {
+        let available =
+            |encoder: &&str| *encoder == "copy" || self.ffmpeg_encoder_available(encoder);
+
+        if let Some(encoder) =
+            video_encoder_candidates(self.output_video_codec, self.use_gpu_encoding)
+                .into_iter()
+                .find(available)
+        {
+            return (encoder, false);
+        }
+
+        let fallback = video_encoder_candidates("h264", self.use_gpu_encoding)
+            .into_iter()
+            .find(available)
+            .unwrap_or("libx264");
+        (fallback, true)
+    }
+}
+
+/// Audio bitrate used for AAC/MP3 re-encodes.
+const AUDIO_BITRATE_KBPS: u32 = 192;
+
+/// How far around the requested start to look for a keyframe to snap to.
+pub(super) const KEYFRAME_SEARCH_WINDOW_SECONDS: f64 = 30.0;
+
+/// Filter graph output pad for merged audio tracks.
+const MIXED_AUDIO_PAD: &str = "mixed";
+
 pub(super) fn default_output_fps(stats: Option<&crate::media::VideoStats>) -> String {
     if let Some(fps) = stats
         .map(|stats| stats.fps.trim())
@@ -296,6 +1252,89 @@ pub(super) fn parse_output_fps(value: &str) -> Option<String> {
     Some(trimmed.to_string())
 }
 
+/// Empty means no limit; otherwise a pixel width of at least 2.
+/// Share of a target size left for audio and video once the container's
+/// own overhead is taken out.
+const TARGET_SIZE_PAYLOAD_SHARE: f64 = 0.97;
+
+/// Below this the target is treated as unreachable rather than encoding an
+/// unwatchable clip.
+const MIN_TARGET_VIDEO_KBPS: u32 = 50;
+
+/// Video bitrate that keeps a clip of `duration_seconds` under `target_mb`
+/// (10^6 bytes, so it also fits MiB-based limits) next to `audio_kbps`.
+fn target_video_bitrate_kbps(
+    target_mb: f64,
+    duration_seconds: f64,
+    audio_kbps: u32,
+) -> Option<u32> {
+    if duration_seconds <= 0.0 {
+        return None;
+    }
+    let total_kbps = target_mb * 8_000.0 * TARGET_SIZE_PAYLOAD_SHARE / duration_seconds;
+    let video_kbps = (total_kbps - f64::from(audio_kbps)).floor();
+    (video_kbps >= f64::from(MIN_TARGET_VIDEO_KBPS)).then_some(video_kbps as u32)
+}
+
+fn parse_target_size_mb(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|size| size.is_finite() && *size > 0.0)
+}
+
+fn parse_max_width(value: &str) -> Result<Option<u32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<u32>() {
+        Ok(width) if width >= 2 => Ok(Some(width)),
+        _ => Err("Max width must be empty or a whole number of pixels >= 2.".to_string()),
+    }
+}
+
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 4.0;
+
+fn parse_speed(value: &str) -> Option<f64> {
+    let speed = value
+        .trim()
+        .trim_end_matches(['x', 'X'])
+        .parse::<f64>()
+        .ok()?;
+    (MIN_SPEED..=MAX_SPEED).contains(&speed).then_some(speed)
+}
+
+fn parse_caption_size(value: &str) -> Option<u32> {
+    let size = value.trim().parse::<u32>().ok()?;
+    (MIN_CAPTION_SIZE..=MAX_CAPTION_SIZE)
+        .contains(&size)
+        .then_some(size)
+}
+
+fn parse_stats_period(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let parsed = trimmed.parse::<f64>().ok()?;
+    if !parsed.is_finite() || parsed <= 0.0 {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+// Progress-only mode caps verbosity at "warning" so only the stats line plus
+// warnings/errors reach the output panel. Quieter user choices are kept.
+fn effective_log_level(selected: &'static str, progress_only: bool) -> &'static str {
+    if !progress_only {
+        return selected;
+    }
+    match selected {
+        "error" => "error",
+        _ => "warning",
+    }
+}
+
 fn parse_output_bitrate_kbps(value: &str) -> Option<u32> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -0,0 +1,158 @@
+// Snapshots of the editor form.
+// - Covers everything loading a file and applying a preset overwrites, so
+//   a command can be planned for another file and the form put back as it
+//   was, edits and all.
+use std::path::PathBuf;
+
+use crate::{
+    media::{MediaStream, VideoStats},
+    model::{AudioTrack, InputField, RightTab, TextInput, TimeInput, VideoBounds},
+};
+
+use super::App;
+
+/// The per-file and preset fields of the editor, as the user left them.
+pub(super) struct EditorForm {
+    right_tab: RightTab,
+    selected_video: Option<PathBuf>,
+    selected_video_stats: Option<VideoStats>,
+    selected_video_bounds: Option<VideoBounds>,
+    media_streams: Vec<MediaStream>,
+    stream_selected: Vec<bool>,
+    stream_cursor: usize,
+    audio_track: AudioTrack,
+    audio_channels: &'static str,
+    segments: Vec<(f64, f64)>,
+    start_time: TimeInput,
+    end_time: TimeInput,
+    pending_time_offset: Option<(InputField, String)>,
+    output_name: String,
+    output_cursor: usize,
+    output_format: &'static str,
+    output_video_codec: &'static str,
+    output_fps: String,
+    output_fps_cursor: usize,
+    output_bitrate_kbps: String,
+    output_bitrate_cursor: usize,
+    output_target_size_mb: TextInput,
+    output_scale_percent: String,
+    output_scale_percent_cursor: usize,
+    output_max_width: TextInput,
+    output_crop: TextInput,
+    output_speed: TextInput,
+    use_gpu_encoding: bool,
+    stream_copy: bool,
+    remove_audio: bool,
+    normalize_audio: bool,
+    normalize_two_pass: bool,
+    gif_palette: bool,
+    keep_metadata: bool,
+    deinterlace: bool,
+    constant_fps: bool,
+    active_input: InputField,
+    start_part: usize,
+    end_part: usize,
+    overwrite_fps_on_next_type: bool,
+    overwrite_bitrate_on_next_type: bool,
+    overwrite_scale_percent_on_next_type: bool,
+    editor_form_scroll: usize,
+    status_message: String,
+}
+
+impl App {
+    pub(super) fn editor_form(&self) -> EditorForm {
+        EditorForm {
+            right_tab: self.right_tab,
+            selected_video: self.selected_video.clone(),
+            selected_video_stats: self.selected_video_stats.clone(),
+            selected_video_bounds: self.selected_video_bounds,
+            media_streams: self.media_streams.clone(),
+            stream_selected: self.stream_selected.clone(),
+            stream_cursor: self.stream_cursor,
+            audio_track: self.audio_track,
+            audio_channels: self.audio_channels,
+            segments: self.segments.clone(),
+            start_time: self.start_time.clone(),
+            end_time: self.end_time.clone(),
+            pending_time_offset: self.pending_time_offset.clone(),
+            output_name: self.output_name.clone(),
+            output_cursor: self.output_cursor,
+            output_format: self.output_format,
+            output_video_codec: self.output_video_codec,
+            output_fps: self.output_fps.clone(),
+            output_fps_cursor: self.output_fps_cursor,
+            output_bitrate_kbps: self.output_bitrate_kbps.clone(),
+            output_bitrate_cursor: self.output_bitrate_cursor,
+            output_target_size_mb: self.output_target_size_mb.clone(),
+            output_scale_percent: self.output_scale_percent.clone(),
+            output_scale_percent_cursor: self.output_scale_percent_cursor,
+            output_max_width: self.output_max_width.clone(),
+            output_crop: self.output_crop.clone(),
+            output_speed: self.output_speed.clone(),
+            use_gpu_encoding: self.use_gpu_encoding,
+            stream_copy: self.stream_copy,
+            remove_audio: self.remove_audio,
+            normalize_audio: self.normalize_audio,
+            normalize_two_pass: self.normalize_two_pass,
+            gif_palette: self.gif_palette,
+            keep_metadata: self.keep_metadata,
+            deinterlace: self.deinterlace,
+            constant_fps: self.constant_fps,
+            active_input: self.active_input,
+            start_part: self.start_part,
+            end_part: self.end_part,
+            overwrite_fps_on_next_type: self.overwrite_fps_on_next_type,
+            overwrite_bitrate_on_next_type: self.overwrite_bitrate_on_next_type,
+            overwrite_scale_percent_on_next_type: self.overwrite_scale_percent_on_next_type,
+            editor_form_scroll: self.editor_form_scroll.get(),
+            status_message: self.status_message.clone(),
+        }
+    }
+
+    pub(super) fn restore_editor_form(&mut self, form: EditorForm) {
+        self.right_tab = form.right_tab;
+        self.selected_video = form.selected_video;
+        self.selected_video_stats = form.selected_video_stats;
+        self.selected_video_bounds = form.selected_video_bounds;
+        self.media_streams = form.media_streams;
+        self.stream_selected = form.stream_selected;
+        self.stream_cursor = form.stream_cursor;
+        self.audio_track = form.audio_track;
+        self.audio_channels = form.audio_channels;
+        self.segments = form.segments;
+        self.start_time = form.start_time;
+        self.end_time = form.end_time;
+        self.pending_time_offset = form.pending_time_offset;
+        self.output_name = form.output_name;
+        self.output_cursor = form.output_cursor;
+        self.output_format = form.output_format;
+        self.output_video_codec = form.output_video_codec;
+        self.output_fps = form.output_fps;
+        self.output_fps_cursor = form.output_fps_cursor;
+        self.output_bitrate_kbps = form.output_bitrate_kbps;
+        self.output_bitrate_cursor = form.output_bitrate_cursor;
+        self.output_target_size_mb = form.output_target_size_mb;
+        self.output_scale_percent = form.output_scale_percent;
+        self.output_scale_percent_cursor = form.output_scale_percent_cursor;
+        self.output_max_width = form.output_max_width;
+        self.output_crop = form.output_crop;
+        self.output_speed = form.output_speed;
+        self.use_gpu_encoding = form.use_gpu_encoding;
+        self.stream_copy = form.stream_copy;
+        self.remove_audio = form.remove_audio;
+        self.normalize_audio = form.normalize_audio;
+        self.normalize_two_pass = form.normalize_two_pass;
+        self.gif_palette = form.gif_palette;
+        self.keep_metadata = form.keep_metadata;
+        self.deinterlace = form.deinterlace;
+        self.constant_fps = form.constant_fps;
+        self.active_input = form.active_input;
+        self.start_part = form.start_part;
+        self.end_part = form.end_part;
+        self.overwrite_fps_on_next_type = form.overwrite_fps_on_next_type;
+        self.overwrite_bitrate_on_next_type = form.overwrite_bitrate_on_next_type;
+        self.overwrite_scale_percent_on_next_type = form.overwrite_scale_percent_on_next_type;
+        self.editor_form_scroll.set(form.editor_form_scroll);
+        self.status_message = form.status_message;
+    }
+}
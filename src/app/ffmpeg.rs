@@ -6,16 +6,23 @@
 use std::{
     fs::OpenOptions,
     io::{self, BufReader, Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
     sync::mpsc,
     thread,
-    time::{SystemTime, UNIX_EPOCH},
+    time::Instant,
 };
 
-use crate::media::summarize_ffmpeg_error;
+use crate::media::{
+    loudnorm_filter, parse_ffmpeg_progress_seconds, parse_loudnorm_measurement, progress_percent,
+    set_file_modified, shell_quote, summarize_ffmpeg_error,
+};
 
-use super::{App, FfmpegEvent, FfmpegStream, RunningEditor};
+use super::{
+    App, FfmpegEvent, FfmpegJob, FfmpegJobKind, FfmpegStream, LoudnormFollowUp, RunningEditor,
+    concat::remove_concat_list, job_logs::run_transcript, remux::remux_playback_note,
+    stats::job_encoder,
+};
 
 impl App {
     pub fn cancel_editor_export(&mut self) {
@@ -23,6 +30,11 @@ impl App {
             self.status_message = "No running editor export to cancel.".to_string();
             return;
         };
+        let batch_note = if self.editor_batch.is_some() {
+            " (and the rest of the batch)"
+        } else {
+            ""
+        };
 
         match running.child.try_wait() {
             Ok(Some(_)) => {
@@ -30,7 +42,9 @@ impl App {
             }
             Ok(None) => match running.child.kill() {
                 Ok(()) => {
-                    self.status_message = "Cancellation requested for editor export.".to_string();
+                    self.status_message =
+                        format!("Cancellation requested for editor export{batch_note}.");
+                    self.cancel_editor_batch();
                     self.ffmpeg_output
                         .append_line("Cancellation requested by user (x).".to_string());
                 }
@@ -44,14 +58,9 @@ impl App {
         }
     }
 
-    pub(super) fn start_ffmpeg_job(
-        &mut self,
-        command_line: String,
-        ffmpeg_args: Vec<String>,
-        output_path: PathBuf,
-    ) -> io::Result<()> {
+    pub(super) fn start_ffmpeg_job(&mut self, job: FfmpegJob) -> io::Result<()> {
         let mut child = Command::new("ffmpeg")
-            .args(&ffmpeg_args)
+            .args(&job.args)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -72,12 +81,22 @@ impl App {
 
         self.ffmpeg_spinner_frame = 0;
         self.ffmpeg_output
-            .begin_stream(&command_line, "Streaming ffmpeg output...");
+            .begin_stream(&job.command_line, "Streaming ffmpeg output...");
         self.running_editor = Some(RunningEditor {
+            kind: job.kind,
             child,
             rx,
-            command_line,
-            output_path,
+            command_line: job.command_line,
+            input_path: job.input_path,
+            output_path: job.output_path,
+            progress_only: job.progress_only,
+            duration_seconds: job.duration_seconds,
+            progress_percent: None,
+            follow_up: job.follow_up,
+            started_at: Instant::now(),
+            pass_started_at: Instant::now(),
+            encoder: job_encoder(&job.args),
+            file_time: job.file_time,
             stdout_raw: Vec::new(),
             stderr_raw: Vec::new(),
             stdout_pending: Vec::new(),
@@ -90,8 +109,12 @@ impl App {
     pub(super) fn pump_running_editor_events(&mut self) {
         let mut streamed_lines = Vec::new();
 
+        let batch = self.perf.pump_batch;
         if let Some(running) = self.running_editor.as_mut() {
-            while let Ok(event) = running.rx.try_recv() {
+            for _ in 0..batch {
+                let Ok(event) = running.rx.try_recv() else {
+                    break;
+                };
                 match event {
                     FfmpegEvent::Chunk { stream, data } => {
                         let lines = consume_stream_chunk(running, stream, &data);
@@ -126,7 +149,16 @@ impl App {
             Err(err) => {
                 self.append_ffmpeg_output_line(format!("stderr: failed to poll ffmpeg: {err}"));
                 self.status_message = format!("Failed to monitor ffmpeg process: {err}");
-                self.running_editor = None;
+                if self
+                    .running_editor
+                    .take()
+                    .is_some_and(|running| running.kind == FfmpegJobKind::Concat)
+                {
+                    remove_concat_list();
+                }
+                if let Some(summary) = self.advance_editor_batch(false) {
+                    self.status_message.push_str(&format!(" | {summary}"));
+                }
             }
         }
     }
@@ -159,7 +191,44 @@ impl App {
         let stdout_raw = running.stdout_raw;
         let stderr_raw = running.stderr_raw;
         let command_line = running.command_line;
+        let input_path = running.input_path;
         let output_path = running.output_path;
+        let kind = running.kind;
+        let started_at = running.started_at;
+
+        if kind == FfmpegJobKind::Concat {
+            remove_concat_list();
+        }
+
+        if status.success()
+            && let Some(follow_up) = running.follow_up
+        {
+            // Analysis transcript only goes to the run log; the export itself
+            // reports the outcome.
+            let _ = self.append_ffmpeg_run_log(
+                &command_line,
+                status.code(),
+                &stdout_raw,
+                &stderr_raw,
+                None,
+                None,
+            );
+            self.start_loudnorm_follow_up(*follow_up, &stderr_raw);
+            if let Some(next) = self.running_editor.as_mut() {
+                next.started_at = started_at;
+            }
+            if self.running_editor.is_none()
+                && let Some(summary) = self.advance_editor_batch(false)
+            {
+                self.status_message.push_str(&format!(" | {summary}"));
+            }
+            return;
+        }
+
+        if kind == FfmpegJobKind::Verify {
+            self.finish_verify(status, &command_line, &input_path, &stdout_raw, &stderr_raw);
+            return;
+        }
 
         if status.success() {
             let mut status_message = match self.append_ffmpeg_run_log(
@@ -168,27 +237,52 @@ impl App {
                 &stdout_raw,
                 &stderr_raw,
                 None,
+                Some(&output_path),
             ) {
                 Ok(log_path) => {
                     format!(
-                        "Created clip: {} (log: {})",
+                        "Created {}: {} (log: {})",
+                        kind.output_label(),
                         output_path.display(),
                         log_path.display()
                     )
                 }
                 Err(log_err) => {
                     format!(
-                        "Created clip: {} (log write failed: {log_err})",
+                        "Created {}: {} (log write failed: {log_err})",
+                        kind.output_label(),
                         output_path.display()
                     )
                 }
             };
 
+            if let Some(time) = running.file_time
+                && let Err(err) = set_file_modified(&output_path, time)
+            {
+                status_message.push_str(&format!(" (file time not set: {err})"));
+            }
+            self.last_output = Some(output_path.clone());
             if let Err(refresh_err) = self.refresh_file_browser_after_save(&output_path) {
                 status_message.push_str(&format!(" (browser refresh failed: {refresh_err})"));
             }
+            if kind == FfmpegJobKind::Clip
+                && !self.editor_batch_running()
+                && let Some(note) = self.apply_after_export(&input_path, &output_path)
+            {
+                status_message.push_str(&format!(" | {note}"));
+            }
+            if kind == FfmpegJobKind::Remux {
+                status_message.push_str(&format!(" | {}", remux_playback_note(&output_path)));
+            }
 
             self.status_message = status_message;
+            self.record_export_stats(
+                &running.encoder,
+                &output_path,
+                running.duration_seconds,
+                started_at.elapsed(),
+            );
+            self.finish_batch_step(true);
         } else {
             let stderr = String::from_utf8_lossy(&stderr_raw);
             let detail = summarize_ffmpeg_error(&stderr);
@@ -199,6 +293,7 @@ impl App {
                 &stdout_raw,
                 &stderr_raw,
                 None,
+                Some(&output_path),
             ) {
                 Ok(log_path) => {
                     self.status_message =
@@ -209,10 +304,56 @@ impl App {
                         format!("ffmpeg failed: {detail} (log write failed: {log_err})");
                 }
             }
+            self.finish_batch_step(false);
+        }
+    }
+
+    /// Moves a running batch on to its next file. The next launch replaces
+    /// the status line; the final summary is appended to it instead.
+    fn finish_batch_step(&mut self, succeeded: bool) {
+        let finished = std::mem::take(&mut self.status_message);
+        match self.advance_editor_batch(succeeded) {
+            Some(summary) => self.status_message = format!("{finished} | {summary}"),
+            None if self.running_editor.is_none() => self.status_message = finished,
+            None => {}
         }
     }
 
+    /// Starts the final pass of a two-pass loudnorm export using the values
+    /// the analysis run printed, or single-pass loudnorm if none parse.
+    fn start_loudnorm_follow_up(&mut self, follow_up: LoudnormFollowUp, analysis_stderr: &[u8]) {
+        let measured = parse_loudnorm_measurement(&String::from_utf8_lossy(analysis_stderr));
+        let mut filters = follow_up.filters_before;
+        filters.push(loudnorm_filter(measured.as_ref()));
+
+        let mut job = follow_up.job;
+        let (inputs, outputs) = follow_up.graph_pads;
+        job.args[follow_up.filter_arg_index] = format!("{inputs}{}{outputs}", filters.join(","));
+        job.command_line = ffmpeg_command_line(&job.args);
+        let status_note = if measured.is_some() {
+            " (pass 2/2)"
+        } else {
+            " (loudness not measurable, using single-pass loudnorm)"
+        };
+        self.launch_editor_export(job, status_note);
+    }
+
     fn append_stream_line(&mut self, stream: FfmpegStream, line: String) {
+        if let Some(running) = self.running_editor.as_mut()
+            && let Some(elapsed) = parse_ffmpeg_progress_seconds(&line)
+        {
+            running.progress_percent = progress_percent(elapsed, running.duration_seconds);
+        }
+        let progress_only = self
+            .running_editor
+            .as_ref()
+            .is_some_and(|running| running.progress_only);
+        if progress_only && is_ffmpeg_progress_line(&line) {
+            self.ffmpeg_output
+                .upsert_progress_line(format!("progress: {line}"));
+            return;
+        }
+
         let prefix = match stream {
             FfmpegStream::Stdout => "stdout",
             FfmpegStream::Stderr => "stderr",
@@ -224,6 +365,8 @@ impl App {
         self.ffmpeg_output.append_line(line);
     }
 
+    /// Appends the run to `ffmpeg_runs.log` and, with per-job logs on, to
+    /// a file named after `output_path`. Returns the most specific log.
     pub(super) fn append_ffmpeg_run_log(
         &self,
         command_line: &str,
@@ -231,45 +374,30 @@ impl App {
         stdout: &[u8],
         stderr: &[u8],
         launch_error: Option<&str>,
+        output_path: Option<&Path>,
     ) -> io::Result<PathBuf> {
         let log_path = self.initial_dir.join("ffmpeg_runs.log");
-        let mut file = OpenOptions::new()
+
+        // Build the whole entry first and append it with one write, so runs
+        // from several instances sharing this log never interleave.
+        let entry = run_transcript(
+            "ffmpeg",
+            command_line,
+            exit_code,
+            stdout,
+            stderr,
+            launch_error,
+        )?;
+        OpenOptions::new()
             .create(true)
             .append(true)
-            .open(&log_path)?;
-
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|duration| duration.as_secs())
-            .unwrap_or(0);
-
-        writeln!(file, "=== ffmpeg run @ {timestamp} ===")?;
-        writeln!(file, "command: {command_line}")?;
-        match exit_code {
-            Some(code) => writeln!(file, "exit_code: {code}")?,
-            None => writeln!(file, "exit_code: <none>")?,
-        }
-
-        if let Some(err) = launch_error {
-            writeln!(file, "launch_error: {err}")?;
-        }
-
-        writeln!(file, "--- stderr ---")?;
-        file.write_all(stderr)?;
-        if !stderr.ends_with(b"\n") {
-            writeln!(file)?;
-        }
+            .open(&log_path)?
+            .write_all(&entry)?;
 
-        writeln!(file, "--- stdout ---")?;
-        file.write_all(stdout)?;
-        if !stdout.ends_with(b"\n") {
-            writeln!(file)?;
+        match self.write_job_log(output_path, &entry)? {
+            Some(job_log_path) => Ok(job_log_path),
+            None => Ok(log_path),
         }
-
-        writeln!(file, "=== end ===")?;
-        writeln!(file)?;
-
-        Ok(log_path)
     }
 }
 
@@ -333,6 +461,13 @@ fn consume_stream_chunk(
     lines
 }
 
+// ffmpeg's periodic stats line, e.g. "frame=  120 fps= 60 ... speed=2.0x"
+// (audio-only encodes start at "size=").
+pub(super) fn is_ffmpeg_progress_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("frame=") || trimmed.starts_with("size=")
+}
+
 fn flush_pending_line(pending: &mut Vec<u8>) -> Option<String> {
     if pending.is_empty() {
         return None;
@@ -345,3 +480,13 @@ fn flush_pending_line(pending: &mut Vec<u8>) -> Option<String> {
 
     if line.is_empty() { None } else { Some(line) }
 }
+
+pub(super) fn ffmpeg_command_line(args: &[String]) -> String {
+    format!(
+        "ffmpeg {}",
+        args.iter()
+            .map(|arg| shell_quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
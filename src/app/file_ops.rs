@@ -0,0 +1,652 @@
+// File operations in the browser: rename, move, copy, and new directory.
+// - Prompts for a name or destination, prefilled where it helps.
+// - Move and copy act on the marked files, or the selected one when
+//   nothing is marked; the destination is a folder, or a new name for a
+//   single file.
+// - Existing targets need an explicit overwrite confirmation.
+// - Copies and moves run on a background thread with byte progress in the
+//   footer, since a large file or a move across drives takes a while.
+// - Marks, tags, and the editor's loaded file follow a renamed or moved file.
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+use crate::model::TextInput;
+
+use super::{App, files::entry_name, remaining_seconds};
+
+/// Read size for copies; progress is reported once per chunk.
+const COPY_CHUNK_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOpKind {
+    Rename,
+    Move,
+    Copy,
+    NewDir,
+}
+
+impl FileOpKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Rename => "Rename",
+            Self::Move => "Move",
+            Self::Copy => "Copy",
+            Self::NewDir => "New Directory",
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Rename => "rename",
+            Self::Move => "move",
+            Self::Copy => "copy",
+            Self::NewDir => "create",
+        }
+    }
+}
+
+/// Name or destination being typed for a file operation.
+pub(super) struct FileOpPrompt {
+    kind: FileOpKind,
+    sources: Vec<PathBuf>,
+    input: TextInput,
+    /// Planned (source, target) pairs waiting for the overwrite answer.
+    pending_overwrite: Option<Vec<(PathBuf, PathBuf)>>,
+    error: Option<String>,
+}
+
+/// What the worker thread reports while it runs.
+enum FileOpEvent {
+    /// Bytes the whole operation will copy at most.
+    Total(u64),
+    /// More bytes are in place.
+    Copied(u64),
+    /// The pair at `index` is done.
+    Finished {
+        index: usize,
+        result: io::Result<()>,
+    },
+}
+
+/// A copy, move, or rename running on a background thread.
+pub(super) struct RunningFileOp {
+    kind: FileOpKind,
+    pairs: Vec<(PathBuf, PathBuf)>,
+    rx: Receiver<FileOpEvent>,
+    total_bytes: u64,
+    copied_bytes: u64,
+    done: usize,
+    failures: Vec<String>,
+    started_at: Instant,
+}
+
+/// The prompt's contents for rendering.
+pub struct FileOpView<'a> {
+    pub kind: FileOpKind,
+    /// What the operation applies to, e.g. `clip.mp4` or `3 marked files`.
+    pub subject: String,
+    pub input: &'a TextInput,
+    /// Existing targets the operation would replace, while confirming.
+    pub overwrites: Option<usize>,
+    pub error: Option<&'a str>,
+}
+
+impl App {
+    /// Renames the selected file or directory.
+    pub fn start_rename_prompt(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return;
+        };
+        if !self.ensure_not_in_use("rename", &entry.path) {
+            return;
+        }
+        self.open_file_op_prompt(FileOpKind::Rename, vec![entry.path], &entry.name);
+    }
+
+    pub fn start_move_prompt(&mut self) {
+        self.start_transfer_prompt(FileOpKind::Move);
+    }
+
+    pub fn start_copy_prompt(&mut self) {
+        self.start_transfer_prompt(FileOpKind::Copy);
+    }
+
+    pub fn start_new_dir_prompt(&mut self) {
+        if self.showing_stdin_listing() {
+            self.status_message = "Leave the stdin listing (-) to create a directory.".to_string();
+            return;
+        }
+        self.open_file_op_prompt(FileOpKind::NewDir, Vec::new(), "");
+    }
+
+    /// Move or copy of the marked files, or the selected one.
+    fn start_transfer_prompt(&mut self, kind: FileOpKind) {
+        let sources = if self.marked.is_empty() {
+            let Some(entry) = self.selected_entry() else {
+                self.status_message = "No entry selected.".to_string();
+                return;
+            };
+            vec![entry.path.clone()]
+        } else {
+            self.marked.clone()
+        };
+        if kind == FileOpKind::Copy && sources.iter().any(|path| path.is_dir()) {
+            self.status_message = "Copy is only supported for files.".to_string();
+            return;
+        }
+        if kind == FileOpKind::Move
+            && !sources
+                .iter()
+                .all(|path| self.ensure_not_in_use("move", path))
+        {
+            return;
+        }
+        let destination = format!("{}/", self.cwd.display());
+        self.open_file_op_prompt(kind, sources, &destination);
+    }
+
+    fn open_file_op_prompt(&mut self, kind: FileOpKind, sources: Vec<PathBuf>, initial: &str) {
+        let mut input = TextInput::new(initial);
+        input.focus_append();
+        self.file_op_prompt = Some(FileOpPrompt {
+            kind,
+            sources,
+            input,
+            pending_overwrite: None,
+            error: None,
+        });
+    }
+
+    pub fn has_file_op_prompt(&self) -> bool {
+        self.file_op_prompt.is_some()
+    }
+
+    /// True while the prompt asks whether to replace existing files.
+    pub fn file_op_confirming_overwrite(&self) -> bool {
+        self.file_op_prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.pending_overwrite.is_some())
+    }
+
+    pub fn file_op_prompt(&self) -> Option<FileOpView<'_>> {
+        let prompt = self.file_op_prompt.as_ref()?;
+        let subject = match prompt.sources.as_slice() {
+            [] => self.cwd.display().to_string(),
+            [path] => entry_name(path),
+            sources if sources == self.marked.as_slice() => {
+                format!("{} marked files", sources.len())
+            }
+            sources => format!("{} files", sources.len()),
+        };
+        Some(FileOpView {
+            kind: prompt.kind,
+            subject,
+            input: &prompt.input,
+            overwrites: prompt
+                .pending_overwrite
+                .as_ref()
+                .map(|pairs| pairs.iter().filter(|(_, target)| target.exists()).count()),
+            error: prompt.error.as_deref(),
+        })
+    }
+
+    pub fn push_file_op_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.input.insert(ch);
+            prompt.error = None;
+        }
+    }
+
+    pub fn backspace_file_op(&mut self) {
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.input.backspace();
+            prompt.error = None;
+        }
+    }
+
+    pub fn move_file_op_cursor_left(&mut self) {
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.input.move_left();
+        }
+    }
+
+    pub fn move_file_op_cursor_right(&mut self) {
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.input.move_right();
+        }
+    }
+
+    pub fn cancel_file_op_prompt(&mut self) {
+        self.file_op_prompt = None;
+    }
+
+    /// Declines the overwrite and goes back to editing the name.
+    pub fn decline_file_op_overwrite(&mut self) {
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.pending_overwrite = None;
+        }
+    }
+
+    /// Plans the operation from the typed text. Existing targets ask for
+    /// confirmation first; otherwise it runs right away.
+    pub fn confirm_file_op_prompt(&mut self) {
+        let Some(prompt) = self.file_op_prompt.as_ref() else {
+            return;
+        };
+        if prompt.kind != FileOpKind::NewDir
+            && let Some(running) = self.running_file_op.as_ref()
+        {
+            let verb = running.kind.verb();
+            self.set_file_op_error(format!("Wait for the running {verb} to finish."));
+            return;
+        }
+        if let Some(pairs) = prompt.pending_overwrite.clone() {
+            self.run_file_op(pairs);
+            return;
+        }
+
+        let kind = prompt.kind;
+        let typed = prompt.input.value().to_string();
+        let plan = match kind {
+            FileOpKind::NewDir => {
+                self.create_dir(&typed);
+                return;
+            }
+            FileOpKind::Rename => rename_target(&prompt.sources[0], &typed),
+            FileOpKind::Move | FileOpKind::Copy => {
+                transfer_targets(&prompt.sources, &typed, &self.cwd)
+            }
+        };
+        let pairs = plan.and_then(|pairs| {
+            for (source, target) in &pairs {
+                if source == target {
+                    return Err("Source and destination are the same.".to_string());
+                }
+                if target.is_dir() {
+                    return Err(format!(
+                        "{} is a directory and can't be replaced.",
+                        target.display()
+                    ));
+                }
+            }
+            Ok(pairs)
+        });
+        let pairs = match pairs {
+            Ok(pairs) => pairs,
+            Err(error) => {
+                self.set_file_op_error(error);
+                return;
+            }
+        };
+
+        let existing = pairs
+            .iter()
+            .map(|(_, target)| target)
+            .filter(|target| target.exists())
+            .cloned()
+            .collect::<Vec<_>>();
+        if existing.is_empty() {
+            self.run_file_op(pairs);
+            return;
+        }
+        if !existing
+            .iter()
+            .all(|target| self.ensure_not_in_use("replace", target))
+        {
+            self.file_op_prompt = None;
+            return;
+        }
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.pending_overwrite = Some(pairs);
+        }
+    }
+
+    fn set_file_op_error(&mut self, error: String) {
+        if let Some(prompt) = self.file_op_prompt.as_mut() {
+            prompt.error = Some(error);
+        }
+    }
+
+    /// Creates the typed directory (with any missing parents) and selects it.
+    fn create_dir(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.set_file_op_error("Enter a directory name.".to_string());
+            return;
+        }
+        let path = self.cwd.join(name);
+        if path.exists() {
+            self.set_file_op_error(format!("{name} already exists."));
+            return;
+        }
+        if let Err(err) = fs::create_dir_all(&path) {
+            self.set_file_op_error(format!("Failed to create {name}: {err}"));
+            return;
+        }
+        self.file_op_prompt = None;
+        self.finish_file_op(&path, format!("Created directory: {name}"));
+    }
+
+    /// Hands the pairs to a worker thread; `pump_file_op` picks up its
+    /// progress and results.
+    fn run_file_op(&mut self, pairs: Vec<(PathBuf, PathBuf)>) {
+        let Some(prompt) = self.file_op_prompt.take() else {
+            return;
+        };
+        let kind = prompt.kind;
+        let (tx, rx) = mpsc::channel();
+        let work = pairs.clone();
+        thread::spawn(move || run_file_op_pairs(kind, &work, &tx));
+
+        self.status_message = match pairs.as_slice() {
+            [(source, _)] => format!("{}: {}...", kind.title(), entry_name(source)),
+            _ => format!("{}: {} files...", kind.title(), pairs.len()),
+        };
+        self.running_file_op = Some(RunningFileOp {
+            kind,
+            pairs,
+            rx,
+            total_bytes: 0,
+            copied_bytes: 0,
+            done: 0,
+            failures: Vec::new(),
+            started_at: Instant::now(),
+        });
+    }
+
+    /// Collects the worker's progress and finishes up once it is done.
+    /// Returns whether anything arrived.
+    pub(super) fn pump_file_op(&mut self) -> bool {
+        let mut arrived = false;
+        let mut moved = Vec::new();
+        let finished = loop {
+            let Some(running) = self.running_file_op.as_mut() else {
+                return false;
+            };
+            match running.rx.try_recv() {
+                Ok(FileOpEvent::Total(bytes)) => running.total_bytes = bytes,
+                Ok(FileOpEvent::Copied(bytes)) => running.copied_bytes += bytes,
+                Ok(FileOpEvent::Finished { index, result }) => {
+                    let (source, target) = &running.pairs[index];
+                    match result {
+                        Ok(()) => {
+                            running.done += 1;
+                            if running.kind != FileOpKind::Copy {
+                                moved.push((source.clone(), target.clone()));
+                            }
+                        }
+                        Err(err) => running
+                            .failures
+                            .push(format!("{}: {err}", entry_name(source))),
+                    }
+                }
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+            arrived = true;
+        };
+        for (source, target) in moved {
+            self.follow_moved_path(&source, &target);
+        }
+        if finished {
+            self.finish_running_file_op();
+            return true;
+        }
+        arrived
+    }
+
+    fn finish_running_file_op(&mut self) {
+        let Some(running) = self.running_file_op.take() else {
+            return;
+        };
+        let kind = running.kind;
+        let pairs = running.pairs;
+        let done = running.done;
+        let failures = running.failures;
+
+        let verb = kind.verb();
+        let summary = match (pairs.as_slice(), failures.first()) {
+            ([(source, target)], None) => format!(
+                "{}: {} -> {}",
+                kind.title(),
+                entry_name(source),
+                target.display()
+            ),
+            ([_], Some(failure)) => format!("Failed to {verb} {failure}"),
+            (_, None) => format!("{}: {done} files", kind.title()),
+            (_, Some(first)) => format!(
+                "{}: {done} of {} files; {} failed (first: {first})",
+                kind.title(),
+                pairs.len(),
+                failures.len()
+            ),
+        };
+        match pairs.first() {
+            Some((_, target)) if done > 0 => self.finish_file_op(target, summary),
+            _ => self.status_message = summary,
+        }
+    }
+
+    pub fn file_op_is_running(&self) -> bool {
+        self.running_file_op.is_some()
+    }
+
+    /// Title of the running operation, e.g. `Copy`, for the footer.
+    pub fn running_file_op_title(&self) -> Option<&'static str> {
+        self.running_file_op
+            .as_ref()
+            .map(|running| running.kind.title())
+    }
+
+    /// Share of the bytes in place, once the worker has sized the sources.
+    pub fn file_op_progress_percent(&self) -> Option<u8> {
+        let running = self.running_file_op.as_ref()?;
+        if running.total_bytes == 0 {
+            return None;
+        }
+        Some((running.copied_bytes.saturating_mul(100) / running.total_bytes).min(100) as u8)
+    }
+
+    pub(super) fn file_op_eta_seconds(&self) -> Option<u64> {
+        let running = self.running_file_op.as_ref()?;
+        remaining_seconds(running.started_at, self.file_op_progress_percent())
+    }
+
+    /// How the running copy or move uses `path`, for the in-use checks.
+    pub(super) fn file_op_using_path(&self, path: &Path) -> Option<String> {
+        let running = self.running_file_op.as_ref()?;
+        let verb = running.kind.verb();
+        running.pairs.iter().find_map(|(source, target)| {
+            if target.starts_with(path) {
+                Some(format!("the running {verb} writes {}", entry_name(target)))
+            } else if source.starts_with(path) {
+                Some(format!("the running {verb} reads {}", entry_name(source)))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Keeps marks, tags, and the editor pointing at a file that moved.
+    fn follow_moved_path(&mut self, source: &Path, target: &Path) {
+        for marked in self.marked.iter_mut() {
+            if marked == source {
+                *marked = target.to_path_buf();
+            }
+        }
+        // A failed index write only loses the tag; the move itself worked.
+        let _ = self.tag_index.rename(source, target);
+        if self.selected_video.as_deref() == Some(source) {
+            self.selected_video = Some(target.to_path_buf());
+        }
+    }
+
+    /// Refreshes the browser and selects `path` when it is listed.
+    fn finish_file_op(&mut self, path: &Path, summary: String) {
+        if let Err(err) = self.reload() {
+            self.status_message = format!("{summary}, but failed to refresh browser: {err}");
+            return;
+        }
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == path) {
+            self.selected = index;
+        }
+        self.status_message = summary;
+    }
+}
+
+/// Rename keeps the entry in its folder; the name can't contain `/`.
+fn rename_target(source: &Path, name: &str) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Enter a new name.".to_string());
+    }
+    if name.contains('/') {
+        return Err("Names can't contain `/`. Use move (m) to change folders.".to_string());
+    }
+    let parent = source.parent().unwrap_or_else(|| Path::new("."));
+    Ok(vec![(source.to_path_buf(), parent.join(name))])
+}
+
+/// An existing directory receives every source under its own name; any
+/// other path is the new name of a single source.
+fn transfer_targets(
+    sources: &[PathBuf],
+    destination: &str,
+    cwd: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let destination = destination.trim();
+    if destination.is_empty() {
+        return Err("Enter a destination folder.".to_string());
+    }
+    let destination = cwd.join(super::quick_jump::expand_home_dir(destination));
+    if destination.is_dir() {
+        return sources
+            .iter()
+            .map(|source| {
+                let name = source
+                    .file_name()
+                    .ok_or_else(|| format!("{} has no file name.", source.display()))?;
+                Ok((source.clone(), destination.join(name)))
+            })
+            .collect();
+    }
+    if sources.len() > 1 {
+        return Err(format!(
+            "{} is not a folder. Several files need an existing folder.",
+            destination.display()
+        ));
+    }
+    match destination.parent() {
+        Some(parent) if parent.is_dir() => Ok(vec![(sources[0].clone(), destination)]),
+        _ => Err(format!("No such folder: {}", destination.display())),
+    }
+}
+
+/// The worker thread: sizes the sources, then copies or moves each pair,
+/// reporting bytes as they land. Stops early once nobody is listening.
+fn run_file_op_pairs(kind: FileOpKind, pairs: &[(PathBuf, PathBuf)], tx: &Sender<FileOpEvent>) {
+    let sizes = pairs
+        .iter()
+        .map(|(source, _)| path_size(source))
+        .collect::<Vec<_>>();
+    if tx.send(FileOpEvent::Total(sizes.iter().sum())).is_err() {
+        return;
+    }
+    for (index, (source, target)) in pairs.iter().enumerate() {
+        let mut reported = 0;
+        let mut progress = |bytes: u64| {
+            reported += bytes;
+            let _ = tx.send(FileOpEvent::Copied(bytes));
+        };
+        let result = match kind {
+            FileOpKind::Copy => copy_file(source, target, &mut progress),
+            _ => move_path(source, target, &mut progress),
+        };
+        // A rename moves everything at once; count what wasn't reported.
+        if result.is_ok() {
+            let _ = tx.send(FileOpEvent::Copied(sizes[index].saturating_sub(reported)));
+        }
+        if tx.send(FileOpEvent::Finished { index, result }).is_err() {
+            return;
+        }
+    }
+}
+
+/// Bytes in a file, or in every file under a folder.
+fn path_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| path_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Renames, falling back to copy and delete across filesystems.
+fn move_path(source: &Path, target: &Path, progress: &mut dyn FnMut(u64)) -> io::Result<()> {
+    match fs::rename(source, target) {
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices && source.is_dir() => {
+            copy_dir_all(source, target, progress)?;
+            fs::remove_dir_all(source)
+        }
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices && source.is_file() => {
+            copy_file(source, target, progress)?;
+            fs::remove_file(source)
+        }
+        result => result,
+    }
+}
+
+/// `fs::copy` in chunks so progress can be reported; keeps the source's
+/// permissions like `fs::copy` does.
+fn copy_file(source: &Path, target: &Path, progress: &mut dyn FnMut(u64)) -> io::Result<()> {
+    let mut reader = fs::File::open(source)?;
+    let permissions = reader.metadata()?.permissions();
+    let mut writer = fs::File::create(target)?;
+    let mut buffer = vec![0; COPY_CHUNK_BYTES];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        writer.write_all(&buffer[..read])?;
+        progress(read as u64);
+    }
+    writer.flush()?;
+    fs::set_permissions(target, permissions)
+}
+
+/// Copies a folder tree; symlinks are copied as the files they point to.
+fn copy_dir_all(source: &Path, target: &Path, progress: &mut dyn FnMut(u64)) -> io::Result<()> {
+    fs::create_dir(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let to = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &to, progress)?;
+        } else {
+            copy_file(&entry.path(), &to, progress)?;
+        }
+    }
+    Ok(())
+}
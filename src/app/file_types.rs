@@ -0,0 +1,179 @@
+// File type icons and colors for the browser.
+// - Groups extensions (video, audio, image, subtitle, archive, ...) and gives
+//   each group an icon, a plain-text marker, and an optional color.
+// - Read from `file_types.ini` in the config dir; missing keys keep the
+//   built-in Nerd Font icons.
+use std::{collections::HashMap, fs, io};
+
+use crate::model::FileEntry;
+
+use super::{App, config_dir};
+
+const FILE_TYPES_FILE_NAME: &str = "file_types.ini";
+
+/// Group names as used in the config file, in lookup order.
+const GROUP_NAMES: [&str; 8] = [
+    "video", "audio", "image", "subtitle", "archive", "text", "code", "other",
+];
+
+struct FileTypeStyle {
+    /// Nerd Font glyph, shown unless `icons = ascii`.
+    icon: String,
+    /// Plain marker such as `[V]`, shown with `icons = ascii`.
+    marker: String,
+    /// Color name or `#rrggbb`; empty keeps the default color.
+    color: String,
+}
+
+pub(crate) struct FileTypeStyles {
+    ascii: bool,
+    directory: FileTypeStyle,
+    /// Indexed like `GROUP_NAMES`; the last entry is the fallback.
+    groups: Vec<FileTypeStyle>,
+    group_by_extension: HashMap<String, usize>,
+}
+
+fn style(icon: &str, marker: &str, color: &str) -> FileTypeStyle {
+    FileTypeStyle {
+        icon: icon.to_string(),
+        marker: marker.to_string(),
+        color: color.to_string(),
+    }
+}
+
+fn default_extensions(group: &str) -> &'static [&'static str] {
+    match group {
+        "video" => &[
+            "mp4", "mov", "mkv", "avi", "webm", "m4v", "mpeg", "mpg", "wmv", "flv",
+        ],
+        "audio" => &["mp3", "m4a", "wav", "flac", "aac", "ogg", "opus", "wma"],
+        "image" => &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"],
+        "subtitle" => &["srt", "vtt", "ass", "ssa", "sub"],
+        "archive" => &["zip", "tar", "gz", "bz2", "xz", "7z", "rar"],
+        "text" => &["md", "txt", "rtf", "pdf"],
+        "code" => &[
+            "rs", "toml", "json", "yaml", "yml", "ts", "js", "py", "go", "java",
+        ],
+        _ => &[],
+    }
+}
+
+impl Default for FileTypeStyles {
+    fn default() -> Self {
+        let groups = vec![
+            style("", "[V]", "lightgreen"),
+            style("", "[A]", "lightgreen"),
+            style("", "[I]", ""),
+            style("󰨖", "[S]", ""),
+            style("", "[Z]", ""),
+            style("", "[T]", ""),
+            style("󰈙", "[C]", ""),
+            style("", "[F]", ""),
+        ];
+        let mut styles = Self {
+            ascii: false,
+            directory: style("", "[D]", ""),
+            groups,
+            group_by_extension: HashMap::new(),
+        };
+        for (index, group) in GROUP_NAMES.iter().enumerate() {
+            styles.set_extensions(index, default_extensions(group).iter().copied());
+        }
+        styles
+    }
+}
+
+impl FileTypeStyles {
+    /// Missing file or keys keep the defaults; unknown keys are skipped.
+    pub(super) fn load() -> io::Result<Self> {
+        let mut styles = Self::default();
+        let Some(path) = config_dir().map(|dir| dir.join(FILE_TYPES_FILE_NAME)) else {
+            return Ok(styles);
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(styles),
+            Err(err) => return Err(err),
+        };
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            styles.apply(key.trim(), value.trim());
+        }
+        Ok(styles)
+    }
+
+    /// One `key = value` line: `icons = nerd|ascii`, or
+    /// `<group>.icon|marker|color|extensions`.
+    fn apply(&mut self, key: &str, value: &str) {
+        if key == "icons" {
+            self.ascii = value.eq_ignore_ascii_case("ascii");
+            return;
+        }
+        let Some((group, property)) = key.split_once('.') else {
+            return;
+        };
+        let index = GROUP_NAMES.iter().position(|name| *name == group);
+        if property == "extensions" {
+            if let Some(index) = index {
+                self.set_extensions(index, value.split_whitespace());
+            }
+            return;
+        }
+        let style = match (group, index) {
+            ("directory", _) => &mut self.directory,
+            (_, Some(index)) => &mut self.groups[index],
+            _ => return,
+        };
+        match property {
+            "icon" => style.icon = value.to_string(),
+            "marker" => style.marker = value.to_string(),
+            "color" => style.color = value.to_string(),
+            _ => {}
+        }
+    }
+
+    /// Moves `extensions` to the group, replacing its earlier list.
+    fn set_extensions<'a>(&mut self, group: usize, extensions: impl Iterator<Item = &'a str>) {
+        self.group_by_extension.retain(|_, index| *index != group);
+        for extension in extensions {
+            let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+            self.group_by_extension.insert(extension, group);
+        }
+    }
+
+    fn style_for(&self, entry: &FileEntry) -> &FileTypeStyle {
+        if entry.is_dir {
+            return &self.directory;
+        }
+        let group = entry
+            .path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| {
+                self.group_by_extension
+                    .get(&ext.to_ascii_lowercase())
+                    .copied()
+            })
+            .unwrap_or(GROUP_NAMES.len() - 1);
+        &self.groups[group]
+    }
+}
+
+impl App {
+    /// Icon (or marker) and color name for a browser row.
+    pub fn file_type_label(&self, entry: &FileEntry) -> (&str, &str) {
+        let style = self.file_types.style_for(entry);
+        let label = if self.file_types.ascii {
+            &style.marker
+        } else {
+            &style.icon
+        };
+        (label, &style.color)
+    }
+}
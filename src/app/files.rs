@@ -1,7 +1,8 @@
 // File-browser behavior for the left pane.
 // - Reads/sorts directory entries and manages selection movement.
 // - Handles directory navigation and entry activation.
-// - Starts delete confirmation flow and removes files after confirmation.
+// - Starts delete confirmation flow and trashes files or a folder after
+//   confirmation.
 // - Populates editor defaults when an editable media file is selected.
 use std::{
     fs, io,
@@ -11,16 +12,42 @@ use std::{
 
 use crate::{
     media::{
-        default_output_name, is_editable_media_file, output_format_for_path, probe_video_stats,
+        CHANNEL_MODES, MediaStream, StreamKind, VideoStats, default_output_name,
+        is_editable_media_file, output_format_for_path, probe_streams, probe_video_stats,
         probe_video_times,
     },
-    model::{FileEntry, InputField, RightTab, TimeInput},
+    model::{
+        AfterExport, AudioTrack, FileEntry, InputField, PageSize, RightTab, TimeInput, VideoBounds,
+    },
 };
 
-use super::{App, PendingDelete, editor::default_output_fps};
+use super::{
+    App, PendingDelete, dir_delete::DirDelete, editor::default_output_fps, trash::in_rt_trash,
+};
 
 const EDITOR_FORM_PAGE_STEP: usize = 8;
 
+/// What selecting a media file reads with ffprobe. Plain data, so it can be
+/// gathered on a background thread and loaded into the form later.
+#[derive(Debug, Clone)]
+pub(super) struct MediaProbe {
+    stats: Option<VideoStats>,
+    streams: Vec<MediaStream>,
+    /// Start, end, and bounds, or why ffprobe couldn't read them.
+    times: Result<(TimeInput, TimeInput, VideoBounds), String>,
+}
+
+impl MediaProbe {
+    /// Runs the three ffprobe calls; blocks until they finish.
+    pub(super) fn read(path: &Path) -> Self {
+        Self {
+            stats: probe_video_stats(path).ok(),
+            streams: probe_streams(path).unwrap_or_default(),
+            times: probe_video_times(path).map_err(|err| err.to_string()),
+        }
+    }
+}
+
 impl App {
     pub fn next(&mut self) {
         if self.entries.is_empty() {
@@ -59,14 +86,125 @@ impl App {
         self.selected = self.selected.saturating_sub(step);
     }
 
+    pub fn select_first_entry(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn select_last_entry(&mut self) {
+        self.selected = self.entries.len().saturating_sub(1);
+    }
+
+    /// Selects the most recently modified file, skipping directories.
+    pub fn select_newest_file(&mut self) {
+        let newest = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !entry.is_dir)
+            .filter_map(|(index, entry)| {
+                let modified = fs::metadata(&entry.path).and_then(|meta| meta.modified());
+                modified.ok().map(|modified| (index, modified))
+            })
+            .max_by_key(|(_, modified)| *modified);
+        match newest {
+            Some((index, _)) => {
+                self.selected = index;
+                self.status_message = format!("Newest file: {}", self.entries[index].name);
+            }
+            None => self.status_message = "No files in this listing.".to_string(),
+        }
+    }
+
+    /// Selects the largest file, skipping directories.
+    pub fn select_largest_file(&mut self) {
+        let largest = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.size_bytes.map(|size| (index, size)))
+            .max_by_key(|(_, size)| *size);
+        match largest {
+            Some((index, _)) => {
+                self.selected = index;
+                self.status_message = format!("Largest file: {}", self.entries[index].name);
+            }
+            None => self.status_message = "No files in this listing.".to_string(),
+        }
+    }
+
+    /// Re-reads the listing, keeping the highlighted entry when it is still
+    /// listed unless exactly one new media file appeared.
     pub fn reload(&mut self) -> io::Result<()> {
-        self.entries = read_entries(&self.cwd)?;
-        if self.entries.is_empty() {
+        let entries = match self.stdin_listing.as_deref() {
+            Some(paths) if self.showing_stdin_listing => listing_entries(paths, &self.cwd),
+            _ => read_entries(&self.cwd)?,
+        };
+        self.show_reloaded_entries(entries);
+        Ok(())
+    }
+
+    /// Shows `entries`, just read from the current listing, as `reload`
+    /// does.
+    pub(super) fn show_reloaded_entries(&mut self, entries: Vec<FileEntry>) {
+        let previous = self.selected_entry().map(|entry| entry.path.clone());
+        if !self.showing_stdin_listing {
+            self.dir_watch.record(&self.cwd, &entries);
+        }
+        let new_media = self.take_new_media_file(&entries);
+        let entries = self.filter_listing(entries);
+        self.mark_entry_durations_stale();
+        if self.fuzzy_filter.is_some() {
+            self.refilter_fuzzy_entries(entries);
+            return;
+        }
+        self.entries = entries;
+        if let Some(index) =
+            previous.and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+        {
+            self.selected = index;
+        } else if self.entries.is_empty() {
             self.selected = 0;
         } else if self.selected >= self.entries.len() {
             self.selected = self.entries.len() - 1;
         }
-        Ok(())
+        if let Some(path) = new_media
+            && let Some(index) = self.entries.iter().position(|entry| entry.path == path)
+        {
+            self.selected = index;
+            self.status_message = format!("New file: {}", self.entries[index].name);
+        }
+    }
+
+    /// Records the directory's paths and returns the media file that
+    /// appeared since the last read, when exactly one did.
+    fn take_new_media_file(&mut self, entries: &[FileEntry]) -> Option<PathBuf> {
+        if self.showing_stdin_listing {
+            return None;
+        }
+        let mut new_media = entries.iter().filter(|entry| {
+            !entry.is_dir
+                && is_editable_media_file(&entry.path)
+                && !self.listed_paths.contains(&entry.path)
+        });
+        let found = match (new_media.next(), new_media.next()) {
+            (Some(entry), None) => Some(entry.path.clone()),
+            _ => None,
+        };
+        self.listed_paths = entries.iter().map(|entry| entry.path.clone()).collect();
+        found
+    }
+
+    /// A click on a browser row selects it; a click on the selected row
+    /// opens it like Enter. Returns whether a media file was opened.
+    pub fn click_entry(&mut self, index: usize) -> io::Result<bool> {
+        if index >= self.entries.len() {
+            return Ok(false);
+        }
+        if index != self.selected {
+            self.selected = index;
+            return Ok(false);
+        }
+        self.activate_selected_entry()
     }
 
     pub fn activate_selected_entry(&mut self) -> io::Result<bool> {
@@ -88,20 +226,46 @@ impl App {
         Ok(false)
     }
 
+    /// Asks to delete the marked files, or the selected file when nothing
+    /// is marked.
     pub fn request_delete_selected_entry(&mut self) {
+        if !self.marked.is_empty() {
+            let paths = self.marked.clone();
+            if !paths
+                .iter()
+                .all(|path| self.ensure_not_in_use("delete", path))
+            {
+                return;
+            }
+            let in_rt_trash = paths.iter().filter(|path| in_rt_trash(path)).count();
+            if in_rt_trash > 0 && in_rt_trash < paths.len() {
+                self.status_message =
+                    "Delete the files in rt-trash on their own: they are removed for good."
+                        .to_string();
+                return;
+            }
+            self.pending_delete = Some(PendingDelete {
+                name: format!("{} marked files", paths.len()),
+                paths,
+                dir: None,
+            });
+            return;
+        }
+
         let Some(entry) = self.selected_entry().cloned() else {
             self.status_message = "No entry selected.".to_string();
             return;
         };
 
-        if entry.is_dir {
-            self.status_message = "Delete is only supported for files.".to_string();
+        if !self.ensure_not_in_use("delete", &entry.path) {
             return;
         }
 
+        let dir = entry.is_dir.then(|| DirDelete::new(&entry.path));
         self.pending_delete = Some(PendingDelete {
             name: entry.name,
-            path: entry.path,
+            paths: vec![entry.path],
+            dir,
         });
     }
 
@@ -126,34 +290,131 @@ impl App {
         }
     }
 
+    pub fn open_last_output(&mut self) {
+        let Some(path) = self.last_output.clone() else {
+            self.status_message = "No export or download finished yet.".to_string();
+            return;
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        if !path.is_file() {
+            self.status_message = format!("Last output no longer exists: {}", path.display());
+            return;
+        }
+
+        match open_with_system_default(&path) {
+            Ok(()) => {
+                self.status_message = format!("Opened last output: {name}");
+            }
+            Err(err) => {
+                self.status_message = format!("Failed to open {name}: {err}");
+            }
+        }
+    }
+
     pub fn cancel_pending_delete(&mut self) {
         self.pending_delete = None;
     }
 
     pub fn confirm_pending_delete(&mut self) {
+        if let Some(pending) = self.pending_delete.as_ref()
+            && let Some(dir) = pending.dir.as_ref()
+            && !dir.confirmed(&pending.name)
+        {
+            self.status_message = format!("Type {} or yes to delete the folder.", pending.name);
+            return;
+        }
         let Some(pending) = self.pending_delete.take() else {
             return;
         };
 
-        match fs::remove_file(&pending.path) {
-            Ok(()) => {
-                self.clear_selected_video_if_matches(&pending.path);
-                if let Err(err) = self.reload() {
-                    self.status_message = format!(
-                        "Deleted {}, but failed to refresh browser: {err}",
-                        pending.name
-                    );
-                    return;
-                }
-                self.status_message = format!("Deleted file: {}", pending.name);
+        let mut failures = Vec::new();
+        let mut free = Vec::new();
+        for path in pending.paths.iter() {
+            // A queued batch export may have started while the prompt was open.
+            match self.job_using_path(path) {
+                Some(usage) => failures.push(format!("{}: {usage}", entry_name(path))),
+                None => free.push(path.clone()),
             }
-            Err(err) => {
-                self.status_message = format!("Failed to delete {}: {err}", pending.name);
+        }
+        let (trashed, trash_failures) = self.trash_paths(&free);
+        failures.extend(trash_failures);
+        for path in &trashed {
+            if let Some(video) = self.selected_video.clone()
+                && video.starts_with(path)
+            {
+                self.clear_selected_video_if_matches(&video);
             }
+            self.marked.retain(|marked| !marked.starts_with(path));
         }
+        let deleted = trashed.len();
+
+        let summary = if pending.paths.iter().all(|path| in_rt_trash(path)) {
+            match (pending.paths.len(), failures.first()) {
+                (1, None) => format!("Deleted {} for good.", pending.name),
+                (1, Some(_)) => format!("Failed to delete {}", failures.join("")),
+                (_, None) => format!("Deleted {deleted} files for good."),
+                (total, Some(first)) => format!(
+                    "Deleted {deleted} of {total} files for good; {} failed (first: {first}). They stay marked.",
+                    failures.len()
+                ),
+            }
+        } else {
+            let summary = match (pending.paths.len(), failures.first()) {
+                (1, None) => match pending.dir.as_ref() {
+                    Some(dir) => match dir.counted_summary() {
+                        Some(contents) => format!(
+                            "Moved folder {} to trash ({contents}). u undoes it.",
+                            pending.name
+                        ),
+                        None => format!("Moved folder {} to trash. u undoes it.", pending.name),
+                    },
+                    None => format!("Moved to trash: {}. u undoes it.", pending.name),
+                },
+                (1, Some(_)) => format!("Failed to delete {}", failures.join("")),
+                (_, None) => format!("Moved {deleted} files to trash. u undoes it."),
+                (total, Some(first)) => format!(
+                    "Moved {deleted} of {total} files to trash; {} failed (first: {first}). They stay marked.",
+                    failures.len()
+                ),
+            };
+            self.rt_trash_summary(summary)
+        };
+        if deleted == 0 {
+            self.status_message = summary;
+            return;
+        }
+        if let Err(err) = self.reload() {
+            self.status_message = format!("{summary} Failed to refresh browser: {err}");
+            return;
+        }
+        self.status_message = summary;
+    }
+
+    /// Replaces the browser listing with `paths`, e.g. the output of `fd`
+    /// piped into `rt --stdin`. Entering a directory leaves the listing;
+    /// `_` comes back to it.
+    pub fn set_stdin_listing(&mut self, paths: Vec<PathBuf>) {
+        self.status_message = format!("Listing {} path(s) from stdin.", paths.len());
+        self.entries = self.filter_listing(listing_entries(&paths, &self.cwd));
+        self.stdin_listing = Some(paths);
+        self.showing_stdin_listing = true;
+        self.selected = 0;
+        self.mark_entry_durations_stale();
+    }
+
+    pub fn showing_stdin_listing(&self) -> bool {
+        self.showing_stdin_listing
     }
 
     pub fn go_parent_dir(&mut self) -> io::Result<()> {
+        // Leaving the stdin listing lands in the directory it was read from.
+        if self.showing_stdin_listing {
+            return self.change_dir(self.cwd.clone());
+        }
         let Some(parent) = self.cwd.parent() else {
             return Ok(());
         };
@@ -161,7 +422,12 @@ impl App {
     }
 
     pub fn go_initial_dir(&mut self) -> io::Result<()> {
-        self.change_dir(self.initial_dir.clone())
+        self.change_dir(self.initial_dir.clone())?;
+        if self.stdin_listing.is_some() {
+            self.showing_stdin_listing = true;
+            self.reload()?;
+        }
+        Ok(())
     }
 
     pub fn scroll_ffmpeg_output_down(&mut self) {
@@ -172,12 +438,20 @@ impl App {
         self.ffmpeg_output.scroll_up();
     }
 
-    pub fn page_ffmpeg_output_down(&mut self) {
-        self.ffmpeg_output.page_down();
+    pub fn page_ffmpeg_output_down(&mut self, size: PageSize) {
+        self.ffmpeg_output.page_down(size, self.log_page_scale);
+    }
+
+    pub fn page_ffmpeg_output_up(&mut self, size: PageSize) {
+        self.ffmpeg_output.page_up(size, self.log_page_scale);
+    }
+
+    pub fn scroll_ffmpeg_output_to_top(&mut self) {
+        self.ffmpeg_output.scroll_to_top();
     }
 
-    pub fn page_ffmpeg_output_up(&mut self) {
-        self.ffmpeg_output.page_up();
+    pub fn scroll_ffmpeg_output_to_bottom(&mut self) {
+        self.ffmpeg_output.scroll_to_bottom();
     }
 
     pub fn scroll_editor_form_down(&mut self) {
@@ -204,11 +478,25 @@ impl App {
         );
     }
 
-    fn change_dir(&mut self, new_cwd: PathBuf) -> io::Result<()> {
+    pub fn scroll_editor_form_to_top(&mut self) {
+        self.editor_form_scroll.set(0);
+    }
+
+    /// Overshoots on purpose; rendering clamps to the real last page.
+    pub fn scroll_editor_form_to_bottom(&mut self) {
+        self.editor_form_scroll.set(usize::MAX);
+    }
+
+    pub(super) fn change_dir(&mut self, new_cwd: PathBuf) -> io::Result<()> {
         let entries = read_entries(&new_cwd)?;
+        self.dir_watch.record(&new_cwd, &entries);
+        self.listed_paths = entries.iter().map(|entry| entry.path.clone()).collect();
+        let entries = self.filter_listing(entries);
         self.cwd = new_cwd;
         self.entries = entries;
         self.selected = 0;
+        self.showing_stdin_listing = false;
+        self.mark_entry_durations_stale();
         Ok(())
     }
 
@@ -235,22 +523,89 @@ impl App {
         Ok(())
     }
 
-    fn select_media(&mut self, path: PathBuf) {
+    /// Runs the configured post-export step and returns a note for the
+    /// status line when it changed the editor.
+    pub(super) fn apply_after_export(
+        &mut self,
+        input_path: &Path,
+        output_path: &Path,
+    ) -> Option<String> {
+        match self.after_export {
+            AfterExport::Keep => None,
+            AfterExport::Reset => {
+                self.select_media(input_path.to_path_buf());
+                Some("form reset".to_string())
+            }
+            AfterExport::Next => {
+                let start = self
+                    .entries
+                    .iter()
+                    .position(|entry| entry.path == input_path)?;
+                let offset = self.entries[start + 1..].iter().position(|entry| {
+                    !entry.is_dir
+                        && entry.path != output_path
+                        && is_editable_media_file(&entry.path)
+                })?;
+                let index = start + 1 + offset;
+                let entry = self.entries[index].clone();
+                self.selected = index;
+                self.select_media(entry.path);
+                Some(format!("next: {}", entry.name))
+            }
+        }
+    }
+
+    pub(super) fn select_media(&mut self, path: PathBuf) {
+        let probe = MediaProbe::read(&path);
+        self.load_media(path, probe);
+    }
+
+    /// Fills the form for `path` from an earlier probe without running
+    /// ffprobe again.
+    pub(super) fn load_media(&mut self, path: PathBuf, probe: MediaProbe) {
         self.right_tab = RightTab::Editor;
-        self.output_name = default_output_name(&path);
+        self.output_name = match &self.editor_defaults.output_dir {
+            Some(dir) => dir.join(default_output_name(&path)).display().to_string(),
+            None => default_output_name(&path),
+        };
         self.output_format = output_format_for_path(&path);
-        self.selected_video_stats = probe_video_stats(&path).ok();
-        self.output_fps = default_output_fps(self.selected_video_stats.as_ref());
+        self.selected_video_stats = probe.stats;
+        self.media_streams = probe.streams;
+        self.stream_selected = default_stream_selection(&self.media_streams);
+        self.stream_cursor = 0;
+        self.audio_track = AudioTrack::Auto;
+        self.audio_channels = CHANNEL_MODES[0];
+        self.segments.clear();
+        self.output_fps = self
+            .editor_defaults
+            .fps
+            .clone()
+            .unwrap_or_else(|| default_output_fps(self.selected_video_stats.as_ref()));
         self.output_fps_cursor = self.output_fps.chars().count();
-        self.output_bitrate_kbps = default_output_bitrate_kbps(self.selected_video_stats.as_ref());
+        self.output_bitrate_kbps = match self.editor_defaults.bitrate_kbps {
+            Some(kbps) => kbps.to_string(),
+            None => default_output_bitrate_kbps(self.selected_video_stats.as_ref()),
+        };
         self.output_bitrate_cursor = self.output_bitrate_kbps.chars().count();
         self.output_scale_percent = "100".to_string();
         self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
-        self.use_gpu_encoding = self.gpu_h264_encoder_available();
+        self.output_crop.set("");
+        self.output_speed.set("1");
+        self.use_gpu_encoding =
+            self.gpu_h264_encoder_available() && self.editor_defaults.gpu.unwrap_or(true);
         self.remove_audio = false;
+        self.pending_time_offset = None;
+        self.deinterlace = self
+            .selected_video_stats
+            .as_ref()
+            .is_some_and(|stats| stats.interlaced);
+        self.constant_fps = self
+            .selected_video_stats
+            .as_ref()
+            .is_some_and(|stats| stats.variable_frame_rate);
         self.sync_output_name_to_available_for_path(&path);
 
-        match probe_video_times(&path) {
+        match probe.times {
             Ok((start_time, end_time, bounds)) => {
                 self.start_time = start_time;
                 self.end_time = end_time;
@@ -258,8 +613,8 @@ impl App {
                 self.status_message = format!(
                     "Selected media: {} (range {}..={})",
                     path.display(),
-                    TimeInput::from_seconds(bounds.start_seconds as f64).to_ffmpeg_timestamp(),
-                    TimeInput::from_seconds(bounds.end_seconds as f64).to_ffmpeg_timestamp()
+                    TimeInput::from_seconds(bounds.start_seconds).to_ffmpeg_timestamp(),
+                    TimeInput::from_seconds(bounds.end_seconds).to_ffmpeg_timestamp()
                 );
             }
             Err(err) => {
@@ -287,11 +642,11 @@ impl App {
         self.selected_video = Some(path);
     }
 
-    fn selected_entry(&self) -> Option<&FileEntry> {
+    pub(super) fn selected_entry(&self) -> Option<&FileEntry> {
         self.entries.get(self.selected)
     }
 
-    fn clear_selected_video_if_matches(&mut self, deleted_path: &Path) {
+    pub(super) fn clear_selected_video_if_matches(&mut self, deleted_path: &Path) {
         if self
             .selected_video
             .as_ref()
@@ -299,6 +654,9 @@ impl App {
         {
             self.selected_video = None;
             self.selected_video_stats = None;
+            self.media_streams.clear();
+            self.stream_selected.clear();
+            self.stream_cursor = 0;
             self.selected_video_bounds = None;
             self.start_time = TimeInput::zero();
             self.end_time = TimeInput::zero();
@@ -306,6 +664,8 @@ impl App {
             self.remove_audio = false;
             self.output_scale_percent = "100".to_string();
             self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
+            self.output_crop.set("");
+            self.output_speed.set("1");
             self.output_cursor = 0;
             self.editor_form_scroll.set(0);
         }
@@ -319,10 +679,11 @@ pub(super) fn read_entries(dir: &Path) -> io::Result<Vec<FileEntry>> {
             let path = entry.path();
             let name = entry.file_name().to_string_lossy().into_owned();
             let is_dir = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
+            let metadata = entry.metadata().ok();
             let size_bytes = if is_dir {
                 None
             } else {
-                entry.metadata().ok().map(|meta| meta.len())
+                metadata.as_ref().map(|meta| meta.len())
             };
 
             FileEntry {
@@ -330,6 +691,7 @@ pub(super) fn read_entries(dir: &Path) -> io::Result<Vec<FileEntry>> {
                 path,
                 is_dir,
                 size_bytes,
+                modified: metadata.and_then(|meta| meta.modified().ok()),
             }
         })
         .collect::<Vec<_>>();
@@ -338,6 +700,42 @@ pub(super) fn read_entries(dir: &Path) -> io::Result<Vec<FileEntry>> {
     Ok(entries)
 }
 
+/// File name of `path` for status messages, or the whole path.
+pub(super) fn entry_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Entries for a piped-in path list, in the order given. Paths that no
+/// longer exist are dropped; names are shown relative to `cwd` when inside it.
+fn listing_entries(paths: &[PathBuf], cwd: &Path) -> Vec<FileEntry> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            let is_dir = metadata.is_dir();
+            let name = path.strip_prefix(cwd).unwrap_or(path).display().to_string();
+            Some(FileEntry {
+                name,
+                path: path.clone(),
+                is_dir,
+                size_bytes: (!is_dir).then_some(metadata.len()),
+                modified: metadata.modified().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Matches the old fixed mapping: the first video and first audio stream.
+fn default_stream_selection(streams: &[MediaStream]) -> Vec<bool> {
+    let first_of = |kind: StreamKind| streams.iter().position(|stream| stream.kind == kind);
+    let defaults = [first_of(StreamKind::Video), first_of(StreamKind::Audio)];
+    (0..streams.len())
+        .map(|index| defaults.contains(&Some(index)))
+        .collect()
+}
+
 fn default_output_bitrate_kbps(stats: Option<&crate::media::VideoStats>) -> String {
     stats
         .and_then(|stats| stats.bitrate_kbps)
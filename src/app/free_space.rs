@@ -0,0 +1,93 @@
+// Minimum free space for new jobs.
+// - `[jobs] min_free_gb` in config.toml sets how much space an export or
+//   download must leave on the drive it writes to.
+// - Exports count their estimated size; downloads count the picked
+//   quality's size from yt-dlp's format list when it has one.
+// - A job that would dip below the minimum asks for confirmation first. A
+//   running batch stops instead, since nobody may be there to confirm.
+// - When `df` can't be read the check is skipped, and the first job started
+//   afterwards says so in its log once per session.
+use std::{path::Path, process::Command};
+
+use crate::media::format_bytes;
+
+use super::{App, config::Config, quick_jump::tilde_path};
+
+pub(super) const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// `[jobs] min_free_gb` in bytes; unset, 0, or negative turns the check off.
+pub(super) fn min_free_bytes_from_config(config: &Config) -> u64 {
+    let gigabytes = config.number("jobs", "min_free_gb").unwrap_or(0.0);
+    if gigabytes.is_finite() && gigabytes > 0.0 {
+        (gigabytes * BYTES_PER_GB) as u64
+    } else {
+        0
+    }
+}
+
+/// Bytes free for the current user on the filesystem holding `dir`, from
+/// `df`. `None` when `df` is missing or its output can't be read.
+fn available_bytes(dir: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // POSIX format: a header, then `fs blocks used available capacity mount`.
+    let kilobytes = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes.saturating_mul(1024))
+}
+
+impl App {
+    /// Why writing `projected` more bytes into `dir` would leave less than
+    /// the configured minimum free, or `None` when it fits, the check is
+    /// off, or the free space can't be read. The last case is remembered for
+    /// `take_free_space_unread_note`.
+    pub(super) fn low_space_warning(&self, dir: &Path, projected: Option<u64>) -> Option<String> {
+        if self.min_free_bytes == 0 {
+            return None;
+        }
+        let Some(available) = available_bytes(dir) else {
+            *self.free_space_unread.borrow_mut() = Some(dir.to_path_buf());
+            return None;
+        };
+        let projected = projected.unwrap_or(0);
+        let remaining = available.saturating_sub(projected);
+        if remaining >= self.min_free_bytes {
+            return None;
+        }
+        let minimum = format_bytes(self.min_free_bytes);
+        let dir = tilde_path(dir);
+        Some(if projected > 0 {
+            format!(
+                "{dir} would have ~{} free after this job, below the {minimum} minimum",
+                format_bytes(remaining)
+            )
+        } else {
+            format!(
+                "{dir} has {} free, below the {minimum} minimum",
+                format_bytes(available)
+            )
+        })
+    }
+
+    /// Once per session, a note that the last free-space check was skipped
+    /// because `df` failed, for the log of the job it let through.
+    pub(super) fn take_free_space_unread_note(&mut self) -> Option<String> {
+        let dir = self.free_space_unread.get_mut().take()?;
+        if self.free_space_unread_noted {
+            return None;
+        }
+        self.free_space_unread_noted = true;
+        Some(format!(
+            "Note: free space in {} couldn't be read with df, so [jobs] min_free_gb isn't being checked.",
+            tilde_path(&dir)
+        ))
+    }
+}
@@ -0,0 +1,149 @@
+// Filter-as-you-type for the file browser.
+// - `/` starts it; the listing narrows to entries whose name contains the
+//   typed letters in order (case-insensitive), best matches first.
+// - Up/Down move through the matches, Enter restores the full listing with
+//   the highlighted match selected, and Esc restores it unchanged.
+use crate::model::FileEntry;
+
+use super::App;
+
+pub(super) struct FuzzyFilter {
+    query: String,
+    /// The listing before the filter, kept up to date by `reload`.
+    all_entries: Vec<FileEntry>,
+    /// Selection to go back to on Esc.
+    origin: usize,
+}
+
+/// Scores `name` against `query` when every query character appears in
+/// order. Consecutive matches and matches at the start of a word score
+/// higher; shorter names win ties.
+fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let name = name.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for query_ch in query.to_lowercase().chars() {
+        let offset = name[next..].iter().position(|ch| *ch == query_ch)?;
+        let index = next + offset;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !name[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(index);
+        next = index + 1;
+    }
+    Some(score * 100 - name.len() as i64)
+}
+
+impl App {
+    pub fn start_fuzzy_filter(&mut self) {
+        if self.fuzzy_filter.is_some() {
+            return;
+        }
+        self.fuzzy_filter = Some(FuzzyFilter {
+            query: String::new(),
+            all_entries: self.entries.clone(),
+            origin: self.selected,
+        });
+    }
+
+    /// The typed query and the number of matches while the filter is open.
+    pub fn fuzzy_filter(&self) -> Option<(&str, usize)> {
+        self.fuzzy_filter
+            .as_ref()
+            .map(|filter| (filter.query.as_str(), self.entries.len()))
+    }
+
+    pub fn push_fuzzy_filter_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(filter) = self.fuzzy_filter.as_mut() {
+            filter.query.push(ch);
+            self.apply_fuzzy_filter();
+        }
+    }
+
+    pub fn backspace_fuzzy_filter(&mut self) {
+        if let Some(filter) = self.fuzzy_filter.as_mut() {
+            filter.query.pop();
+            self.apply_fuzzy_filter();
+        }
+    }
+
+    pub fn select_next_fuzzy_match(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous_fuzzy_match(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Enter: back to the full listing with the highlighted match selected.
+    pub fn confirm_fuzzy_filter(&mut self) {
+        let Some(filter) = self.fuzzy_filter.take() else {
+            return;
+        };
+        let target = self.selected_entry().map(|entry| entry.path.clone());
+        self.entries = filter.all_entries;
+        self.mark_entry_durations_stale();
+        self.selected = target
+            .and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+            .unwrap_or(filter.origin)
+            .min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Esc: back to the full listing and the selection from before.
+    pub fn cancel_fuzzy_filter(&mut self) {
+        let Some(filter) = self.fuzzy_filter.take() else {
+            return;
+        };
+        self.entries = filter.all_entries;
+        self.mark_entry_durations_stale();
+        self.selected = filter.origin.min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Takes a re-read listing while the filter is open and filters it
+    /// again, keeping the highlighted entry when it still matches.
+    pub(super) fn refilter_fuzzy_entries(&mut self, entries: Vec<FileEntry>) {
+        let Some(filter) = self.fuzzy_filter.as_mut() else {
+            self.entries = entries;
+            return;
+        };
+        filter.all_entries = entries;
+        let selected = self.selected_entry().map(|entry| entry.path.clone());
+        self.apply_fuzzy_filter();
+        if let Some(index) =
+            selected.and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Rebuilds the shown entries from the full listing, best match first;
+    /// an empty query shows everything in the usual order.
+    fn apply_fuzzy_filter(&mut self) {
+        let Some(filter) = self.fuzzy_filter.as_ref() else {
+            return;
+        };
+        let mut matches = filter
+            .all_entries
+            .iter()
+            .filter_map(|entry| {
+                fuzzy_score(&filter.query, &entry.name).map(|score| (score, entry.clone()))
+            })
+            .collect::<Vec<_>>();
+        if !filter.query.is_empty() {
+            // Stable, so equal scores keep directories first and names sorted.
+            matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+        self.entries = matches.into_iter().map(|(_, entry)| entry).collect();
+        self.selected = 0;
+    }
+}
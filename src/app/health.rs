@@ -0,0 +1,456 @@
+// Diagnostics for the tools and folders rt depends on.
+// - Checks tool versions, the encoders in the ffmpeg build, hardware
+//   encoding with a one-frame test encode, write access to the config dir,
+//   the trash, and the current folder, and the clipboard tool.
+// - Runs on a background thread; the report lists a fix for every problem.
+// - Opens by itself on the first run, and with `H` or `--health` after that.
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::media::{VIDEO_CODECS, gpu_video_encoders, video_encoder_candidates};
+
+use super::{App, clipboard::clipboard_tool, config_dir, trash::os_trash_files_dir};
+
+/// Written to the config dir once the first-run report has been shown.
+const FIRST_RUN_MARKER: &str = "health_checked";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthLevel {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// One line of the report.
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub section: &'static str,
+    pub label: String,
+    pub level: HealthLevel,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+impl HealthCheck {
+    fn ok(section: &'static str, label: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            section,
+            label: label.into(),
+            level: HealthLevel::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(
+        section: &'static str,
+        level: HealthLevel,
+        label: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            section,
+            label: label.into(),
+            level,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// The open report, filled in when the background checks finish.
+pub(super) struct HealthScreen {
+    rx: Option<Receiver<Vec<HealthCheck>>>,
+    checks: Vec<HealthCheck>,
+    scroll: usize,
+    /// Opened on the first run; closing it writes the marker.
+    first_run: bool,
+}
+
+/// The report for rendering.
+pub struct HealthView<'a> {
+    pub running: bool,
+    pub checks: &'a [HealthCheck],
+    pub scroll: usize,
+}
+
+/// True until the first-run report has been closed once.
+pub(super) fn is_first_run() -> bool {
+    config_dir().is_some_and(|dir| !dir.join(FIRST_RUN_MARKER).exists())
+}
+
+impl App {
+    /// Opens the report and starts the checks in the background.
+    pub fn open_health_check(&mut self) {
+        self.start_health_check(false);
+    }
+
+    pub(super) fn start_health_check(&mut self, first_run: bool) {
+        let (tx, rx) = mpsc::channel();
+        let encoders = self.ffmpeg_encoders.clone();
+        let cwd = self.cwd.clone();
+        thread::spawn(move || {
+            let _ = tx.send(run_checks(&encoders, &cwd));
+        });
+        self.health = Some(HealthScreen {
+            rx: Some(rx),
+            checks: Vec::new(),
+            scroll: 0,
+            first_run,
+        });
+    }
+
+    pub fn health_check(&self) -> Option<HealthView<'_>> {
+        self.health.as_ref().map(|health| HealthView {
+            running: health.rx.is_some(),
+            checks: &health.checks,
+            scroll: health.scroll,
+        })
+    }
+
+    pub fn scroll_health_check(&mut self, down: bool) {
+        if let Some(health) = self.health.as_mut() {
+            health.scroll = if down {
+                (health.scroll + 1).min(health.checks.len().saturating_sub(1))
+            } else {
+                health.scroll.saturating_sub(1)
+            };
+        }
+    }
+
+    pub fn close_health_check(&mut self) {
+        let Some(health) = self.health.take() else {
+            return;
+        };
+        if health.first_run
+            && let Some(dir) = config_dir()
+        {
+            // Without the marker the report just shows again next time.
+            let _ =
+                fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(FIRST_RUN_MARKER), ""));
+        }
+    }
+
+    /// Picks up the finished checks. True when the report changed.
+    pub(super) fn pump_health_check(&mut self) -> bool {
+        let Some(health) = self.health.as_mut() else {
+            return false;
+        };
+        let Some(rx) = health.rx.as_ref() else {
+            return false;
+        };
+        let checks = match rx.try_recv() {
+            Ok(checks) => checks,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => vec![HealthCheck::problem(
+                "rt",
+                HealthLevel::Fail,
+                "Checks",
+                "the check thread stopped early",
+                "Open the report again with H.",
+            )],
+        };
+        health.checks = checks;
+        health.rx = None;
+        let problems = health
+            .checks
+            .iter()
+            .filter(|check| check.level != HealthLevel::Ok)
+            .count();
+        self.status_message = if problems == 0 {
+            "Health check: everything works.".to_string()
+        } else {
+            format!("Health check: {problems} problem(s), each with a suggested fix.")
+        };
+        true
+    }
+
+    pub(super) fn health_check_running(&self) -> bool {
+        self.health
+            .as_ref()
+            .is_some_and(|health| health.rx.is_some())
+    }
+}
+
+fn run_checks(encoders: &HashSet<String>, cwd: &Path) -> Vec<HealthCheck> {
+    let mut checks = tool_checks();
+    let ffmpeg_found = checks
+        .first()
+        .is_some_and(|check| check.level == HealthLevel::Ok);
+    if ffmpeg_found {
+        checks.extend(encoder_checks(encoders));
+        checks.extend(hardware_checks(encoders));
+    }
+    checks.extend(folder_checks(cwd));
+    checks.push(clipboard_check());
+    checks
+}
+
+/// `program`'s version from the first line it prints for `arg`.
+fn tool_version(program: &str, arg: &str, version_word: usize) -> Result<String, String> {
+    let output = Command::new(program)
+        .arg(arg)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => "not found in PATH".to_string(),
+            _ => err.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(format!("`{program} {arg}` exited with {}", output.status));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next().unwrap_or_default();
+    Ok(first_line
+        .split_whitespace()
+        .nth(version_word)
+        .unwrap_or(first_line)
+        .to_string())
+}
+
+fn tool_checks() -> Vec<HealthCheck> {
+    const SECTION: &str = "TOOLS";
+    let install_ffmpeg = "Install ffmpeg (apt install ffmpeg, brew install ffmpeg, or winget install ffmpeg) and restart rt.";
+    let mut checks = Vec::new();
+    // `ffmpeg version 7.0.1 Copyright ...`
+    for (program, needed_for) in [
+        ("ffmpeg", "the editor and every export"),
+        ("ffprobe", "reading durations and streams"),
+    ] {
+        checks.push(match tool_version(program, "-version", 2) {
+            Ok(version) => HealthCheck::ok(SECTION, program, version),
+            Err(error) => HealthCheck::problem(
+                SECTION,
+                HealthLevel::Fail,
+                program,
+                format!("{error}; needed for {needed_for}"),
+                install_ffmpeg,
+            ),
+        });
+    }
+    checks.push(match tool_version("yt-dlp", "--version", 0) {
+        Ok(version) => HealthCheck::ok(SECTION, "yt-dlp", version),
+        Err(error) => HealthCheck::problem(
+            SECTION,
+            HealthLevel::Warn,
+            "yt-dlp",
+            format!("{error}; the Downloader tab needs it"),
+            "Install yt-dlp (pipx install yt-dlp, brew install yt-dlp, or winget install yt-dlp).",
+        ),
+    });
+    let player = tool_version("mpv", "--version", 1)
+        .map(|version| format!("mpv {version}"))
+        .or_else(|_| {
+            tool_version("ffplay", "-version", 2).map(|version| format!("ffplay {version}"))
+        });
+    checks.push(match player {
+        Ok(player) => HealthCheck::ok(SECTION, "Player", player),
+        Err(_) => HealthCheck::problem(
+            SECTION,
+            HealthLevel::Warn,
+            "Player",
+            "neither mpv nor ffplay found; range playback is off",
+            "Install mpv, or an ffmpeg build that includes ffplay.",
+        ),
+    });
+    checks
+}
+
+fn encoder_checks(encoders: &HashSet<String>) -> Vec<HealthCheck> {
+    const SECTION: &str = "ENCODERS";
+    VIDEO_CODECS
+        .iter()
+        .filter(|codec| **codec != "copy")
+        .map(|codec| {
+            let software = video_encoder_candidates(codec, false);
+            let found = software
+                .iter()
+                .filter(|encoder| encoders.contains(**encoder))
+                .copied()
+                .collect::<Vec<_>>();
+            if found.is_empty() {
+                HealthCheck::problem(
+                    SECTION,
+                    HealthLevel::Warn,
+                    *codec,
+                    format!("no {} in this ffmpeg build", software.join(" or ")),
+                    format!(
+                        "Install a full ffmpeg build (your distro's ffmpeg package or ffmpeg.org) to export {codec}."
+                    ),
+                )
+            } else {
+                HealthCheck::ok(SECTION, *codec, found.join(", "))
+            }
+        })
+        .collect()
+}
+
+fn hardware_checks(encoders: &HashSet<String>) -> Vec<HealthCheck> {
+    const SECTION: &str = "HARDWARE";
+    let mut checks = Vec::new();
+    let hwaccels = Command::new("ffmpeg")
+        .args(["-hide_banner", "-hwaccels"])
+        .stdin(Stdio::null())
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .skip(1)
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    checks.push(if hwaccels.is_empty() {
+        HealthCheck::ok(SECTION, "Decoding", "no hardware decoders in this build")
+    } else {
+        HealthCheck::ok(SECTION, "Decoding", hwaccels.join(", "))
+    });
+
+    let mut gpu_encoders = VIDEO_CODECS
+        .iter()
+        .flat_map(|codec| gpu_video_encoders(codec))
+        .filter(|encoder| encoders.contains(**encoder))
+        .copied()
+        .collect::<Vec<_>>();
+    gpu_encoders.dedup();
+    if gpu_encoders.is_empty() {
+        checks.push(HealthCheck::ok(
+            SECTION,
+            "NVENC",
+            "not in this ffmpeg build; exports use the CPU",
+        ));
+    }
+    for encoder in gpu_encoders {
+        checks.push(match test_encode(encoder) {
+            Ok(()) => HealthCheck::ok(SECTION, encoder, "test encode works"),
+            Err(error) => HealthCheck::problem(
+                SECTION,
+                HealthLevel::Warn,
+                encoder,
+                error,
+                "Check the NVIDIA driver, or leave GPU off in the editor (gpu = false under [editor]).",
+            ),
+        });
+    }
+    checks
+}
+
+/// Encodes one black frame with `encoder`, which fails when the encoder is
+/// compiled in but no usable GPU is present.
+fn test_encode(encoder: &str) -> Result<(), String> {
+    let output = Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-f",
+            "lavfi",
+            "-i",
+            "color=c=black:s=256x256:d=0.1",
+            "-frames:v",
+            "1",
+            "-c:v",
+            encoder,
+            "-f",
+            "null",
+            "-",
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| err.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("test encode failed")
+        .trim()
+        .to_string())
+}
+
+/// Creates and removes a file in `dir`, creating `dir` when missing.
+fn check_writable(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".rt-write-test-{}", process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(probe)
+}
+
+fn folder_checks(cwd: &Path) -> Vec<HealthCheck> {
+    const SECTION: &str = "FOLDERS";
+    let folder =
+        |label: &str, dir: PathBuf, level: HealthLevel, fix: String| match check_writable(&dir) {
+            Ok(()) => HealthCheck::ok(SECTION, label, dir.display().to_string()),
+            Err(err) => HealthCheck::problem(
+                SECTION,
+                level,
+                label,
+                format!("{}: {err}", dir.display()),
+                fix,
+            ),
+        };
+    let mut checks = Vec::new();
+    checks.push(match config_dir() {
+        Some(dir) => folder(
+            "Config",
+            dir.clone(),
+            HealthLevel::Fail,
+            format!(
+                "Make {} writable; settings, presets, tags, and stats are saved there.",
+                dir.display()
+            ),
+        ),
+        None => HealthCheck::problem(
+            SECTION,
+            HealthLevel::Fail,
+            "Config",
+            "no config dir: HOME and XDG_CONFIG_HOME are unset",
+            "Set HOME (or XDG_CONFIG_HOME) before starting rt.",
+        ),
+    });
+    checks.push(match os_trash_files_dir() {
+        Some(dir) => folder(
+            "Trash",
+            dir,
+            HealthLevel::Warn,
+            "Deleted files go to rt-trash in the config dir instead, kept until deleted there."
+                .to_string(),
+        ),
+        None => HealthCheck::ok(SECTION, "Trash", "rt-trash in the config dir"),
+    });
+    checks.push(folder(
+        "This folder",
+        cwd.to_path_buf(),
+        HealthLevel::Warn,
+        "Exports land next to their source; set output_dir under [editor] to write elsewhere."
+            .to_string(),
+    ));
+    checks
+}
+
+fn clipboard_check() -> HealthCheck {
+    const SECTION: &str = "CLIPBOARD";
+    match clipboard_tool() {
+        Some(tool) => HealthCheck::ok(SECTION, "Copy", tool),
+        None => HealthCheck::problem(
+            SECTION,
+            HealthLevel::Warn,
+            "Copy",
+            "no clipboard tool; copies use the terminal (OSC 52), which some terminals ignore",
+            "Install wl-clipboard (Wayland) or xclip (X11).",
+        ),
+    }
+}
@@ -1,134 +1,387 @@
 // Editor-form input editing logic.
-// - Implements Tab/Shift+Tab traversal across time/output fields.
+// - Implements Tab/Shift+Tab traversal across time/stream/output fields.
 // - Handles cursor movement and character insert/delete in editable fields.
 // - Keeps output names/extensions normalized and collision-safe.
 use std::path::Path;
 
 use crate::{
     media::{
-        OUTPUT_FORMATS, enforce_output_extension, next_available_output_path,
-        output_path_without_numbered_suffix, resolve_output_path,
+        CAPTION_POSITIONS, CHANNEL_MODES, DENOISE_LEVELS, FFMPEG_LOG_LEVELS, FILE_TIME_MODES,
+        OUTPUT_FORMATS, SHARPEN_LEVELS, VIDEO_CODECS, WAVEFORM_STYLES, enforce_output_extension,
+        next_available_output_path, output_path_without_numbered_suffix, resolve_output_path,
     },
-    model::InputField,
+    model::{AudioTrack, InputField, TextInput, TimeInput},
 };
 
 use super::App;
 
+/// Editor form fields in Tab order. Disabled fields are skipped.
+const EDITOR_INPUT_ORDER: [InputField; 43] = [
+    InputField::Start,
+    InputField::End,
+    InputField::Timeline,
+    InputField::Format,
+    InputField::StreamCopy,
+    InputField::Waveform,
+    InputField::Codec,
+    InputField::Gpu,
+    InputField::Fps,
+    InputField::ConstantFps,
+    InputField::Bitrate,
+    InputField::TargetSize,
+    InputField::ScalePercent,
+    InputField::Crop,
+    InputField::Deinterlace,
+    InputField::Denoise,
+    InputField::Sharpen,
+    InputField::Color,
+    InputField::Brightness,
+    InputField::Contrast,
+    InputField::Saturation,
+    InputField::Gamma,
+    InputField::Speed,
+    InputField::Caption,
+    InputField::CaptionPosition,
+    InputField::CaptionSize,
+    InputField::CaptionOutline,
+    InputField::RemoveAudio,
+    InputField::Streams,
+    InputField::AudioTrack,
+    InputField::Channels,
+    InputField::Normalize,
+    InputField::TwoPass,
+    InputField::GifPalette,
+    InputField::MaxWidth,
+    InputField::FrameSequence,
+    InputField::KeepMetadata,
+    InputField::FileTime,
+    InputField::LogLevel,
+    InputField::StatsPeriod,
+    InputField::ProgressOnly,
+    InputField::ExtraArgs,
+    InputField::Output,
+];
+
 impl App {
     pub fn next_input(&mut self) {
+        self.commit_time_offset();
         match self.active_input {
-            InputField::Start => {
-                if self.start_part < 2 {
-                    self.start_part += 1;
-                } else {
-                    self.active_input = InputField::End;
-                    self.end_part = 0;
-                }
-            }
-            InputField::End => {
-                if self.end_part < 2 {
-                    self.end_part += 1;
-                } else {
-                    self.active_input = InputField::Format;
-                }
-            }
-            InputField::Format => {
-                if self.video_options_enabled() {
-                    self.active_input = InputField::Fps;
-                    self.output_fps_cursor = self.output_fps.chars().count();
-                    self.overwrite_fps_on_next_type = true;
-                } else {
-                    self.active_input = InputField::Output;
-                    self.output_cursor = self.output_name.chars().count();
-                }
-            }
-            InputField::Fps => {
-                if self.bitrate_enabled() {
-                    self.active_input = InputField::Bitrate;
-                    self.output_bitrate_cursor = self.output_bitrate_kbps.chars().count();
-                    self.overwrite_bitrate_on_next_type = true;
-                } else {
-                    self.active_input = InputField::ScalePercent;
-                    self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
-                    self.overwrite_scale_percent_on_next_type = true;
-                }
-            }
-            InputField::Bitrate => {
-                self.active_input = InputField::ScalePercent;
-                self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
-                self.overwrite_scale_percent_on_next_type = true;
-            }
-            InputField::ScalePercent => {
-                self.active_input = InputField::RemoveAudio;
-            }
-            InputField::RemoveAudio => {
-                self.active_input = InputField::Output;
-                self.output_cursor = self.output_name.chars().count();
+            InputField::Start if self.start_part < 3 => self.start_part += 1,
+            InputField::End if self.end_part < 3 => self.end_part += 1,
+            InputField::Streams if self.stream_cursor + 1 < self.media_streams.len() => {
+                self.stream_cursor += 1
             }
-            InputField::Output => {
-                self.active_input = InputField::Start;
-                self.start_part = 0;
+            _ => {
+                let next = self.adjacent_enabled_input(true);
+                self.enter_input(next, true);
             }
         }
     }
 
     pub fn previous_input(&mut self) {
+        self.commit_time_offset();
         match self.active_input {
-            InputField::Start => {
-                if self.start_part > 0 {
-                    self.start_part -= 1;
-                } else {
-                    self.active_input = InputField::Output;
-                    self.output_cursor = self.output_name.chars().count();
-                }
+            InputField::Start if self.start_part > 0 => self.start_part -= 1,
+            InputField::End if self.end_part > 0 => self.end_part -= 1,
+            InputField::Streams if self.stream_cursor > 0 => self.stream_cursor -= 1,
+            _ => {
+                let previous = self.adjacent_enabled_input(false);
+                self.enter_input(previous, false);
             }
-            InputField::End => {
-                if self.end_part > 0 {
-                    self.end_part -= 1;
-                } else {
-                    self.active_input = InputField::Start;
-                    self.start_part = 2;
-                }
+        }
+    }
+
+    /// Whether a form field can take focus for the current form state.
+    pub fn editor_input_enabled(&self, field: InputField) -> bool {
+        self.editor_input_disabled_reason(field).is_none()
+    }
+
+    /// Short explanation shown in place of a field that doesn't apply, e.g.
+    /// bitrate for GIF output or encoder options while stream copy is on.
+    pub fn editor_input_disabled_reason(&self, field: InputField) -> Option<&'static str> {
+        let video_only = matches!(
+            field,
+            InputField::StreamCopy
+                | InputField::Waveform
+                | InputField::Codec
+                | InputField::Gpu
+                | InputField::Fps
+                | InputField::ConstantFps
+                | InputField::Bitrate
+                | InputField::TargetSize
+                | InputField::ScalePercent
+                | InputField::Crop
+                | InputField::Deinterlace
+                | InputField::Denoise
+                | InputField::Sharpen
+                | InputField::Color
+                | InputField::Brightness
+                | InputField::Contrast
+                | InputField::Saturation
+                | InputField::Gamma
+                | InputField::Caption
+                | InputField::CaptionPosition
+                | InputField::CaptionSize
+                | InputField::CaptionOutline
+                | InputField::RemoveAudio
+                | InputField::Streams
+        );
+        let encoder_only = matches!(
+            field,
+            InputField::StreamCopy
+                | InputField::Waveform
+                | InputField::Streams
+                | InputField::AudioTrack
+                | InputField::Channels
+                | InputField::Codec
+                | InputField::Gpu
+                | InputField::ConstantFps
+                | InputField::Bitrate
+                | InputField::TargetSize
+                | InputField::Normalize
+                | InputField::TwoPass
+        );
+        let audio_filter = matches!(
+            field,
+            InputField::Normalize
+                | InputField::TwoPass
+                | InputField::AudioTrack
+                | InputField::Channels
+        );
+        let reencode_only = matches!(
+            field,
+            InputField::Waveform
+                | InputField::Codec
+                | InputField::Gpu
+                | InputField::Fps
+                | InputField::ConstantFps
+                | InputField::Bitrate
+                | InputField::TargetSize
+                | InputField::ScalePercent
+                | InputField::Crop
+                | InputField::Deinterlace
+                | InputField::Denoise
+                | InputField::Sharpen
+                | InputField::Color
+                | InputField::Brightness
+                | InputField::Contrast
+                | InputField::Saturation
+                | InputField::Gamma
+                | InputField::Speed
+                | InputField::Caption
+                | InputField::CaptionPosition
+                | InputField::CaptionSize
+                | InputField::CaptionOutline
+                | InputField::Normalize
+                | InputField::TwoPass
+                | InputField::Channels
+        );
+        let audio_or_timing = matches!(field, InputField::RemoveAudio | InputField::Speed);
+        // The picture comes from the audio, so nothing edits a source frame.
+        let replaced_by_waveform = matches!(
+            field,
+            InputField::ConstantFps
+                | InputField::Deinterlace
+                | InputField::Crop
+                | InputField::Denoise
+                | InputField::Sharpen
+                | InputField::Color
+                | InputField::Brightness
+                | InputField::Contrast
+                | InputField::Saturation
+                | InputField::Gamma
+                | InputField::ScalePercent
+                | InputField::Speed
+                | InputField::Caption
+                | InputField::CaptionPosition
+                | InputField::CaptionSize
+                | InputField::CaptionOutline
+                | InputField::RemoveAudio
+                | InputField::Streams
+        );
+        let color_value = matches!(
+            field,
+            InputField::Brightness
+                | InputField::Contrast
+                | InputField::Saturation
+                | InputField::Gamma
+        );
+        let caption_style = matches!(
+            field,
+            InputField::CaptionPosition | InputField::CaptionSize | InputField::CaptionOutline
+        );
+
+        if video_only && !self.video_options_enabled() {
+            Some("n/a for audio-only")
+        } else if encoder_only && self.animated_image_output_selected() {
+            Some("n/a for animated images")
+        } else if (encoder_only || audio_or_timing) && self.still_image_output_selected() {
+            Some("n/a for still images")
+        } else if field == InputField::FrameSequence && !self.still_image_output_selected() {
+            Some("PNG/JPEG only")
+        } else if field == InputField::Fps
+            && self.still_image_output_selected()
+            && !self.frame_sequence
+        {
+            Some("single frame, enable Sequence")
+        } else if field == InputField::Timeline && self.timeline_bounds().is_none() {
+            Some("duration unknown")
+        } else if field == InputField::FileTime && self.frame_sequence_selected() {
+            Some("n/a for frame sequences")
+        } else if field == InputField::GifPalette && !self.is_gif_output() {
+            Some("GIF only")
+        } else if field == InputField::MaxWidth && !self.animated_image_output_selected() {
+            Some("GIF/WebP/APNG only")
+        } else if matches!(
+            field,
+            InputField::StreamCopy
+                | InputField::Waveform
+                | InputField::Streams
+                | InputField::TwoPass
+        ) && !self.segments.is_empty()
+        {
+            Some("n/a with segments")
+        } else if reencode_only && self.stream_copy_active() {
+            Some("n/a for stream copy")
+        } else if field == InputField::Waveform
+            && !self.media_streams.is_empty()
+            && self.audio_streams().is_empty()
+        {
+            Some("no audio stream")
+        } else if replaced_by_waveform && self.waveform_active() {
+            Some("n/a with waveform")
+        } else if field == InputField::Bitrate && self.target_size_set() {
+            Some("set by target size")
+        } else if color_value && !self.color_adjust {
+            Some("enable Color first")
+        } else if caption_style && self.output_caption.value().trim().is_empty() {
+            Some("enter caption text first")
+        } else if field == InputField::Streams && self.media_streams.is_empty() {
+            Some("no streams found")
+        } else if field == InputField::AudioTrack && self.audio_streams().len() < 2 {
+            Some("single audio track")
+        } else if field == InputField::Channels && self.audio_is_mono() {
+            Some("mono source")
+        } else if field == InputField::Gpu && !self.gpu_encoding_supported() {
+            Some("no NVENC encoder for this codec")
+        } else if audio_filter
+            && self.remove_audio
+            && self.editor_input_enabled(InputField::RemoveAudio)
+        {
+            Some("audio removed")
+        } else if field == InputField::TwoPass && !self.normalize_audio {
+            Some("enable Normalize first")
+        } else {
+            None
+        }
+    }
+
+    fn adjacent_enabled_input(&self, forward: bool) -> InputField {
+        let len = EDITOR_INPUT_ORDER.len();
+        let mut index = EDITOR_INPUT_ORDER
+            .iter()
+            .position(|field| *field == self.active_input)
+            .unwrap_or(0);
+        for _ in 0..len {
+            index = if forward {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            };
+            let field = EDITOR_INPUT_ORDER[index];
+            if self.editor_input_enabled(field) {
+                return field;
             }
-            InputField::Format => {
-                self.active_input = InputField::End;
-                self.end_part = 2;
+        }
+        self.active_input
+    }
+
+    /// Focuses `field` and resets its cursor the same way for Tab and Shift+Tab:
+    /// text fields put the cursor at the end and arm overwrite-on-type, and
+    /// time fields and the stream list start on the first (forward) or last
+    /// (backward) part.
+    fn enter_input(&mut self, field: InputField, forward: bool) {
+        self.active_input = field;
+        if let Some(input) = self.text_input_mut(field) {
+            input.focus();
+            return;
+        }
+        let time_part = if forward { 0 } else { 3 };
+        match field {
+            InputField::Start => self.start_part = time_part,
+            InputField::End => self.end_part = time_part,
+            InputField::Streams => {
+                self.stream_cursor = if forward {
+                    0
+                } else {
+                    self.media_streams.len().saturating_sub(1)
+                };
             }
-            InputField::Fps => self.active_input = InputField::Format,
-            InputField::Bitrate => {
-                self.active_input = InputField::Fps;
+            InputField::Fps => {
                 self.output_fps_cursor = self.output_fps.chars().count();
                 self.overwrite_fps_on_next_type = true;
             }
-            InputField::ScalePercent => {
-                if self.bitrate_enabled() {
-                    self.active_input = InputField::Bitrate;
-                    self.output_bitrate_cursor = self.output_bitrate_kbps.chars().count();
-                    self.overwrite_bitrate_on_next_type = true;
-                } else {
-                    self.active_input = InputField::Fps;
-                    self.output_fps_cursor = self.output_fps.chars().count();
-                    self.overwrite_fps_on_next_type = true;
-                }
+            InputField::Bitrate => {
+                self.output_bitrate_cursor = self.output_bitrate_kbps.chars().count();
+                self.overwrite_bitrate_on_next_type = true;
             }
-            InputField::RemoveAudio => {
-                self.active_input = InputField::ScalePercent;
+            InputField::ScalePercent => {
                 self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
                 self.overwrite_scale_percent_on_next_type = true;
             }
-            InputField::Output => {
-                if self.video_options_enabled() {
-                    self.active_input = InputField::RemoveAudio;
-                } else {
-                    self.active_input = InputField::Format;
-                }
-            }
+            InputField::Output => self.output_cursor = self.output_name.chars().count(),
+            _ => {}
+        }
+    }
+
+    /// Fields backed by the shared `TextInput` component. The older String
+    /// fields (FPS, bitrate, scale, output) keep their own handling below.
+    fn text_input_mut(&mut self, field: InputField) -> Option<&mut TextInput> {
+        match field {
+            InputField::Crop => Some(&mut self.output_crop),
+            InputField::StatsPeriod => Some(&mut self.ffmpeg_stats_period),
+            InputField::MaxWidth => Some(&mut self.output_max_width),
+            InputField::Speed => Some(&mut self.output_speed),
+            InputField::Caption => Some(&mut self.output_caption),
+            InputField::CaptionSize => Some(&mut self.caption_size),
+            InputField::TargetSize => Some(&mut self.output_target_size_mb),
+            InputField::ExtraArgs => Some(&mut self.ffmpeg_extra_args),
+            InputField::Brightness => Some(&mut self.color_brightness),
+            InputField::Contrast => Some(&mut self.color_contrast),
+            InputField::Saturation => Some(&mut self.color_saturation),
+            InputField::Gamma => Some(&mut self.color_gamma),
+            _ => None,
         }
     }
 
+    /// Whether the focused field is typed into, so a paste can be fed to it
+    /// one character at a time. Toggles and pickers would read spaces and
+    /// letters as commands instead.
+    pub fn active_input_takes_text(&mut self) -> bool {
+        let field = self.active_input;
+        matches!(
+            field,
+            InputField::Output | InputField::Fps | InputField::Bitrate | InputField::ScalePercent
+        ) || self.text_input_mut(field).is_some()
+    }
+
     pub fn move_cursor_left(&mut self) {
+        if let Some(input) = self.text_input_mut(self.active_input) {
+            input.move_left();
+            return;
+        }
         match self.active_input {
             InputField::Format => self.select_previous_output_format(),
+            InputField::Codec => self.cycle_video_codec(false),
+            InputField::CaptionPosition => self.cycle_caption_position(false),
+            InputField::AudioTrack => self.cycle_audio_track(false),
+            InputField::Channels => self.cycle_audio_channels(false),
+            InputField::LogLevel => self.cycle_ffmpeg_log_level(false),
+            InputField::Waveform => self.cycle_waveform_style(false),
+            InputField::Denoise => self.cycle_denoise_level(false),
+            InputField::Sharpen => self.cycle_sharpen_level(false),
+            InputField::FileTime => self.cycle_file_time_mode(false),
+            InputField::Timeline => self.nudge_timeline_marker(-1.0),
             InputField::Fps => {
                 self.output_fps_cursor = self.output_fps_cursor.saturating_sub(1);
                 self.overwrite_fps_on_next_type = false;
@@ -148,8 +401,22 @@ impl App {
     }
 
     pub fn move_cursor_right(&mut self) {
+        if let Some(input) = self.text_input_mut(self.active_input) {
+            input.move_right();
+            return;
+        }
         match self.active_input {
             InputField::Format => self.select_next_output_format(),
+            InputField::Codec => self.cycle_video_codec(true),
+            InputField::CaptionPosition => self.cycle_caption_position(true),
+            InputField::AudioTrack => self.cycle_audio_track(true),
+            InputField::Channels => self.cycle_audio_channels(true),
+            InputField::LogLevel => self.cycle_ffmpeg_log_level(true),
+            InputField::Waveform => self.cycle_waveform_style(true),
+            InputField::Denoise => self.cycle_denoise_level(true),
+            InputField::Sharpen => self.cycle_sharpen_level(true),
+            InputField::FileTime => self.cycle_file_time_mode(true),
+            InputField::Timeline => self.nudge_timeline_marker(1.0),
             InputField::Fps => {
                 let max = self.output_fps.chars().count();
                 self.output_fps_cursor = (self.output_fps_cursor + 1).min(max);
@@ -178,18 +445,53 @@ impl App {
     }
 
     pub fn push_active_input_char(&mut self, ch: char) {
+        let field = self.active_input;
+        let enabled = self.editor_input_enabled(field);
+        if let Some(input) = self.text_input_mut(field) {
+            if enabled && text_input_accepts(field, ch) {
+                input.insert(ch);
+            }
+            return;
+        }
         match self.active_input {
             InputField::Start => {
-                if ch.is_ascii_digit() {
+                if !self.push_time_offset_char(ch) && ch.is_ascii_digit() {
                     self.start_time.push_digit_to_part(self.start_part, ch);
                 }
             }
             InputField::End => {
-                if ch.is_ascii_digit() {
+                if !self.push_time_offset_char(ch) && ch.is_ascii_digit() {
                     self.end_time.push_digit_to_part(self.end_part, ch);
                 }
             }
-            InputField::Format => {}
+            InputField::Timeline => match ch {
+                ' ' => self.switch_timeline_marker(),
+                'H' => self.nudge_timeline_marker(-10.0),
+                'L' => self.nudge_timeline_marker(10.0),
+                _ => {}
+            },
+            InputField::Format
+            | InputField::Codec
+            | InputField::CaptionPosition
+            | InputField::AudioTrack
+            | InputField::Channels
+            | InputField::Waveform
+            | InputField::Denoise
+            | InputField::Sharpen
+            | InputField::FileTime
+            | InputField::LogLevel => {}
+            InputField::Crop
+            | InputField::StatsPeriod
+            | InputField::MaxWidth
+            | InputField::Speed
+            | InputField::Caption
+            | InputField::CaptionSize
+            | InputField::TargetSize
+            | InputField::ExtraArgs
+            | InputField::Brightness
+            | InputField::Contrast
+            | InputField::Saturation
+            | InputField::Gamma => {}
             InputField::Fps => {
                 if !self.video_options_enabled() {
                     return;
@@ -238,6 +540,74 @@ impl App {
                     self.toggle_remove_audio();
                 }
             }
+            InputField::ProgressOnly => {
+                if ch == ' ' {
+                    self.ffmpeg_progress_only = !self.ffmpeg_progress_only;
+                }
+            }
+            InputField::KeepMetadata => {
+                if ch == ' ' {
+                    self.keep_metadata = !self.keep_metadata;
+                }
+            }
+            InputField::StreamCopy => {
+                if self.editor_input_enabled(InputField::StreamCopy) && ch == ' ' {
+                    self.stream_copy = !self.stream_copy;
+                }
+            }
+            InputField::Gpu => {
+                if self.editor_input_enabled(InputField::Gpu) && ch == ' ' {
+                    self.use_gpu_encoding = !self.use_gpu_encoding;
+                }
+            }
+            InputField::FrameSequence => {
+                if self.editor_input_enabled(InputField::FrameSequence) && ch == ' ' {
+                    self.frame_sequence = !self.frame_sequence;
+                }
+            }
+            InputField::Color => {
+                if self.editor_input_enabled(InputField::Color) && ch == ' ' {
+                    self.color_adjust = !self.color_adjust;
+                }
+            }
+            InputField::CaptionOutline => {
+                if self.editor_input_enabled(InputField::CaptionOutline) && ch == ' ' {
+                    self.caption_outline = !self.caption_outline;
+                }
+            }
+            InputField::Deinterlace => {
+                if self.editor_input_enabled(InputField::Deinterlace) && ch == ' ' {
+                    self.deinterlace = !self.deinterlace;
+                }
+            }
+            InputField::ConstantFps => {
+                if self.editor_input_enabled(InputField::ConstantFps) && ch == ' ' {
+                    self.constant_fps = !self.constant_fps;
+                }
+            }
+            InputField::GifPalette => {
+                if self.editor_input_enabled(InputField::GifPalette) && ch == ' ' {
+                    self.gif_palette = !self.gif_palette;
+                }
+            }
+            InputField::Normalize => {
+                if self.editor_input_enabled(InputField::Normalize) && ch == ' ' {
+                    self.normalize_audio = !self.normalize_audio;
+                }
+            }
+            InputField::TwoPass => {
+                if self.editor_input_enabled(InputField::TwoPass) && ch == ' ' {
+                    self.normalize_two_pass = !self.normalize_two_pass;
+                }
+            }
+            InputField::Streams => {
+                if self.editor_input_enabled(InputField::Streams)
+                    && ch == ' '
+                    && let Some(selected) = self.stream_selected.get_mut(self.stream_cursor)
+                {
+                    *selected = !*selected;
+                }
+            }
             InputField::Output => {
                 let byte_index = byte_index_for_char(&self.output_name, self.output_cursor);
                 self.output_name.insert(byte_index, ch);
@@ -246,7 +616,195 @@ impl App {
         }
     }
 
+    /// Replaces the active time field with a pasted timestamp, or both
+    /// fields with a pasted `start - end` range. Returns false when a time
+    /// field is not active.
+    pub fn paste_into_time_input(&mut self, text: &str) -> bool {
+        if !matches!(self.active_input, InputField::Start | InputField::End) {
+            return false;
+        }
+        if let Some((start, end)) = TimeInput::parse_timestamp_range(text) {
+            if end.to_millis() <= start.to_millis() {
+                self.status_message = format!(
+                    "Range end {} is not after its start {}; times unchanged.",
+                    end.to_ffmpeg_timestamp(),
+                    start.to_ffmpeg_timestamp()
+                );
+                return true;
+            }
+            self.status_message = format!(
+                "Pasted range {} - {}.",
+                start.to_ffmpeg_timestamp(),
+                end.to_ffmpeg_timestamp()
+            );
+            self.start_time = start;
+            self.end_time = end;
+            return true;
+        }
+        let trimmed = text.trim();
+        if trimmed.starts_with(['-', '+', 'f']) {
+            self.pending_time_offset = Some((self.active_input, trimmed.to_string()));
+            self.commit_time_offset();
+            return true;
+        }
+        if let Some(frame) = trimmed.strip_suffix('f') {
+            self.pending_time_offset = Some((self.active_input, format!("f{frame}")));
+            self.commit_time_offset();
+            return true;
+        }
+        let time = match self.active_input {
+            InputField::Start => &mut self.start_time,
+            InputField::End => &mut self.end_time,
+            _ => return false,
+        };
+        match TimeInput::parse_timestamp(text) {
+            Some(parsed) => {
+                *time = parsed;
+                self.status_message =
+                    format!("Pasted {} into the time field.", time.to_ffmpeg_timestamp());
+            }
+            None => {
+                self.status_message = format!(
+                    "Not a timestamp: {:?} (use HH:MM:SS.mmm, MM:SS, 120f, or start - end).",
+                    text.trim()
+                );
+            }
+        }
+        true
+    }
+
+    /// The `-30`, `+1:10`, or `f120` being typed into `field`, if any.
+    pub fn time_offset_entry(&self, field: InputField) -> Option<&str> {
+        self.pending_time_offset
+            .as_ref()
+            .filter(|(entry_field, _)| *entry_field == field)
+            .map(|(_, entry)| entry.as_str())
+    }
+
+    pub fn cancel_time_offset_entry(&mut self) {
+        self.pending_time_offset = None;
+    }
+
+    /// A leading `-` or `+` starts a relative entry that takes digits, `:`,
+    /// and `.` until the field is left, and `f` starts a frame number.
+    /// Returns whether `ch` was consumed.
+    fn push_time_offset_char(&mut self, ch: char) -> bool {
+        if let Some((field, entry)) = &mut self.pending_time_offset
+            && *field == self.active_input
+        {
+            let frame_entry = entry.starts_with('f');
+            if ch.is_ascii_digit() || (!frame_entry && (ch == ':' || ch == '.')) {
+                entry.push(ch);
+            }
+            return true;
+        }
+        if ch == '-' || ch == '+' || ch == 'f' {
+            self.pending_time_offset = Some((self.active_input, ch.to_string()));
+            return true;
+        }
+        false
+    }
+
+    /// Turns a pending relative entry into an absolute time: `-N` counts
+    /// back from the probed end, `+N` forward from the probed start, and
+    /// `fN` is the start of frame N at the source frame rate.
+    pub(super) fn commit_time_offset(&mut self) {
+        let Some((field, entry)) = self.pending_time_offset.take() else {
+            return;
+        };
+        let (sign, magnitude) = entry.split_at(1);
+        if sign == "f" {
+            self.commit_frame_entry(field, magnitude);
+            return;
+        }
+        let Some(offset) = TimeInput::parse_timestamp(magnitude) else {
+            self.status_message = format!("Not a time offset: {entry:?} (e.g. -30 or +1:10).");
+            return;
+        };
+        let Some(bounds) = self.selected_video_bounds else {
+            self.status_message =
+                "Relative times need the clip length, and it could not be probed.".to_string();
+            return;
+        };
+        let offset_seconds = offset.to_seconds_f64();
+        let (first, last) = (bounds.start_seconds, bounds.end_seconds);
+        let seconds = if sign == "-" {
+            last - offset_seconds
+        } else {
+            first + offset_seconds
+        }
+        .clamp(first, last);
+        let time = TimeInput::from_seconds(seconds);
+        let label = if field == InputField::Start {
+            "Start"
+        } else {
+            "End"
+        };
+        let anchor = if sign == "-" {
+            "before the end"
+        } else {
+            "after the start"
+        };
+        self.status_message = format!(
+            "{label} set to {} ({offset_seconds}s {anchor}).",
+            time.to_ffmpeg_timestamp()
+        );
+        if field == InputField::Start {
+            self.start_time = time;
+        } else {
+            self.end_time = time;
+        }
+    }
+
+    fn commit_frame_entry(&mut self, field: InputField, digits: &str) {
+        let Ok(frame) = digits.parse::<u64>() else {
+            self.status_message = format!("Not a frame number: {digits:?} (e.g. f120).");
+            return;
+        };
+        let fps = self
+            .selected_video_stats
+            .as_ref()
+            .and_then(|stats| stats.frame_rate);
+        let Some(time) = fps.and_then(|fps| TimeInput::from_frame(frame, fps)) else {
+            self.status_message =
+                "Frame numbers need the source frame rate, and it could not be probed.".to_string();
+            return;
+        };
+        self.status_message = format!(
+            "{} set to {} (frame {frame} at {:.3} fps).",
+            if field == InputField::Start {
+                "Start"
+            } else {
+                "End"
+            },
+            time.to_ffmpeg_timestamp(),
+            fps.unwrap_or_default()
+        );
+        if field == InputField::Start {
+            self.start_time = time;
+        } else {
+            self.end_time = time;
+        }
+    }
+
     pub fn backspace_active_input(&mut self) {
+        let field = self.active_input;
+        let enabled = self.editor_input_enabled(field);
+        if let Some(input) = self.text_input_mut(field) {
+            if enabled {
+                input.backspace();
+            }
+            return;
+        }
+        if let Some((field, entry)) = &mut self.pending_time_offset
+            && *field == self.active_input
+        {
+            entry.pop();
+            if entry.is_empty() {
+                self.pending_time_offset = None;
+            }
+            return;
+        }
         match self.active_input {
             InputField::Start => {
                 self.start_time.clear_part(self.start_part);
@@ -254,7 +812,29 @@ impl App {
             InputField::End => {
                 self.end_time.clear_part(self.end_part);
             }
-            InputField::Format => {}
+            InputField::Timeline
+            | InputField::Format
+            | InputField::Codec
+            | InputField::CaptionPosition
+            | InputField::AudioTrack
+            | InputField::Channels
+            | InputField::Waveform
+            | InputField::Denoise
+            | InputField::Sharpen
+            | InputField::FileTime
+            | InputField::LogLevel => {}
+            InputField::Crop
+            | InputField::StatsPeriod
+            | InputField::MaxWidth
+            | InputField::Speed
+            | InputField::Caption
+            | InputField::CaptionSize
+            | InputField::TargetSize
+            | InputField::ExtraArgs
+            | InputField::Brightness
+            | InputField::Contrast
+            | InputField::Saturation
+            | InputField::Gamma => {}
             InputField::Fps => {
                 if !self.video_options_enabled() {
                     return;
@@ -297,7 +877,20 @@ impl App {
                 self.output_scale_percent.replace_range(start..end, "");
                 self.output_scale_percent_cursor -= 1;
             }
-            InputField::RemoveAudio => {}
+            InputField::RemoveAudio
+            | InputField::ProgressOnly
+            | InputField::KeepMetadata
+            | InputField::StreamCopy
+            | InputField::Gpu
+            | InputField::CaptionOutline
+            | InputField::FrameSequence
+            | InputField::GifPalette
+            | InputField::Deinterlace
+            | InputField::ConstantFps
+            | InputField::Color
+            | InputField::Normalize
+            | InputField::TwoPass
+            | InputField::Streams => {}
             InputField::Output => {
                 if self.output_cursor == 0 {
                     return;
@@ -311,6 +904,72 @@ impl App {
         }
     }
 
+    fn cycle_ffmpeg_log_level(&mut self, forward: bool) {
+        self.ffmpeg_log_level = cycle_choice(&FFMPEG_LOG_LEVELS, self.ffmpeg_log_level, forward);
+    }
+
+    fn cycle_caption_position(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::CaptionPosition) {
+            self.caption_position =
+                cycle_choice(&CAPTION_POSITIONS, self.caption_position, forward);
+        }
+    }
+
+    fn cycle_waveform_style(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::Waveform) {
+            self.waveform_style = cycle_choice(&WAVEFORM_STYLES, self.waveform_style, forward);
+        }
+    }
+
+    /// A waveform replaces the picture; off while the field doesn't apply.
+    pub fn waveform_active(&self) -> bool {
+        self.waveform_style != WAVEFORM_STYLES[0] && self.editor_input_enabled(InputField::Waveform)
+    }
+
+    fn cycle_denoise_level(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::Denoise) {
+            self.denoise_level = cycle_choice(&DENOISE_LEVELS, self.denoise_level, forward);
+        }
+    }
+
+    fn cycle_sharpen_level(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::Sharpen) {
+            self.sharpen_level = cycle_choice(&SHARPEN_LEVELS, self.sharpen_level, forward);
+        }
+    }
+
+    fn cycle_file_time_mode(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::FileTime) {
+            self.file_time_mode = cycle_choice(&FILE_TIME_MODES, self.file_time_mode, forward);
+        }
+    }
+
+    fn cycle_audio_track(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::AudioTrack) {
+            let track_count = self.audio_streams().len();
+            self.audio_track = self.audio_track.cycle(track_count, forward);
+        }
+    }
+
+    fn cycle_audio_channels(&mut self, forward: bool) {
+        if self.editor_input_enabled(InputField::Channels) {
+            self.audio_channels = cycle_choice(&CHANNEL_MODES, self.audio_channels, forward);
+        }
+    }
+
+    /// Every audio track has one channel, so there is nothing to swap or
+    /// downmix. Mixing tracks always produces stereo.
+    fn audio_is_mono(&self) -> bool {
+        let streams = self.audio_streams();
+        self.audio_track != AudioTrack::Mix
+            && !streams.is_empty()
+            && streams.iter().all(|stream| stream.channels == Some(1))
+    }
+
+    fn cycle_video_codec(&mut self, forward: bool) {
+        self.output_video_codec = cycle_choice(&VIDEO_CODECS, self.output_video_codec, forward);
+    }
+
     fn select_previous_output_format(&mut self) {
         let current_index = OUTPUT_FORMATS
             .iter()
@@ -353,7 +1012,7 @@ impl App {
         self.sync_output_extension_to_selected_format();
     }
 
-    fn sync_output_extension_to_selected_format(&mut self) {
+    pub(super) fn sync_output_extension_to_selected_format(&mut self) {
         if self.output_name.trim().is_empty() {
             return;
         }
@@ -369,7 +1028,8 @@ impl App {
 
     pub(super) fn sync_output_name_to_available_for_path(&mut self, input_path: &Path) {
         let requested_output_name = enforce_output_extension(&self.output_name, self.output_format);
-        let requested_output_path = resolve_output_path(input_path, &requested_output_name);
+        let requested_output_path =
+            resolve_output_path(&self.output_anchor(input_path), &requested_output_name);
         let normalized_output_path = output_path_without_numbered_suffix(&requested_output_path);
         let available_output_path = next_available_output_path(&normalized_output_path);
         self.sync_output_name_with_path(&requested_output_name, &available_output_path);
@@ -397,6 +1057,40 @@ impl App {
     }
 }
 
+/// Returns the entry after (or before) `current`, wrapping at either end.
+fn cycle_choice(choices: &[&'static str], current: &str, forward: bool) -> &'static str {
+    let len = choices.len();
+    let current_index = choices
+        .iter()
+        .position(|choice| *choice == current)
+        .unwrap_or(0);
+    let next_index = if forward {
+        (current_index + 1) % len
+    } else {
+        (current_index + len - 1) % len
+    };
+    choices[next_index]
+}
+
+/// Character filter for `TextInput`-backed editor fields.
+fn text_input_accepts(field: InputField, ch: char) -> bool {
+    match field {
+        InputField::Crop => ch.is_ascii_digit() || ch == ':',
+        InputField::StatsPeriod => ch.is_ascii_digit() || ch == '.',
+        InputField::MaxWidth => ch.is_ascii_digit(),
+        InputField::Speed => ch.is_ascii_digit() || ch == '.',
+        InputField::Caption => !ch.is_control(),
+        InputField::CaptionSize => ch.is_ascii_digit(),
+        InputField::TargetSize => ch.is_ascii_digit() || ch == '.',
+        InputField::ExtraArgs => !ch.is_control(),
+        InputField::Brightness => ch.is_ascii_digit() || ch == '.' || ch == '-',
+        InputField::Contrast | InputField::Saturation | InputField::Gamma => {
+            ch.is_ascii_digit() || ch == '.'
+        }
+        _ => false,
+    }
+}
+
 pub(super) fn byte_index_for_char(input: &str, char_index: usize) -> usize {
     if char_index == 0 {
         return 0;
@@ -0,0 +1,94 @@
+// Run transcripts for ffmpeg and yt-dlp jobs.
+// - Formats one run (command, exit code, stderr, stdout) as a log entry.
+// - With --job-logs, also writes each export or download to its own file
+//   under `logs/`, named after the output, so one failed job's transcript
+//   can be attached on its own.
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::media::next_available_output_path;
+
+use super::App;
+
+const JOB_LOG_DIR_NAME: &str = "logs";
+
+/// One run as a log entry. The pid tells apart entries from several rt
+/// instances sharing a log.
+pub(super) fn run_transcript(
+    tool: &str,
+    command_line: &str,
+    exit_code: Option<i32>,
+    stdout: &[u8],
+    stderr: &[u8],
+    launch_error: Option<&str>,
+) -> io::Result<Vec<u8>> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let mut entry = Vec::new();
+    writeln!(
+        entry,
+        "=== {tool} run @ {timestamp} (rt pid {}) ===",
+        process::id()
+    )?;
+    writeln!(entry, "command: {command_line}")?;
+    match exit_code {
+        Some(code) => writeln!(entry, "exit_code: {code}")?,
+        None => writeln!(entry, "exit_code: <none>")?,
+    }
+
+    if let Some(err) = launch_error {
+        writeln!(entry, "launch_error: {err}")?;
+    }
+
+    writeln!(entry, "--- stderr ---")?;
+    entry.extend_from_slice(stderr);
+    if !stderr.ends_with(b"\n") {
+        writeln!(entry)?;
+    }
+
+    writeln!(entry, "--- stdout ---")?;
+    entry.extend_from_slice(stdout);
+    if !stdout.ends_with(b"\n") {
+        writeln!(entry)?;
+    }
+
+    writeln!(entry, "=== end ===")?;
+    writeln!(entry)?;
+    Ok(entry)
+}
+
+impl App {
+    pub fn set_job_logs(&mut self, enabled: bool) {
+        self.job_logs = enabled;
+    }
+
+    /// Writes `entry` to `logs/<output name>.log` in the start directory when
+    /// per-job logs are on. Repeated runs for the same output get numbered
+    /// files instead of replacing the earlier transcript.
+    pub(super) fn write_job_log(
+        &self,
+        output_path: Option<&Path>,
+        entry: &[u8],
+    ) -> io::Result<Option<PathBuf>> {
+        if !self.job_logs {
+            return Ok(None);
+        }
+        let name = output_path
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "job".to_string());
+        let dir = self.initial_dir.join(JOB_LOG_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+        let path = next_available_output_path(&dir.join(format!("{name}.log")));
+        fs::write(&path, entry)?;
+        Ok(Some(path))
+    }
+}
@@ -0,0 +1,107 @@
+// Files that running jobs read or write.
+// - Finds the job using a path: the editor's ffmpeg run (including every
+//   input of a concat), files queued in a batch, a copy or move, and the
+//   download target.
+// - The browser checks it before deleting so a job never loses its input
+//   or output halfway through.
+// - The footer pins the folder a job writes to once the browser has moved
+//   elsewhere, so finished files don't land somewhere out of sight.
+use std::{
+    iter,
+    path::{Path, PathBuf},
+};
+
+use super::{App, FfmpegJobKind, files::entry_name, quick_jump::tilde_path};
+
+impl App {
+    /// How a running job uses `path`, or a file inside it when `path` is a
+    /// folder, e.g. `the running clip export writes it`. `None` when no job
+    /// touches it.
+    pub(super) fn job_using_path(&self, path: &Path) -> Option<String> {
+        let it = |used: &Path| if used == path { "it" } else { "a file in it" };
+        if let Some(running) = self.running_editor.as_ref() {
+            let label = running.kind.output_label();
+            if running.output_path.starts_with(path) && running.kind != FfmpegJobKind::Verify {
+                return Some(format!(
+                    "the running {label} job writes {}",
+                    it(&running.output_path)
+                ));
+            }
+            let concat_inputs = self
+                .concat_items
+                .iter()
+                .filter(|_| running.kind == FfmpegJobKind::Concat)
+                .map(|item| item.path.as_path());
+            let read = iter::once(running.input_path.as_path())
+                .chain(concat_inputs)
+                .find(|input| input.starts_with(path));
+            if let Some(read) = read {
+                return Some(format!("the running {label} job reads {}", it(read)));
+            }
+        }
+        if let Some(batch) = self.editor_batch.as_ref()
+            && let Some(queued) = batch.queued_under(path)
+        {
+            return Some(format!("{} is queued in the running batch", it(queued)));
+        }
+        if let Some(usage) = self.file_op_using_path(path) {
+            return Some(usage);
+        }
+        if let Some(running) = self.running_downloader.as_ref() {
+            match running.output_file.as_deref() {
+                Some(output) => {
+                    // yt-dlp writes `<name>.part` until the download completes.
+                    let partial = PathBuf::from(format!("{}.part", output.display()));
+                    if let Some(written) = [output, partial.as_path()]
+                        .into_iter()
+                        .find(|written| written.starts_with(path))
+                    {
+                        return Some(format!("the running download writes {}", it(written)));
+                    }
+                }
+                // A playlist writes files named as they arrive.
+                None if running.output_dir.starts_with(path) => {
+                    return Some("the running download writes into it".to_string());
+                }
+                None => {}
+            }
+        }
+        None
+    }
+
+    /// Refuses a file operation on `path` while a job uses it, with the
+    /// reason on the status line. True when the operation may go ahead.
+    pub(super) fn ensure_not_in_use(&mut self, action: &str, path: &Path) -> bool {
+        let Some(usage) = self.job_using_path(path) else {
+            return true;
+        };
+        self.status_message = format!(
+            "Can't {action} {}: {usage}. Wait for it to finish or cancel it (x in the tool output).",
+            entry_name(path)
+        );
+        false
+    }
+
+    /// Folder the running editor job writes to, when it isn't the one the
+    /// browser shows.
+    pub fn editor_writes_elsewhere(&self) -> Option<String> {
+        let running = self.running_editor.as_ref()?;
+        if running.kind == FfmpegJobKind::Verify {
+            return None;
+        }
+        let dir = running.output_path.parent()?;
+        (dir != self.cwd).then(|| tilde_path(dir))
+    }
+
+    /// Folder the running download writes to, when it isn't the one the
+    /// browser shows.
+    pub fn downloader_writes_elsewhere(&self) -> Option<String> {
+        let running = self.running_downloader.as_ref()?;
+        let dir = running
+            .output_file
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or(&running.output_dir);
+        (dir != self.cwd).then(|| tilde_path(dir))
+    }
+}
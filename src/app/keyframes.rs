@@ -0,0 +1,302 @@
+// Keyframe hints for the editor's start and end times.
+// - Scans keyframes around each time on a background thread.
+// - Caches the neighbours so the form can show where a copy cut will land.
+// - Snaps the focused time field to a keyframe on request.
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::{
+    media::{is_video_file, nearest_keyframe, probe_keyframe_times},
+    model::{InputField, TimeInput},
+};
+
+use super::{App, editor::KEYFRAME_SEARCH_WINDOW_SECONDS};
+
+/// Keyframes on either side of the start or end time. The time fields hold
+/// milliseconds, so a keyframe inside that millisecond counts as at the time.
+pub(crate) struct KeyframeHint {
+    path: PathBuf,
+    millis: u64,
+    /// `None` when the scan itself failed.
+    pub(crate) keyframes: Option<Vec<f64>>,
+}
+
+impl KeyframeHint {
+    fn time(&self) -> f64 {
+        self.millis as f64 / 1000.0
+    }
+
+    /// Last keyframe at or before the time.
+    pub(crate) fn previous(&self) -> Option<f64> {
+        let millisecond_end = self.time() + 0.001;
+        self.keyframes
+            .as_ref()?
+            .iter()
+            .copied()
+            .filter(|keyframe| *keyframe < millisecond_end)
+            .max_by(f64::total_cmp)
+    }
+
+    /// First keyframe after the time.
+    pub(crate) fn next(&self) -> Option<f64> {
+        let millisecond_end = self.time() + 0.001;
+        self.keyframes
+            .as_ref()?
+            .iter()
+            .copied()
+            .filter(|keyframe| *keyframe >= millisecond_end)
+            .min_by(f64::total_cmp)
+    }
+
+    /// A keyframe falls inside the time's millisecond, so a copy cuts
+    /// cleanly.
+    pub(crate) fn on_keyframe(&self) -> bool {
+        self.previous()
+            .is_some_and(|keyframe| keyframe >= self.time())
+    }
+}
+
+/// An export held back until the scan for its start time is in, since a
+/// stream copy can only cut on a keyframe. Each holds the form's unsnapped
+/// commands from when it was asked for, so an edit made meanwhile is noticed.
+pub(super) enum KeyframeWait {
+    /// The form's export.
+    Export { commands: Option<Vec<String>> },
+    /// The batch file loaded into the form.
+    Batch {
+        path: PathBuf,
+        name: String,
+        commands: Option<Vec<String>>,
+    },
+}
+
+pub(super) struct RunningKeyframeProbe {
+    path: PathBuf,
+    /// `Start` or `End`, whichever time was scanned.
+    field: InputField,
+    millis: u64,
+    rx: Receiver<Option<Vec<f64>>>,
+}
+
+impl App {
+    /// Keeps the keyframe hints in step with the editor's start and end
+    /// times. Only one scan runs at a time, start first; a stale result is
+    /// dropped and rescanned.
+    pub(super) fn refresh_keyframe_hint(&mut self) {
+        if let Some(running) = &self.running_keyframe_probe {
+            let keyframes = match running.rx.try_recv() {
+                Ok(keyframes) => keyframes,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => None,
+            };
+            if let Some(running) = self.running_keyframe_probe.take() {
+                let hint = Some(KeyframeHint {
+                    path: running.path,
+                    millis: running.millis,
+                    keyframes,
+                });
+                if running.field == InputField::End {
+                    self.end_keyframe_hint = hint;
+                } else {
+                    self.keyframe_hint = hint;
+                }
+            }
+        }
+
+        let Some(path) = self.selected_video.clone() else {
+            return;
+        };
+        if !is_video_file(&path) {
+            return;
+        }
+        let (field, millis) = if self.keyframe_hint().is_none() {
+            (InputField::Start, self.start_time.to_millis())
+        } else if self.end_keyframe_hint().is_none() {
+            (InputField::End, self.end_time.to_millis())
+        } else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let probe_path = path.clone();
+        thread::spawn(move || {
+            let keyframes = probe_keyframe_times(
+                &probe_path,
+                millis as f64 / 1000.0,
+                KEYFRAME_SEARCH_WINDOW_SECONDS,
+            )
+            .ok();
+            let _ = tx.send(keyframes);
+        });
+        self.running_keyframe_probe = Some(RunningKeyframeProbe {
+            path,
+            field,
+            millis,
+            rx,
+        });
+    }
+
+    /// Whether a stream copy export must wait for the start's scan.
+    pub(super) fn keyframe_scan_pending(&self) -> bool {
+        self.stream_copy_active()
+            && self.selected_video.as_deref().is_some_and(is_video_file)
+            && self.keyframe_hint().is_none()
+    }
+
+    /// The commands the form builds without its start's keyframe hint.
+    /// They stay the same from before a scan to after it unless the form
+    /// or the file changed.
+    pub(super) fn unsnapped_editor_commands(&mut self) -> Option<Vec<String>> {
+        let hint = self.keyframe_hint.take();
+        let commands = self.editor_command_preview().ok();
+        self.keyframe_hint = hint;
+        commands
+    }
+
+    /// Starts the export held back for a keyframe scan once it finished,
+    /// unless the form changed while it waited.
+    pub(super) fn resume_keyframe_wait(&mut self) {
+        if self.keyframe_wait.is_none() || self.keyframe_scan_pending() {
+            return;
+        }
+        let Some(wait) = self.keyframe_wait.take() else {
+            return;
+        };
+        let commands = self.unsnapped_editor_commands();
+        match wait {
+            KeyframeWait::Export { commands: asked } if asked == commands => {
+                self.run_editor_export();
+            }
+            KeyframeWait::Export { .. } => {
+                self.status_message =
+                    "The form changed during the keyframe scan, so the export wasn't started. Press Enter to export."
+                        .to_string();
+            }
+            KeyframeWait::Batch {
+                path,
+                name,
+                commands: asked,
+            } if asked == commands => self.resume_batch_file(path, name),
+            // Load the file and preset again, undoing the edit.
+            KeyframeWait::Batch { path, .. } => self.restart_batch_file(path),
+        }
+    }
+
+    /// Drops a single export held for a keyframe scan, for Esc.
+    pub fn cancel_held_export(&mut self) {
+        if matches!(self.keyframe_wait, Some(KeyframeWait::Export { .. })) {
+            self.keyframe_wait = None;
+            self.status_message = "Export cancelled before it started.".to_string();
+        }
+    }
+
+    /// Drops whatever export is held for a keyframe scan, stopping the
+    /// batch it belongs to.
+    pub(super) fn cancel_keyframe_wait(&mut self) {
+        match self.keyframe_wait.take() {
+            Some(KeyframeWait::Export { .. }) => {
+                self.status_message = "Export cancelled before it started.".to_string();
+            }
+            Some(KeyframeWait::Batch { path, .. }) => self.stop_batch_before(path),
+            None => {}
+        }
+    }
+
+    /// Hint for the current video and start time, once scanned.
+    pub fn keyframe_hint(&self) -> Option<&KeyframeHint> {
+        self.current_keyframe_hint(self.keyframe_hint.as_ref(), &self.start_time)
+    }
+
+    /// Hint for the current video and end time, once scanned.
+    pub fn end_keyframe_hint(&self) -> Option<&KeyframeHint> {
+        self.current_keyframe_hint(self.end_keyframe_hint.as_ref(), &self.end_time)
+    }
+
+    fn current_keyframe_hint<'a>(
+        &self,
+        hint: Option<&'a KeyframeHint>,
+        time: &TimeInput,
+    ) -> Option<&'a KeyframeHint> {
+        hint.filter(|hint| {
+            self.selected_video.as_ref() == Some(&hint.path) && hint.millis == time.to_millis()
+        })
+    }
+
+    /// Snaps whichever time field is focused: the end to its nearest
+    /// keyframe, otherwise the start back to the keyframe a copy begins on.
+    pub fn snap_time_to_keyframe(&mut self) {
+        if self.active_input == InputField::End {
+            self.snap_end_to_nearest_keyframe();
+        } else {
+            self.snap_start_to_previous_keyframe();
+        }
+    }
+
+    /// Moves the start time back to the keyframe a stream copy would begin
+    /// on. The field holds milliseconds, so it takes the millisecond
+    /// containing that keyframe.
+    fn snap_start_to_previous_keyframe(&mut self) {
+        if self.selected_video.is_none() {
+            self.status_message = "Select a video before snapping to a keyframe.".to_string();
+            return;
+        }
+        let Some(hint) = self.keyframe_hint() else {
+            self.status_message = "Still scanning keyframes. Try again in a moment.".to_string();
+            return;
+        };
+        let Some(keyframe) = hint.previous() else {
+            self.status_message = format!(
+                "No keyframe within {KEYFRAME_SEARCH_WINDOW_SECONDS}s before the start time."
+            );
+            return;
+        };
+
+        self.start_time = TimeInput::from_seconds(floor_to_millis(keyframe));
+        self.status_message = format!(
+            "Start snapped to previous keyframe at {keyframe:.3}s ({}).",
+            self.start_time.to_ffmpeg_timestamp()
+        );
+    }
+
+    /// Moves the end time to the closest keyframe on either side, as long as
+    /// it stays after the start.
+    fn snap_end_to_nearest_keyframe(&mut self) {
+        if self.selected_video.is_none() {
+            self.status_message = "Select a video before snapping to a keyframe.".to_string();
+            return;
+        }
+        let Some(hint) = self.end_keyframe_hint() else {
+            self.status_message = "Still scanning keyframes. Try again in a moment.".to_string();
+            return;
+        };
+        let target = hint.time();
+        let Some(keyframe) = hint
+            .keyframes
+            .as_deref()
+            .and_then(|keyframes| nearest_keyframe(keyframes, target))
+        else {
+            self.status_message =
+                format!("No keyframe within {KEYFRAME_SEARCH_WINDOW_SECONDS}s of the end time.");
+            return;
+        };
+        let end = floor_to_millis(keyframe);
+        if end <= self.start_time.to_seconds_f64() {
+            self.status_message =
+                format!("Nearest keyframe at {keyframe:.3}s is not after the start time.");
+            return;
+        }
+
+        self.end_time = TimeInput::from_seconds(end);
+        self.status_message = format!(
+            "End snapped to nearest keyframe at {keyframe:.3}s ({}).",
+            self.end_time.to_ffmpeg_timestamp()
+        );
+    }
+}
+
+fn floor_to_millis(seconds: f64) -> f64 {
+    (seconds * 1000.0).floor() / 1000.0
+}
@@ -0,0 +1,107 @@
+// Marked files in the browser and actions that use them.
+// - Toggles marks on files, remembering the order they were marked in.
+// - Writes an m3u8 playlist of the marked media files.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::media::{is_editable_media_file, next_available_output_path};
+
+use super::App;
+
+const PLAYLIST_FILE_NAME: &str = "playlist.m3u8";
+
+impl App {
+    /// Marks or unmarks the selected file. Marks survive directory changes
+    /// so sets can be collected across folders.
+    pub fn toggle_selected_mark(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return;
+        };
+        if entry.is_dir {
+            self.status_message = "Only files can be marked.".to_string();
+            return;
+        }
+
+        if let Some(index) = self.marked.iter().position(|path| *path == entry.path) {
+            self.marked.remove(index);
+            self.status_message = format!("Unmarked {} ({} marked)", entry.name, self.marked.len());
+        } else {
+            self.marked.push(entry.path);
+            self.status_message = format!("Marked {} ({} marked)", entry.name, self.marked.len());
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.status_message = "Cleared all marks.".to_string();
+    }
+
+    /// 1-based position of `path` in mark order.
+    pub fn mark_number(&self, path: &Path) -> Option<usize> {
+        self.marked
+            .iter()
+            .position(|marked| marked == path)
+            .map(|index| index + 1)
+    }
+
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Writes the marked media files, in mark order, to a playlist in the
+    /// current directory.
+    pub fn write_playlist_from_marks(&mut self) {
+        let media = self
+            .marked
+            .iter()
+            .filter(|path| is_editable_media_file(path))
+            .cloned()
+            .collect::<Vec<_>>();
+        if media.is_empty() {
+            self.status_message = "Mark media files with Space first.".to_string();
+            return;
+        }
+
+        let playlist_path = next_available_output_path(&self.cwd.join(PLAYLIST_FILE_NAME));
+        if let Err(err) = write_playlist(&playlist_path, &media) {
+            self.status_message = format!("Failed to write playlist: {err}");
+            return;
+        }
+
+        let name = playlist_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let skipped = self.marked.len() - media.len();
+        self.status_message = if skipped == 0 {
+            format!("Wrote {name} with {} files", media.len())
+        } else {
+            format!(
+                "Wrote {name} with {} files (skipped {skipped} non-media)",
+                media.len()
+            )
+        };
+        if let Err(err) = self.refresh_file_browser_after_save(&playlist_path) {
+            self.status_message = format!("Wrote {name}, but failed to refresh browser: {err}");
+        }
+    }
+}
+
+/// Entries inside the playlist's directory are written relative to it so
+/// the folder can be moved as a unit; anything else stays absolute.
+fn write_playlist(playlist_path: &Path, files: &[PathBuf]) -> io::Result<()> {
+    let playlist_dir = playlist_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut contents = String::from("#EXTM3U\n");
+    for file in files {
+        let title = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let entry = file.strip_prefix(playlist_dir).unwrap_or(file);
+        contents.push_str(&format!("#EXTINF:-1,{title}\n{}\n", entry.display()));
+    }
+    fs::write(playlist_path, contents)
+}
@@ -2,46 +2,183 @@
 // - Stores file-browser state, editor form inputs, tab/focus state, and output logs.
 // - Owns background ffmpeg job state and process communication handles.
 // - Exposes cross-cutting helpers used by event handling and rendering code.
+mod batch;
+mod bitrate;
+mod bookmarks;
+mod breadcrumb;
+mod changelog;
+mod cleanup;
+mod clipboard;
+mod concat;
+mod config;
+mod config_export;
+mod contact_sheet;
+mod dir_delete;
+mod dir_watch;
 mod downloader;
+mod downloader_defaults;
+mod downloader_url;
+mod durations;
 mod editor;
+mod editor_form;
 mod ffmpeg;
+mod file_ops;
+mod file_types;
 mod files;
+mod free_space;
+mod fuzzy_filter;
+mod health;
 mod input;
+mod job_logs;
+mod job_paths;
+mod keyframes;
+mod marks;
+mod pane_split;
+mod path_jump;
+mod playback;
+mod presets;
+mod preview;
+mod quick_jump;
+mod remux;
+mod screen_reader;
+mod segments;
+mod state_lock;
+mod stats;
+mod stream_input;
+mod tags;
+mod timeline;
 mod tool_output;
+mod trash;
+mod type_ahead;
+mod verify;
+mod visibility;
 
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
     env, fs, io,
     path::PathBuf,
     process::{Child, Command, Stdio},
-    sync::mpsc::Receiver,
+    sync::{Arc, atomic::AtomicBool, mpsc::Receiver},
+    time::{Duration, Instant, SystemTime},
 };
 
 use crate::{
-    media::{OUTPUT_FORMATS, VideoStats, is_audio_output_format},
-    model::{DownloaderStep, FileEntry, Focus, InputField, RightTab, TimeInput, VideoBounds},
+    keymap::Keymap,
+    media::{
+        CAPTION_POSITIONS, CHANNEL_MODES, DEFAULT_FFMPEG_LOG_LEVEL, DENOISE_LEVELS,
+        FILE_TIME_MODES, MediaStream, OUTPUT_FORMATS, SHARPEN_LEVELS, VIDEO_CODECS, VideoStats,
+        WAVEFORM_STYLES, gpu_video_encoders, is_animated_image_format, is_audio_output_format,
+        is_still_image_format,
+    },
+    model::{
+        AfterExport, AudioTrack, CountPrefix, DownloaderStep, FileEntry, Focus, InputField,
+        PerfSettings, RightTab, TagFilter, TextInput, TimeInput, VideoBounds,
+    },
+    theme::Theme,
 };
 
+use self::batch::{BatchPrompt, BatchReview, EditorBatch};
+use self::bitrate::{BitrateGraph, RunningBitrateProbe};
+use self::bookmarks::{BookmarkPicker, load_bookmarks};
+use self::changelog::{ChangelogScreen, record_version};
+use self::cleanup::{CleanupPrompt, CleanupReview};
+use self::concat::ConcatItem;
+use self::config::{Config, EditorDefaults};
+use self::dir_delete::DirDelete;
+use self::dir_watch::DirWatch;
+use self::downloader_defaults::DownloaderDefaults;
+use self::durations::EntryDurations;
+use self::file_ops::{FileOpPrompt, RunningFileOp};
+use self::file_types::FileTypeStyles;
 use self::files::read_entries;
+use self::free_space::min_free_bytes_from_config;
+use self::fuzzy_filter::FuzzyFilter;
+use self::health::{HealthScreen, is_first_run};
+use self::keyframes::{KeyframeHint, KeyframeWait, RunningKeyframeProbe};
+use self::pane_split::PaneSplit;
+use self::path_jump::PathJump;
+use self::playback::RunningPlayback;
+use self::presets::{ExportPreset, PresetPicker, load_presets};
+use self::preview::{FramePreview, RunningFramePreview};
+use self::quick_jump::QuickJumps;
+use self::stats::JobStats;
+use self::stream_input::StreamPrompt;
+use self::tags::{NotePrompt, TagIndex};
 use self::tool_output::ToolOutput;
+use self::trash::LastDelete;
+use self::type_ahead::TypeAhead;
+
+pub use self::breadcrumb::breadcrumb_segments;
+pub use self::concat::ConcatMode;
+pub use self::file_ops::FileOpKind;
+pub use self::health::HealthLevel;
+pub use self::tags::stars;
+pub use self::timeline::TimelineMarker;
 
 pub struct App {
     pub(crate) cwd: PathBuf,
     initial_dir: PathBuf,
     pub(crate) entries: Vec<FileEntry>,
+    /// Every path in the last read of `cwd`, before the tag filter, so a
+    /// reload can spot new files.
+    listed_paths: HashSet<PathBuf>,
+    /// Reload the listing when the folder changes on disk.
+    auto_refresh: bool,
+    dir_watch: DirWatch,
     file_browser_visible_rows: Cell<usize>,
     pub(crate) selected: usize,
     pub(crate) selected_video: Option<PathBuf>,
     pub(crate) start_time: TimeInput,
     pub(crate) end_time: TimeInput,
     pub(crate) output_format: &'static str,
+    pub(crate) output_video_codec: &'static str,
     pub(crate) output_fps: String,
     pub(crate) output_bitrate_kbps: String,
+    /// Output size in MB to derive the video bitrate from; empty when off.
+    pub(crate) output_target_size_mb: TextInput,
     pub(crate) output_scale_percent: String,
+    pub(crate) output_crop: TextInput,
+    pub(crate) output_speed: TextInput,
+    pub(crate) output_caption: TextInput,
+    pub(crate) caption_position: &'static str,
+    pub(crate) caption_size: TextInput,
+    pub(crate) caption_outline: bool,
     use_gpu_encoding: bool,
     pub(crate) remove_audio: bool,
+    pub(crate) stream_copy: bool,
+    pub(crate) ffmpeg_log_level: &'static str,
+    pub(crate) ffmpeg_stats_period: TextInput,
+    /// Raw flags appended to the generated ffmpeg command.
+    pub(crate) ffmpeg_extra_args: TextInput,
+    pub(crate) gif_palette: bool,
+    /// Runs `bwdif` first; turned on when the source is interlaced.
+    pub(crate) deinterlace: bool,
+    /// Forces a constant frame rate; turned on for variable-rate sources.
+    pub(crate) constant_fps: bool,
+    pub(crate) waveform_style: &'static str,
+    pub(crate) denoise_level: &'static str,
+    pub(crate) sharpen_level: &'static str,
+    /// Expands the Color rows; their `eq` filter only applies while on.
+    pub(crate) color_adjust: bool,
+    pub(crate) color_brightness: TextInput,
+    pub(crate) color_contrast: TextInput,
+    pub(crate) color_saturation: TextInput,
+    pub(crate) color_gamma: TextInput,
+    pub(crate) normalize_audio: bool,
+    pub(crate) normalize_two_pass: bool,
+    pub(crate) output_max_width: TextInput,
+    pub(crate) frame_sequence: bool,
+    /// Copies the source's global metadata (titles, creation dates); off
+    /// strips it.
+    pub(crate) keep_metadata: bool,
+    /// Modification time given to the output: `now`, the source's, or the
+    /// source's moved on by the start time.
+    pub(crate) file_time_mode: &'static str,
+    pub(crate) ffmpeg_progress_only: bool,
     pub(crate) output_name: String,
     pub(crate) active_input: InputField,
+    timeline_marker: TimelineMarker,
     pub(crate) start_part: usize,
     pub(crate) end_part: usize,
     pub(crate) output_fps_cursor: usize,
@@ -52,12 +189,27 @@ pub struct App {
     pub(crate) overwrite_bitrate_on_next_type: bool,
     pub(crate) overwrite_scale_percent_on_next_type: bool,
     pub(crate) selected_video_stats: Option<VideoStats>,
+    /// Streams of the selected media and whether each is mapped into the
+    /// export. Empty when ffprobe could not list them.
+    pub(crate) media_streams: Vec<MediaStream>,
+    pub(crate) stream_selected: Vec<bool>,
+    pub(crate) stream_cursor: usize,
+    pub(crate) audio_track: AudioTrack,
+    pub(crate) audio_channels: &'static str,
+    /// Start/End ranges joined into one export, in seconds. Empty means the
+    /// Start and End fields alone.
+    segments: Vec<(f64, f64)>,
     selected_video_bounds: Option<VideoBounds>,
+    /// Relative time typed into Start or End, applied when the field is left.
+    pending_time_offset: Option<(InputField, String)>,
     pub(crate) status_message: String,
     pub(crate) editor_form_scroll: Cell<usize>,
     editor_last_focus_line: Cell<Option<usize>>,
     pub(crate) ffmpeg_output: ToolOutput,
     pub(crate) downloader_url: String,
+    downloader_url_error: Option<String>,
+    /// Drop `si`, `utm_*`, and similar parameters before probing.
+    downloader_strip_tracking: bool,
     pub(crate) downloader_video_title: Option<String>,
     pub(crate) downloader_url_cursor: usize,
     pub(crate) downloader_step: DownloaderStep,
@@ -68,25 +220,120 @@ pub struct App {
     pub(crate) downloader_option_focus: Option<usize>,
     downloader_quality_choices: Vec<DownloaderQualityChoice>,
     downloader_quality_index: usize,
+    downloader_probe_mode: DownloaderProbeMode,
     pub(crate) downloader_output: ToolOutput,
     ffmpeg_available: bool,
     downloader_available: bool,
     gpu_h264_encoder_available: bool,
+    ffmpeg_encoders: HashSet<String>,
     pub(crate) show_keybinds: bool,
+    pub(crate) count_prefix: CountPrefix,
+    log_page_scale: f64,
     pub(crate) keybinds_scroll: Cell<usize>,
+    /// Plain, borderless output for screen readers.
+    screen_reader: bool,
+    /// Status line the cursor was last moved to, in screen reader mode.
+    announced_status: RefCell<String>,
     pub(crate) ffmpeg_spinner_frame: usize,
     pub(crate) downloader_spinner_frame: usize,
+    perf: PerfSettings,
+    last_spinner_step: Instant,
     pub(crate) right_tab: RightTab,
+    /// Last right-side pane focused in each tab, indexed by tab number - 1.
+    tab_focus: [Focus; RightTab::ALL.len()],
     pending_delete: Option<PendingDelete>,
     pending_cancel: Option<PendingCancel>,
+    pending_export: Option<PendingExport>,
+    note_prompt: Option<NotePrompt>,
+    stream_prompt: Option<StreamPrompt>,
+    path_jump: Option<PathJump>,
+    file_op_prompt: Option<FileOpPrompt>,
+    running_file_op: Option<RunningFileOp>,
+    presets: Vec<ExportPreset>,
+    preset_picker: Option<PresetPicker>,
+    /// Lifetime totals, loaded while the stats popup is open.
+    job_stats: Option<JobStats>,
+    /// Diagnostics report, open over the rest of the UI.
+    health: Option<HealthScreen>,
+    /// Release notes, open over the rest of the UI.
+    changelog: Option<ChangelogScreen>,
+    batch_prompt: Option<BatchPrompt>,
+    batch_review: Option<BatchReview>,
+    cleanup_prompt: Option<CleanupPrompt>,
+    cleanup_review: Option<CleanupReview>,
+    /// Convert-all queue; its exports run one at a time as editor jobs.
+    editor_batch: Option<EditorBatch>,
+    /// Marked files in the order they were marked.
+    marked: Vec<PathBuf>,
+    /// Bookmarked directories, in the order they are numbered.
+    bookmarks: Vec<PathBuf>,
+    bookmark_picker: Option<BookmarkPicker>,
+    /// Files the last delete moved to the trash, while `u` can restore them.
+    last_delete: Option<LastDelete>,
+    tag_index: TagIndex,
+    /// `[editor]` values from `config.toml`, applied to each new form.
+    editor_defaults: EditorDefaults,
+    keymap: Keymap,
+    theme: Theme,
+    pane_split: PaneSplit,
+    file_types: FileTypeStyles,
+    /// Ancestor picked in the Files title, in levels above `cwd`.
+    breadcrumb: Option<usize>,
+    quick_jumps: QuickJumps,
+    /// `g` was the last browser key, so a jump letter may follow.
+    quick_jump_armed: bool,
+    type_ahead: Option<TypeAhead>,
+    fuzzy_filter: Option<FuzzyFilter>,
+    entry_durations: EntryDurations,
+    tag_filter: TagFilter,
+    /// List names starting with `.`; toggled from the browser.
+    show_hidden_files: bool,
+    /// List only folders and audio/video files.
+    media_only: bool,
+    /// Most recent successful export or download, for quick opening.
+    last_output: Option<PathBuf>,
+    after_export: AfterExport,
+    /// Write every export and download to its own file under `logs/` too.
+    job_logs: bool,
+    export_confirm_minutes: f64,
+    export_confirm_megabytes: u64,
+    /// Free space new jobs must leave on their drive; 0 turns the check off.
+    min_free_bytes: u64,
+    /// Folder whose free space `df` last failed to read, until noted.
+    free_space_unread: RefCell<Option<PathBuf>>,
+    free_space_unread_noted: bool,
+    /// URL whose download was held back by the free-space warning.
+    downloader_low_space_shown_for: Option<String>,
     running_editor: Option<RunningEditor>,
     running_downloader_probe: Option<RunningDownloaderProbe>,
+    /// Probe results for this session, so returning to a URL is instant.
+    downloader_probe_cache: HashMap<(String, DownloaderProbeMode), CachedDownloaderProbe>,
+    running_bitrate_probe: Option<RunningBitrateProbe>,
+    bitrate_graph: Option<BitrateGraph>,
+    running_keyframe_probe: Option<RunningKeyframeProbe>,
+    keyframe_hint: Option<KeyframeHint>,
+    end_keyframe_hint: Option<KeyframeHint>,
+    keyframe_wait: Option<KeyframeWait>,
+    frame_preview_enabled: bool,
+    running_frame_preview: Option<RunningFramePreview>,
+    frame_preview: Option<FramePreview>,
+    running_playback: Option<RunningPlayback>,
+    concat_items: Vec<ConcatItem>,
+    concat_cursor: usize,
+    /// Paths piped in with `--stdin`, kept so `_` can return to them.
+    stdin_listing: Option<Vec<PathBuf>>,
+    showing_stdin_listing: bool,
     running_downloader: Option<RunningDownloader>,
 }
 
+/// Files waiting for the delete confirmation: the marked set, or the
+/// selected file when nothing is marked.
 struct PendingDelete {
+    /// File name, or a count such as `3 marked files`.
     name: String,
-    path: PathBuf,
+    paths: Vec<PathBuf>,
+    /// Set when deleting a folder, which needs its name typed to confirm.
+    dir: Option<DirDelete>,
 }
 
 enum PendingCancel {
@@ -94,11 +341,83 @@ enum PendingCancel {
     Downloader,
 }
 
+/// An export held back until the user confirms its summary.
+struct PendingExport {
+    job: FfmpegJob,
+    summary: String,
+    status_note: String,
+    /// Why the export would leave too little free space, when it would.
+    low_space: Option<String>,
+}
+
+/// What an ffmpeg job produces, for its status message and post-run steps.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FfmpegJobKind {
+    Clip,
+    ContactSheet,
+    Remux,
+    Concat,
+    /// Decodes to the null muxer; `output_path` is the scanned input.
+    Verify,
+}
+
+impl FfmpegJobKind {
+    fn output_label(self) -> &'static str {
+        match self {
+            Self::Clip => "clip",
+            Self::ContactSheet => "contact sheet",
+            Self::Remux => "remuxed copy",
+            Self::Concat => "joined file",
+            Self::Verify => "integrity check",
+        }
+    }
+}
+
+/// Everything needed to launch one ffmpeg process from the editor.
+struct FfmpegJob {
+    kind: FfmpegJobKind,
+    command_line: String,
+    input_path: PathBuf,
+    args: Vec<String>,
+    output_path: PathBuf,
+    progress_only: bool,
+    /// Expected output duration, used to turn ffmpeg's `time=` into a percentage.
+    duration_seconds: f64,
+    /// Set on a loudness analysis run: the export to start once it succeeds.
+    follow_up: Option<Box<LoudnormFollowUp>>,
+    /// Modification time to give the output once it is written.
+    file_time: Option<SystemTime>,
+}
+
+/// Second pass of a two-pass loudnorm export. Its `-af` value is rewritten
+/// with the values measured by the first pass before it starts.
+struct LoudnormFollowUp {
+    job: FfmpegJob,
+    filter_arg_index: usize,
+    /// Audio filters that run before loudnorm in the final pass.
+    filters_before: Vec<String>,
+    /// Input and output pads around the chain when it runs in
+    /// `-filter_complex` (track mixing); empty for a plain `-af`.
+    graph_pads: (String, String),
+}
+
 struct RunningEditor {
+    kind: FfmpegJobKind,
     child: Child,
     rx: Receiver<FfmpegEvent>,
     command_line: String,
+    input_path: PathBuf,
     output_path: PathBuf,
+    progress_only: bool,
+    duration_seconds: f64,
+    progress_percent: Option<u8>,
+    follow_up: Option<Box<LoudnormFollowUp>>,
+    /// When the export started, counting a loudness measuring pass.
+    started_at: Instant,
+    /// When this ffmpeg run started, for the remaining-time estimate.
+    pass_started_at: Instant,
+    encoder: String,
+    file_time: Option<SystemTime>,
     stdout_raw: Vec<u8>,
     stderr_raw: Vec<u8>,
     stdout_pending: Vec<u8>,
@@ -108,22 +427,54 @@ struct RunningEditor {
 struct RunningDownloaderProbe {
     rx: Receiver<DownloaderProbeResult>,
     command_line: String,
+    /// Cache key together with `mode`.
+    target_url: String,
+    mode: DownloaderProbeMode,
+    /// Re-probe after an option toggle: keeps the step and the old list on
+    /// failure.
+    refresh: bool,
+    /// Set to kill the probe's yt-dlp process.
+    cancel: Arc<AtomicBool>,
 }
 
 struct RunningDownloader {
     child: Child,
     rx: Receiver<DownloaderEvent>,
     command_line: String,
+    progress_percent: Option<u8>,
+    /// yt-dlp's own `ETA` from the last progress row.
+    eta_seconds: Option<u64>,
+    /// Latest file yt-dlp reported writing; starts as the predicted name.
+    output_file: Option<PathBuf>,
+    /// Folder the download was started in; playlists have no single file.
+    output_dir: PathBuf,
+    started_at: Instant,
     stdout_raw: Vec<u8>,
     stderr_raw: Vec<u8>,
     stdout_pending: Vec<u8>,
     stderr_pending: Vec<u8>,
 }
 
+/// Options a quality list was probed with. Toggling one of them on the
+/// quality step re-probes so the list matches what will be downloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct DownloaderProbeMode {
+    audio_only: bool,
+    playlist: bool,
+}
+
 #[derive(Debug, Clone)]
 struct DownloaderQualityChoice {
     selector: String,
     label: String,
+    /// yt-dlp's size estimate for this pick, when the format list had one.
+    size_bytes: Option<u64>,
+}
+
+#[derive(Clone)]
+struct CachedDownloaderProbe {
+    choices: Vec<DownloaderQualityChoice>,
+    title: Option<String>,
 }
 
 #[derive(Clone, Copy)]
@@ -170,15 +521,76 @@ impl App {
         let entries = read_entries(&cwd)?;
         let ffmpeg_available = detect_ffmpeg_available();
         let downloader_available = detect_downloader_available();
-        let gpu_h264_encoder_available = if ffmpeg_available {
-            detect_ffmpeg_encoder_available("h264_nvenc")
+        let ffmpeg_encoders = if ffmpeg_available {
+            detect_ffmpeg_encoders()
         } else {
-            false
+            HashSet::new()
         };
-
-        Ok(Self {
+        let gpu_h264_encoder_available = ffmpeg_encoders.contains("h264_nvenc");
+        let (tag_index, mut status_message) = match TagIndex::load() {
+            Ok(index) => (index, "Select a media file in the left pane.".to_string()),
+            Err(err) => (
+                TagIndex::empty(),
+                format!("Failed to load file tags: {err}"),
+            ),
+        };
+        let presets = load_presets().unwrap_or_else(|err| {
+            status_message = format!("Failed to load presets: {err}");
+            Vec::new()
+        });
+        let file_types = FileTypeStyles::load().unwrap_or_else(|err| {
+            status_message = format!("Failed to load file type styles: {err}");
+            FileTypeStyles::default()
+        });
+        let quick_jumps = QuickJumps::load().unwrap_or_else(|err| {
+            status_message = format!("Failed to load quick jumps: {err}");
+            QuickJumps::load_defaults()
+        });
+        let config = Config::load().unwrap_or_else(|err| {
+            status_message = format!("Failed to load config: {err}");
+            Config::default()
+        });
+        let bookmarks = load_bookmarks(&config).unwrap_or_else(|err| {
+            status_message = format!("Failed to load bookmarks: {err}");
+            Vec::new()
+        });
+        let editor_defaults = EditorDefaults::from_config(&config);
+        let min_free_bytes = min_free_bytes_from_config(&config);
+        let keymap = Keymap::with_overrides(
+            config
+                .table("keys")
+                .map(|(action, keys)| (action, keys.as_text())),
+        )
+        .unwrap_or_else(|err| {
+            status_message = format!("Invalid [keys] in config: {err}");
+            Keymap::default()
+        });
+        let theme = Theme::from_entries(
+            config
+                .table("theme")
+                .map(|(role, color)| (role, color.as_text())),
+        )
+        .unwrap_or_else(|err| {
+            status_message = format!("Invalid [theme] in config: {err}");
+            Theme::dark()
+        });
+        let saved_downloader = DownloaderDefaults::load().unwrap_or_else(|err| {
+            status_message = format!("Failed to load downloader defaults: {err}");
+            DownloaderDefaults::default()
+        });
+        let downloader_defaults = DownloaderDefaults::from_config(&config, saved_downloader);
+        let saved_split = PaneSplit::load().unwrap_or_else(|err| {
+            status_message = format!("Failed to load pane layout: {err}");
+            PaneSplit::default()
+        });
+        let pane_split = PaneSplit::from_config(&config, saved_split);
+
+        let mut app = Self {
+            dir_watch: DirWatch::new(&cwd, &entries),
             cwd: cwd.clone(),
             initial_dir: cwd,
+            listed_paths: entries.iter().map(|entry| entry.path.clone()).collect(),
+            auto_refresh: config.bool("ui", "auto_refresh").unwrap_or(true),
             entries,
             file_browser_visible_rows: Cell::new(1),
             selected: 0,
@@ -186,13 +598,49 @@ impl App {
             start_time: TimeInput::zero(),
             end_time: TimeInput::zero(),
             output_format: OUTPUT_FORMATS[0],
-            output_fps: "30".to_string(),
-            output_bitrate_kbps: "8000".to_string(),
+            output_video_codec: editor_defaults.codec.unwrap_or(VIDEO_CODECS[0]),
+            output_fps: editor_defaults
+                .fps
+                .clone()
+                .unwrap_or_else(|| "30".to_string()),
+            output_bitrate_kbps: editor_defaults
+                .bitrate_kbps
+                .map_or_else(|| "8000".to_string(), |kbps| kbps.to_string()),
+            output_target_size_mb: TextInput::new(""),
             output_scale_percent: "100".to_string(),
-            use_gpu_encoding: gpu_h264_encoder_available,
+            output_crop: TextInput::new(""),
+            output_speed: TextInput::new("1"),
+            output_caption: TextInput::new(""),
+            caption_position: CAPTION_POSITIONS[0],
+            caption_size: TextInput::new("48"),
+            caption_outline: true,
+            use_gpu_encoding: gpu_h264_encoder_available && editor_defaults.gpu.unwrap_or(true),
             remove_audio: false,
+            stream_copy: false,
+            ffmpeg_log_level: DEFAULT_FFMPEG_LOG_LEVEL,
+            ffmpeg_stats_period: TextInput::new("0.5"),
+            ffmpeg_extra_args: TextInput::new(""),
+            gif_palette: true,
+            deinterlace: false,
+            constant_fps: false,
+            waveform_style: WAVEFORM_STYLES[0],
+            denoise_level: DENOISE_LEVELS[0],
+            sharpen_level: SHARPEN_LEVELS[0],
+            color_adjust: false,
+            color_brightness: TextInput::new("0"),
+            color_contrast: TextInput::new("1"),
+            color_saturation: TextInput::new("1"),
+            color_gamma: TextInput::new("1"),
+            normalize_audio: false,
+            normalize_two_pass: true,
+            output_max_width: TextInput::new(""),
+            frame_sequence: false,
+            keep_metadata: true,
+            file_time_mode: FILE_TIME_MODES[0],
+            ffmpeg_progress_only: false,
             output_name: String::new(),
             active_input: InputField::Start,
+            timeline_marker: TimelineMarker::Start,
             start_part: 0,
             end_part: 0,
             output_fps_cursor: 0,
@@ -203,40 +651,138 @@ impl App {
             overwrite_bitrate_on_next_type: true,
             overwrite_scale_percent_on_next_type: true,
             selected_video_stats: None,
+            media_streams: Vec::new(),
+            stream_selected: Vec::new(),
+            stream_cursor: 0,
+            audio_track: AudioTrack::Auto,
+            audio_channels: CHANNEL_MODES[0],
+            segments: Vec::new(),
             selected_video_bounds: None,
-            status_message: "Select a media file in the left pane.".to_string(),
+            pending_time_offset: None,
+            status_message,
             editor_form_scroll: Cell::new(0),
             editor_last_focus_line: Cell::new(None),
             ffmpeg_output: ToolOutput::empty(),
             downloader_url: String::new(),
+            downloader_url_error: None,
+            downloader_strip_tracking: config.bool("downloader", "strip_tracking").unwrap_or(true),
             downloader_video_title: None,
             downloader_url_cursor: 0,
             downloader_step: DownloaderStep::UrlInput,
-            downloader_audio_only: false,
-            downloader_sponsorblock: false,
-            downloader_subtitles: false,
+            downloader_audio_only: downloader_defaults.audio_only,
+            downloader_sponsorblock: downloader_defaults.sponsorblock,
+            downloader_subtitles: downloader_defaults.subtitles,
             downloader_playlist: false,
             downloader_option_focus: None,
             downloader_quality_choices: vec![DownloaderQualityChoice {
                 selector: "bestvideo+bestaudio/best".to_string(),
                 label: "AUTO    auto best      --     --         auto  video".to_string(),
+                size_bytes: None,
             }],
             downloader_quality_index: 0,
+            downloader_probe_mode: DownloaderProbeMode::default(),
             downloader_output: ToolOutput::empty(),
             ffmpeg_available,
             downloader_available,
             gpu_h264_encoder_available,
+            ffmpeg_encoders,
             show_keybinds: false,
+            count_prefix: CountPrefix::default(),
+            log_page_scale: config
+                .number("ui", "log_page_scale")
+                .filter(|scale| scale.is_finite() && *scale > 0.0)
+                .unwrap_or(1.0),
             keybinds_scroll: Cell::new(0),
+            screen_reader: config.bool("ui", "screen_reader").unwrap_or(false),
+            announced_status: RefCell::new(String::new()),
             ffmpeg_spinner_frame: 0,
             downloader_spinner_frame: 0,
+            perf: PerfSettings::from_config(&config),
+            last_spinner_step: Instant::now(),
             right_tab: RightTab::Editor,
+            tab_focus: [Focus::RightTop; RightTab::ALL.len()],
             pending_delete: None,
             pending_cancel: None,
+            pending_export: None,
+            note_prompt: None,
+            stream_prompt: None,
+            path_jump: None,
+            file_op_prompt: None,
+            running_file_op: None,
+            presets,
+            preset_picker: None,
+            job_stats: None,
+            health: None,
+            changelog: None,
+            batch_prompt: None,
+            batch_review: None,
+            cleanup_prompt: None,
+            cleanup_review: None,
+            editor_batch: None,
+            marked: Vec::new(),
+            bookmarks,
+            bookmark_picker: None,
+            last_delete: None,
+            tag_index,
+            editor_defaults,
+            keymap,
+            theme,
+            pane_split,
+            file_types,
+            breadcrumb: None,
+            quick_jumps,
+            quick_jump_armed: false,
+            type_ahead: None,
+            fuzzy_filter: None,
+            entry_durations: EntryDurations::default(),
+            tag_filter: TagFilter::All,
+            show_hidden_files: true,
+            media_only: false,
+            last_output: None,
+            after_export: config
+                .string("editor", "after_export")
+                .and_then(|name| AfterExport::from_name(name.trim()))
+                .unwrap_or(AfterExport::Keep),
+            job_logs: false,
+            export_confirm_minutes: config
+                .number("editor", "confirm_minutes")
+                .filter(|minutes| minutes.is_finite() && *minutes >= 0.0)
+                .unwrap_or(10.0),
+            export_confirm_megabytes: config
+                .integer("editor", "confirm_mb")
+                .and_then(|megabytes| u64::try_from(megabytes).ok())
+                .unwrap_or(500),
+            min_free_bytes,
+            free_space_unread: RefCell::new(None),
+            free_space_unread_noted: false,
+            downloader_low_space_shown_for: None,
             running_editor: None,
             running_downloader_probe: None,
+            downloader_probe_cache: HashMap::new(),
+            running_bitrate_probe: None,
+            bitrate_graph: None,
+            running_keyframe_probe: None,
+            keyframe_wait: None,
+            keyframe_hint: None,
+            end_keyframe_hint: None,
+            frame_preview_enabled: false,
+            running_frame_preview: None,
+            frame_preview: None,
+            running_playback: None,
+            concat_items: Vec::new(),
+            concat_cursor: 0,
+            stdin_listing: None,
+            showing_stdin_listing: false,
             running_downloader: None,
-        })
+        };
+        // A fresh install gets the health check; an upgrade gets its notes.
+        if is_first_run() {
+            app.start_health_check(true);
+            record_version();
+        } else {
+            app.open_whats_new();
+        }
+        Ok(app)
     }
 
     pub fn toggle_keybinds(&mut self) {
@@ -281,14 +827,47 @@ impl App {
             .set(self.keybinds_scroll().saturating_sub(8));
     }
 
-    pub fn tick(&mut self) {
+    /// Advances background jobs and probes. Returns whether any were in
+    /// flight, since only then can the screen change without input.
+    pub fn tick(&mut self) -> bool {
+        let was_busy = self.background_work_running();
+        // Spinners step on their own clock so faster polling doesn't spin them faster.
+        let spinner_step = self.last_spinner_step.elapsed() >= self.perf.spinner_interval;
+        if spinner_step {
+            self.last_spinner_step = Instant::now();
+        }
+
         if self.running_editor.is_some() {
-            self.ffmpeg_spinner_frame = (self.ffmpeg_spinner_frame + 1) % spinner_frames().len();
+            if spinner_step {
+                self.ffmpeg_spinner_frame =
+                    (self.ffmpeg_spinner_frame + 1) % spinner_frames().len();
+            }
             self.pump_running_editor_events();
             self.try_finish_running_editor();
         }
 
-        if self.running_downloader_probe.is_some() || self.running_downloader.is_some() {
+        let type_ahead_expired = self.expire_type_ahead();
+        let dir_changed = self.poll_dir_watch();
+        let dir_count_progressed = self.pump_dir_delete_count();
+        let durations_arrived = self.pump_entry_durations();
+        let health_arrived = self.pump_health_check();
+        let batch_plans_arrived = self.pump_batch_review_probes();
+        let stream_probe_finished = self.try_finish_stream_probe();
+        let file_op_progressed = self.pump_file_op();
+        self.refresh_keyframe_hint();
+        self.resume_keyframe_wait();
+        self.refresh_frame_preview();
+        self.expire_last_delete(false);
+        if self.running_playback.is_some() {
+            self.try_finish_range_playback();
+        }
+        if self.running_bitrate_probe.is_some() {
+            self.try_finish_bitrate_probe();
+        }
+
+        if spinner_step
+            && (self.running_downloader_probe.is_some() || self.running_downloader.is_some())
+        {
             self.downloader_spinner_frame =
                 (self.downloader_spinner_frame + 1) % spinner_frames().len();
         }
@@ -299,6 +878,128 @@ impl App {
             self.pump_running_downloader_events();
             self.try_finish_running_downloader();
         }
+
+        was_busy
+            || self.background_work_running()
+            || type_ahead_expired
+            || dir_changed
+            || dir_count_progressed
+            || durations_arrived
+            || health_arrived
+            || batch_plans_arrived
+            || stream_probe_finished
+            || file_op_progressed
+    }
+
+    fn background_work_running(&self) -> bool {
+        self.running_editor.is_some()
+            || self.running_downloader_probe.is_some()
+            || self.running_downloader.is_some()
+            || self.running_bitrate_probe.is_some()
+            || self.running_keyframe_probe.is_some()
+            || self.running_frame_preview.is_some()
+            || self.running_playback.is_some()
+            || self.running_file_op.is_some()
+            || self.health_check_running()
+    }
+
+    pub fn editor_progress_percent(&self) -> Option<u8> {
+        self.running_editor
+            .as_ref()
+            .and_then(|running| running.progress_percent)
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn editor_is_running(&self) -> bool {
+        self.running_editor.is_some()
+    }
+
+    pub fn downloader_progress_percent(&self) -> Option<u8> {
+        self.running_downloader
+            .as_ref()
+            .and_then(|running| running.progress_percent)
+    }
+
+    pub fn downloader_is_running(&self) -> bool {
+        self.running_downloader.is_some()
+    }
+
+    /// Seconds left in the running ffmpeg pass, at its pace so far.
+    pub fn editor_eta_seconds(&self) -> Option<u64> {
+        let running = self.running_editor.as_ref()?;
+        remaining_seconds(running.pass_started_at, running.progress_percent)
+    }
+
+    pub fn downloader_eta_seconds(&self) -> Option<u64> {
+        self.running_downloader
+            .as_ref()
+            .and_then(|running| running.eta_seconds)
+    }
+
+    /// Terminal window title reflecting running jobs, e.g. "rt: encoding 42%".
+    pub fn window_title(&self) -> String {
+        let mut jobs = Vec::new();
+        if let Some(batch) = self.editor_batch_progress() {
+            jobs.push(job_title_part(
+                &format!("converting {}/{}", batch.finished + 1, batch.total),
+                self.editor_batch_percent(),
+                self.editor_batch_eta_seconds(),
+            ));
+        } else if self.editor_is_running() {
+            jobs.push(job_title_part(
+                "encoding",
+                self.editor_progress_percent(),
+                self.editor_eta_seconds(),
+            ));
+        }
+        if self.downloader_is_running() {
+            jobs.push(job_title_part(
+                "downloading",
+                self.downloader_progress_percent(),
+                self.downloader_eta_seconds(),
+            ));
+        }
+        if let Some(title) = self.running_file_op_title() {
+            jobs.push(job_title_part(
+                &title.to_lowercase(),
+                self.file_op_progress_percent(),
+                self.file_op_eta_seconds(),
+            ));
+        }
+
+        if jobs.is_empty() {
+            "rt".to_string()
+        } else {
+            format!("rt: {}", jobs.join(" | "))
+        }
+    }
+
+    pub fn set_after_export(&mut self, after_export: AfterExport) {
+        self.after_export = after_export;
+    }
+
+    pub fn perf_settings(&self) -> PerfSettings {
+        self.perf
+    }
+
+    pub fn set_perf_settings(&mut self, perf: PerfSettings) {
+        self.perf = perf;
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.perf.poll_interval
+    }
+
+    /// Multiplier applied to half/full page jumps in the tool output panels.
+    pub fn set_log_page_scale(&mut self, scale: f64) {
+        self.log_page_scale = scale;
     }
 
     pub fn set_file_browser_visible_rows(&self, rows: usize) {
@@ -361,10 +1062,25 @@ impl App {
             .clamped_scroll_for_viewport(visible_line_count)
     }
 
+    /// Whether the selected codec has an NVENC encoder in this ffmpeg build.
+    pub fn gpu_encoding_supported(&self) -> bool {
+        gpu_video_encoders(self.output_video_codec)
+            .iter()
+            .any(|encoder| self.ffmpeg_encoder_available(encoder))
+    }
+
+    pub fn use_gpu_encoding(&self) -> bool {
+        self.use_gpu_encoding
+    }
+
     pub fn gpu_h264_encoder_available(&self) -> bool {
         self.gpu_h264_encoder_available
     }
 
+    pub fn ffmpeg_encoder_available(&self, encoder_name: &str) -> bool {
+        self.ffmpeg_encoders.contains(encoder_name)
+    }
+
     pub fn right_tab(&self) -> RightTab {
         self.right_tab
     }
@@ -373,32 +1089,79 @@ impl App {
         self.output_format == "gif"
     }
 
+    pub fn animated_image_output_selected(&self) -> bool {
+        is_animated_image_format(self.output_format)
+    }
+
+    pub fn still_image_output_selected(&self) -> bool {
+        is_still_image_format(self.output_format)
+    }
+
+    /// PNG/JPEG output writes numbered frames instead of a single image.
+    pub fn frame_sequence_selected(&self) -> bool {
+        self.still_image_output_selected() && self.frame_sequence
+    }
+
     pub fn audio_only_output_selected(&self) -> bool {
         is_audio_output_format(self.output_format)
     }
 
     pub fn bitrate_enabled(&self) -> bool {
-        !self.is_gif_output() && !self.audio_only_output_selected()
+        !self.animated_image_output_selected()
+            && !self.still_image_output_selected()
+            && !self.audio_only_output_selected()
     }
 
     pub fn video_options_enabled(&self) -> bool {
         !self.audio_only_output_selected()
     }
 
-    pub fn select_next_right_tab(&mut self) {
+    /// Whether a target size is typed in, which replaces the bitrate.
+    pub fn target_size_set(&self) -> bool {
+        !self.output_target_size_mb.value().trim().is_empty()
+    }
+
+    /// Stream copy only applies to re-encodable video containers (not animated images/audio).
+    pub fn stream_copy_active(&self) -> bool {
+        self.stream_copy && self.bitrate_enabled() && self.segments.is_empty()
+    }
+
+    /// Switches tabs and returns the pane to focus: whichever of the tab's
+    /// panes was focused when it was last left.
+    pub fn select_next_right_tab(&mut self) -> Focus {
         self.right_tab = self.right_tab.next();
+        self.remembered_tab_focus()
     }
 
-    pub fn select_right_tab_by_number(&mut self, number: usize) -> bool {
-        let Some(tab) = RightTab::from_number(number) else {
-            return false;
-        };
+    pub fn select_right_tab_by_number(&mut self, number: usize) -> Option<Focus> {
+        self.right_tab = RightTab::from_number(number)?;
+        Some(self.remembered_tab_focus())
+    }
+
+    /// Puts back the tab a digit showed before it turned out to be a count.
+    pub fn restore_right_tab(&mut self, tab: RightTab) {
         self.right_tab = tab;
-        true
+    }
+
+    /// Records the focused pane for the active tab; file browser focus is
+    /// shared across tabs, so it isn't remembered per tab.
+    pub fn remember_tab_focus(&mut self, focus: Focus) {
+        if focus != Focus::Left {
+            self.tab_focus[self.right_tab.number() - 1] = focus;
+        }
+    }
+
+    fn remembered_tab_focus(&self) -> Focus {
+        let mut focus = self.tab_focus[self.right_tab.number() - 1];
+        self.normalize_focus(&mut focus);
+        focus
     }
 
     pub fn can_focus_right_bottom(&self) -> bool {
-        matches!(self.right_tab, RightTab::Editor | RightTab::Downloader)
+        matches!(
+            self.right_tab,
+            RightTab::Editor | RightTab::Downloader | RightTab::Concat
+        )
     }
 
     pub fn normalize_focus(&self, focus: &mut Focus) {
@@ -433,10 +1196,10 @@ impl App {
         self.pending_delete.is_some()
     }
 
-    pub fn pending_delete_target(&self) -> Option<(&str, &std::path::Path)> {
+    pub fn pending_delete_target(&self) -> Option<(&str, &[PathBuf])> {
         self.pending_delete
             .as_ref()
-            .map(|pending| (pending.name.as_str(), pending.path.as_path()))
+            .map(|pending| (pending.name.as_str(), pending.paths.as_slice()))
     }
 
     pub fn has_pending_cancel(&self) -> bool {
@@ -453,11 +1216,23 @@ impl App {
 
     pub fn request_cancel_for_focused_tool(&mut self) {
         self.pending_cancel = match self.right_tab {
-            RightTab::Editor if self.running_editor.is_some() => Some(PendingCancel::Editor),
+            RightTab::Editor | RightTab::Concat if self.running_editor.is_some() => {
+                Some(PendingCancel::Editor)
+            }
             RightTab::Downloader if self.running_downloader.is_some() => {
                 Some(PendingCancel::Downloader)
             }
-            RightTab::Editor => {
+            // Nothing has run yet for an export waiting on a keyframe scan.
+            RightTab::Editor | RightTab::Concat if self.keyframe_wait.is_some() => {
+                self.cancel_keyframe_wait();
+                None
+            }
+            // A probe has nothing to lose, so it stops without asking.
+            RightTab::Downloader if self.running_downloader_probe.is_some() => {
+                self.cancel_downloader_probe();
+                None
+            }
+            RightTab::Editor | RightTab::Concat => {
                 self.status_message = "No running editor export to cancel.".to_string();
                 None
             }
@@ -511,6 +1286,55 @@ fn resolve_start_dir(start_dir: Option<PathBuf>) -> io::Result<PathBuf> {
     Ok(absolute)
 }
 
+/// Per-user config directory for rt (`$XDG_CONFIG_HOME/rt`, falling back
+/// to `~/.config/rt`, or `%APPDATA%\rt` on Windows).
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("rt"))
+    }
+
+    #[cfg(not(windows))]
+    {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("rt"))
+    }
+}
+
+fn job_title_part(label: &str, percent: Option<u8>, eta_seconds: Option<u64>) -> String {
+    match (percent, eta_seconds) {
+        (Some(percent), Some(eta)) => format!("{label} {percent}%, {} left", format_eta(eta)),
+        (Some(percent), None) => format!("{label} {percent}%"),
+        (None, _) => label.to_string(),
+    }
+}
+
+/// Time left for a job `percent` done since `started_at`, assuming the rest
+/// goes at the same pace. `None` until there is enough progress to tell.
+pub(super) fn remaining_seconds(started_at: Instant, percent: Option<u8>) -> Option<u64> {
+    let percent = u64::from(percent.filter(|percent| (1..100).contains(percent))?);
+    let elapsed = started_at.elapsed().as_secs();
+    if elapsed < 2 {
+        return None;
+    }
+    Some(elapsed * (100 - percent) / percent)
+}
+
+/// `42s`, `3:05`, or `1:02:03`.
+fn format_eta(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else if minutes > 0 {
+        format!("{minutes}:{seconds:02}")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
 fn spinner_frames() -> &'static [char] {
     &['|', '/', '-', '\\']
 }
@@ -537,21 +1361,24 @@ fn detect_downloader_available() -> bool {
         .unwrap_or(false)
 }
 
-fn detect_ffmpeg_encoder_available(encoder_name: &str) -> bool {
+// Read the encoder list once so codec fallbacks don't spawn ffmpeg per export.
+fn detect_ffmpeg_encoders() -> HashSet<String> {
     let Ok(output) = Command::new("ffmpeg")
         .args(["-hide_banner", "-encoders"])
         .output()
     else {
-        return false;
+        return HashSet::new();
     };
 
     if !output.status.success() {
-        return false;
+        return HashSet::new();
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    stdout
+    // Encoder rows look like " V....D libx264   libx264 H.264 ..."; the
+    // second column is the encoder name.
+    String::from_utf8_lossy(&output.stdout)
         .lines()
-        .map(str::trim)
-        .any(|line| line.split_whitespace().any(|word| word == encoder_name))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect()
 }
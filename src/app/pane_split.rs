@@ -0,0 +1,141 @@
+// Pane proportions kept between launches.
+// - Alt+Left/Alt+Right move the split between the browser and the right
+//   panel; Alt+Up/Alt+Down grow or shrink the expanded tool output.
+// - Saved on every change to `layout.ini` in the config dir. A key set
+//   under `[layout]` in `config.toml` wins over the saved value.
+use std::{fs, io, path::PathBuf};
+
+use super::{App, config_dir, state_lock::write_state_file};
+
+const PANE_SPLIT_FILE_NAME: &str = "layout.ini";
+const FILES_PERCENT_RANGE: (u16, u16) = (15, 70);
+const OUTPUT_PERCENT_RANGE: (u16, u16) = (20, 85);
+/// How far one key press moves a split.
+const RESIZE_STEP: i16 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct PaneSplit {
+    /// Width of the file browser, in percent of the screen.
+    pub(super) files_percent: u16,
+    /// Height of the focused tool output, in percent of the right panel.
+    pub(super) output_percent: u16,
+}
+
+impl Default for PaneSplit {
+    fn default() -> Self {
+        Self {
+            files_percent: 34,
+            output_percent: 70,
+        }
+    }
+}
+
+fn clamp_percent(value: i64, (min, max): (u16, u16)) -> u16 {
+    value.clamp(i64::from(min), i64::from(max)) as u16
+}
+
+impl PaneSplit {
+    /// Percentages out of range are pulled back into it.
+    pub(super) fn new(files_percent: i64, output_percent: i64) -> Self {
+        Self {
+            files_percent: clamp_percent(files_percent, FILES_PERCENT_RANGE),
+            output_percent: clamp_percent(output_percent, OUTPUT_PERCENT_RANGE),
+        }
+    }
+
+    /// The saved split; a missing file or key keeps the default.
+    pub(super) fn load() -> io::Result<Self> {
+        let base = Self::default();
+        let Some(path) = split_path() else {
+            return Ok(base);
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(base),
+            Err(err) => return Err(err),
+        };
+        let mut files_percent = i64::from(base.files_percent);
+        let mut output_percent = i64::from(base.output_percent);
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<i64>() else {
+                continue;
+            };
+            match key.trim() {
+                "files_percent" => files_percent = value,
+                "output_percent" => output_percent = value,
+                _ => {}
+            }
+        }
+        Ok(Self::new(files_percent, output_percent))
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = split_path() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no config directory (set HOME or XDG_CONFIG_HOME)",
+            ));
+        };
+        let contents = format!(
+            "# rt pane layout, updated when a split is resized\nfiles_percent = {}\noutput_percent = {}\n",
+            self.files_percent, self.output_percent
+        );
+        write_state_file(&path, &contents)
+    }
+}
+
+fn split_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(PANE_SPLIT_FILE_NAME))
+}
+
+impl App {
+    pub fn files_pane_percent(&self) -> u16 {
+        self.pane_split.files_percent
+    }
+
+    pub fn output_pane_percent(&self) -> u16 {
+        self.pane_split.output_percent
+    }
+
+    /// Alt+Right widens the browser, Alt+Left narrows it.
+    pub fn resize_files_pane(&mut self, grow: bool) {
+        let step = if grow { RESIZE_STEP } else { -RESIZE_STEP };
+        let split = PaneSplit::new(
+            i64::from(self.pane_split.files_percent) + i64::from(step),
+            i64::from(self.pane_split.output_percent),
+        );
+        self.apply_pane_split(split);
+    }
+
+    /// Alt+Up grows the expanded tool output, Alt+Down shrinks it.
+    pub fn resize_output_pane(&mut self, grow: bool) {
+        let step = if grow { RESIZE_STEP } else { -RESIZE_STEP };
+        let split = PaneSplit::new(
+            i64::from(self.pane_split.files_percent),
+            i64::from(self.pane_split.output_percent) + i64::from(step),
+        );
+        self.apply_pane_split(split);
+    }
+
+    fn apply_pane_split(&mut self, split: PaneSplit) {
+        if split == self.pane_split {
+            return;
+        }
+        self.pane_split = split;
+        self.status_message = format!(
+            "Layout: browser {}%, tool output {}%",
+            split.files_percent, split.output_percent
+        );
+        if let Err(err) = split.save() {
+            self.status_message
+                .push_str(&format!(" (not saved: {err})"));
+        }
+    }
+}
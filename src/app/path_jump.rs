@@ -0,0 +1,236 @@
+// Jumping the browser to a typed path.
+// - `:` opens a prompt for an absolute, `~`, or relative path; relative
+//   paths start from the browser's folder and `..` steps up like `cd`.
+// - Tab completes the last part against the folders on disk: one match is
+//   filled in, several fill their common start and are listed below.
+// - A file path opens its folder with the file selected.
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::model::TextInput;
+
+use super::{
+    App,
+    quick_jump::{expand_home_dir, tilde_path},
+};
+
+/// How many Tab matches the prompt lists.
+const MAX_LISTED_MATCHES: usize = 8;
+
+/// Path being typed for the jump.
+pub(super) struct PathJump {
+    input: TextInput,
+    /// Folder names the last Tab matched, when it matched more than one.
+    matches: Vec<String>,
+    error: Option<String>,
+}
+
+/// The prompt's contents for rendering.
+pub struct PathJumpView<'a> {
+    pub input: &'a TextInput,
+    pub matches: &'a [String],
+    /// Matches beyond the listed ones.
+    pub more: usize,
+    pub error: Option<&'a str>,
+}
+
+/// `path` with `.` and `..` resolved by name, as `cd` does, without
+/// following symlinks.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// The longest start all of `names` share.
+fn common_prefix(names: &[String]) -> String {
+    let Some(first) = names.first() else {
+        return String::new();
+    };
+    let mut prefix = first.as_str();
+    for name in &names[1..] {
+        let shared = prefix
+            .char_indices()
+            .zip(name.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(name.len()), |((index, _), _)| index);
+        prefix = &prefix[..shared];
+    }
+    prefix.to_string()
+}
+
+impl App {
+    pub fn start_path_jump(&mut self) {
+        // Typing replaces the current folder; Tab or Right extends it.
+        let mut input =
+            TextInput::new(&format!("{}/", tilde_path(&self.cwd).trim_end_matches('/')));
+        input.focus();
+        self.path_jump = Some(PathJump {
+            input,
+            matches: Vec::new(),
+            error: None,
+        });
+    }
+
+    pub fn has_path_jump(&self) -> bool {
+        self.path_jump.is_some()
+    }
+
+    pub fn path_jump(&self) -> Option<PathJumpView<'_>> {
+        self.path_jump.as_ref().map(|jump| PathJumpView {
+            input: &jump.input,
+            matches: &jump.matches[..jump.matches.len().min(MAX_LISTED_MATCHES)],
+            more: jump.matches.len().saturating_sub(MAX_LISTED_MATCHES),
+            error: jump.error.as_deref(),
+        })
+    }
+
+    pub fn push_path_jump_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(jump) = self.path_jump.as_mut() {
+            jump.input.insert(ch);
+            jump.matches.clear();
+            jump.error = None;
+        }
+    }
+
+    pub fn backspace_path_jump(&mut self) {
+        if let Some(jump) = self.path_jump.as_mut() {
+            jump.input.backspace();
+            jump.matches.clear();
+            jump.error = None;
+        }
+    }
+
+    pub fn move_path_jump_cursor_left(&mut self) {
+        if let Some(jump) = self.path_jump.as_mut() {
+            jump.input.move_left();
+        }
+    }
+
+    pub fn move_path_jump_cursor_right(&mut self) {
+        if let Some(jump) = self.path_jump.as_mut() {
+            jump.input.move_right();
+        }
+    }
+
+    pub fn cancel_path_jump(&mut self) {
+        self.path_jump = None;
+    }
+
+    /// The typed path as an absolute one.
+    fn resolve_jump_path(&self, typed: &str) -> PathBuf {
+        let expanded = expand_home_dir(typed);
+        normalize(&self.cwd.join(expanded))
+    }
+
+    /// Completes the part after the last `/` against the folders in the
+    /// part before it. Dotfolders only match once a `.` is typed.
+    pub fn complete_path_jump(&mut self) {
+        let Some(jump) = self.path_jump.as_ref() else {
+            return;
+        };
+        let typed = jump.input.value().to_string();
+        let (dir_part, partial) = match typed.rfind('/') {
+            Some(index) => typed.split_at(index + 1),
+            None => ("", typed.as_str()),
+        };
+        let dir = if dir_part.is_empty() {
+            self.cwd.clone()
+        } else {
+            self.resolve_jump_path(dir_part)
+        };
+
+        let mut names = match fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| name.starts_with(partial))
+                .filter(|name| partial.starts_with('.') || !name.starts_with('.'))
+                .collect::<Vec<_>>(),
+            Err(err) => {
+                self.set_path_jump_error(format!("Can't read {}: {err}", tilde_path(&dir)));
+                return;
+            }
+        };
+        names.sort_by_key(|name| name.to_lowercase());
+
+        let Some(jump) = self.path_jump.as_mut() else {
+            return;
+        };
+        jump.error = None;
+        match names.len() {
+            0 => {
+                jump.matches.clear();
+                jump.error = Some(format!("No folder starting with `{partial}`."));
+            }
+            1 => {
+                jump.input.set(&format!("{dir_part}{}/", names[0]));
+                jump.input.focus_append();
+                jump.matches.clear();
+            }
+            _ => {
+                let prefix = common_prefix(&names);
+                jump.input.set(&format!("{dir_part}{prefix}"));
+                jump.input.focus_append();
+                jump.matches = names;
+            }
+        }
+    }
+
+    /// Opens the typed folder, or a file's folder with the file selected.
+    /// A path that doesn't exist or can't be read keeps the prompt open
+    /// with the reason.
+    pub fn confirm_path_jump(&mut self) {
+        let Some(jump) = self.path_jump.as_mut() else {
+            return;
+        };
+        let typed = jump.input.value().trim().to_string();
+        if typed.is_empty() {
+            jump.error = Some("Type a path, e.g. ~/Videos or ../exports.".to_string());
+            return;
+        }
+        let target = self.resolve_jump_path(&typed);
+        let (dir, file) = if target.is_dir() {
+            (target, None)
+        } else if target.exists()
+            && let Some(parent) = target.parent()
+        {
+            (parent.to_path_buf(), Some(target.clone()))
+        } else {
+            self.set_path_jump_error(format!("{} does not exist.", tilde_path(&target)));
+            return;
+        };
+
+        if let Err(err) = self.change_dir(dir.clone()) {
+            self.set_path_jump_error(format!("Can't open {}: {err}", tilde_path(&dir)));
+            return;
+        }
+        self.path_jump = None;
+        if let Some(file) = file
+            && let Some(index) = self.entries.iter().position(|entry| entry.path == file)
+        {
+            self.selected = index;
+        }
+        self.status_message = format!("Opened {}", tilde_path(&self.cwd));
+    }
+
+    fn set_path_jump_error(&mut self, error: String) {
+        if let Some(jump) = self.path_jump.as_mut() {
+            jump.error = Some(error);
+        }
+    }
+}
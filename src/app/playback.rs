@@ -0,0 +1,136 @@
+// Range playback for the editor.
+// - Plays Start to End in mpv, or ffplay when mpv is not installed, to
+//   audition a cut before exporting.
+// - The player runs as a background process; the same key stops it.
+use std::{
+    io,
+    path::Path,
+    process::{Child, Command, Stdio},
+};
+
+use crate::model::TimeInput;
+
+use super::App;
+
+pub(super) struct RunningPlayback {
+    child: Child,
+    player: &'static str,
+}
+
+/// Player command for a range. mpv takes the end time, ffplay the length.
+fn player_command(player: &str, path: &Path, start: f64, end: f64) -> Command {
+    let mut command = Command::new(player);
+    if player == "mpv" {
+        command
+            .arg("--really-quiet")
+            .arg(format!("--start={start:.3}"))
+            .arg(format!("--end={end:.3}"))
+            .arg("--");
+    } else {
+        command
+            .arg("-hide_banner")
+            .arg("-loglevel")
+            .arg("error")
+            .arg("-autoexit")
+            .arg("-ss")
+            .arg(format!("{start:.3}"))
+            .arg("-t")
+            .arg(format!("{:.3}", end - start));
+    }
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    command
+}
+
+fn spawn_player(path: &Path, start: f64, end: f64) -> io::Result<RunningPlayback> {
+    for player in ["mpv", "ffplay"] {
+        match player_command(player, path, start, end).spawn() {
+            Ok(child) => return Ok(RunningPlayback { child, player }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "neither mpv nor ffplay found in PATH",
+    ))
+}
+
+impl App {
+    /// Plays the Start-End range, or stops the player if it is open.
+    pub fn toggle_range_playback(&mut self) {
+        if self.running_playback.is_some() {
+            self.stop_range_playback();
+            return;
+        }
+        self.commit_time_offset();
+        let Some(path) = self.selected_video.clone() else {
+            self.status_message = "No video selected. Choose one in the left pane.".to_string();
+            return;
+        };
+        let (start, end) = match self.validated_time_range() {
+            Ok(range) => range,
+            Err(message) => {
+                self.status_message = message;
+                return;
+            }
+        };
+
+        match spawn_player(&path, start, end) {
+            Ok(playback) => {
+                self.status_message = format!(
+                    "Playing {}-{} in {}. Ctrl+r stops it.",
+                    TimeInput::from_seconds(start).to_ffmpeg_timestamp(),
+                    TimeInput::from_seconds(end).to_ffmpeg_timestamp(),
+                    playback.player
+                );
+                self.running_playback = Some(playback);
+            }
+            Err(err) => self.status_message = format!("Failed to start playback: {err}"),
+        }
+    }
+
+    fn stop_range_playback(&mut self) {
+        let Some(mut playback) = self.running_playback.take() else {
+            return;
+        };
+        match playback.child.kill() {
+            Ok(()) => {
+                let _ = playback.child.wait();
+                self.status_message = format!("Stopped {}.", playback.player);
+            }
+            Err(err) => {
+                self.status_message = format!("Failed to stop {}: {err}", playback.player);
+            }
+        }
+    }
+
+    /// Clears the player once it exits on its own (end of range or closed).
+    pub(super) fn try_finish_range_playback(&mut self) {
+        let Some(playback) = self.running_playback.as_mut() else {
+            return;
+        };
+        match playback.child.try_wait() {
+            Ok(None) => {}
+            Ok(Some(status)) => {
+                self.status_message = if status.success() {
+                    "Playback finished.".to_string()
+                } else {
+                    format!("{} exited with {status}.", playback.player)
+                };
+                self.running_playback = None;
+            }
+            Err(err) => {
+                self.status_message = format!("Failed to check {}: {err}", playback.player);
+                self.running_playback = None;
+            }
+        }
+    }
+
+    pub fn range_playback_running(&self) -> bool {
+        self.running_playback.is_some()
+    }
+}
@@ -0,0 +1,417 @@
+// Named export presets for the editor.
+// - Saves the current format, codec, frame, and audio options under a name.
+// - Persists presets in an INI-style file under the config dir.
+// - Drives the preset picker popup (Ctrl+p in the editor).
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    media::{OUTPUT_FORMATS, VIDEO_CODECS},
+    model::{InputField, TextInput},
+};
+
+use super::{App, config_dir, state_lock::write_state_file};
+
+const PRESETS_FILE_NAME: &str = "presets.ini";
+
+/// Editor settings that a preset restores. Per-file values (times, crop,
+/// caption, streams, output name) are left alone.
+#[derive(Clone)]
+pub(crate) struct ExportPreset {
+    pub(crate) name: String,
+    format: &'static str,
+    codec: &'static str,
+    gpu: bool,
+    fps: String,
+    bitrate_kbps: String,
+    target_size_mb: String,
+    scale_percent: String,
+    max_width: String,
+    stream_copy: bool,
+    remove_audio: bool,
+    normalize_audio: bool,
+    normalize_two_pass: bool,
+    gif_palette: bool,
+    keep_metadata: bool,
+}
+
+impl ExportPreset {
+    fn with_name(name: String) -> Self {
+        Self {
+            name,
+            format: OUTPUT_FORMATS[0],
+            codec: VIDEO_CODECS[0],
+            gpu: false,
+            fps: "30".to_string(),
+            bitrate_kbps: String::new(),
+            target_size_mb: String::new(),
+            scale_percent: "100".to_string(),
+            max_width: String::new(),
+            stream_copy: false,
+            remove_audio: false,
+            normalize_audio: false,
+            normalize_two_pass: false,
+            gif_palette: true,
+            keep_metadata: true,
+        }
+    }
+
+    /// Applies one `key = value` line; unknown keys and values are ignored so
+    /// older builds can read files written by newer ones.
+    fn set_field(&mut self, key: &str, value: &str) {
+        match key {
+            "format" => {
+                if let Some(format) = OUTPUT_FORMATS.iter().find(|format| **format == value) {
+                    self.format = format;
+                }
+            }
+            "codec" => {
+                if let Some(codec) = VIDEO_CODECS.iter().find(|codec| **codec == value) {
+                    self.codec = codec;
+                }
+            }
+            "gpu" => self.gpu = value == "true",
+            "fps" => self.fps = value.to_string(),
+            "bitrate_kbps" => self.bitrate_kbps = value.to_string(),
+            "target_size_mb" => self.target_size_mb = value.to_string(),
+            "scale_percent" => self.scale_percent = value.to_string(),
+            "max_width" => self.max_width = value.to_string(),
+            "stream_copy" => self.stream_copy = value == "true",
+            "remove_audio" => self.remove_audio = value == "true",
+            "normalize_audio" => self.normalize_audio = value == "true",
+            "normalize_two_pass" => self.normalize_two_pass = value == "true",
+            "gif_palette" => self.gif_palette = value == "true",
+            "keep_metadata" => self.keep_metadata = value == "true",
+            _ => {}
+        }
+    }
+
+    fn write_to(&self, contents: &mut String) {
+        contents.push_str(&format!("[{}]\n", self.name));
+        for (key, value) in [
+            ("format", self.format.to_string()),
+            ("codec", self.codec.to_string()),
+            ("gpu", self.gpu.to_string()),
+            ("fps", self.fps.clone()),
+            ("bitrate_kbps", self.bitrate_kbps.clone()),
+            ("target_size_mb", self.target_size_mb.clone()),
+            ("scale_percent", self.scale_percent.clone()),
+            ("max_width", self.max_width.clone()),
+            ("stream_copy", self.stream_copy.to_string()),
+            ("remove_audio", self.remove_audio.to_string()),
+            ("normalize_audio", self.normalize_audio.to_string()),
+            ("normalize_two_pass", self.normalize_two_pass.to_string()),
+            ("gif_palette", self.gif_palette.to_string()),
+            ("keep_metadata", self.keep_metadata.to_string()),
+        ] {
+            contents.push_str(&format!("{key} = {value}\n"));
+        }
+    }
+
+    /// Short description for the picker, e.g. `mp4 h264 30fps 2500k 50%`.
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = vec![self.format.to_string()];
+        if self.stream_copy {
+            parts.push("stream copy".to_string());
+        } else {
+            parts.push(self.codec.to_string());
+            if !self.fps.is_empty() {
+                parts.push(format!("{}fps", self.fps));
+            }
+            if !self.target_size_mb.is_empty() {
+                parts.push(format!("<= {} MB", self.target_size_mb));
+            } else if !self.bitrate_kbps.is_empty() {
+                parts.push(format!("{}k", self.bitrate_kbps));
+            }
+            if self.scale_percent != "100" {
+                parts.push(format!("{}%", self.scale_percent));
+            }
+            if !self.max_width.is_empty() {
+                parts.push(format!("max {}px", self.max_width));
+            }
+        }
+        if self.remove_audio {
+            parts.push("no audio".to_string());
+        } else if self.normalize_audio {
+            parts.push("loudnorm".to_string());
+        }
+        if !self.keep_metadata {
+            parts.push("no metadata".to_string());
+        }
+        parts.join(" ")
+    }
+}
+
+/// Popup state: the highlighted preset, plus the name being typed when
+/// saving the current settings.
+pub(super) struct PresetPicker {
+    cursor: usize,
+    naming: Option<TextInput>,
+}
+
+/// Loads presets from the config dir. A missing file means no presets.
+pub(super) fn load_presets() -> io::Result<Vec<ExportPreset>> {
+    let Some(path) = presets_path() else {
+        return Ok(Vec::new());
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut presets: Vec<ExportPreset> = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            presets.push(ExportPreset::with_name(name.trim().to_string()));
+            continue;
+        }
+        if let (Some(preset), Some((key, value))) = (presets.last_mut(), line.split_once('=')) {
+            preset.set_field(key.trim(), value.trim());
+        }
+    }
+    Ok(presets)
+}
+
+fn save_presets(presets: &[ExportPreset]) -> io::Result<()> {
+    let Some(path) = presets_path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no config directory (set HOME or XDG_CONFIG_HOME)",
+        ));
+    };
+    let mut contents = String::from("# rt export presets\n");
+    for preset in presets {
+        contents.push('\n');
+        preset.write_to(&mut contents);
+    }
+    write_state_file(&path, &contents)
+}
+
+fn presets_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(PRESETS_FILE_NAME))
+}
+
+impl App {
+    pub fn open_preset_picker(&mut self) {
+        self.preset_picker = Some(PresetPicker {
+            cursor: 0,
+            naming: None,
+        });
+    }
+
+    pub fn has_preset_picker(&self) -> bool {
+        self.preset_picker.is_some()
+    }
+
+    pub fn cancel_preset_picker(&mut self) {
+        self.preset_picker = None;
+    }
+
+    pub fn presets(&self) -> &[ExportPreset] {
+        &self.presets
+    }
+
+    /// Highlighted preset and the name input while saving, for rendering.
+    pub fn preset_picker(&self) -> Option<(usize, Option<&TextInput>)> {
+        self.preset_picker
+            .as_ref()
+            .map(|picker| (picker.cursor, picker.naming.as_ref()))
+    }
+
+    pub fn preset_naming(&self) -> bool {
+        self.preset_picker
+            .as_ref()
+            .is_some_and(|picker| picker.naming.is_some())
+    }
+
+    pub fn select_next_preset(&mut self) {
+        let count = self.presets.len();
+        if let Some(picker) = self.preset_picker.as_mut()
+            && picker.cursor + 1 < count
+        {
+            picker.cursor += 1;
+        }
+    }
+
+    pub fn select_previous_preset(&mut self) {
+        if let Some(picker) = self.preset_picker.as_mut() {
+            picker.cursor = picker.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn apply_selected_preset(&mut self) {
+        if let Some(picker) = self.preset_picker.as_ref() {
+            self.apply_preset(picker.cursor);
+        }
+    }
+
+    /// Applies the preset shown as `number` (1-based) in the picker.
+    pub fn apply_preset_number(&mut self, number: usize) {
+        if number >= 1 && number <= self.presets.len() {
+            self.apply_preset(number - 1);
+        }
+    }
+
+    fn apply_preset(&mut self, index: usize) {
+        let Some(preset) = self.presets.get(index).cloned() else {
+            return;
+        };
+        self.preset_picker = None;
+        self.apply_export_preset(&preset);
+        self.status_message = format!("Applied preset: {} ({})", preset.name, preset.summary());
+    }
+
+    /// Restores the settings `preset` covers, leaving per-file values alone.
+    pub(super) fn apply_export_preset(&mut self, preset: &ExportPreset) {
+        self.output_format = preset.format;
+        self.output_video_codec = preset.codec;
+        self.use_gpu_encoding = preset.gpu && self.gpu_h264_encoder_available();
+        self.output_fps = preset.fps.clone();
+        self.output_fps_cursor = self.output_fps.chars().count();
+        self.overwrite_fps_on_next_type = true;
+        self.output_bitrate_kbps = preset.bitrate_kbps.clone();
+        self.output_bitrate_cursor = self.output_bitrate_kbps.chars().count();
+        self.overwrite_bitrate_on_next_type = true;
+        self.output_scale_percent = preset.scale_percent.clone();
+        self.output_scale_percent_cursor = self.output_scale_percent.chars().count();
+        self.overwrite_scale_percent_on_next_type = true;
+        self.output_target_size_mb.set(&preset.target_size_mb);
+        self.output_max_width.set(&preset.max_width);
+        self.stream_copy = preset.stream_copy;
+        self.remove_audio = preset.remove_audio;
+        self.normalize_audio = preset.normalize_audio;
+        self.normalize_two_pass = preset.normalize_two_pass;
+        self.gif_palette = preset.gif_palette;
+        self.keep_metadata = preset.keep_metadata;
+
+        if !self.editor_input_enabled(self.active_input) {
+            self.active_input = InputField::Output;
+        }
+        self.sync_output_extension_to_selected_format();
+    }
+
+    pub fn start_preset_naming(&mut self) {
+        if let Some(picker) = self.preset_picker.as_mut() {
+            let mut input = TextInput::new("");
+            input.focus_append();
+            picker.naming = Some(input);
+        }
+    }
+
+    pub fn push_preset_name_char(&mut self, ch: char) {
+        // `[` and `]` would break the section headers in the file.
+        if ch.is_control() || ch == '[' || ch == ']' {
+            return;
+        }
+        if let Some(input) = self.preset_name_input_mut() {
+            input.insert(ch);
+        }
+    }
+
+    pub fn backspace_preset_name(&mut self) {
+        if let Some(input) = self.preset_name_input_mut() {
+            input.backspace();
+        }
+    }
+
+    pub fn move_preset_name_cursor_left(&mut self) {
+        if let Some(input) = self.preset_name_input_mut() {
+            input.move_left();
+        }
+    }
+
+    pub fn move_preset_name_cursor_right(&mut self) {
+        if let Some(input) = self.preset_name_input_mut() {
+            input.move_right();
+        }
+    }
+
+    fn preset_name_input_mut(&mut self) -> Option<&mut TextInput> {
+        self.preset_picker.as_mut()?.naming.as_mut()
+    }
+
+    /// Saves the current editor settings under the typed name, replacing a
+    /// preset with the same name.
+    pub fn confirm_preset_name(&mut self) {
+        let Some(name) = self
+            .preset_picker
+            .as_ref()
+            .and_then(|picker| picker.naming.as_ref())
+            .map(|input| input.value().trim().to_string())
+        else {
+            return;
+        };
+        if name.is_empty() {
+            self.status_message = "Type a name for the preset first.".to_string();
+            return;
+        }
+
+        let preset = self.current_export_preset(name.clone());
+        let index = match self
+            .presets
+            .iter()
+            .position(|existing| existing.name == name)
+        {
+            Some(index) => {
+                self.presets[index] = preset;
+                index
+            }
+            None => {
+                self.presets.push(preset);
+                self.presets.len() - 1
+            }
+        };
+
+        self.preset_picker = Some(PresetPicker {
+            cursor: index,
+            naming: None,
+        });
+        self.status_message = match save_presets(&self.presets) {
+            Ok(()) => format!("Saved preset: {name}"),
+            Err(err) => format!("Failed to save presets: {err}"),
+        };
+    }
+
+    /// The current form's preset-covered settings under `name`.
+    pub(super) fn current_export_preset(&self, name: String) -> ExportPreset {
+        ExportPreset {
+            name,
+            format: self.output_format,
+            codec: self.output_video_codec,
+            gpu: self.use_gpu_encoding,
+            fps: self.output_fps.clone(),
+            bitrate_kbps: self.output_bitrate_kbps.clone(),
+            target_size_mb: self.output_target_size_mb.value().trim().to_string(),
+            scale_percent: self.output_scale_percent.clone(),
+            max_width: self.output_max_width.value().to_string(),
+            stream_copy: self.stream_copy,
+            remove_audio: self.remove_audio,
+            normalize_audio: self.normalize_audio,
+            normalize_two_pass: self.normalize_two_pass,
+            gif_palette: self.gif_palette,
+            keep_metadata: self.keep_metadata,
+        }
+    }
+
+    pub fn delete_selected_preset(&mut self) {
+        let Some(picker) = self.preset_picker.as_mut() else {
+            return;
+        };
+        if picker.cursor >= self.presets.len() {
+            return;
+        }
+        let preset = self.presets.remove(picker.cursor);
+        picker.cursor = picker.cursor.min(self.presets.len().saturating_sub(1));
+        self.status_message = match save_presets(&self.presets) {
+            Ok(()) => format!("Deleted preset: {}", preset.name),
+            Err(err) => format!("Failed to save presets: {err}"),
+        };
+    }
+}
@@ -0,0 +1,172 @@
+// Frame preview for the editor.
+// - Decodes the frame at the focused time with ffmpeg on a background thread.
+// - Keeps a small RGB copy that the editor draws with half-block characters,
+//   two pixels per terminal cell, so it works in any truecolor terminal.
+// - Follows the Start/End fields and renders again after each change.
+use std::{
+    io,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::{
+    media::{extract_frame_rgb, is_video_file},
+    model::InputField,
+};
+
+use super::App;
+
+/// Width of the preview in pixels, which is also its width in cells.
+const PREVIEW_WIDTH: u32 = 64;
+/// Tallest preview in pixels (half as many rows), for portrait videos.
+const PREVIEW_MAX_HEIGHT: u32 = 48;
+
+pub(crate) struct FramePreview {
+    path: PathBuf,
+    millis: u64,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    /// Packed RGB, or the error when ffmpeg produced no frame.
+    pub(crate) rgb: Result<Vec<u8>, String>,
+}
+
+impl FramePreview {
+    pub(crate) fn time_seconds(&self) -> f64 {
+        self.millis as f64 / 1000.0
+    }
+
+    pub(crate) fn pixel(&self, x: u32, y: u32) -> Option<(u8, u8, u8)> {
+        let rgb = self.rgb.as_ref().ok()?;
+        let offset = ((y * self.width + x) * 3) as usize;
+        let pixel = rgb.get(offset..offset + 3)?;
+        Some((pixel[0], pixel[1], pixel[2]))
+    }
+}
+
+pub(super) struct RunningFramePreview {
+    path: PathBuf,
+    millis: u64,
+    width: u32,
+    height: u32,
+    rx: Receiver<io::Result<Vec<u8>>>,
+}
+
+/// Preview size for a source resolution: square pixels, an even height so
+/// each cell gets a top and bottom pixel, and 16:9 when the size is unknown.
+fn preview_size(source: Option<(u32, u32)>) -> (u32, u32) {
+    let (source_width, source_height) = source
+        .filter(|(width, height)| *width > 0 && *height > 0)
+        .unwrap_or((16, 9));
+    let height = PREVIEW_WIDTH * source_height / source_width;
+    if height <= PREVIEW_MAX_HEIGHT {
+        return (PREVIEW_WIDTH, (height / 2 * 2).max(2));
+    }
+    let width = (PREVIEW_MAX_HEIGHT * source_width / source_height).max(1);
+    (width, PREVIEW_MAX_HEIGHT)
+}
+
+impl App {
+    pub fn toggle_frame_preview(&mut self) {
+        self.frame_preview_enabled = !self.frame_preview_enabled;
+        if self.frame_preview_enabled {
+            self.status_message =
+                "Frame preview on. It follows the focused Start/End time.".to_string();
+        } else {
+            self.frame_preview = None;
+            self.status_message = "Frame preview off.".to_string();
+        }
+    }
+
+    pub fn frame_preview_enabled(&self) -> bool {
+        self.frame_preview_enabled
+    }
+
+    /// The End time while End is focused, otherwise the start time.
+    fn frame_preview_millis(&self) -> u64 {
+        if self.active_input == InputField::End {
+            self.end_time.to_millis()
+        } else {
+            self.start_time.to_millis()
+        }
+    }
+
+    /// Keeps the preview in step with the editor's time fields. One frame is
+    /// decoded at a time; a stale result is shown until the next one lands.
+    pub(super) fn refresh_frame_preview(&mut self) {
+        if let Some(running) = &self.running_frame_preview {
+            let result = match running.rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => Err(io::Error::other("preview thread stopped")),
+            };
+            if let Some(running) = self.running_frame_preview.take()
+                && self.frame_preview_enabled
+            {
+                self.frame_preview = Some(FramePreview {
+                    path: running.path,
+                    millis: running.millis,
+                    width: running.width,
+                    height: running.height,
+                    rgb: result.map_err(|err| err.to_string()),
+                });
+            }
+        }
+
+        if !self.frame_preview_enabled {
+            return;
+        }
+        let Some(path) = self.selected_video.clone() else {
+            return;
+        };
+        if !is_video_file(&path) {
+            return;
+        }
+        let millis = self.frame_preview_millis();
+        if self
+            .frame_preview
+            .as_ref()
+            .is_some_and(|preview| preview.path == path && preview.millis == millis)
+        {
+            return;
+        }
+
+        let (width, height) = preview_size(
+            self.selected_video_stats
+                .as_ref()
+                .and_then(|stats| stats.width.zip(stats.height)),
+        );
+        let (tx, rx) = mpsc::channel();
+        let probe_path = path.clone();
+        thread::spawn(move || {
+            let _ = tx.send(extract_frame_rgb(
+                &probe_path,
+                millis as f64 / 1000.0,
+                width,
+                height,
+            ));
+        });
+        self.running_frame_preview = Some(RunningFramePreview {
+            path,
+            millis,
+            width,
+            height,
+            rx,
+        });
+    }
+
+    /// Latest preview of the current video, possibly of an earlier time.
+    pub fn frame_preview(&self) -> Option<&FramePreview> {
+        self.frame_preview
+            .as_ref()
+            .filter(|preview| self.selected_video.as_ref() == Some(&preview.path))
+    }
+
+    /// A frame for the current time is still being decoded.
+    pub fn frame_preview_pending(&self) -> bool {
+        self.running_frame_preview.is_some()
+            || self
+                .frame_preview()
+                .is_none_or(|preview| preview.millis != self.frame_preview_millis())
+    }
+}
@@ -0,0 +1,160 @@
+// Quick jumps to well-known directories from the file browser.
+// - `g` followed by a letter opens a directory: `gd` Downloads, `gh` home,
+//   `gv` Videos (Movies on macOS), using the XDG user dirs on Linux.
+// - `jumps.ini` in the config dir adds letters or points them elsewhere.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{App, config_dir};
+
+const JUMPS_FILE_NAME: &str = "jumps.ini";
+
+pub(super) struct QuickJumps {
+    targets: Vec<(char, PathBuf)>,
+}
+
+pub(super) fn home_dir() -> Option<PathBuf> {
+    let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    env::var_os(var).map(PathBuf::from)
+}
+
+/// `~` and `$HOME` at the start of a path stand for the home directory.
+fn expand_home(value: &str, home: Option<&Path>) -> PathBuf {
+    for prefix in ["~", "$HOME"] {
+        if let Some(rest) = value.strip_prefix(prefix)
+            && (rest.is_empty() || rest.starts_with(['/', '\\']))
+            && let Some(home) = home
+        {
+            return home.join(rest.trim_start_matches(['/', '\\']));
+        }
+    }
+    PathBuf::from(value)
+}
+
+/// `path` with the home directory shown as `~`, for display.
+pub(super) fn tilde_path(path: &Path) -> String {
+    match home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// `expand_home` for the current user.
+pub(super) fn expand_home_dir(value: &str) -> PathBuf {
+    expand_home(value, home_dir().as_deref())
+}
+
+/// A directory from `user-dirs.dirs`, e.g. `XDG_DOWNLOAD_DIR="$HOME/Downloads"`.
+fn xdg_user_dir(key: &str, home: &Path) -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home.join(".config"));
+    let contents = fs::read_to_string(config.join("user-dirs.dirs")).ok()?;
+    contents.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(key)?.strip_prefix('=')?;
+        Some(expand_home(value.trim().trim_matches('"'), Some(home)))
+    })
+}
+
+fn default_targets() -> Vec<(char, PathBuf)> {
+    let Some(home) = home_dir() else {
+        return Vec::new();
+    };
+    let videos_name = if cfg!(target_os = "macos") {
+        "Movies"
+    } else {
+        "Videos"
+    };
+    let downloads =
+        xdg_user_dir("XDG_DOWNLOAD_DIR", &home).unwrap_or_else(|| home.join("Downloads"));
+    let videos = xdg_user_dir("XDG_VIDEOS_DIR", &home).unwrap_or_else(|| home.join(videos_name));
+    vec![('d', downloads), ('h', home), ('v', videos)]
+}
+
+impl QuickJumps {
+    pub(super) fn load_defaults() -> Self {
+        Self {
+            targets: default_targets(),
+        }
+    }
+
+    /// Lines are `<letter> = <path>`; a letter from the file replaces the
+    /// built-in one.
+    pub(super) fn load() -> io::Result<Self> {
+        let mut jumps = Self::load_defaults();
+        let Some(path) = config_dir().map(|dir| dir.join(JUMPS_FILE_NAME)) else {
+            return Ok(jumps);
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(jumps),
+            Err(err) => return Err(err),
+        };
+        let home = home_dir();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let mut letters = key.trim().chars();
+            let (Some(letter), None) = (letters.next(), letters.next()) else {
+                continue;
+            };
+            let target = expand_home(value.trim(), home.as_deref());
+            jumps.targets.retain(|(existing, _)| *existing != letter);
+            jumps.targets.push((letter, target));
+        }
+        Ok(jumps)
+    }
+
+    fn target(&self, letter: char) -> Option<&Path> {
+        self.targets
+            .iter()
+            .find(|(existing, _)| *existing == letter)
+            .map(|(_, path)| path.as_path())
+    }
+}
+
+impl App {
+    /// Called for `g`: the next key may name a quick jump.
+    pub fn arm_quick_jump(&mut self) {
+        self.quick_jump_armed = true;
+    }
+
+    /// Whether a `g` is waiting for its second key.
+    pub fn quick_jump_pending(&self) -> bool {
+        self.quick_jump_armed
+    }
+
+    /// Disarms the pending `g`, returning whether it was armed.
+    pub fn take_quick_jump_armed(&mut self) -> bool {
+        std::mem::take(&mut self.quick_jump_armed)
+    }
+
+    pub fn has_quick_jump(&self, letter: char) -> bool {
+        self.quick_jumps.target(letter).is_some()
+    }
+
+    pub fn quick_jump(&mut self, letter: char) -> io::Result<()> {
+        let Some(target) = self.quick_jumps.target(letter).map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        if !target.is_dir() {
+            self.status_message = format!("g{letter}: {} does not exist", target.display());
+            return Ok(());
+        }
+        self.change_dir(target)?;
+        self.status_message = format!("Opened {}", self.cwd.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Container repair for the file browser.
+// - Remuxes the selected file with `-c copy` into a fresh container.
+// - Probes the result afterwards to report whether it plays.
+use std::path::{Path, PathBuf};
+
+use crate::media::{
+    is_editable_media_file, next_available_output_path, probe_streams, probe_video_times,
+    supports_faststart,
+};
+
+use super::{App, FfmpegJob, FfmpegJobKind, ffmpeg::ffmpeg_command_line};
+
+impl App {
+    /// Rewrites the selected file as `<name>_remux.<ext>` without
+    /// re-encoding, which rebuilds indexes and a missing `moov` atom when the
+    /// packets themselves are still readable.
+    pub fn remux_selected_file(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return;
+        };
+        if entry.is_dir || !is_editable_media_file(&entry.path) {
+            self.status_message = format!("Remux needs a media file: {}", entry.name);
+            return;
+        }
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to remux files.".to_string();
+            return;
+        }
+
+        // Broken containers often report no duration; the remux still runs,
+        // just without a percentage.
+        let duration_seconds = probe_video_times(&entry.path)
+            .map(|(_, _, bounds)| (bounds.end_seconds - bounds.start_seconds).max(0.0))
+            .unwrap_or(0.0);
+
+        let output_path = next_available_output_path(&remux_path(&entry.path));
+        let args = remux_args(&entry.path, &output_path);
+        let job = FfmpegJob {
+            kind: FfmpegJobKind::Remux,
+            command_line: ffmpeg_command_line(&args),
+            input_path: entry.path,
+            args,
+            output_path,
+            progress_only: self.ffmpeg_progress_only,
+            duration_seconds,
+            follow_up: None,
+            file_time: None,
+        };
+        self.launch_editor_export(job, " (remux)");
+    }
+}
+
+/// Probes a finished remux and describes whether players will accept it.
+pub(super) fn remux_playback_note(output_path: &Path) -> String {
+    let streams = probe_streams(output_path).unwrap_or_default();
+    let duration = probe_video_times(output_path)
+        .map(|(_, end, _)| end.to_seconds())
+        .unwrap_or(0);
+
+    if streams.is_empty() {
+        "result has no readable streams; it will not play".to_string()
+    } else if duration == 0 {
+        format!(
+            "result has {} stream(s) but no duration; players may not seek",
+            streams.len()
+        )
+    } else {
+        format!("result plays: {} stream(s), {duration}s", streams.len())
+    }
+}
+
+fn remux_path(input_path: &Path) -> PathBuf {
+    let stem = input_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "media".to_string());
+    let extension = input_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mkv".to_string());
+    input_path.with_file_name(format!("{stem}_remux.{extension}"))
+}
+
+/// Regenerates timestamps and drops corrupt packets so a damaged source
+/// still produces a clean container.
+fn remux_args(input_path: &Path, output_path: &Path) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "warning".to_string(),
+        "-fflags".to_string(),
+        "+genpts+discardcorrupt".to_string(),
+        "-err_detect".to_string(),
+        "ignore_err".to_string(),
+        "-i".to_string(),
+        input_path.display().to_string(),
+        "-map".to_string(),
+        "0".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        "-ignore_unknown".to_string(),
+    ];
+
+    if supports_faststart(output_path) {
+        args.push("-movflags".to_string());
+        args.push("+faststart".to_string());
+    }
+
+    args.push(output_path.display().to_string());
+    args
+}
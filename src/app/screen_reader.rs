@@ -0,0 +1,36 @@
+// Output for terminal screen readers.
+// - `--screen-reader` or `[ui] screen_reader = true` in config.toml turns
+//   it on.
+// - Panes and popups drop their box-drawing borders and spinners hold
+//   still, so a reader isn't kept busy spelling out decoration.
+// - The footer names the focused pane and shows the status line as plain
+//   text. The terminal cursor moves there when the status changes, and
+//   otherwise rests on the selected file, for readers that follow it.
+// - Highlighted rows get a `>` and the active tab brackets, so nothing is
+//   told by color alone.
+use super::App;
+
+impl App {
+    pub fn set_screen_reader(&mut self, enabled: bool) {
+        self.screen_reader = enabled;
+    }
+
+    pub fn screen_reader(&self) -> bool {
+        self.screen_reader
+    }
+
+    pub fn status_message(&self) -> &str {
+        &self.status_message
+    }
+
+    /// Whether the status line changed since the last call, so a frame can
+    /// move the cursor to it once.
+    pub fn take_status_announcement(&self) -> bool {
+        let mut announced = self.announced_status.borrow_mut();
+        if *announced == self.status_message {
+            return false;
+        }
+        announced.clone_from(&self.status_message);
+        true
+    }
+}
@@ -0,0 +1,79 @@
+// Multi-segment exports for the editor.
+// - Collects several Start/End ranges from the time fields.
+// - Exports them trimmed and joined into one file with `trim` and `concat`.
+use crate::model::TimeInput;
+
+use super::App;
+
+/// Earliest start and latest end of the listed segments, which is the part
+/// of the input ffmpeg has to read.
+pub(super) fn segments_span(segments: &[(f64, f64)]) -> (f64, f64) {
+    let start = segments
+        .iter()
+        .map(|(start, _)| *start)
+        .min_by(f64::total_cmp)
+        .unwrap_or(0.0);
+    let end = segments
+        .iter()
+        .map(|(_, end)| *end)
+        .max_by(f64::total_cmp)
+        .unwrap_or(0.0);
+    (start, end)
+}
+
+/// Length of the joined output before any speed change.
+pub(super) fn segments_total_seconds(segments: &[(f64, f64)]) -> f64 {
+    segments.iter().map(|(start, end)| end - start).sum()
+}
+
+impl App {
+    pub fn segments(&self) -> &[(f64, f64)] {
+        &self.segments
+    }
+
+    /// Adds the current Start/End range as the next segment.
+    pub fn add_segment(&mut self) {
+        self.commit_time_offset();
+        if self.selected_video.is_none() {
+            self.status_message = "No video selected. Choose one in the left pane.".to_string();
+            return;
+        }
+        match self.validated_time_range() {
+            Ok(range) => {
+                self.segments.push(range);
+                self.status_message = format!(
+                    "Added segment {} ({}-{}).",
+                    self.segments.len(),
+                    TimeInput::from_seconds(range.0).to_ffmpeg_timestamp(),
+                    TimeInput::from_seconds(range.1).to_ffmpeg_timestamp()
+                );
+            }
+            Err(message) => self.status_message = message,
+        }
+    }
+
+    pub fn remove_last_segment(&mut self) {
+        if self.segments.pop().is_some() {
+            self.status_message = match self.segments.len() {
+                0 => "Removed the last segment. Start/End is used again.".to_string(),
+                remaining => format!("Removed a segment, {remaining} left."),
+            };
+        } else {
+            self.status_message = "No segments to remove.".to_string();
+        }
+    }
+
+    /// The audio stream joined with the segments, or `None` without audio.
+    pub(super) fn segment_audio_map(&self) -> Option<String> {
+        if self.remove_audio || (!self.media_streams.is_empty() && self.audio_streams().is_empty())
+        {
+            return None;
+        }
+        Some(
+            self.chosen_audio_stream()
+                .or_else(|| self.audio_streams().first().copied())
+                .map(|stream| stream.map_arg())
+                .unwrap_or_else(|| "0:a:0".to_string()),
+        )
+    }
+}
@@ -0,0 +1,112 @@
+// Safe writes for state shared between rt instances.
+// - Snapshots (presets, tags, layout, ...) are replaced whole; the last save
+//   wins, and a rename means readers never see half a file.
+// - Counters are re-read and merged under a lock file so saves add up. The
+//   bookmarks table is rewritten into config.toml the same way, so the rest
+//   of the file is never lost to a stale copy.
+// - Writes through a per-process temp file so concurrent saves never mix.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+const LOCK_FILE_NAME: &str = "state.lock";
+
+/// How long a save waits for another instance before giving up.
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+/// A lock older than this is left over from a crashed instance. Saves take
+/// milliseconds, so a live holder never gets close.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+/// Holds `state.lock` in one directory; removed again on drop.
+struct StateLock {
+    path: PathBuf,
+}
+
+impl StateLock {
+    fn acquire(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(LOCK_FILE_NAME);
+        let started = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    // The pid is only for whoever finds a stuck lock by hand.
+                    let _ = writeln!(file, "{}", process::id());
+                    return Ok(Self { path });
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if lock_is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed() >= LOCK_WAIT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::WouldBlock,
+                            format!(
+                                "another rt instance is saving state (lock: {})",
+                                path.display()
+                            ),
+                        ));
+                    }
+                    thread::sleep(Duration::from_millis(20));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for StateLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock_is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age >= STALE_LOCK_AGE)
+}
+
+/// Replaces `path` with `contents`. Write then rename so a crash never
+/// leaves a truncated file. No lock: the caller's snapshot is the whole
+/// file, so holding one would not stop another instance's later save from
+/// replacing it.
+pub(super) fn write_state_file(path: &Path, contents: &str) -> io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir)?;
+    replace_file(dir, path, contents)
+}
+
+/// Rewrites `path` from its current contents (`None` when missing) under
+/// one hold of the lock, so counters kept by several instances add up.
+pub(super) fn update_state_file(
+    path: &Path,
+    update: impl FnOnce(Option<String>) -> String,
+) -> io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir)?;
+    let _lock = StateLock::acquire(dir)?;
+    let current = match fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    replace_file(dir, path, &update(current))
+}
+
+fn replace_file(dir: &Path, path: &Path, contents: &str) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = dir.join(format!(".{file_name}.{}.tmp", process::id()));
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
@@ -0,0 +1,245 @@
+// Lifetime export and download statistics.
+// - Adds every finished export and download to `stats.ini` in the config dir.
+// - Keeps encode time per video encoder, so a hardware encoder that got
+//   slower stands out next to the others.
+// - Shown in a popup opened from the file browser.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use super::{App, config_dir, state_lock::update_state_file};
+
+const STATS_FILE_NAME: &str = "stats.ini";
+
+/// Exports made with one encoder. `media_seconds` is the output length, so
+/// `media_seconds / encode_seconds` is the speed ffmpeg reports as `1.5x`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EncoderStats {
+    pub(crate) name: String,
+    pub(crate) exports: u64,
+    pub(crate) media_seconds: f64,
+    pub(crate) encode_seconds: f64,
+}
+
+impl EncoderStats {
+    pub(crate) fn speed(&self) -> Option<f64> {
+        average_speed(self.media_seconds, self.encode_seconds)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JobStats {
+    pub(crate) exports: u64,
+    pub(crate) export_bytes: u64,
+    pub(crate) media_seconds: f64,
+    pub(crate) encode_seconds: f64,
+    pub(crate) downloads: u64,
+    pub(crate) download_bytes: u64,
+    pub(crate) download_seconds: f64,
+    /// Sorted by name.
+    pub(crate) encoders: Vec<EncoderStats>,
+}
+
+impl JobStats {
+    pub(crate) fn average_speed(&self) -> Option<f64> {
+        average_speed(self.media_seconds, self.encode_seconds)
+    }
+
+    fn load() -> io::Result<Self> {
+        let Some(path) = stats_path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Unknown keys and unparsable values are skipped, so a hand-edited
+    /// file never stops the counters.
+    fn parse(contents: &str) -> Self {
+        let mut stats = Self::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if let Some(name) = key.strip_prefix("encoder.") {
+                let mut fields = value.split_whitespace();
+                let (Some(exports), Some(media_seconds), Some(encode_seconds)) = (
+                    fields.next().and_then(|field| field.parse().ok()),
+                    fields.next().and_then(|field| field.parse().ok()),
+                    fields.next().and_then(|field| field.parse().ok()),
+                ) else {
+                    continue;
+                };
+                stats.encoders.push(EncoderStats {
+                    name: name.to_string(),
+                    exports,
+                    media_seconds,
+                    encode_seconds,
+                });
+                continue;
+            }
+            match key {
+                "exports" => stats.exports = value.parse().unwrap_or(0),
+                "export_bytes" => stats.export_bytes = value.parse().unwrap_or(0),
+                "media_seconds" => stats.media_seconds = value.parse().unwrap_or(0.0),
+                "encode_seconds" => stats.encode_seconds = value.parse().unwrap_or(0.0),
+                "downloads" => stats.downloads = value.parse().unwrap_or(0),
+                "download_bytes" => stats.download_bytes = value.parse().unwrap_or(0),
+                "download_seconds" => stats.download_seconds = value.parse().unwrap_or(0.0),
+                _ => {}
+            }
+        }
+        stats.encoders.sort_by(|a, b| a.name.cmp(&b.name));
+        stats
+    }
+
+    fn serialize(&self) -> String {
+        let mut contents = format!(
+            "# rt statistics, updated after every finished export and download\n\
+             exports = {}\nexport_bytes = {}\nmedia_seconds = {:.3}\nencode_seconds = {:.3}\n\
+             downloads = {}\ndownload_bytes = {}\ndownload_seconds = {:.3}\n",
+            self.exports,
+            self.export_bytes,
+            self.media_seconds,
+            self.encode_seconds,
+            self.downloads,
+            self.download_bytes,
+            self.download_seconds
+        );
+        contents.push_str("# encoder.<name> = exports media_seconds encode_seconds\n");
+        for encoder in &self.encoders {
+            contents.push_str(&format!(
+                "encoder.{} = {} {:.3} {:.3}\n",
+                encoder.name, encoder.exports, encoder.media_seconds, encoder.encode_seconds
+            ));
+        }
+        contents
+    }
+
+    fn add_export(&mut self, encoder: &str, bytes: u64, media_seconds: f64, elapsed: Duration) {
+        let encode_seconds = elapsed.as_secs_f64();
+        self.exports += 1;
+        self.export_bytes += bytes;
+        self.media_seconds += media_seconds;
+        self.encode_seconds += encode_seconds;
+        let index = match self
+            .encoders
+            .binary_search_by(|stats| stats.name.as_str().cmp(encoder))
+        {
+            Ok(index) => index,
+            Err(index) => {
+                self.encoders.insert(
+                    index,
+                    EncoderStats {
+                        name: encoder.to_string(),
+                        ..EncoderStats::default()
+                    },
+                );
+                index
+            }
+        };
+        let stats = &mut self.encoders[index];
+        stats.exports += 1;
+        stats.media_seconds += media_seconds;
+        stats.encode_seconds += encode_seconds;
+    }
+
+    fn add_download(&mut self, bytes: u64, elapsed: Duration) {
+        self.downloads += 1;
+        self.download_bytes += bytes;
+        self.download_seconds += elapsed.as_secs_f64();
+    }
+}
+
+fn average_speed(media_seconds: f64, encode_seconds: f64) -> Option<f64> {
+    (encode_seconds > 0.0 && media_seconds > 0.0).then(|| media_seconds / encode_seconds)
+}
+
+fn stats_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(STATS_FILE_NAME))
+}
+
+/// Reads, changes, and writes the stats file in one locked step.
+fn update_stats(update: impl FnOnce(&mut JobStats)) -> io::Result<()> {
+    let Some(path) = stats_path() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no config directory (set HOME or XDG_CONFIG_HOME)",
+        ));
+    };
+    update_state_file(&path, |current| {
+        let mut stats = current.as_deref().map(JobStats::parse).unwrap_or_default();
+        update(&mut stats);
+        stats.serialize()
+    })
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+}
+
+/// The encoder an ffmpeg job writes with, for the per-encoder speeds.
+pub(super) fn job_encoder(args: &[String]) -> String {
+    ["-c:v", "-c", "-c:a"]
+        .iter()
+        .find_map(|flag| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|index| args.get(index + 1))
+        })
+        .cloned()
+        .unwrap_or_else(|| "default".to_string())
+}
+
+impl App {
+    pub fn open_job_stats(&mut self) {
+        match JobStats::load() {
+            Ok(stats) => self.job_stats = Some(stats),
+            Err(err) => self.status_message = format!("Failed to read stats: {err}"),
+        }
+    }
+
+    pub fn job_stats(&self) -> Option<&JobStats> {
+        self.job_stats.as_ref()
+    }
+
+    pub fn close_job_stats(&mut self) {
+        self.job_stats = None;
+    }
+
+    /// Counts a finished export; a failure is appended to the status.
+    pub(super) fn record_export_stats(
+        &mut self,
+        encoder: &str,
+        output_path: &Path,
+        media_seconds: f64,
+        elapsed: Duration,
+    ) {
+        let bytes = file_size(output_path);
+        if let Err(err) =
+            update_stats(|stats| stats.add_export(encoder, bytes, media_seconds, elapsed))
+        {
+            self.status_message
+                .push_str(&format!(" (stats not saved: {err})"));
+        }
+    }
+
+    /// Counts a finished download; a failure is appended to the status.
+    pub(super) fn record_download_stats(&mut self, output_path: Option<&Path>, elapsed: Duration) {
+        let bytes = output_path.map(file_size).unwrap_or(0);
+        if let Err(err) = update_stats(|stats| stats.add_download(bytes, elapsed)) {
+            self.status_message
+                .push_str(&format!(" (stats not saved: {err})"));
+        }
+    }
+}
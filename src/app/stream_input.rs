@@ -0,0 +1,206 @@
+// Network inputs for the editor.
+// - Prompts for an http(s) or rtmp(s) URL and loads it like a local file, so
+//   a quick trim of a remote video or live stream skips the full download.
+// - Probing runs on a background thread while the prompt shows it is
+//   waiting, since a slow server can take the full timeout per call.
+// - Probing and exports read the stream with a network timeout (see
+//   `stream_input_args`); exports land in the browser's folder.
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::{
+    media::{STREAM_SCHEMES, default_output_name, is_stream_url},
+    model::TextInput,
+};
+
+use super::{App, files::MediaProbe};
+
+/// Stream URL being typed before the editor probes it.
+pub(super) struct StreamPrompt {
+    input: TextInput,
+    /// Why the last Enter rejected the URL, until it is edited.
+    error: Option<String>,
+    /// The URL being probed; dropping the receiver abandons the probe.
+    probing: Option<(String, Receiver<MediaProbe>)>,
+}
+
+impl App {
+    /// Opens the prompt, starting from the loaded stream's URL if any.
+    pub fn start_stream_prompt(&mut self) {
+        let current = self
+            .selected_video
+            .as_deref()
+            .filter(|path| is_stream_url(path))
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        let mut input = TextInput::new(&current);
+        input.focus_append();
+        self.stream_prompt = Some(StreamPrompt {
+            input,
+            error: None,
+            probing: None,
+        });
+    }
+
+    pub fn has_stream_prompt(&self) -> bool {
+        self.stream_prompt.is_some()
+    }
+
+    /// URL input, the last validation error, and whether the URL is being
+    /// probed, for rendering the prompt.
+    pub fn stream_prompt(&self) -> Option<(&TextInput, Option<&str>, bool)> {
+        self.stream_prompt.as_ref().map(|prompt| {
+            (
+                &prompt.input,
+                prompt.error.as_deref(),
+                prompt.probing.is_some(),
+            )
+        })
+    }
+
+    /// The URL is being probed; it loads into the editor once done.
+    pub fn stream_prompt_probing(&self) -> bool {
+        self.stream_prompt
+            .as_ref()
+            .is_some_and(|prompt| prompt.probing.is_some())
+    }
+
+    pub fn push_stream_prompt_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(prompt) = self.stream_prompt.as_mut()
+            && prompt.probing.is_none()
+        {
+            prompt.input.insert(ch);
+            prompt.error = None;
+        }
+    }
+
+    pub fn backspace_stream_prompt(&mut self) {
+        if let Some(prompt) = self.stream_prompt.as_mut()
+            && prompt.probing.is_none()
+        {
+            prompt.input.backspace();
+            prompt.error = None;
+        }
+    }
+
+    pub fn move_stream_prompt_cursor_left(&mut self) {
+        if let Some(prompt) = self.stream_prompt.as_mut() {
+            prompt.input.move_left();
+        }
+    }
+
+    pub fn move_stream_prompt_cursor_right(&mut self) {
+        if let Some(prompt) = self.stream_prompt.as_mut() {
+            prompt.input.move_right();
+        }
+    }
+
+    pub fn cancel_stream_prompt(&mut self) {
+        self.stream_prompt = None;
+    }
+
+    /// Starts probing the typed URL in the background; it loads into the
+    /// editor once the probe is in. An invalid URL keeps the prompt open
+    /// with the reason.
+    pub fn confirm_stream_prompt(&mut self) {
+        let Some(prompt) = self.stream_prompt.as_mut() else {
+            return;
+        };
+        if prompt.probing.is_some() {
+            return;
+        }
+        let url = match check_stream_url(prompt.input.value()) {
+            Ok(url) => url,
+            Err(error) => {
+                prompt.error = Some(error);
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let probe_path = PathBuf::from(&url);
+        thread::spawn(move || {
+            let _ = tx.send(MediaProbe::read(&probe_path));
+        });
+        self.status_message = format!("Probing {url}...");
+        prompt.probing = Some((url, rx));
+    }
+
+    /// Loads the probed stream into the editor once its probe is in.
+    /// Returns whether the prompt changed.
+    pub(super) fn try_finish_stream_probe(&mut self) -> bool {
+        let Some((url, rx)) = self
+            .stream_prompt
+            .as_ref()
+            .and_then(|prompt| prompt.probing.as_ref())
+        else {
+            return false;
+        };
+        let probe = match rx.try_recv() {
+            Ok(probe) => probe,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => {
+                let url = url.clone();
+                if let Some(prompt) = self.stream_prompt.as_mut() {
+                    prompt.probing = None;
+                    prompt.error = Some(format!("Probing {url} stopped unexpectedly."));
+                }
+                return true;
+            }
+        };
+        let url = url.clone();
+        self.stream_prompt = None;
+
+        self.load_media(PathBuf::from(&url), probe);
+        if self.selected_video_bounds.is_none() {
+            self.status_message = format!(
+                "Loaded stream without a duration (unreachable, timed out, or live): {url}. Set Start and End by hand."
+            );
+        }
+        true
+    }
+
+    /// Path that relative output names resolve next to. Streams have no
+    /// folder of their own, so their exports go to the browser's.
+    pub(super) fn output_anchor(&self, input_path: &Path) -> PathBuf {
+        if is_stream_url(input_path) {
+            self.cwd.join(default_output_name(input_path))
+        } else {
+            input_path.to_path_buf()
+        }
+    }
+}
+
+/// Checks that `input` is a single http(s) or rtmp(s) URL with a host.
+fn check_stream_url(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a URL such as https://example.com/clip.mp4.".to_string());
+    }
+    if input.chars().any(char::is_whitespace) {
+        return Err("URL contains spaces. Paste a single link.".to_string());
+    }
+    let Some((scheme, rest)) = input.split_once("://") else {
+        return Err("Missing scheme. Start with https:// or rtmp://.".to_string());
+    };
+    if !STREAM_SCHEMES
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(scheme))
+    {
+        return Err(format!(
+            "Unsupported scheme `{scheme}://`. Use http, https, rtmp, or rtmps."
+        ));
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    if host.split(':').next().unwrap_or_default().is_empty() {
+        return Err("URL has no host.".to_string());
+    }
+    Ok(input.to_string())
+}
@@ -0,0 +1,316 @@
+// Per-file star ratings and notes for quick clip triage.
+// - Persists tags in a tab-separated sidecar index under the config dir.
+// - Handles rating/note edits from the file browser and the note prompt.
+// - Applies the tag filter to directory listings.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::model::{FileEntry, FileTag, TagFilter, TextInput};
+
+use super::{App, config_dir, state_lock::write_state_file};
+
+const TAG_INDEX_FILE_NAME: &str = "tags.tsv";
+
+/// Ratings and notes keyed by absolute file path.
+pub(super) struct TagIndex {
+    path: Option<PathBuf>,
+    tags: HashMap<PathBuf, FileTag>,
+}
+
+/// Note being typed for one file in the browser.
+pub(super) struct NotePrompt {
+    name: String,
+    path: PathBuf,
+    input: TextInput,
+}
+
+impl TagIndex {
+    /// Loads the index from the config dir. A missing file is an empty index.
+    pub(super) fn load() -> io::Result<Self> {
+        let path = config_dir().map(|dir| dir.join(TAG_INDEX_FILE_NAME));
+        let mut tags = HashMap::new();
+        if let Some(path) = path.as_deref() {
+            match fs::read_to_string(path) {
+                Ok(contents) => {
+                    for line in contents.lines() {
+                        if let Some((file, tag)) = parse_index_line(line) {
+                            tags.insert(file, tag);
+                        }
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(Self { path, tags })
+    }
+
+    pub(super) fn empty() -> Self {
+        Self {
+            path: None,
+            tags: HashMap::new(),
+        }
+    }
+
+    pub(super) fn get(&self, file: &Path) -> Option<&FileTag> {
+        self.tags.get(file)
+    }
+
+    /// Stores `tag` for `file`, dropping the entry once it is empty.
+    fn set(&mut self, file: &Path, tag: FileTag) -> io::Result<()> {
+        if tag.is_empty() {
+            self.tags.remove(file);
+        } else {
+            self.tags.insert(file.to_path_buf(), tag);
+        }
+        self.save()
+    }
+
+    pub(super) fn remove(&mut self, file: &Path) -> io::Result<()> {
+        if self.tags.remove(file).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Moves the tag of `from` to `to` after the file was renamed or moved.
+    pub(super) fn rename(&mut self, from: &Path, to: &Path) -> io::Result<()> {
+        match self.tags.remove(from) {
+            Some(tag) => {
+                self.tags.insert(to.to_path_buf(), tag);
+                self.save()
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = self.path.as_deref() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no config directory (set HOME or XDG_CONFIG_HOME)",
+            ));
+        };
+        let mut files = self.tags.keys().collect::<Vec<_>>();
+        files.sort();
+        let mut contents = String::new();
+        for file in files {
+            let tag = &self.tags[file];
+            contents.push_str(&format!(
+                "{}\t{}\t{}\n",
+                tag.rating,
+                escape_field(&tag.note),
+                escape_field(&file.to_string_lossy())
+            ));
+        }
+
+        write_state_file(path, &contents)
+    }
+}
+
+impl App {
+    pub fn file_tag(&self, path: &Path) -> Option<&FileTag> {
+        self.tag_index.get(path)
+    }
+
+    pub fn tag_filter(&self) -> TagFilter {
+        self.tag_filter
+    }
+
+    /// Cycles the selected file's rating 0 -> 1 -> ... -> 5 -> 0 stars.
+    pub fn cycle_selected_rating(&mut self) {
+        let Some(entry) = self.selected_tag_target() else {
+            return;
+        };
+
+        let mut tag = self.tag_index.get(&entry.path).cloned().unwrap_or_default();
+        tag.rating = (tag.rating + 1) % (FileTag::MAX_RATING + 1);
+        let rating = tag.rating;
+        match self.tag_index.set(&entry.path, tag) {
+            Ok(()) if rating == 0 => {
+                self.status_message = format!("Cleared rating: {}", entry.name);
+            }
+            Ok(()) => {
+                self.status_message = format!("Rated {}: {}", entry.name, stars(rating));
+            }
+            Err(err) => self.status_message = format!("Failed to save tags: {err}"),
+        }
+        self.apply_tag_filter_to_entries();
+    }
+
+    pub fn cycle_tag_filter(&mut self) {
+        self.tag_filter = self.tag_filter.next();
+        match self.reload() {
+            Ok(()) => {
+                self.status_message = format!("Showing files: {}", self.tag_filter.label());
+            }
+            Err(err) => self.status_message = format!("Failed to refresh browser: {err}"),
+        }
+    }
+
+    pub fn start_note_prompt(&mut self) {
+        let Some(entry) = self.selected_tag_target() else {
+            return;
+        };
+
+        let note = self
+            .tag_index
+            .get(&entry.path)
+            .map(|tag| tag.note.as_str())
+            .unwrap_or_default();
+        let mut input = TextInput::new(note);
+        input.focus_append();
+        self.note_prompt = Some(NotePrompt {
+            name: entry.name,
+            path: entry.path,
+            input,
+        });
+    }
+
+    pub fn has_note_prompt(&self) -> bool {
+        self.note_prompt.is_some()
+    }
+
+    /// File name and note input, for rendering the prompt.
+    pub fn note_prompt(&self) -> Option<(&str, &TextInput)> {
+        self.note_prompt
+            .as_ref()
+            .map(|prompt| (prompt.name.as_str(), &prompt.input))
+    }
+
+    pub fn push_note_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        if let Some(prompt) = self.note_prompt.as_mut() {
+            prompt.input.insert(ch);
+        }
+    }
+
+    pub fn backspace_note(&mut self) {
+        if let Some(prompt) = self.note_prompt.as_mut() {
+            prompt.input.backspace();
+        }
+    }
+
+    pub fn move_note_cursor_left(&mut self) {
+        if let Some(prompt) = self.note_prompt.as_mut() {
+            prompt.input.move_left();
+        }
+    }
+
+    pub fn move_note_cursor_right(&mut self) {
+        if let Some(prompt) = self.note_prompt.as_mut() {
+            prompt.input.move_right();
+        }
+    }
+
+    pub fn cancel_note_prompt(&mut self) {
+        self.note_prompt = None;
+    }
+
+    /// Saves the typed note. An empty note removes it.
+    pub fn confirm_note_prompt(&mut self) {
+        let Some(prompt) = self.note_prompt.take() else {
+            return;
+        };
+
+        let mut tag = self
+            .tag_index
+            .get(&prompt.path)
+            .cloned()
+            .unwrap_or_default();
+        tag.note = prompt.input.value().trim().to_string();
+        let cleared = tag.note.is_empty();
+        match self.tag_index.set(&prompt.path, tag) {
+            Ok(()) if cleared => self.status_message = format!("Cleared note: {}", prompt.name),
+            Ok(()) => self.status_message = format!("Saved note: {}", prompt.name),
+            Err(err) => self.status_message = format!("Failed to save tags: {err}"),
+        }
+        self.apply_tag_filter_to_entries();
+    }
+
+    pub(super) fn filter_entries_by_tag(&self, entries: Vec<FileEntry>) -> Vec<FileEntry> {
+        if self.tag_filter == TagFilter::All {
+            return entries;
+        }
+        entries
+            .into_iter()
+            .filter(|entry| {
+                entry.is_dir || self.tag_filter.matches(self.tag_index.get(&entry.path))
+            })
+            .collect()
+    }
+
+    /// Drops entries that no longer match after a tag edit, keeping the
+    /// selection in range.
+    fn apply_tag_filter_to_entries(&mut self) {
+        let entries = std::mem::take(&mut self.entries);
+        self.entries = self.filter_entries_by_tag(entries);
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    fn selected_tag_target(&mut self) -> Option<FileEntry> {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return None;
+        };
+        if entry.is_dir {
+            self.status_message = "Ratings and notes are only supported for files.".to_string();
+            return None;
+        }
+        Some(entry)
+    }
+}
+
+/// Filled stars for `rating`, e.g. `★★★`.
+pub fn stars(rating: u8) -> String {
+    "★".repeat(usize::from(rating))
+}
+
+fn parse_index_line(line: &str) -> Option<(PathBuf, FileTag)> {
+    let mut fields = line.splitn(3, '\t');
+    let rating = fields.next()?.parse::<u8>().ok()?.min(FileTag::MAX_RATING);
+    let note = unescape_field(fields.next()?);
+    let path = unescape_field(fields.next()?);
+    if path.is_empty() {
+        return None;
+    }
+    Some((PathBuf::from(path), FileTag { rating, note }))
+}
+
+fn escape_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn unescape_field(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
@@ -0,0 +1,60 @@
+// Timeline field for the editor's trim range.
+// - Places the Start and End times on the file's duration.
+// - Left/Right (h/l) move the active marker by a second, H/L by ten, and
+//   Space switches between the markers; the time fields follow.
+use crate::model::{InputField, TimeInput};
+
+use super::App;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineMarker {
+    Start,
+    End,
+}
+
+/// Seconds one Left/Right press moves a marker; H/L move ten times as far.
+const TIMELINE_STEP_SECONDS: f64 = 1.0;
+
+impl App {
+    pub fn timeline_marker(&self) -> TimelineMarker {
+        self.timeline_marker
+    }
+
+    /// First and last timestamp of the file, when ffprobe reported them.
+    pub fn timeline_bounds(&self) -> Option<(f64, f64)> {
+        self.selected_video_bounds
+            .map(|bounds| (bounds.start_seconds, bounds.end_seconds))
+            .filter(|(first, last)| last > first)
+    }
+
+    pub(super) fn switch_timeline_marker(&mut self) {
+        self.timeline_marker = match self.timeline_marker {
+            TimelineMarker::Start => TimelineMarker::End,
+            TimelineMarker::End => TimelineMarker::Start,
+        };
+    }
+
+    /// Moves the active marker by `steps`, keeping the start before the end
+    /// and both inside the file.
+    pub(super) fn nudge_timeline_marker(&mut self, steps: f64) {
+        if !self.editor_input_enabled(InputField::Timeline) {
+            return;
+        }
+        let Some((first, last)) = self.timeline_bounds() else {
+            return;
+        };
+        let delta = steps * TIMELINE_STEP_SECONDS;
+        let start = self.start_time.to_seconds_f64();
+        let end = self.end_time.to_seconds_f64().min(last);
+        match self.timeline_marker {
+            TimelineMarker::Start => {
+                let upper = (end - 0.001).max(first);
+                self.start_time = TimeInput::from_seconds((start + delta).clamp(first, upper));
+            }
+            TimelineMarker::End => {
+                let lower = (start + 0.001).min(last);
+                self.end_time = TimeInput::from_seconds((end + delta).clamp(lower, last));
+            }
+        }
+    }
+}
@@ -4,16 +4,19 @@
 // - Provides helpers for common command/output line formatting.
 use std::cell::Cell;
 
+use crate::model::PageSize;
+
 #[derive(Debug, Clone)]
 pub(crate) struct ToolOutput {
     lines: Vec<String>,
     scroll: Cell<usize>,
     last_max_scroll_top: Cell<usize>,
+    last_visible_line_count: Cell<usize>,
     follow_tail: bool,
+    progress_line_index: Option<usize>,
 }
 
 impl ToolOutput {
-    const PAGE_STEP: usize = 12;
     const MAX_LINES: usize = 20_000;
 
     pub(crate) fn empty() -> Self {
@@ -21,7 +24,9 @@ impl ToolOutput {
             lines: Vec::new(),
             scroll: Cell::new(0),
             last_max_scroll_top: Cell::new(0),
+            last_visible_line_count: Cell::new(1),
             follow_tail: true,
+            progress_line_index: None,
         }
     }
 
@@ -29,12 +34,14 @@ impl ToolOutput {
         self.lines = vec![format!("$ {command_line}"), streaming_message.to_string()];
         self.scroll.set(self.lines.len().saturating_sub(1));
         self.follow_tail = true;
+        self.progress_line_index = None;
     }
 
     pub(crate) fn replace_with_command_error(&mut self, command_line: &str, error_message: &str) {
         self.lines = vec![format!("$ {command_line}"), error_message.to_string()];
         self.scroll.set(0);
         self.follow_tail = true;
+        self.progress_line_index = None;
     }
 
     pub(crate) fn append_prefixed(&mut self, prefix: &str, line: String) {
@@ -49,6 +56,20 @@ impl ToolOutput {
         }
     }
 
+    /// Keeps a single progress line per stream: the first call appends it and
+    /// later calls overwrite it in place, so periodic stats don't flood the log.
+    pub(crate) fn upsert_progress_line(&mut self, line: String) {
+        if let Some(index) = self.progress_line_index
+            && let Some(existing) = self.lines.get_mut(index)
+        {
+            *existing = line;
+            return;
+        }
+
+        self.append_line(line);
+        self.progress_line_index = self.lines.len().checked_sub(1);
+    }
+
     pub(crate) fn scroll_down(&mut self) {
         let max_scroll = self.last_max_scroll_top.get();
         let next = (self.scroll.get() + 1).min(max_scroll);
@@ -63,21 +84,45 @@ impl ToolOutput {
         self.follow_tail = false;
     }
 
-    pub(crate) fn page_down(&mut self) {
+    pub(crate) fn page_down(&mut self, size: PageSize, scale: f64) {
         let max_scroll = self.last_max_scroll_top.get();
-        let next = (self.scroll.get() + Self::PAGE_STEP).min(max_scroll);
+        let next = (self.scroll.get() + self.page_step(size, scale)).min(max_scroll);
         self.scroll.set(next);
         if next >= max_scroll {
             self.follow_tail = true;
         }
     }
 
-    pub(crate) fn page_up(&mut self) {
-        self.scroll
-            .set(self.scroll.get().saturating_sub(Self::PAGE_STEP));
+    pub(crate) fn page_up(&mut self, size: PageSize, scale: f64) {
+        self.scroll.set(
+            self.scroll
+                .get()
+                .saturating_sub(self.page_step(size, scale)),
+        );
         self.follow_tail = false;
     }
 
+    /// Lines per page for the last rendered viewport, keeping one line of
+    /// overlap on full pages like the file browser does.
+    fn page_step(&self, size: PageSize, scale: f64) -> usize {
+        let full_page = self.last_visible_line_count.get().saturating_sub(1).max(1);
+        let base = match size {
+            PageSize::Half => full_page.div_ceil(2),
+            PageSize::Full => full_page,
+        };
+        ((base as f64 * scale).round() as usize).max(1)
+    }
+
+    pub(crate) fn scroll_to_top(&mut self) {
+        self.scroll.set(0);
+        self.follow_tail = false;
+    }
+
+    pub(crate) fn scroll_to_bottom(&mut self) {
+        self.scroll.set(self.last_max_scroll_top.get());
+        self.follow_tail = true;
+    }
+
     pub(crate) fn lines(&self) -> &[String] {
         &self.lines
     }
@@ -90,6 +135,7 @@ impl ToolOutput {
         let visible_line_count = visible_line_count.max(1);
         let max_scroll_top = self.lines.len().saturating_sub(visible_line_count);
         self.last_max_scroll_top.set(max_scroll_top);
+        self.last_visible_line_count.set(visible_line_count);
         let clamped = self.scroll().min(max_scroll_top);
         self.scroll.set(clamped);
         clamped
@@ -103,5 +149,8 @@ impl ToolOutput {
         let overflow = self.lines.len() - Self::MAX_LINES;
         self.lines.drain(0..overflow);
         self.scroll.set(self.scroll.get().saturating_sub(overflow));
+        self.progress_line_index = self
+            .progress_line_index
+            .and_then(|index| index.checked_sub(overflow));
     }
 }
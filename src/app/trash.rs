@@ -0,0 +1,448 @@
+// Deletes that can be taken back.
+// - The browser's delete moves files to the OS trash: the freedesktop trash
+//   on Linux and the BSDs, `~/.Trash` on macOS. Files on another drive go
+//   to that drive's own trash (`.Trash-$uid`, or `.Trashes/$uid` on macOS)
+//   at its top folder. Without one, or when it can't be written, files go
+//   to `rt-trash` in the config dir instead.
+// - Trashing is always a rename. A file that could only reach a trash by
+//   being copied to another drive is left where it is and reported.
+// - `u` puts the last delete back within `UNDO_GRACE`. After that the undo
+//   is dropped, but the files stay in their trash. `rt-trash` is never
+//   purged; deleting a file inside it removes it for good, which is how it
+//   gets emptied.
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use super::{App, config_dir, files::entry_name, quick_jump::home_dir};
+
+/// How long the last delete can be undone.
+pub(super) const UNDO_GRACE: Duration = Duration::from_secs(10 * 60);
+
+const RT_TRASH_DIR_NAME: &str = "rt-trash";
+
+/// Where a delete sends its files.
+#[derive(Clone)]
+enum TrashDir {
+    /// A freedesktop trash with `files/` and `info/` folders.
+    Freedesktop(PathBuf),
+    /// A plain folder: `~/.Trash` or one `rt-trash` batch.
+    Plain(PathBuf),
+}
+
+impl TrashDir {
+    fn root(&self) -> &Path {
+        match self {
+            Self::Freedesktop(dir) | Self::Plain(dir) => dir,
+        }
+    }
+}
+
+/// A file moved to a trash, and where it came from.
+struct TrashedFile {
+    original: PathBuf,
+    trashed: PathBuf,
+    /// `.trashinfo` record that lets the desktop's trash restore it.
+    info: Option<PathBuf>,
+}
+
+/// The most recent delete, kept for undo until the grace period ends.
+pub(super) struct LastDelete {
+    files: Vec<TrashedFile>,
+    at: Instant,
+    /// `rt-trash` batch folder, removed once an undo empties it.
+    rt_batch: Option<PathBuf>,
+}
+
+impl LastDelete {
+    fn expired(&self) -> bool {
+        self.at.elapsed() >= UNDO_GRACE
+    }
+}
+
+/// The user's trash, or `None` on platforms rt doesn't know one for.
+fn os_trash_dir() -> Option<TrashDir> {
+    if cfg!(target_os = "macos") {
+        return home_dir().map(|home| TrashDir::Plain(home.join(".Trash")));
+    }
+    if cfg!(unix) {
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home_dir().map(|home| home.join(".local/share")))?;
+        return Some(TrashDir::Freedesktop(data_home.join("Trash")));
+    }
+    None
+}
+
+/// The trash for `path`: `home`, the user's trash, when it is on the same
+/// drive, otherwise the trash at the top folder of `path`'s drive.
+#[cfg(unix)]
+fn trash_for(path: &Path, home: Option<&TrashDir>) -> Option<TrashDir> {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = fs::symlink_metadata(path).ok()?.dev();
+    // The home trash may not exist yet; its nearest folder tells the drive.
+    let home_device = home.and_then(|trash| {
+        trash
+            .root()
+            .ancestors()
+            .find_map(|dir| fs::metadata(dir).ok())
+            .map(|metadata| metadata.dev())
+    });
+    if home_device == Some(device) {
+        return home.cloned();
+    }
+    let top = path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| fs::metadata(dir).is_ok_and(|metadata| metadata.dev() == device))
+        .last()?;
+    let uid = fs::metadata(home_dir()?).ok()?.uid();
+    Some(if cfg!(target_os = "macos") {
+        TrashDir::Plain(top.join(".Trashes").join(uid.to_string()))
+    } else {
+        TrashDir::Freedesktop(top.join(format!(".Trash-{uid}")))
+    })
+}
+
+#[cfg(not(unix))]
+fn trash_for(_path: &Path, home: Option<&TrashDir>) -> Option<TrashDir> {
+    home.cloned()
+}
+
+/// Creates a trash folder readable only by the user, as the trash spec
+/// asks for the per-drive ones.
+fn create_trash_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+/// Renames `path` to `trashed`. Never copies: a trash on another drive
+/// would mean copying the whole file there first.
+fn rename_into_trash(path: &Path, trashed: &Path) -> io::Result<()> {
+    fs::rename(path, trashed).map_err(|err| {
+        if err.kind() == io::ErrorKind::CrossesDevices {
+            io::Error::new(err.kind(), "the trash is on another drive")
+        } else {
+            err
+        }
+    })
+}
+
+/// Folder deleted files are moved into, when the OS has a trash.
+pub(super) fn os_trash_files_dir() -> Option<PathBuf> {
+    match os_trash_dir()? {
+        TrashDir::Freedesktop(dir) => Some(dir.join("files")),
+        TrashDir::Plain(dir) => Some(dir),
+    }
+}
+
+fn rt_trash_root() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(RT_TRASH_DIR_NAME))
+}
+
+/// Whether `path` is `rt-trash` or inside it, so deleting it empties it.
+pub(super) fn in_rt_trash(path: &Path) -> bool {
+    rt_trash_root().is_some_and(|root| path.starts_with(root))
+}
+
+/// Removes `path` for good, folder or file.
+fn remove_for_good(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// A new `rt-trash` batch folder, named by the delete's time.
+fn new_rt_batch_dir() -> io::Result<PathBuf> {
+    let dir = rt_trash_root()
+        .ok_or_else(|| io::Error::other("no config dir for rt-trash"))?
+        .join(format!(
+            "{}-{}",
+            unix_seconds(SystemTime::now()),
+            process::id()
+        ));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// `name`, then `name (1)`, `name (2)`, ... until `taken` says it's free.
+fn free_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{ext}")),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| format!("{stem} ({n}){ext}"))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+/// Moves `path` into `trash`.
+fn trash_into(path: &Path, trash: &TrashDir) -> io::Result<TrashedFile> {
+    let name = entry_name(path);
+    match trash {
+        TrashDir::Plain(dir) => {
+            create_trash_dir(dir)?;
+            let trashed = dir.join(free_name(&name, |name| dir.join(name).exists()));
+            rename_into_trash(path, &trashed)?;
+            Ok(TrashedFile {
+                original: path.to_path_buf(),
+                trashed,
+                info: None,
+            })
+        }
+        TrashDir::Freedesktop(dir) => {
+            let files = dir.join("files");
+            let infos = dir.join("info");
+            create_trash_dir(&files)?;
+            create_trash_dir(&infos)?;
+            let name = free_name(&name, |name| {
+                files.join(name).exists() || infos.join(format!("{name}.trashinfo")).exists()
+            });
+            let info = infos.join(format!("{name}.trashinfo"));
+            let mut record = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info)?;
+            write!(
+                record,
+                "[Trash Info]\nPath={}\nDeletionDate={}\n",
+                encode_trash_path(path),
+                deletion_date(SystemTime::now())
+            )?;
+            let trashed = files.join(&name);
+            if let Err(err) = rename_into_trash(path, &trashed) {
+                let _ = fs::remove_file(&info);
+                return Err(err);
+            }
+            Ok(TrashedFile {
+                original: path.to_path_buf(),
+                trashed,
+                info: Some(info),
+            })
+        }
+    }
+}
+
+/// Percent-encodes the bytes the trash spec doesn't allow in `Path=`.
+fn encode_trash_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"/-_.~!$&'()*+,;=:@".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// `YYYY-MM-DDThh:mm:ss` in UTC for `DeletionDate=`.
+fn deletion_date(time: SystemTime) -> String {
+    let seconds = unix_seconds(time);
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        rest / 3_600,
+        rest % 3_600 / 60,
+        rest % 60
+    )
+}
+
+impl App {
+    /// Moves `paths` to the trash, replacing the previous undo. Paths inside
+    /// `rt-trash` are removed for good instead. Returns the paths that were
+    /// trashed or removed and the failures as `name: error`.
+    pub(super) fn trash_paths(&mut self, paths: &[PathBuf]) -> (Vec<PathBuf>, Vec<String>) {
+        self.expire_last_delete(true);
+        let os_trash = os_trash_dir();
+        let mut rt_batch: Option<PathBuf> = None;
+        let mut files = Vec::new();
+        let mut trashed = Vec::new();
+        let mut failures = Vec::new();
+        for path in paths {
+            if in_rt_trash(path) {
+                match remove_for_good(path) {
+                    Ok(()) => {
+                        let _ = self.tag_index.remove(path);
+                        trashed.push(path.clone());
+                    }
+                    Err(err) => failures.push(format!("{}: {err}", entry_name(path))),
+                }
+                continue;
+            }
+            let mut result = match trash_for(path, os_trash.as_ref()) {
+                Some(trash) => trash_into(path, &trash),
+                None => Err(io::Error::other("no OS trash")),
+            };
+            if result.is_err() {
+                result = match rt_batch.clone() {
+                    Some(dir) => Ok(dir),
+                    None => new_rt_batch_dir().inspect(|dir| rt_batch = Some(dir.clone())),
+                }
+                .and_then(|dir| trash_into(path, &TrashDir::Plain(dir)));
+            }
+            match result {
+                Ok(file) => {
+                    // A failed index write only loses a stale tag; keep going.
+                    let _ = self.tag_index.rename(path, &file.trashed);
+                    trashed.push(path.clone());
+                    files.push(file);
+                }
+                Err(err) => failures.push(format!("{}: {err}", entry_name(path))),
+            }
+        }
+        if !files.is_empty() {
+            self.last_delete = Some(LastDelete {
+                files,
+                at: Instant::now(),
+                rt_batch,
+            });
+        }
+        (trashed, failures)
+    }
+
+    /// Puts the files of the last delete back where they were.
+    pub fn undo_last_delete(&mut self) {
+        self.expire_last_delete(false);
+        let Some(last) = self.last_delete.take() else {
+            self.status_message = format!(
+                "Nothing to undo. Deletes can be undone for {} minutes.",
+                UNDO_GRACE.as_secs() / 60
+            );
+            return;
+        };
+
+        let mut restored = Vec::new();
+        let mut failures = Vec::new();
+        let mut kept = Vec::new();
+        for file in last.files {
+            let result = if file.original.exists() {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "a file now exists there",
+                ))
+            } else {
+                // The trash is on the file's own drive, so this is a rename.
+                fs::rename(&file.trashed, &file.original)
+            };
+            match result {
+                Ok(()) => {
+                    if let Some(info) = file.info.as_ref() {
+                        let _ = fs::remove_file(info);
+                    }
+                    let _ = self.tag_index.rename(&file.trashed, &file.original);
+                    restored.push(file.original);
+                }
+                Err(err) => {
+                    failures.push(format!("{}: {err}", entry_name(&file.original)));
+                    kept.push(file);
+                }
+            }
+        }
+        // Files that couldn't go back stay undoable until the grace ends.
+        if !kept.is_empty() {
+            self.last_delete = Some(LastDelete {
+                files: kept,
+                at: last.at,
+                rt_batch: last.rt_batch,
+            });
+        } else if let Some(dir) = last.rt_batch {
+            let _ = fs::remove_dir(dir);
+        }
+
+        let summary = match (restored.len(), failures.first()) {
+            (1, None) => format!("Restored {}.", entry_name(&restored[0])),
+            (count, None) => format!("Restored {count} files."),
+            (0, Some(_)) => format!("Failed to restore {}", failures.join("; ")),
+            (count, Some(first)) => format!(
+                "Restored {count} files; {} failed (first: {first}).",
+                failures.len()
+            ),
+        };
+        let Some(first) = restored.first().cloned() else {
+            self.status_message = summary;
+            return;
+        };
+        if let Err(err) = self.reload() {
+            self.status_message = format!("{summary} Failed to refresh browser: {err}");
+            return;
+        }
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == first) {
+            self.selected = index;
+        }
+        self.status_message = summary;
+    }
+
+    /// Drops the undo once its grace period is over, or right away with
+    /// `force`. The files stay in the trash.
+    pub(super) fn expire_last_delete(&mut self, force: bool) {
+        if !self
+            .last_delete
+            .as_ref()
+            .is_some_and(|last| force || last.expired())
+        {
+            return;
+        }
+        let Some(last) = self.last_delete.take() else {
+            return;
+        };
+        for file in &last.files {
+            let _ = self.tag_index.remove(&file.trashed);
+        }
+    }
+
+    /// Status line note for a last delete that fell back to `rt-trash`.
+    pub(super) fn rt_trash_note(&self) -> Option<String> {
+        let batch = self.last_delete.as_ref()?.rt_batch.as_ref()?;
+        Some(format!(
+            "The trash couldn't take them, so they went to {}, which keeps them until you delete them there",
+            batch.parent().unwrap_or(batch).display()
+        ))
+    }
+
+    /// `summary` with the `rt-trash` note appended when the delete used it.
+    pub(super) fn rt_trash_summary(&self, summary: String) -> String {
+        match self.rt_trash_note() {
+            Some(note) => format!("{summary} {note}."),
+            None => summary,
+        }
+    }
+
+    /// Whether the pending delete removes files from `rt-trash` for good.
+    pub fn pending_delete_empties_rt_trash(&self) -> bool {
+        self.pending_delete_target()
+            .is_some_and(|(_, paths)| paths.iter().any(|path| in_rt_trash(path)))
+    }
+}
@@ -0,0 +1,104 @@
+// Type-ahead find for the file browser.
+// - `'` starts it; typed letters build a prefix and the selection jumps to
+//   the next entry whose name starts with it (case-insensitive).
+// - Tab moves to the following match, Backspace shortens the prefix, and
+//   the find ends after a short pause or on any other key.
+use std::time::{Duration, Instant};
+
+use super::App;
+
+/// How long the find stays open after the last key.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+pub(super) struct TypeAhead {
+    prefix: String,
+    /// Selection when the find started, so a shorter prefix searches from
+    /// the same place again.
+    origin: usize,
+    last_key: Instant,
+}
+
+impl App {
+    pub fn start_type_ahead(&mut self) {
+        self.type_ahead = Some(TypeAhead {
+            prefix: String::new(),
+            origin: self.selected,
+            last_key: Instant::now(),
+        });
+    }
+
+    /// The typed prefix while the find is open.
+    pub fn type_ahead_prefix(&self) -> Option<&str> {
+        self.type_ahead.as_ref().map(|find| find.prefix.as_str())
+    }
+
+    pub fn end_type_ahead(&mut self) {
+        self.type_ahead = None;
+    }
+
+    pub fn push_type_ahead_char(&mut self, ch: char) {
+        if ch.is_control() {
+            return;
+        }
+        let Some(find) = self.type_ahead.as_mut() else {
+            return;
+        };
+        find.prefix.push(ch);
+        find.last_key = Instant::now();
+        let origin = find.origin;
+        self.select_type_ahead_match(origin);
+    }
+
+    pub fn backspace_type_ahead(&mut self) {
+        let Some(find) = self.type_ahead.as_mut() else {
+            return;
+        };
+        find.prefix.pop();
+        find.last_key = Instant::now();
+        let origin = find.origin;
+        if find.prefix.is_empty() {
+            self.selected = origin;
+        } else {
+            self.select_type_ahead_match(origin);
+        }
+    }
+
+    /// Tab: the next entry after the selection with the same prefix.
+    pub fn next_type_ahead_match(&mut self) {
+        let Some(find) = self.type_ahead.as_mut() else {
+            return;
+        };
+        find.last_key = Instant::now();
+        let from = self.selected + 1;
+        self.select_type_ahead_match(from);
+    }
+
+    /// Selects the first match at or after `from`, wrapping around. With no
+    /// match the selection stays and the status line says so.
+    fn select_type_ahead_match(&mut self, from: usize) {
+        let Some(prefix) = self.type_ahead_prefix().map(str::to_lowercase) else {
+            return;
+        };
+        let count = self.entries.len();
+        let found = (0..count)
+            .map(|offset| (from + offset) % count)
+            .find(|&index| self.entries[index].name.to_lowercase().starts_with(&prefix));
+        match found {
+            Some(index) => self.selected = index,
+            None => self.status_message = format!("No entry starts with \"{prefix}\""),
+        }
+    }
+
+    /// Closes the find once it has been idle; returns whether it closed.
+    pub(super) fn expire_type_ahead(&mut self) -> bool {
+        if self
+            .type_ahead
+            .as_ref()
+            .is_some_and(|find| find.last_key.elapsed() >= TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead = None;
+            return true;
+        }
+        false
+    }
+}
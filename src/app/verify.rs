@@ -0,0 +1,122 @@
+// Integrity scan for the file browser.
+// - Decodes the selected file to the null muxer in the background.
+// - Reports any decode errors ffmpeg printed once the run finishes.
+use std::{path::Path, process::ExitStatus};
+
+use crate::media::{is_editable_media_file, probe_video_times};
+
+use super::{
+    App, FfmpegJob, FfmpegJobKind,
+    ffmpeg::{ffmpeg_command_line, is_ffmpeg_progress_line},
+};
+
+impl App {
+    /// Decodes every stream of the selected media file with
+    /// `ffmpeg -v error -f null -` to confirm it is intact.
+    pub fn verify_selected_file(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            self.status_message = "No entry selected.".to_string();
+            return;
+        };
+        if entry.is_dir || !is_editable_media_file(&entry.path) {
+            self.status_message = format!("Verify needs a media file: {}", entry.name);
+            return;
+        }
+        if self.running_editor.is_some() {
+            self.status_message = "ffmpeg is already running. Wait for it to finish.".to_string();
+            return;
+        }
+        if !self.ffmpeg_available() {
+            self.status_message =
+                "ffmpeg was not found in PATH. Install ffmpeg to verify files.".to_string();
+            return;
+        }
+
+        // The duration only drives the progress gauge, so a failed probe
+        // still runs the scan; ffmpeg will report why the file is unreadable.
+        let duration_seconds = probe_video_times(&entry.path)
+            .map(|(_, _, bounds)| (bounds.end_seconds - bounds.start_seconds).max(0.0))
+            .unwrap_or(0.0);
+
+        let args = verify_args(&entry.path);
+        let job = FfmpegJob {
+            kind: FfmpegJobKind::Verify,
+            command_line: ffmpeg_command_line(&args),
+            input_path: entry.path.clone(),
+            args,
+            output_path: entry.path,
+            progress_only: self.ffmpeg_progress_only,
+            duration_seconds,
+            follow_up: None,
+            file_time: None,
+        };
+        self.launch_editor_export(job, " (integrity check)");
+    }
+
+    /// Reports the outcome of a finished verify run. Nothing is written, so
+    /// unlike other jobs there is no output to select or post-process.
+    pub(super) fn finish_verify(
+        &mut self,
+        status: ExitStatus,
+        command_line: &str,
+        input_path: &Path,
+        stdout_raw: &[u8],
+        stderr_raw: &[u8],
+    ) {
+        let name = input_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| input_path.display().to_string());
+        let stderr = String::from_utf8_lossy(stderr_raw);
+        let errors = decode_error_lines(&stderr);
+
+        let mut status_message = match (status.success(), errors.first()) {
+            (true, None) => format!("Verified {name}: no decode errors."),
+            (true, Some(first)) => format!(
+                "Verified {name}: {} decode error{} (first: {first})",
+                errors.len(),
+                if errors.len() == 1 { "" } else { "s" }
+            ),
+            (false, Some(first)) => format!("Verify failed for {name}: {first}"),
+            (false, None) => format!("Verify failed for {name}: ffmpeg exited with an error."),
+        };
+
+        match self.append_ffmpeg_run_log(
+            command_line,
+            status.code(),
+            stdout_raw,
+            stderr_raw,
+            None,
+            Some(input_path),
+        ) {
+            Ok(log_path) => status_message.push_str(&format!(" (log: {})", log_path.display())),
+            Err(log_err) => status_message.push_str(&format!(" (log write failed: {log_err})")),
+        }
+        self.status_message = status_message;
+    }
+}
+
+fn verify_args(input_path: &Path) -> Vec<String> {
+    vec![
+        "-hide_banner".to_string(),
+        "-v".to_string(),
+        "error".to_string(),
+        "-stats".to_string(),
+        "-i".to_string(),
+        input_path.display().to_string(),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// With `-v error` everything ffmpeg prints besides the stats line is an
+/// error, so each remaining line counts as one decode error.
+fn decode_error_lines(stderr: &str) -> Vec<&str> {
+    stderr
+        .split(['\n', '\r'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| !is_ffmpeg_progress_line(line))
+        .collect()
+}
@@ -0,0 +1,64 @@
+// Browser visibility toggles for cluttered folders.
+// - Hides or shows dotfiles (names starting with `.`).
+// - Narrows the listing to audio and video files; folders stay so the
+//   browser can still be navigated.
+// - Both apply before the tag filter, to directories and `--stdin` lists.
+use crate::{media::is_editable_media_file, model::FileEntry};
+
+use super::App;
+
+impl App {
+    pub fn show_hidden_files(&self) -> bool {
+        self.show_hidden_files
+    }
+
+    pub fn media_only(&self) -> bool {
+        self.media_only
+    }
+
+    pub fn toggle_hidden_files(&mut self) {
+        self.show_hidden_files = !self.show_hidden_files;
+        let label = if self.show_hidden_files {
+            "Showing dotfiles"
+        } else {
+            "Hiding dotfiles"
+        };
+        self.reload_keeping_selection(label);
+    }
+
+    pub fn toggle_media_only(&mut self) {
+        self.media_only = !self.media_only;
+        let label = if self.media_only {
+            "Showing folders and media files only"
+        } else {
+            "Showing all files"
+        };
+        self.reload_keeping_selection(label);
+    }
+
+    /// Applies the visibility toggles and then the tag filter.
+    pub(super) fn filter_listing(&self, entries: Vec<FileEntry>) -> Vec<FileEntry> {
+        let entries = entries
+            .into_iter()
+            .filter(|entry| self.show_hidden_files || !entry.name.starts_with('.'))
+            .filter(|entry| !self.media_only || entry.is_dir || is_editable_media_file(&entry.path))
+            .collect();
+        self.filter_entries_by_tag(entries)
+    }
+
+    /// Re-reads the listing after a toggle and keeps the highlighted entry
+    /// when it is still shown.
+    fn reload_keeping_selection(&mut self, label: &str) {
+        let previous = self.selected_entry().map(|entry| entry.path.clone());
+        if let Err(err) = self.reload() {
+            self.status_message = format!("Failed to refresh browser: {err}");
+            return;
+        }
+        if let Some(index) =
+            previous.and_then(|path| self.entries.iter().position(|entry| entry.path == path))
+        {
+            self.selected = index;
+        }
+        self.status_message = format!("{label} ({} entries).", self.entries.len());
+    }
+}
@@ -0,0 +1,232 @@
+// Key bindings for the file browser.
+// - Maps key presses to browser `Action`s through a lookup table.
+// - `[keys]` in `config.toml` rebinds an action, e.g. `delete = "D"` or
+//   `down = "Down"` for arrow-only navigation.
+use crossterm::event::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    First,
+    /// First entry, then a quick-jump letter may follow.
+    Go,
+    Last,
+    Newest,
+    Largest,
+    Open,
+    Parent,
+    InitialDir,
+    Breadcrumb,
+    Bookmark,
+    Bookmarks,
+    PathJump,
+    Find,
+    Filter,
+    Delete,
+    UndoDelete,
+    Rename,
+    Move,
+    Copy,
+    NewDir,
+    OpenExternal,
+    OpenLastOutput,
+    Rate,
+    Note,
+    TagFilter,
+    ToggleHidden,
+    MediaOnly,
+    Mark,
+    ClearMarks,
+    Playlist,
+    ContactSheet,
+    Verify,
+    Remux,
+    AddToConcat,
+    Batch,
+    Cleanup,
+    Stats,
+    Health,
+    ExportConfig,
+    Changelog,
+    Reload,
+}
+
+/// Config name and default keys for every action, in keybinds-popup order.
+const DEFAULT_BINDINGS: [(Action, &str, &str); 46] = [
+    (Action::Quit, "quit", "q"),
+    (Action::Down, "down", "Down j"),
+    (Action::Up, "up", "Up k"),
+    (Action::PageDown, "page_down", "PageDown"),
+    (Action::PageUp, "page_up", "PageUp"),
+    (Action::First, "first", "Home"),
+    (Action::Go, "go", "g"),
+    (Action::Last, "last", "End G"),
+    (Action::Newest, "newest", "N"),
+    (Action::Largest, "largest", "L"),
+    (Action::Open, "open", "Enter"),
+    (Action::Parent, "parent", "h -"),
+    (Action::InitialDir, "initial_dir", "_"),
+    (Action::Breadcrumb, "breadcrumb", "b"),
+    (Action::Bookmark, "bookmark", "F"),
+    (Action::Bookmarks, "bookmarks", "`"),
+    (Action::PathJump, "path_jump", ":"),
+    (Action::Find, "find", "'"),
+    (Action::Filter, "filter", "/"),
+    (Action::Delete, "delete", "d"),
+    (Action::UndoDelete, "undo_delete", "u"),
+    (Action::Rename, "rename", "e"),
+    (Action::Move, "move", "m"),
+    (Action::Copy, "copy", "y"),
+    (Action::NewDir, "mkdir", "+"),
+    (Action::OpenExternal, "open_external", "x"),
+    (Action::OpenLastOutput, "open_last_output", "o"),
+    (Action::Rate, "rate", "*"),
+    (Action::Note, "note", "n"),
+    (Action::TagFilter, "tag_filter", "f"),
+    (Action::ToggleHidden, "hidden", "."),
+    (Action::MediaOnly, "media_only", "M"),
+    (Action::Mark, "mark", "Space"),
+    (Action::ClearMarks, "clear_marks", "U"),
+    (Action::Playlist, "playlist", "p"),
+    (Action::ContactSheet, "contact_sheet", "c"),
+    (Action::Verify, "verify", "v"),
+    (Action::Remux, "remux", "R"),
+    (Action::AddToConcat, "add_to_concat", "a"),
+    (Action::Batch, "batch", "B"),
+    (Action::Cleanup, "cleanup", "C"),
+    (Action::Stats, "stats", "S"),
+    (Action::Health, "health", "H"),
+    (Action::ExportConfig, "export_config", "W"),
+    (Action::Changelog, "changelog", "V"),
+    (Action::Reload, "reload", "r"),
+];
+
+/// Named keys accepted in `[keys]`; anything else must be one character.
+const NAMED_KEYS: [(&str, KeyCode); 12] = [
+    ("Space", KeyCode::Char(' ')),
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+pub struct Keymap {
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+fn parse_key(name: &str) -> Result<KeyCode, String> {
+    if let Some((_, code)) = NAMED_KEYS
+        .iter()
+        .find(|(named, _)| named.eq_ignore_ascii_case(name))
+    {
+        return Ok(*code);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if !ch.is_ascii_digit() => Ok(KeyCode::Char(ch)),
+        (Some(ch), None) => Err(format!("`{ch}` is taken by the count prefix")),
+        _ => Err(format!("unknown key `{name}`")),
+    }
+}
+
+fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(ch) if ch != ' ' => ch.to_string(),
+        _ => NAMED_KEYS
+            .iter()
+            .find(|(_, named)| *named == code)
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| format!("{code:?}")),
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .flat_map(|(action, _, keys)| {
+                keys.split_whitespace()
+                    .filter_map(|key| parse_key(key).ok())
+                    .map(|code| (code, *action))
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Applies `action = "key key ..."` overrides on top of the defaults.
+    /// Each override replaces the action's keys, takes those keys from any
+    /// other action, and `""` unbinds the action.
+    pub fn with_overrides<'a>(
+        overrides: impl IntoIterator<Item = (&'a str, String)>,
+    ) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        for (name, keys) in overrides {
+            let action = DEFAULT_BINDINGS
+                .iter()
+                .find(|(_, config_name, _)| *config_name == name)
+                .map(|(action, _, _)| *action)
+                .ok_or_else(|| format!("unknown action `{name}`"))?;
+            let codes = keys
+                .split_whitespace()
+                .map(parse_key)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| format!("{name}: {err}"))?;
+            keymap
+                .bindings
+                .retain(|(code, bound)| *bound != action && !codes.contains(code));
+            keymap
+                .bindings
+                .extend(codes.into_iter().map(|code| (code, action)));
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == code)
+            .map(|(_, action)| *action)
+    }
+
+    /// Every action's config name and keys as `[keys]` would give them,
+    /// e.g. `("parent", "h -")`. Unbound actions get `""`.
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, String)> + '_ {
+        DEFAULT_BINDINGS.iter().map(|(action, name, _)| {
+            let keys = self
+                .bindings
+                .iter()
+                .filter(|(_, bound)| bound == action)
+                .map(|(code, _)| key_name(*code))
+                .collect::<Vec<_>>();
+            (*name, keys.join(" "))
+        })
+    }
+
+    /// The action's keys for the keybinds popup, e.g. `h/-`.
+    pub fn keys_label(&self, action: Action) -> String {
+        let keys = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(code, _)| key_name(*code))
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            "(unbound)".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+}
@@ -1,53 +1,203 @@
 // Application entrypoint.
 // - Parses CLI startup arguments.
+// - Mirrors job progress into the terminal window title (opt out with --no-title).
 // - Owns the crossterm event loop and maps key events to App actions.
+// - Captures the mouse: clicks focus panes and pick files or tabs, and the
+//   wheel scrolls whatever is under the pointer.
 // - Delegates all drawing to the UI layer each frame.
 mod app;
+mod keymap;
 mod media;
 mod model;
+mod theme;
 mod ui;
 
-use std::{env, io, path::PathBuf, time::Duration};
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::Duration,
+};
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    execute,
+    terminal::SetTitle,
+};
 
 use app::App;
-use model::{Focus, InputField, RightTab};
+use keymap::Action;
+use model::{AfterExport, Focus, InputField, PageSize, PerfSettings, RightTab};
+
+/// The tab a lone digit switched to at once. A motion right after it
+/// makes the digit a count and brings the previous tab back.
+struct TentativeTab {
+    previous: RightTab,
+    /// Where focus goes once the digit stands as the tab shortcut.
+    focus: Focus,
+}
+
+struct CliArgs {
+    start_dir: Option<PathBuf>,
+    window_title: bool,
+    // Flags left out are `None` and keep the config.toml value.
+    log_page_scale: Option<f64>,
+    confirm_minutes: Option<f64>,
+    confirm_megabytes: Option<u64>,
+    after_export: Option<AfterExport>,
+    job_logs: bool,
+    /// Open the diagnostics report on startup.
+    health: bool,
+    screen_reader: bool,
+    /// Replaces the `[perf]` settings as a whole.
+    perf_profile: Option<PerfSettings>,
+    poll_ms: Option<u64>,
+    spinner_ms: Option<u64>,
+    pump_batch: Option<usize>,
+    /// Newline-separated paths read from stdin for `--stdin`.
+    stdin_paths: Option<Vec<PathBuf>>,
+}
 
 fn main() -> io::Result<()> {
-    let start_dir = parse_start_dir_arg()?;
+    let args = parse_cli_args()?;
+    let window_title = args.window_title;
     let mut terminal = ratatui::init();
-    let result = run(&mut terminal, start_dir);
+    // Without mouse capture the terminal still selects text as usual.
+    let _ = execute!(io::stdout(), EnableMouseCapture);
+    // Pastes arrive as one event, so a pasted timestamp fills the field.
+    let _ = execute!(io::stdout(), EnableBracketedPaste);
+    if window_title {
+        push_window_title();
+    }
+    let result = run(&mut terminal, args);
+    if window_title {
+        pop_window_title();
+    }
+    let _ = execute!(io::stdout(), DisableBracketedPaste);
+    let _ = execute!(io::stdout(), DisableMouseCapture);
     ratatui::restore();
     result
 }
 
-fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> io::Result<()> {
-    let mut app = App::new(start_dir)?;
+fn run(terminal: &mut ratatui::DefaultTerminal, args: CliArgs) -> io::Result<()> {
+    let mut app = App::new(args.start_dir)?;
+    if let Some(scale) = args.log_page_scale {
+        app.set_log_page_scale(scale);
+    }
+    app.set_export_confirm_thresholds(args.confirm_minutes, args.confirm_megabytes);
+    if let Some(after_export) = args.after_export {
+        app.set_after_export(after_export);
+    }
+    app.set_job_logs(args.job_logs);
+    if args.screen_reader {
+        app.set_screen_reader(true);
+    }
+    if args.health && app.health_check().is_none() {
+        app.open_health_check();
+    }
+    // Individual settings override the profile wherever they appear.
+    let perf = args
+        .perf_profile
+        .unwrap_or_else(|| app.perf_settings())
+        .with_overrides(args.poll_ms, args.spinner_ms, args.pump_batch);
+    app.set_perf_settings(perf);
+    if let Some(paths) = args.stdin_paths {
+        app.set_stdin_listing(paths);
+    }
     let mut focus = Focus::Left;
+    let mut last_window_title = String::new();
+    // Only input and background work change the screen, so an idle loop
+    // skips drawing frames identical to the last one.
+    let mut needs_redraw = true;
+    let mut layout = ui::PaneLayout::default();
+    let mut tentative_tab: Option<TentativeTab> = None;
 
     loop {
         app.normalize_focus(&mut focus);
-        app.tick();
-        terminal.draw(|frame| ui::render(frame, &app, focus))?;
+        if tentative_tab.is_none() {
+            app.remember_tab_focus(focus);
+        }
+        if app.tick() {
+            needs_redraw = true;
+        }
+        // A lone digit that no motion follows stays the tab shortcut.
+        if app
+            .count_prefix
+            .take_expired(COUNT_PREFIX_TIMEOUT)
+            .is_some()
+        {
+            needs_redraw = true;
+            if let Some(tab) = tentative_tab.take() {
+                focus = tab.focus;
+            }
+        }
+        if needs_redraw {
+            terminal.draw(|frame| layout = ui::render(frame, &app, focus))?;
+            needs_redraw = false;
+        }
+        if args.window_title {
+            let title = app.window_title();
+            if title != last_window_title {
+                execute!(io::stdout(), SetTitle(&title))?;
+                last_window_title = title;
+            }
+        }
 
-        if event::poll(Duration::from_millis(100))? {
+        if event::poll(app.poll_interval())? {
             let event = event::read()?;
+            needs_redraw = true;
             if let Event::Paste(text) = event {
                 handle_paste_event(&mut app, focus, &text);
                 continue;
             }
+            if let Event::Mouse(mouse) = event {
+                handle_mouse_event(&mut app, &mut focus, &layout, mouse)?;
+                continue;
+            }
 
             if let Event::Key(key) = event
                 && key.kind == KeyEventKind::Press
             {
                 if key.code == KeyCode::Esc {
+                    app.count_prefix.clear();
+                    if let Some(tab) = tentative_tab.take() {
+                        app.restore_right_tab(tab.previous);
+                    }
+                    if focus != Focus::Left
+                        && app.right_tab() == RightTab::Downloader
+                        && !overlay_open(&app)
+                    {
+                        app.cancel_downloader_probe();
+                    }
+                    app.cancel_pending_export();
+                    app.cancel_held_export();
                     if app.has_pending_cancel() {
                         app.cancel_pending_cancel();
                     }
                     if app.has_pending_delete() {
                         app.cancel_pending_delete();
                     }
+                    app.cancel_note_prompt();
+                    app.cancel_stream_prompt();
+                    app.cancel_path_jump();
+                    app.cancel_file_op_prompt();
+                    app.cancel_batch_prompt();
+                    app.cancel_batch_review();
+                    app.cancel_cleanup_prompt();
+                    app.cancel_cleanup_review();
+                    app.cancel_preset_picker();
+                    app.cancel_bookmark_picker();
+                    app.close_job_stats();
+                    app.close_health_check();
+                    app.close_changelog();
+                    app.cancel_breadcrumb();
+                    app.end_type_ahead();
+                    app.cancel_fuzzy_filter();
+                    app.cancel_time_offset_entry();
                     if app.show_keybinds {
                         app.hide_keybinds();
                     }
@@ -74,6 +224,25 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                     continue;
                 }
 
+                if app.has_pending_export() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('c')
+                    {
+                        break Ok(());
+                    }
+
+                    match key.code {
+                        KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            app.confirm_pending_export()
+                        }
+                        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                            app.cancel_pending_export()
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
                 if app.has_pending_delete() {
                     if key.modifiers.contains(KeyModifiers::CONTROL)
                         && key.code == KeyCode::Char('c')
@@ -81,6 +250,23 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                         break Ok(());
                     }
 
+                    // A folder needs its name typed, so letters go to the input.
+                    if app.pending_dir_delete().is_some() {
+                        if key.modifiers.contains(KeyModifiers::CONTROL) {
+                            continue;
+                        }
+                        match key.code {
+                            KeyCode::Enter => app.confirm_pending_delete(),
+                            KeyCode::Esc => app.cancel_pending_delete(),
+                            KeyCode::Backspace => app.backspace_dir_delete(),
+                            KeyCode::Left => app.move_dir_delete_cursor_left(),
+                            KeyCode::Right => app.move_dir_delete_cursor_right(),
+                            KeyCode::Char(ch) => app.push_dir_delete_char(ch),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
                     match key.code {
                         KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
                             app.confirm_pending_delete()
@@ -93,6 +279,355 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                     continue;
                 }
 
+                if app.has_note_prompt() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_note_prompt(),
+                        KeyCode::Backspace => app.backspace_note(),
+                        KeyCode::Left => app.move_note_cursor_left(),
+                        KeyCode::Right => app.move_note_cursor_right(),
+                        KeyCode::Char(ch) => app.push_note_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_file_op_prompt() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    if app.file_op_confirming_overwrite() {
+                        match key.code {
+                            KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                app.confirm_file_op_prompt()
+                            }
+                            KeyCode::Char('n') | KeyCode::Char('N') => {
+                                app.decline_file_op_overwrite()
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_file_op_prompt(),
+                        KeyCode::Backspace => app.backspace_file_op(),
+                        KeyCode::Left => app.move_file_op_cursor_left(),
+                        KeyCode::Right => app.move_file_op_cursor_right(),
+                        KeyCode::Char(ch) => app.push_file_op_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_stream_prompt() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => {
+                            // The editor gets focus for the stream once the
+                            // probe is in and the prompt closes.
+                            app.confirm_stream_prompt();
+                            if app.stream_prompt_probing() {
+                                focus = Focus::RightTop;
+                            }
+                        }
+                        KeyCode::Backspace => app.backspace_stream_prompt(),
+                        KeyCode::Left => app.move_stream_prompt_cursor_left(),
+                        KeyCode::Right => app.move_stream_prompt_cursor_right(),
+                        KeyCode::Char(ch) => app.push_stream_prompt_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_path_jump() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_path_jump(),
+                        KeyCode::Tab => app.complete_path_jump(),
+                        KeyCode::Backspace => app.backspace_path_jump(),
+                        KeyCode::Left => app.move_path_jump_cursor_left(),
+                        KeyCode::Right => app.move_path_jump_cursor_right(),
+                        KeyCode::Char(ch) => app.push_path_jump_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_batch_prompt() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_batch_prompt(),
+                        KeyCode::Tab => app.toggle_batch_prompt_skip_existing(),
+                        KeyCode::Backspace => app.backspace_batch_prompt(),
+                        KeyCode::Left => app.move_batch_prompt_cursor_left(),
+                        KeyCode::Right => app.move_batch_prompt_cursor_right(),
+                        KeyCode::Char(ch) => app.push_batch_prompt_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_batch_review() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_batch_review(),
+                        KeyCode::Down | KeyCode::Char('j') => app.select_next_batch_review_item(),
+                        KeyCode::Up | KeyCode::Char('k') => app.select_previous_batch_review_item(),
+                        KeyCode::Char(' ') => app.toggle_batch_review_item(),
+                        KeyCode::Char('a') => app.toggle_all_batch_review_items(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_cleanup_prompt() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_cleanup_prompt(),
+                        KeyCode::Backspace => app.backspace_cleanup_prompt(),
+                        KeyCode::Left => app.move_cleanup_prompt_cursor_left(),
+                        KeyCode::Right => app.move_cleanup_prompt_cursor_right(),
+                        KeyCode::Char(ch) => app.push_cleanup_prompt_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_cleanup_review() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_cleanup_review(),
+                        KeyCode::Down | KeyCode::Char('j') => app.select_next_cleanup_review_item(),
+                        KeyCode::Up | KeyCode::Char('k') => {
+                            app.select_previous_cleanup_review_item()
+                        }
+                        KeyCode::Char(' ') => app.toggle_cleanup_review_item(),
+                        KeyCode::Char('a') => app.toggle_all_cleanup_review_items(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.health_check().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter | KeyCode::Char('q' | 'H') => app.close_health_check(),
+                        KeyCode::Down | KeyCode::Char('j') => app.scroll_health_check(true),
+                        KeyCode::Up | KeyCode::Char('k') => app.scroll_health_check(false),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.changelog().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter | KeyCode::Char('q') => app.close_changelog(),
+                        KeyCode::Char('V') => app.open_changelog(),
+                        KeyCode::Down | KeyCode::Char('j') => app.scroll_changelog(true),
+                        KeyCode::Up | KeyCode::Char('k') => app.scroll_changelog(false),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.job_stats().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    if matches!(key.code, KeyCode::Enter | KeyCode::Char('q' | 'S')) {
+                        app.close_job_stats();
+                    }
+                    continue;
+                }
+
+                if app.breadcrumb_levels_up().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Left | KeyCode::Char('h') => app.breadcrumb_up(),
+                        KeyCode::Right | KeyCode::Char('l') => app.breadcrumb_down(),
+                        KeyCode::Enter => app.confirm_breadcrumb()?,
+                        KeyCode::Char('b' | 'q') => app.cancel_breadcrumb(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if focus == Focus::Left && app.fuzzy_filter().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Enter => app.confirm_fuzzy_filter(),
+                        KeyCode::Down => app.select_next_fuzzy_match(),
+                        KeyCode::Up => app.select_previous_fuzzy_match(),
+                        KeyCode::Backspace => app.backspace_fuzzy_filter(),
+                        KeyCode::Char(ch) => app.push_fuzzy_filter_char(ch),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Type-ahead keeps letters, Tab, and Backspace; any other key
+                // ends it and is then handled as usual.
+                if focus == Focus::Left && app.type_ahead_prefix().is_some() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        app.end_type_ahead();
+                    } else {
+                        match key.code {
+                            KeyCode::Char(ch) => {
+                                app.push_type_ahead_char(ch);
+                                continue;
+                            }
+                            KeyCode::Backspace => {
+                                app.backspace_type_ahead();
+                                continue;
+                            }
+                            KeyCode::Tab => {
+                                app.next_type_ahead_match();
+                                continue;
+                            }
+                            _ => app.end_type_ahead(),
+                        }
+                    }
+                }
+
+                if app.has_preset_picker() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    if app.preset_naming() {
+                        match key.code {
+                            KeyCode::Enter => app.confirm_preset_name(),
+                            KeyCode::Backspace => app.backspace_preset_name(),
+                            KeyCode::Left => app.move_preset_name_cursor_left(),
+                            KeyCode::Right => app.move_preset_name_cursor_right(),
+                            KeyCode::Char(ch) => app.push_preset_name_char(ch),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Down | KeyCode::Char('j') => app.select_next_preset(),
+                        KeyCode::Up | KeyCode::Char('k') => app.select_previous_preset(),
+                        KeyCode::Enter => app.apply_selected_preset(),
+                        KeyCode::Char('s') => app.start_preset_naming(),
+                        KeyCode::Char('d') | KeyCode::Delete => app.delete_selected_preset(),
+                        KeyCode::Char('q') => app.cancel_preset_picker(),
+                        KeyCode::Char(ch) => {
+                            if let Some(number) = ch.to_digit(10) {
+                                app.apply_preset_number(number as usize);
+                            }
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.has_bookmark_picker() {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        if key.code == KeyCode::Char('c') {
+                            break Ok(());
+                        }
+                        continue;
+                    }
+
+                    match key.code {
+                        KeyCode::Down | KeyCode::Char('j') => app.select_next_bookmark(),
+                        KeyCode::Up | KeyCode::Char('k') => app.select_previous_bookmark(),
+                        KeyCode::Char('J') => app.move_selected_bookmark(true),
+                        KeyCode::Char('K') => app.move_selected_bookmark(false),
+                        KeyCode::Enter => app.open_selected_bookmark()?,
+                        KeyCode::Char('d') | KeyCode::Delete => app.delete_selected_bookmark(),
+                        KeyCode::Char('q') => app.cancel_bookmark_picker(),
+                        KeyCode::Char(ch) => {
+                            if let Some(number) = ch.to_digit(10) {
+                                app.open_bookmark_number(number as usize)?;
+                            }
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
                 if key.code == KeyCode::Char('?') && !is_text_input_focus(&app, focus) {
                     app.toggle_keybinds();
                     continue;
@@ -116,34 +651,71 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                     continue;
                 }
 
+                // After `g` a digit opens that bookmark instead of counting.
+                // A lone digit shows its tab straight away, as if no count
+                // followed; a second digit makes it a count.
+                let lone_digit = app.count_prefix.pending().is_none();
+                if accepts_count_prefix(focus)
+                    && !app.quick_jump_pending()
+                    && key.modifiers.is_empty()
+                    && let KeyCode::Char(ch) = key.code
+                    && app.count_prefix.push_digit(ch)
+                {
+                    if let Some(tab) = tentative_tab.take() {
+                        app.restore_right_tab(tab.previous);
+                    } else if lone_digit && let Some(number) = ch.to_digit(10) {
+                        let previous = app.right_tab();
+                        if let Some(tab_focus) = app.select_right_tab_by_number(number as usize) {
+                            tentative_tab = Some(TentativeTab {
+                                previous,
+                                focus: tab_focus,
+                            });
+                        }
+                    }
+                    continue;
+                }
+                let mut count = app.count_prefix.take();
+                if let Some(tab) = tentative_tab.take() {
+                    if takes_count(&app, focus, key) {
+                        app.restore_right_tab(tab.previous);
+                    } else {
+                        focus = tab.focus;
+                        count = 1;
+                    }
+                }
+                let quick_jump_armed = app.take_quick_jump_armed();
+
+                if key.modifiers.contains(KeyModifiers::ALT)
+                    && matches!(
+                        key.code,
+                        KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                    )
+                {
+                    match key.code {
+                        KeyCode::Left => app.resize_files_pane(false),
+                        KeyCode::Right => app.resize_files_pane(true),
+                        KeyCode::Up => app.resize_output_pane(true),
+                        _ => app.resize_output_pane(false),
+                    }
+                    continue;
+                }
+
                 if key.modifiers.contains(KeyModifiers::CONTROL) {
                     match key.code {
                         KeyCode::Char('h') | KeyCode::Left => focus = Focus::Left,
-                        KeyCode::Char('l') => {
-                            if focus == Focus::Left {
-                                focus = Focus::RightTop;
-                            }
-                        }
-                        KeyCode::Right => {
-                            if focus == Focus::Left {
-                                focus = Focus::RightTop;
-                            }
+                        KeyCode::Char('l') | KeyCode::Right if focus == Focus::Left => {
+                            focus = Focus::RightTop;
                         }
                         KeyCode::Char('j') | KeyCode::Down => focus = app.next_focus(focus),
                         KeyCode::Char('k') | KeyCode::Up => focus = app.previous_focus(focus),
                         KeyCode::Char('n') => {
-                            app.select_next_right_tab();
-                            focus = Focus::RightTop;
+                            focus = app.select_next_right_tab();
                         }
-                        KeyCode::Char('o') => {
-                            if app.can_focus_right_bottom() {
-                                focus = Focus::RightBottom;
-                            }
+                        KeyCode::Char('o') if app.can_focus_right_bottom() => {
+                            focus = Focus::RightBottom;
                         }
-                        KeyCode::Char('u')
-                            if focus == Focus::Left =>
-                        {
-                            app.page_files_up();
+                        KeyCode::Char('u') if focus == Focus::Left => {
+                            repeat(count, || app.page_files_up());
                         }
                         KeyCode::Char('u')
                             if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
@@ -151,15 +723,17 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                             app.page_editor_form_up();
                         }
                         KeyCode::Char('u') if focus == Focus::RightBottom => {
-                            match app.right_tab() {
-                                RightTab::Editor => app.page_ffmpeg_output_up(),
-                                RightTab::Downloader => app.page_downloader_output_up(),
-                            }
+                            repeat(count, || match app.right_tab() {
+                                RightTab::Editor | RightTab::Concat => {
+                                    app.page_ffmpeg_output_up(PageSize::Half)
+                                }
+                                RightTab::Downloader => {
+                                    app.page_downloader_output_up(PageSize::Half)
+                                }
+                            });
                         }
-                        KeyCode::Char('d')
-                            if focus == Focus::Left =>
-                        {
-                            app.page_files_down();
+                        KeyCode::Char('d') if focus == Focus::Left => {
+                            repeat(count, || app.page_files_down());
                         }
                         KeyCode::Char('d')
                             if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
@@ -167,10 +741,65 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                             app.page_editor_form_down();
                         }
                         KeyCode::Char('d') | KeyCode::Char('p') if focus == Focus::RightBottom => {
-                            match app.right_tab() {
-                                RightTab::Editor => app.page_ffmpeg_output_down(),
-                                RightTab::Downloader => app.page_downloader_output_down(),
-                            }
+                            repeat(count, || match app.right_tab() {
+                                RightTab::Editor | RightTab::Concat => {
+                                    app.page_ffmpeg_output_down(PageSize::Half)
+                                }
+                                RightTab::Downloader => {
+                                    app.page_downloader_output_down(PageSize::Half)
+                                }
+                            });
+                        }
+                        KeyCode::Char('b')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.analyze_bitrate_for_selected_video();
+                        }
+                        KeyCode::Char('s')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.snap_time_to_keyframe();
+                        }
+                        KeyCode::Char('t')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.toggle_frame_preview();
+                        }
+                        KeyCode::Char('r')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.toggle_range_playback();
+                        }
+                        KeyCode::Char('r')
+                            if focus == Focus::RightTop
+                                && app.right_tab() == RightTab::Downloader =>
+                        {
+                            app.reprobe_downloader_qualities();
+                        }
+                        KeyCode::Char('a')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.add_segment();
+                        }
+                        KeyCode::Char('x')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.remove_last_segment();
+                        }
+                        KeyCode::Char('p')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.open_preset_picker();
+                        }
+                        KeyCode::Char('y')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.copy_editor_command();
+                        }
+                        KeyCode::Char('g')
+                            if focus == Focus::RightTop && app.right_tab() == RightTab::Editor =>
+                        {
+                            app.start_stream_prompt();
                         }
                         KeyCode::Char('c') => break Ok(()),
                         _ => {}
@@ -180,30 +809,78 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
 
                 if let Some(tab_number) = tab_number_shortcut(key.code, key.modifiers)
                     && !is_top_form_focus(focus)
-                    && app.select_right_tab_by_number(tab_number)
+                    && !(quick_jump_armed && focus == Focus::Left)
+                    && let Some(tab_focus) = app.select_right_tab_by_number(tab_number)
                 {
-                    focus = Focus::RightTop;
+                    focus = tab_focus;
                     continue;
                 }
 
                 match focus {
                     Focus::Left => match key.code {
-                        KeyCode::Char('q') => break Ok(()),
-                        KeyCode::Down | KeyCode::Char('j') => app.next(),
-                        KeyCode::Up | KeyCode::Char('k') => app.previous(),
-                        KeyCode::PageDown => app.page_files_down(),
-                        KeyCode::PageUp => app.page_files_up(),
-                        KeyCode::Enter => {
-                            if app.activate_selected_entry()? {
-                                focus = Focus::RightTop;
-                            }
+                        KeyCode::Char(letter) if quick_jump_armed && app.has_quick_jump(letter) => {
+                            app.quick_jump(letter)?
                         }
-                        KeyCode::Char('h') | KeyCode::Char('-') => app.go_parent_dir()?,
-                        KeyCode::Char('_') => app.go_initial_dir()?,
-                        KeyCode::Char('d') => app.request_delete_selected_entry(),
-                        KeyCode::Char('x') => app.open_selected_with_system_default(),
-                        KeyCode::Char('r') => app.reload()?,
-                        _ => {}
+                        KeyCode::Char(digit @ '1'..='9') if quick_jump_armed => {
+                            app.open_bookmark_number(digit as usize - '0' as usize)?
+                        }
+                        code => match app.keymap().action(code) {
+                            Some(Action::Quit) => break Ok(()),
+                            Some(Action::Down) => repeat(count, || app.next()),
+                            Some(Action::Up) => repeat(count, || app.previous()),
+                            Some(Action::PageDown) => repeat(count, || app.page_files_down()),
+                            Some(Action::PageUp) => repeat(count, || app.page_files_up()),
+                            Some(Action::First) => app.select_first_entry(),
+                            Some(Action::Go) => {
+                                app.select_first_entry();
+                                app.arm_quick_jump();
+                            }
+                            Some(Action::Last) => app.select_last_entry(),
+                            Some(Action::Newest) => app.select_newest_file(),
+                            Some(Action::Largest) => app.select_largest_file(),
+                            Some(Action::Open) => {
+                                let opened_media = app.activate_selected_entry()?;
+                                if opened_media {
+                                    focus = Focus::RightTop;
+                                }
+                            }
+                            Some(Action::Parent) => app.go_parent_dir()?,
+                            Some(Action::InitialDir) => app.go_initial_dir()?,
+                            Some(Action::Breadcrumb) => app.start_breadcrumb(count),
+                            Some(Action::Bookmark) => app.toggle_cwd_bookmark(),
+                            Some(Action::Bookmarks) => app.open_bookmark_picker(),
+                            Some(Action::PathJump) => app.start_path_jump(),
+                            Some(Action::Find) => app.start_type_ahead(),
+                            Some(Action::Filter) => app.start_fuzzy_filter(),
+                            Some(Action::Delete) => app.request_delete_selected_entry(),
+                            Some(Action::UndoDelete) => app.undo_last_delete(),
+                            Some(Action::Rename) => app.start_rename_prompt(),
+                            Some(Action::Move) => app.start_move_prompt(),
+                            Some(Action::Copy) => app.start_copy_prompt(),
+                            Some(Action::NewDir) => app.start_new_dir_prompt(),
+                            Some(Action::OpenExternal) => app.open_selected_with_system_default(),
+                            Some(Action::OpenLastOutput) => app.open_last_output(),
+                            Some(Action::Rate) => app.cycle_selected_rating(),
+                            Some(Action::Note) => app.start_note_prompt(),
+                            Some(Action::TagFilter) => app.cycle_tag_filter(),
+                            Some(Action::ToggleHidden) => app.toggle_hidden_files(),
+                            Some(Action::MediaOnly) => app.toggle_media_only(),
+                            Some(Action::Mark) => app.toggle_selected_mark(),
+                            Some(Action::ClearMarks) => app.clear_marks(),
+                            Some(Action::Playlist) => app.write_playlist_from_marks(),
+                            Some(Action::ContactSheet) => app.create_contact_sheet_for_selected(),
+                            Some(Action::Verify) => app.verify_selected_file(),
+                            Some(Action::Remux) => app.remux_selected_file(),
+                            Some(Action::AddToConcat) => app.add_to_concat_list(),
+                            Some(Action::Batch) => app.start_batch_prompt(),
+                            Some(Action::Cleanup) => app.start_cleanup_prompt(),
+                            Some(Action::Stats) => app.open_job_stats(),
+                            Some(Action::Health) => app.open_health_check(),
+                            Some(Action::ExportConfig) => app.export_effective_config(),
+                            Some(Action::Changelog) => app.open_changelog(),
+                            Some(Action::Reload) => app.reload()?,
+                            None => {}
+                        },
                     },
                     Focus::RightTop => match app.right_tab() {
                         RightTab::Editor => match key.code {
@@ -213,12 +890,14 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                             KeyCode::Up => app.scroll_editor_form_up(),
                             KeyCode::PageDown => app.page_editor_form_down(),
                             KeyCode::PageUp => app.page_editor_form_up(),
+                            KeyCode::Home => app.scroll_editor_form_to_top(),
+                            KeyCode::End => app.scroll_editor_form_to_bottom(),
                             KeyCode::Right => app.move_cursor_right(),
                             KeyCode::Left => app.move_cursor_left(),
-                            KeyCode::Char('h') if app.active_input == InputField::Format => {
+                            KeyCode::Char('h') if app.active_input.is_choice() => {
                                 app.move_cursor_left()
                             }
-                            KeyCode::Char('l') if app.active_input == InputField::Format => {
+                            KeyCode::Char('l') if app.active_input.is_choice() => {
                                 app.move_cursor_right()
                             }
                             KeyCode::Enter => app.run_editor_export(),
@@ -232,6 +911,14 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                             KeyCode::Enter => app.downloader_press_enter(),
                             KeyCode::Down => app.select_downloader_quality_down(),
                             KeyCode::Up => app.select_downloader_quality_up(),
+                            KeyCode::Home => app.select_first_downloader_quality(),
+                            KeyCode::End => app.select_last_downloader_quality(),
+                            KeyCode::Char('g') if !app.downloader_accepts_text_input() => {
+                                app.select_first_downloader_quality()
+                            }
+                            KeyCode::Char('G') if !app.downloader_accepts_text_input() => {
+                                app.select_last_downloader_quality()
+                            }
                             KeyCode::Right => app.move_downloader_cursor_right(),
                             KeyCode::Left => app.move_downloader_cursor_left(),
                             KeyCode::Char(' ') => app.toggle_focused_downloader_option(),
@@ -239,20 +926,68 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
                             KeyCode::Char(ch) => app.push_downloader_url_char(ch),
                             _ => {}
                         },
+                        RightTab::Concat => match key.code {
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                repeat(count, || app.select_next_concat_item())
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                repeat(count, || app.select_previous_concat_item())
+                            }
+                            KeyCode::Home | KeyCode::Char('g') => app.select_first_concat_item(),
+                            KeyCode::End | KeyCode::Char('G') => app.select_last_concat_item(),
+                            KeyCode::Char('J') => app.move_concat_item(true),
+                            KeyCode::Char('K') => app.move_concat_item(false),
+                            KeyCode::Char('d') | KeyCode::Delete => {
+                                app.remove_selected_concat_item()
+                            }
+                            KeyCode::Char('D') => app.clear_concat_list(),
+                            KeyCode::Enter => app.run_concat(),
+                            _ => {}
+                        },
                     },
                     Focus::RightBottom => match app.right_tab() {
-                        RightTab::Editor => match key.code {
-                            KeyCode::Down | KeyCode::Char('j') => app.scroll_ffmpeg_output_down(),
-                            KeyCode::Up | KeyCode::Char('k') => app.scroll_ffmpeg_output_up(),
+                        RightTab::Editor | RightTab::Concat => match key.code {
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                repeat(count, || app.scroll_ffmpeg_output_down())
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                repeat(count, || app.scroll_ffmpeg_output_up())
+                            }
+                            KeyCode::PageDown => {
+                                repeat(count, || app.page_ffmpeg_output_down(PageSize::Full))
+                            }
+                            KeyCode::PageUp => {
+                                repeat(count, || app.page_ffmpeg_output_up(PageSize::Full))
+                            }
+                            KeyCode::Home | KeyCode::Char('g') => app.scroll_ffmpeg_output_to_top(),
+                            KeyCode::End | KeyCode::Char('G') => {
+                                app.scroll_ffmpeg_output_to_bottom()
+                            }
                             KeyCode::Char('x') => app.request_cancel_for_focused_tool(),
+                            KeyCode::Char('o') => app.open_last_output(),
                             _ => {}
                         },
                         RightTab::Downloader => match key.code {
                             KeyCode::Down | KeyCode::Char('j') => {
-                                app.scroll_downloader_output_down()
+                                repeat(count, || app.scroll_downloader_output_down())
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                repeat(count, || app.scroll_downloader_output_up())
+                            }
+                            KeyCode::PageDown => {
+                                repeat(count, || app.page_downloader_output_down(PageSize::Full))
+                            }
+                            KeyCode::PageUp => {
+                                repeat(count, || app.page_downloader_output_up(PageSize::Full))
+                            }
+                            KeyCode::Home | KeyCode::Char('g') => {
+                                app.scroll_downloader_output_to_top()
+                            }
+                            KeyCode::End | KeyCode::Char('G') => {
+                                app.scroll_downloader_output_to_bottom()
                             }
-                            KeyCode::Up | KeyCode::Char('k') => app.scroll_downloader_output_up(),
                             KeyCode::Char('x') => app.request_cancel_for_focused_tool(),
+                            KeyCode::Char('o') => app.open_last_output(),
                             _ => {}
                         },
                     },
@@ -263,8 +998,89 @@ fn run(terminal: &mut ratatui::DefaultTerminal, start_dir: Option<PathBuf>) -> i
 }
 
 fn handle_paste_event(app: &mut App, focus: Focus, text: &str) {
+    if focus == Focus::Left && app.fuzzy_filter().is_some() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_fuzzy_filter_char(ch);
+        }
+        return;
+    }
+
+    if app.pending_dir_delete().is_some() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_dir_delete_char(ch);
+        }
+        return;
+    }
+
+    if app.has_note_prompt() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_note_char(ch);
+        }
+        return;
+    }
+
+    if app.has_file_op_prompt() {
+        if !app.file_op_confirming_overwrite() {
+            for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+                app.push_file_op_char(ch);
+            }
+        }
+        return;
+    }
+
+    if app.has_stream_prompt() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_stream_prompt_char(ch);
+        }
+        return;
+    }
+
+    if app.has_path_jump() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_path_jump_char(ch);
+        }
+        return;
+    }
+
+    if app.has_batch_review() || app.has_cleanup_review() {
+        return;
+    }
+
+    if app.has_cleanup_prompt() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_cleanup_prompt_char(ch);
+        }
+        return;
+    }
+
+    if app.has_batch_prompt() {
+        for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+            app.push_batch_prompt_char(ch);
+        }
+        return;
+    }
+
+    if app.job_stats().is_some()
+        || app.health_check().is_some()
+        || app.changelog().is_some()
+        || app.breadcrumb_levels_up().is_some()
+        || app.has_bookmark_picker()
+    {
+        return;
+    }
+
+    if app.has_preset_picker() {
+        if app.preset_naming() {
+            for ch in text.chars().filter(|ch| *ch != '\n' && *ch != '\r') {
+                app.push_preset_name_char(ch);
+            }
+        }
+        return;
+    }
+
     if app.has_pending_delete()
         || app.has_pending_cancel()
+        || app.has_pending_export()
         || app.show_keybinds
         || focus != Focus::RightTop
     {
@@ -281,13 +1097,109 @@ fn handle_paste_event(app: &mut App, focus: Focus, text: &str) {
             }
         }
         RightTab::Editor => {
-            if app.active_input == InputField::Output {
+            if app.paste_into_time_input(text) {
+                return;
+            }
+            if app.active_input_takes_text() {
                 for ch in sanitized {
                     app.push_active_input_char(ch);
                 }
             }
         }
+        RightTab::Concat => {}
+    }
+}
+
+/// Popups and prompts take the keyboard, so the mouse leaves them alone.
+fn overlay_open(app: &App) -> bool {
+    app.show_keybinds
+        || app.has_pending_delete()
+        || app.has_pending_cancel()
+        || app.has_pending_export()
+        || app.has_note_prompt()
+        || app.has_stream_prompt()
+        || app.has_path_jump()
+        || app.has_file_op_prompt()
+        || app.has_batch_prompt()
+        || app.has_batch_review()
+        || app.has_cleanup_prompt()
+        || app.has_cleanup_review()
+        || app.has_preset_picker()
+        || app.has_bookmark_picker()
+        || app.job_stats().is_some()
+        || app.health_check().is_some()
+        || app.changelog().is_some()
+        || app.breadcrumb_levels_up().is_some()
+}
+
+fn handle_mouse_event(
+    app: &mut App,
+    focus: &mut Focus,
+    layout: &ui::PaneLayout,
+    mouse: MouseEvent,
+) -> io::Result<()> {
+    if overlay_open(app) {
+        return Ok(());
+    }
+    let (column, row) = (mouse.column, mouse.row);
+    let hit = |area: ratatui::layout::Rect| area.contains((column, row).into());
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            app.end_type_ahead();
+            // A click on a match picks it like Enter; anywhere else the
+            // full listing comes back as with Esc.
+            if app.fuzzy_filter().is_some() {
+                match layout.entry_at(column, row) {
+                    Some(index) if index < app.entries.len() => {
+                        app.selected = index;
+                        app.confirm_fuzzy_filter();
+                    }
+                    _ => app.cancel_fuzzy_filter(),
+                }
+                return Ok(());
+            }
+            if let Some(tab) = layout.tab_at(column, row) {
+                if let Some(tab_focus) = app.select_right_tab_by_number(tab.number()) {
+                    *focus = tab_focus;
+                }
+            } else if hit(layout.files) {
+                *focus = Focus::Left;
+                if let Some(index) = layout.entry_at(column, row)
+                    && app.click_entry(index)?
+                {
+                    *focus = Focus::RightTop;
+                }
+            } else if hit(layout.right_top) {
+                *focus = Focus::RightTop;
+            } else if hit(layout.right_bottom) && app.can_focus_right_bottom() {
+                *focus = Focus::RightBottom;
+            }
+        }
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            let down = mouse.kind == MouseEventKind::ScrollDown;
+            if hit(layout.files) {
+                if down { app.next() } else { app.previous() }
+            } else if hit(layout.right_top) {
+                match (app.right_tab(), down) {
+                    (RightTab::Editor, true) => app.scroll_editor_form_down(),
+                    (RightTab::Editor, false) => app.scroll_editor_form_up(),
+                    (RightTab::Downloader, true) => app.select_downloader_quality_down(),
+                    (RightTab::Downloader, false) => app.select_downloader_quality_up(),
+                    (RightTab::Concat, true) => app.select_next_concat_item(),
+                    (RightTab::Concat, false) => app.select_previous_concat_item(),
+                }
+            } else if hit(layout.right_bottom) {
+                match (app.right_tab(), down) {
+                    (RightTab::Downloader, true) => app.scroll_downloader_output_down(),
+                    (RightTab::Downloader, false) => app.scroll_downloader_output_up(),
+                    (_, true) => app.scroll_ffmpeg_output_down(),
+                    (_, false) => app.scroll_ffmpeg_output_up(),
+                }
+            }
+        }
+        _ => {}
     }
+    Ok(())
 }
 
 fn is_text_input_focus(app: &App, focus: Focus) -> bool {
@@ -297,7 +1209,50 @@ fn is_text_input_focus(app: &App, focus: Focus) -> bool {
 
     match app.right_tab() {
         RightTab::Downloader => app.downloader_accepts_text_input(),
-        RightTab::Editor => app.active_input == InputField::Output,
+        RightTab::Editor => matches!(
+            app.active_input,
+            InputField::Output | InputField::Caption | InputField::ExtraArgs
+        ),
+        RightTab::Concat => false,
+    }
+}
+
+/// Panels without text inputs, where digits can build a repeat count.
+fn accepts_count_prefix(focus: Focus) -> bool {
+    !is_top_form_focus(focus)
+}
+
+/// Keys that repeat with a count in `focus`.
+fn takes_count(app: &App, focus: Focus, key: KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('u' | 'd') => focus != Focus::RightTop,
+            KeyCode::Char('p') => focus == Focus::RightBottom,
+            _ => false,
+        };
+    }
+    match focus {
+        Focus::Left => matches!(
+            app.keymap().action(key.code),
+            Some(
+                Action::Down | Action::Up | Action::PageDown | Action::PageUp | Action::Breadcrumb
+            )
+        ),
+        Focus::RightTop => false,
+        Focus::RightBottom => matches!(
+            key.code,
+            KeyCode::Down
+                | KeyCode::Up
+                | KeyCode::PageDown
+                | KeyCode::PageUp
+                | KeyCode::Char('j' | 'k')
+        ),
+    }
+}
+
+fn repeat(count: usize, mut action: impl FnMut()) {
+    for _ in 0..count {
+        action();
     }
 }
 
@@ -320,17 +1275,193 @@ fn tab_number_shortcut(code: KeyCode, modifiers: KeyModifiers) -> Option<usize>
     ch.to_digit(10).map(|value| value as usize)
 }
 
-fn parse_start_dir_arg() -> io::Result<Option<PathBuf>> {
+fn parse_cli_args() -> io::Result<CliArgs> {
+    let mut start_dir = None;
+    let mut window_title = true;
+    let mut log_page_scale = None;
+    let mut confirm_minutes = None;
+    let mut confirm_megabytes = None;
+    let mut after_export = None;
+    let mut read_stdin = false;
+    let mut job_logs = false;
+    let mut health = false;
+    let mut screen_reader = false;
+    let mut perf_profile = None;
+    let mut poll_ms = None;
+    let mut spinner_ms = None;
+    let mut pump_batch = None;
+
     let mut args = env::args_os().skip(1);
-    let first = args.next().map(PathBuf::from);
-    if let Some(extra) = args.next() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!(
-                "Unexpected extra argument: {:?}. Usage: rt [start-directory]",
-                extra
-            ),
-        ));
-    }
-    Ok(first)
+    while let Some(arg) = args.next() {
+        if arg == "--no-title" {
+            window_title = false;
+            continue;
+        }
+        if arg == "--log-page-scale" {
+            log_page_scale = Some(flag_value(&mut args, "--log-page-scale", |scale: &f64| {
+                scale.is_finite() && *scale > 0.0
+            })?);
+            continue;
+        }
+        if arg == "--confirm-minutes" {
+            confirm_minutes = Some(flag_value(
+                &mut args,
+                "--confirm-minutes",
+                |minutes: &f64| minutes.is_finite() && *minutes >= 0.0,
+            )?);
+            continue;
+        }
+        if arg == "--after-export" {
+            let value = args
+                .next()
+                .and_then(|value| AfterExport::from_name(value.to_str()?))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("--after-export needs keep, reset, or next. Usage: {USAGE}"),
+                    )
+                })?;
+            after_export = Some(value);
+            continue;
+        }
+        if arg == "--profile" {
+            let profile = args
+                .next()
+                .and_then(|value| PerfSettings::from_profile(value.to_str()?))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "--profile needs one of {}. Usage: {USAGE}",
+                            PerfSettings::PROFILE_NAMES.join(", ")
+                        ),
+                    )
+                })?;
+            perf_profile = Some(profile);
+            continue;
+        }
+        if arg == "--poll-ms" {
+            poll_ms = Some(flag_value(&mut args, "--poll-ms", |ms: &u64| *ms > 0)?);
+            continue;
+        }
+        if arg == "--spinner-ms" {
+            spinner_ms = Some(flag_value(&mut args, "--spinner-ms", |_: &u64| true)?);
+            continue;
+        }
+        if arg == "--pump-batch" {
+            pump_batch = Some(flag_value(&mut args, "--pump-batch", |batch: &usize| {
+                *batch > 0
+            })?);
+            continue;
+        }
+        if arg == "--stdin" {
+            read_stdin = true;
+            continue;
+        }
+        if arg == "--job-logs" {
+            job_logs = true;
+            continue;
+        }
+        if arg == "--health" {
+            health = true;
+            continue;
+        }
+        if arg == "--screen-reader" {
+            screen_reader = true;
+            continue;
+        }
+        if arg == "--confirm-mb" {
+            confirm_megabytes = Some(flag_value(&mut args, "--confirm-mb", |_: &u64| true)?);
+            continue;
+        }
+        if start_dir.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unexpected extra argument: {arg:?}. Usage: {USAGE}"),
+            ));
+        }
+        start_dir = Some(PathBuf::from(arg));
+    }
+
+    // Read the whole list before the terminal is taken over; key input
+    // then comes from the controlling tty rather than the pipe.
+    let stdin_paths = if read_stdin {
+        Some(read_stdin_paths()?)
+    } else {
+        None
+    };
+
+    Ok(CliArgs {
+        start_dir,
+        window_title,
+        log_page_scale,
+        confirm_minutes,
+        confirm_megabytes,
+        after_export,
+        job_logs,
+        health,
+        screen_reader,
+        perf_profile,
+        poll_ms,
+        spinner_ms,
+        pump_batch,
+        stdin_paths,
+    })
+}
+
+/// Reads one path per line, skipping blank lines and duplicates. Relative
+/// paths resolve against the current directory.
+fn read_stdin_paths() -> io::Result<Vec<PathBuf>> {
+    let cwd = env::current_dir()?;
+    let mut paths = Vec::new();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        let path: PathBuf = cwd.join(line).components().collect();
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Parses the value following `flag`, rejecting missing, malformed, or
+/// out-of-range values.
+fn flag_value<T: std::str::FromStr>(
+    args: &mut impl Iterator<Item = std::ffi::OsString>,
+    flag: &str,
+    valid: impl Fn(&T) -> bool,
+) -> io::Result<T> {
+    args.next()
+        .and_then(|value| value.to_str()?.parse::<T>().ok())
+        .filter(|value| valid(value))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{flag} needs a valid number. Usage: {USAGE}"),
+            )
+        })
+}
+
+/// How long a typed count waits for a motion before it is treated as a
+/// tab number shortcut instead.
+const COUNT_PREFIX_TIMEOUT: Duration = Duration::from_millis(600);
+
+const USAGE: &str = "rt [--no-title] [--log-page-scale <factor>] [--confirm-minutes <n>] [--confirm-mb <n>] [--after-export keep|reset|next] [--profile low-power|balanced|snappy] [--poll-ms <n>] [--spinner-ms <n>] [--pump-batch <n>] [--stdin] [--job-logs] [--health] [--screen-reader] [start-directory]";
+
+// xterm title stack (XTWINOPS 22/23): save the user's title on startup and
+// put it back on exit. Terminals without support ignore these sequences.
+fn push_window_title() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x1b[22;0t");
+    let _ = stdout.flush();
+}
+
+fn pop_window_title() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x1b[23;0t");
+    let _ = stdout.flush();
 }
@@ -4,14 +4,175 @@
 // - Handles output filename/extension rules and numbered collision resolution.
 use std::{
     collections::HashMap,
-    io,
+    fs, io,
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
 };
 
 use crate::model::{TimeInput, VideoBounds};
 
-pub const OUTPUT_FORMATS: [&str; 8] = ["mp4", "mov", "mkv", "gif", "mp3", "m4a", "wav", "flac"];
+pub const OUTPUT_FORMATS: [&str; 12] = [
+    "mp4", "mov", "mkv", "gif", "webp", "apng", "png", "jpg", "mp3", "m4a", "wav", "flac",
+];
+
+/// ffmpeg `-loglevel` values offered in the editor, quietest first. Nothing
+/// below "error", so a failed run still leaves stderr to summarize.
+pub const FFMPEG_LOG_LEVELS: [&str; 5] = ["error", "warning", "info", "verbose", "debug"];
+pub const DEFAULT_FFMPEG_LOG_LEVEL: &str = "warning";
+
+/// Video codec choices in the editor. `copy` passes the source stream through.
+pub const VIDEO_CODECS: [&str; 5] = ["h264", "hevc", "vp9", "av1", "copy"];
+
+/// Whether ffmpeg can mux `codec` into the `format` container. VP9 and AV1
+/// go into mp4 and mkv but not QuickTime mov.
+pub fn codec_fits_container(codec: &str, format: &str) -> bool {
+    !(format == "mov" && matches!(codec, "vp9" | "av1"))
+}
+
+/// ffmpeg encoders that can produce `codec`, in preference order.
+/// NVENC encoders are listed first only when GPU encoding is requested.
+pub fn video_encoder_candidates(codec: &str, prefer_gpu: bool) -> Vec<&'static str> {
+    let (gpu, cpu) = video_encoders(codec);
+    let mut candidates = Vec::new();
+    if prefer_gpu {
+        candidates.extend_from_slice(gpu);
+    }
+    candidates.extend_from_slice(cpu);
+    candidates
+}
+
+/// NVENC encoders that can produce `codec`; empty when there is no GPU path.
+pub fn gpu_video_encoders(codec: &str) -> &'static [&'static str] {
+    video_encoders(codec).0
+}
+
+fn video_encoders(codec: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match codec {
+        "hevc" => (&["hevc_nvenc"], &["libx265"]),
+        "vp9" => (&[], &["libvpx-vp9"]),
+        "av1" => (&["av1_nvenc"], &["libsvtav1", "libaom-av1"]),
+        "copy" => (&[], &["copy"]),
+        _ => (&["h264_nvenc"], &["libx264"]),
+    }
+}
+
+/// Speed/quality trade-off flags for an encoder (each encoder names these differently).
+pub fn video_encoder_speed_args(encoder: &str) -> Vec<String> {
+    let args: &[&str] = match encoder {
+        "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" => &["-preset", "p4"],
+        "libx264" | "libx265" => &["-preset", "veryfast"],
+        "libsvtav1" => &["-preset", "8"],
+        "libaom-av1" => &["-cpu-used", "6", "-row-mt", "1"],
+        "libvpx-vp9" => &["-deadline", "good", "-cpu-used", "4", "-row-mt", "1"],
+        _ => &[],
+    };
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// `atempo` stages for a playback speed. Each stage is kept within 0.5-2.0,
+/// the range every ffmpeg version accepts, so larger changes are chained.
+pub fn atempo_filters(speed: f64) -> Vec<String> {
+    let mut filters = Vec::new();
+    let mut remaining = speed;
+    while remaining > 2.0 {
+        filters.push("atempo=2.0".to_string());
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        filters.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    filters.push(format!("atempo={remaining}"));
+    filters
+}
+
+/// Filters that merge audio tracks with the given channel counts into one
+/// stereo track: `amerge` stacks their channels, then `pan` folds each
+/// track's left and right (mono tracks feed both) back into two channels.
+/// `<` in the pan expression renormalizes gains so the sum cannot clip.
+pub fn amerge_stereo_filters(channels: &[u32]) -> Vec<String> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    let mut offset = 0;
+    for &count in channels {
+        let count = count.max(1);
+        left.push(format!("c{offset}"));
+        right.push(format!("c{}", offset + u32::from(count > 1)));
+        offset += count;
+    }
+    vec![
+        format!("amerge=inputs={}", channels.len()),
+        format!("pan=stereo|c0<{}|c1<{}", left.join("+"), right.join("+")),
+    ]
+}
+
+/// EBU R128 loudness targets: -16 LUFS integrated, common for web/streaming.
+const LOUDNORM_TARGETS: &str = "I=-16:TP=-1.5:LRA=11";
+
+/// Values reported by a loudnorm analysis pass (`print_format=json`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoudnormMeasurement {
+    input_i: f64,
+    input_tp: f64,
+    input_lra: f64,
+    input_thresh: f64,
+    target_offset: f64,
+}
+
+/// Filter for a normalizing encode. With measurements this is the second,
+/// linear pass; without, ffmpeg normalizes dynamically in one pass.
+pub fn loudnorm_filter(measured: Option<&LoudnormMeasurement>) -> String {
+    match measured {
+        Some(measured) => format!(
+            "loudnorm={LOUDNORM_TARGETS}:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true",
+            measured.input_i,
+            measured.input_tp,
+            measured.input_lra,
+            measured.input_thresh,
+            measured.target_offset
+        ),
+        None => format!("loudnorm={LOUDNORM_TARGETS}"),
+    }
+}
+
+pub fn loudnorm_analysis_filter() -> String {
+    format!("loudnorm={LOUDNORM_TARGETS}:print_format=json")
+}
+
+/// Reads the JSON block loudnorm prints at the end of an analysis run.
+/// Returns `None` for silent clips, where ffmpeg reports `-inf`.
+pub fn parse_loudnorm_measurement(stderr: &str) -> Option<LoudnormMeasurement> {
+    let block = &stderr[stderr.rfind("\"input_i\"")?..];
+    let value = |key: &str| -> Option<f64> {
+        let rest = &block[block.find(&format!("\"{key}\""))? + key.len() + 2..];
+        let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+        let value = rest.strip_prefix('"')?.split('"').next()?;
+        value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+    };
+
+    Some(LoudnormMeasurement {
+        input_i: value("input_i")?,
+        input_tp: value("input_tp")?,
+        input_lra: value("input_lra")?,
+        input_thresh: value("input_thresh")?,
+        target_offset: value("target_offset")?,
+    })
+}
+
+/// Looping image formats: no audio, no bitrate, frame rate and width only.
+pub fn is_animated_image_format(format: &str) -> bool {
+    matches!(normalize_output_format(format), "gif" | "webp" | "apng")
+}
+
+/// Still frames: one image at the start time, or a numbered sequence.
+pub fn is_still_image_format(format: &str) -> bool {
+    matches!(normalize_output_format(format), "png" | "jpg")
+}
 
 pub fn is_audio_output_format(format: &str) -> bool {
     matches!(
@@ -27,11 +188,18 @@ pub struct VideoStats {
     pub width: Option<u32>,
     pub height: Option<u32>,
     pub fps: String,
+    /// Average frame rate, for frame-number times.
+    pub frame_rate: Option<f64>,
     pub video_codec: String,
     pub audio_codec: String,
     pub size: String,
     pub bitrate: String,
     pub bitrate_kbps: Option<u32>,
+    /// ffprobe reported a field order such as `tt` or `bb`.
+    pub interlaced: bool,
+    /// The average frame rate differs from the container's base rate, as in
+    /// most phone and screen recordings.
+    pub variable_frame_rate: bool,
 }
 
 pub fn is_video_file(path: &Path) -> bool {
@@ -56,11 +224,70 @@ pub fn is_audio_file(path: &Path) -> bool {
     )
 }
 
+/// MP4-family containers, which take `-movflags +faststart`.
+pub fn supports_faststart(path: &Path) -> bool {
+    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+
+    matches!(
+        ext.to_ascii_lowercase().as_str(),
+        "mp4" | "m4v" | "m4a" | "mov"
+    )
+}
+
 pub fn is_editable_media_file(path: &Path) -> bool {
     is_video_file(path) || is_audio_file(path)
 }
 
+/// URL schemes the editor can read directly instead of a local file.
+pub const STREAM_SCHEMES: [&str; 4] = ["http", "https", "rtmp", "rtmps"];
+
+/// Longest wait for network data before ffmpeg/ffprobe give up on a stream,
+/// in microseconds, so a dead host doesn't hang the probe or the export.
+const STREAM_TIMEOUT_MICROS: &str = "15000000";
+
+/// True when `path` holds an http(s) or rtmp(s) URL rather than a file.
+pub fn is_stream_url(path: &Path) -> bool {
+    path.to_str()
+        .and_then(|value| value.split_once("://"))
+        .is_some_and(|(scheme, _)| {
+            STREAM_SCHEMES
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(scheme))
+        })
+}
+
+/// Input options that go before `-i` (or the ffprobe input): a read
+/// timeout for streams, nothing for local files.
+pub fn stream_input_args(path: &Path) -> Vec<String> {
+    if is_stream_url(path) {
+        vec!["-rw_timeout".to_string(), STREAM_TIMEOUT_MICROS.to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Last path segment of a stream URL without its query, used to name
+/// exports. Bare hosts are named `stream`.
+fn stream_file_name(path: &Path) -> Option<PathBuf> {
+    if !is_stream_url(path) {
+        return None;
+    }
+    let url = path.to_str()?;
+    let (_, rest) = url.split_once("://")?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let name = rest
+        .split_once('/')
+        .and_then(|(_, path)| path.rsplit('/').find(|segment| !segment.is_empty()))
+        .unwrap_or("stream");
+    Some(PathBuf::from(name))
+}
+
 pub fn default_output_name(path: &Path) -> String {
+    if let Some(name) = stream_file_name(path) {
+        return default_output_name(&name);
+    }
     let stem = path
         .file_stem()
         .map(|name| name.to_string_lossy().into_owned())
@@ -78,6 +305,9 @@ pub fn default_output_name(path: &Path) -> String {
 }
 
 pub fn output_format_for_path(path: &Path) -> &'static str {
+    if let Some(name) = stream_file_name(path) {
+        return output_format_for_path(&name);
+    }
     path.extension()
         .and_then(|ext| ext.to_str())
         .map(normalize_output_format)
@@ -144,6 +374,34 @@ pub fn next_available_output_path(path: &Path) -> PathBuf {
     }
 }
 
+/// Turns `clip.png` into the ffmpeg pattern `clip_%05d.png` plus the path
+/// of its first frame, numbering the stem (`clip(1)_%05d.png`) until that
+/// first frame doesn't already exist.
+pub fn image_sequence_paths(path: &Path) -> (PathBuf, PathBuf) {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("png");
+    let stem = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "frame".to_string());
+    let (base_stem, _) = split_numbered_suffix(&stem);
+
+    let mut candidate_stem = base_stem.clone();
+    let mut number = 1;
+    loop {
+        let first_frame = parent.join(format!("{candidate_stem}_00001.{extension}"));
+        if !first_frame.exists() {
+            let pattern = parent.join(format!("{candidate_stem}_%05d.{extension}"));
+            return (pattern, first_frame);
+        }
+        candidate_stem = format!("{base_stem}({number})");
+        number += 1;
+    }
+}
+
 pub fn output_path_without_numbered_suffix(path: &Path) -> PathBuf {
     let parent = path.parent().unwrap_or_else(|| Path::new("."));
     let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
@@ -168,6 +426,7 @@ pub fn probe_video_times(path: &Path) -> io::Result<(TimeInput, TimeInput, Video
         .arg("format=start_time,duration")
         .arg("-of")
         .arg("default=noprint_wrappers=1:nokey=1")
+        .args(stream_input_args(path))
         .arg(path)
         .output()?;
 
@@ -190,8 +449,8 @@ pub fn probe_video_times(path: &Path) -> io::Result<(TimeInput, TimeInput, Video
     let end_secs = (start_secs + duration_secs).max(start_secs);
 
     let bounds = VideoBounds {
-        start_seconds: start_secs.round() as u32,
-        end_seconds: end_secs.round() as u32,
+        start_seconds: start_secs,
+        end_seconds: end_secs,
     };
 
     Ok((
@@ -201,6 +460,240 @@ pub fn probe_video_times(path: &Path) -> io::Result<(TimeInput, TimeInput, Video
     ))
 }
 
+/// Lists keyframe timestamps of the first video stream within `window`
+/// seconds of `around_seconds`. Reads packet flags only, so no decoding.
+pub fn probe_keyframe_times(path: &Path, around_seconds: f64, window: f64) -> io::Result<Vec<f64>> {
+    let from = (around_seconds - window).max(0.0);
+    let to = around_seconds + window;
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-select_streams")
+        .arg("v:0")
+        .arg("-read_intervals")
+        .arg(format!("{from}%{to}"))
+        .arg("-show_entries")
+        .arg("packet=pts_time,flags")
+        .arg("-of")
+        .arg("csv=p=0")
+        .args(stream_input_args(path))
+        .arg(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("ffprobe keyframe scan failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let (pts_time, flags) = line.trim().split_once(',')?;
+            if !flags.contains('K') {
+                return None;
+            }
+            parse_probe_seconds(pts_time)
+        })
+        .collect())
+}
+
+/// Sums packet sizes from every stream into one-second buckets and returns
+/// the bitrate of each second in kbit/s. Reads every packet, so long files
+/// take a while; call it off the UI thread.
+pub fn probe_bitrate_per_second(path: &Path) -> io::Result<Vec<u32>> {
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("packet=pts_time,size")
+        .arg("-of")
+        .arg("csv=p=0")
+        .args(stream_input_args(path))
+        .arg(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("ffprobe packet scan failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut bytes_per_second: Vec<u64> = Vec::new();
+    for line in stdout.lines() {
+        let Some((pts_time, size)) = line.trim().split_once(',') else {
+            continue;
+        };
+        let (Some(seconds), Ok(size)) = (parse_probe_seconds(pts_time), size.parse::<u64>()) else {
+            continue;
+        };
+        let bucket = seconds.max(0.0) as usize;
+        if bucket >= bytes_per_second.len() {
+            bytes_per_second.resize(bucket + 1, 0);
+        }
+        bytes_per_second[bucket] += size;
+    }
+
+    Ok(bytes_per_second
+        .into_iter()
+        .map(|bytes| u32::try_from(bytes * 8 / 1000).unwrap_or(u32::MAX))
+        .collect())
+}
+
+/// Picks the keyframe closest to `target`, preferring the earlier one on ties.
+pub fn nearest_keyframe(keyframes: &[f64], target: f64) -> Option<f64> {
+    keyframes.iter().copied().min_by(|a, b| {
+        let by_distance = (a - target).abs().total_cmp(&(b - target).abs());
+        by_distance.then(a.total_cmp(b))
+    })
+}
+
+/// Decodes the frame at `seconds`, scaled to `width`x`height`, as packed
+/// RGB bytes (three per pixel, row by row).
+pub fn extract_frame_rgb(
+    path: &Path,
+    seconds: f64,
+    width: u32,
+    height: u32,
+) -> io::Result<Vec<u8>> {
+    let output = Command::new("ffmpeg")
+        .arg("-v")
+        .arg("error")
+        .arg("-ss")
+        .arg(format!("{seconds:.3}"))
+        .args(stream_input_args(path))
+        .arg("-i")
+        .arg(path)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-vf")
+        .arg(format!("scale={width}:{height}"))
+        .arg("-f")
+        .arg("rawvideo")
+        .arg("-pix_fmt")
+        .arg("rgb24")
+        .arg("pipe:1")
+        .stdin(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("ffmpeg frame extraction failed"));
+    }
+    let expected = width as usize * height as usize * 3;
+    if output.stdout.len() < expected {
+        return Err(io::Error::other("no frame at this time"));
+    }
+    let mut rgb = output.stdout;
+    rgb.truncate(expected);
+    Ok(rgb)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Video,
+    Audio,
+    Subtitle,
+}
+
+/// One input stream as reported by ffprobe.
+#[derive(Debug, Clone)]
+pub struct MediaStream {
+    /// Absolute stream index, used as `-map 0:<index>`.
+    pub index: usize,
+    pub kind: StreamKind,
+    pub codec: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub channels: Option<u32>,
+}
+
+impl MediaStream {
+    pub fn map_arg(&self) -> String {
+        format!("0:{}", self.index)
+    }
+
+    /// Compact description, e.g. `#1 audio aac 2ch eng "Commentary"`.
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            StreamKind::Video => "video",
+            StreamKind::Audio => "audio",
+            StreamKind::Subtitle => "subtitle",
+        };
+        let mut label = format!("#{} {kind} {}", self.index, self.codec);
+        if let Some(channels) = self.channels {
+            label.push_str(&format!(" {channels}ch"));
+        }
+        if let Some(language) = &self.language {
+            label.push_str(&format!(" {language}"));
+        }
+        if let Some(title) = &self.title {
+            label.push_str(&format!(" \"{title}\""));
+        }
+        label
+    }
+
+    /// Bitmap subtitles (PGS, VobSub, DVB) cannot become MP4 text tracks.
+    pub fn is_bitmap_subtitle(&self) -> bool {
+        matches!(
+            self.codec.as_str(),
+            "hdmv_pgs_subtitle" | "dvd_subtitle" | "dvb_subtitle" | "xsub"
+        )
+    }
+}
+
+/// Lists the video, audio, and subtitle streams of `path`. Attachments and
+/// data streams are skipped; cover art counts as video.
+pub fn probe_streams(path: &Path) -> io::Result<Vec<MediaStream>> {
+    let output = Command::new("ffprobe")
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("stream=index,codec_type,codec_name,channels:stream_tags=language,title")
+        .arg("-of")
+        .arg("compact=p=0:nk=0")
+        .args(stream_input_args(path))
+        .arg(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("ffprobe stream listing failed"));
+    }
+
+    let streams = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_compact_stream_line)
+        .collect();
+    Ok(streams)
+}
+
+fn parse_compact_stream_line(line: &str) -> Option<MediaStream> {
+    let fields = line
+        .trim()
+        .split('|')
+        .filter_map(|field| field.split_once('='))
+        .collect::<HashMap<_, _>>();
+    let kind = match *fields.get("codec_type")? {
+        "video" => StreamKind::Video,
+        "audio" => StreamKind::Audio,
+        "subtitle" => StreamKind::Subtitle,
+        _ => return None,
+    };
+    let tag = |key: &str| {
+        fields
+            .get(key)
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty() && *value != "und")
+            .map(str::to_string)
+    };
+
+    Some(MediaStream {
+        index: fields.get("index")?.parse().ok()?,
+        kind,
+        codec: tag("codec_name").unwrap_or_else(|| "unknown".to_string()),
+        language: tag("tag:language"),
+        title: tag("tag:title"),
+        channels: fields.get("channels").and_then(|value| value.parse().ok()),
+    })
+}
+
 pub fn probe_video_stats(path: &Path) -> io::Result<VideoStats> {
     let video_output = Command::new("ffprobe")
         .arg("-v")
@@ -208,11 +701,12 @@ pub fn probe_video_stats(path: &Path) -> io::Result<VideoStats> {
         .arg("-select_streams")
         .arg("v:0")
         .arg("-show_entries")
-        .arg("stream=codec_name,width,height,avg_frame_rate")
+        .arg("stream=codec_name,width,height,avg_frame_rate,r_frame_rate,field_order")
         .arg("-show_entries")
         .arg("format=duration,size,bit_rate")
         .arg("-of")
         .arg("default=noprint_wrappers=1:nokey=0")
+        .args(stream_input_args(path))
         .arg(path)
         .output()?;
 
@@ -236,9 +730,10 @@ pub fn probe_video_stats(path: &Path) -> io::Result<VideoStats> {
         (Some(width), Some(height)) => format!("{width}x{height}"),
         _ => "n/a".to_string(),
     };
-    let fps = stats_map
+    let frame_rate = stats_map
         .get("avg_frame_rate")
-        .and_then(|value| parse_fraction(value))
+        .and_then(|value| parse_fraction(value));
+    let fps = frame_rate
         .map(|value| format!("{value:.2}"))
         .unwrap_or_else(|| "n/a".to_string());
     let duration = stats_map
@@ -259,6 +754,22 @@ pub fn probe_video_stats(path: &Path) -> io::Result<VideoStats> {
         .unwrap_or_else(|| "n/a".to_string());
     let bitrate_kbps = bitrate_bits_per_second.and_then(bitrate_kbps_from_bits_per_second);
 
+    let interlaced = stats_map
+        .get("field_order")
+        .is_some_and(|order| matches!(order.as_str(), "tt" | "bb" | "tb" | "bt"));
+
+    let variable_frame_rate = match (
+        stats_map
+            .get("avg_frame_rate")
+            .and_then(|value| parse_fraction(value)),
+        stats_map
+            .get("r_frame_rate")
+            .and_then(|value| parse_fraction(value)),
+    ) {
+        (Some(average), Some(base)) => is_variable_frame_rate(average, base),
+        _ => false,
+    };
+
     let audio_codec = probe_audio_codec(path).unwrap_or_else(|_| "n/a".to_string());
 
     Ok(VideoStats {
@@ -267,14 +778,23 @@ pub fn probe_video_stats(path: &Path) -> io::Result<VideoStats> {
         width,
         height,
         fps,
+        frame_rate,
         video_codec,
         audio_codec,
         size,
         bitrate,
         bitrate_kbps,
+        interlaced,
+        variable_frame_rate,
     })
 }
 
+/// Rates within half a percent count as constant, which absorbs the timestamp
+/// rounding in the probed average.
+fn is_variable_frame_rate(average: f64, base: f64) -> bool {
+    average > 0.0 && base > 0.0 && (average - base).abs() / base > 0.005
+}
+
 pub fn scaled_resolution_for_percent(width: u32, height: u32, percent: u32) -> (u32, u32) {
     if percent == 100 {
         return (width, height);
@@ -286,6 +806,310 @@ pub fn scaled_resolution_for_percent(width: u32, height: u32, percent: u32) -> (
     )
 }
 
+/// Pixel rectangle passed to ffmpeg's `crop=w:h:x:y` filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CropRect {
+    pub width: u32,
+    pub height: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl CropRect {
+    pub fn to_filter(self) -> String {
+        format!("crop={}:{}:{}:{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// Parses the editor crop input against the probed source resolution.
+/// - Empty input means no crop.
+/// - `W:H` is an aspect ratio (e.g. `16:9`) cropped from the center.
+/// - `W:H:X:Y` is an explicit rectangle in source pixels.
+pub fn parse_crop_input(
+    value: &str,
+    source: Option<(u32, u32)>,
+) -> Result<Option<CropRect>, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    let Some(parts) = trimmed
+        .split(':')
+        .map(|part| part.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Err("Crop must be W:H (aspect) or W:H:X:Y using whole numbers.".to_string());
+    };
+
+    match parts.as_slice() {
+        [ratio_width, ratio_height] => {
+            if *ratio_width == 0 || *ratio_height == 0 {
+                return Err("Crop aspect ratio values must be greater than 0.".to_string());
+            }
+            let Some((source_width, source_height)) = source else {
+                return Err("Aspect crop needs a probed source resolution.".to_string());
+            };
+            Ok(Some(centered_aspect_crop(
+                source_width,
+                source_height,
+                *ratio_width,
+                *ratio_height,
+            )))
+        }
+        [width, height, x, y] => {
+            if *width == 0 || *height == 0 {
+                return Err("Crop width and height must be greater than 0.".to_string());
+            }
+            if width % 2 == 1 || height % 2 == 1 {
+                return Err("Crop width and height must be even numbers.".to_string());
+            }
+            if let Some((source_width, source_height)) = source
+                && (x.saturating_add(*width) > source_width
+                    || y.saturating_add(*height) > source_height)
+            {
+                return Err(format!(
+                    "Crop {width}x{height} at {x},{y} exceeds source {source_width}x{source_height}."
+                ));
+            }
+            Ok(Some(CropRect {
+                width: *width,
+                height: *height,
+                x: *x,
+                y: *y,
+            }))
+        }
+        _ => Err("Crop must be W:H (aspect) or W:H:X:Y using whole numbers.".to_string()),
+    }
+}
+
+/// Denoise strengths offered in the editor. `strong` switches from
+/// `hqdn3d` to the much slower but cleaner `nlmeans`.
+pub const DENOISE_LEVELS: [&str; 4] = ["off", "light", "medium", "strong"];
+pub const SHARPEN_LEVELS: [&str; 4] = ["off", "light", "medium", "strong"];
+
+/// Modification time choices for exports. `clip start` adds the start time
+/// to the source's, which keeps clips from one recording in order.
+pub const FILE_TIME_MODES: [&str; 3] = ["now", "source", "clip start"];
+
+/// The modification time `mode` gives an export of `source` starting at
+/// `start_seconds`; `None` leaves the time of writing.
+pub fn export_file_time(mode: &str, source: &Path, start_seconds: f64) -> Option<SystemTime> {
+    let source_time = match mode {
+        "source" | "clip start" => fs::metadata(source).and_then(|meta| meta.modified()).ok()?,
+        _ => return None,
+    };
+    if mode == "clip start" {
+        return source_time.checked_add(Duration::from_secs_f64(start_seconds.max(0.0)));
+    }
+    Some(source_time)
+}
+
+pub fn set_file_modified(path: &Path, time: SystemTime) -> io::Result<()> {
+    fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(time)
+}
+
+pub fn denoise_filter(level: &str) -> Option<String> {
+    let filter = match level {
+        "light" => "hqdn3d=2:1.5:3:2.25",
+        "medium" => "hqdn3d=4:3:6:4.5",
+        "strong" => "nlmeans=s=3.0",
+        _ => return None,
+    };
+    Some(filter.to_string())
+}
+
+/// `unsharp` on luma only, so sharpening doesn't bring back chroma noise.
+pub fn sharpen_filter(level: &str) -> Option<String> {
+    let amount = match level {
+        "light" => "0.5",
+        "medium" => "1.0",
+        "strong" => "1.5",
+        _ => return None,
+    };
+    Some(format!("unsharp=5:5:{amount}:5:5:0.0"))
+}
+
+/// Channel fixes for clips where the mic only recorded to one side.
+pub const CHANNEL_MODES: [&str; 5] = ["keep", "mono", "left to both", "right to both", "swap"];
+
+/// `pan` filter for a channel mode. `c0` is the left channel, `c1` the
+/// right; extra channels of a surround source are dropped.
+pub fn channel_filter(mode: &str) -> Option<String> {
+    let filter = match mode {
+        "mono" => "pan=mono|c0=0.5*c0+0.5*c1",
+        "left to both" => "pan=stereo|c0=c0|c1=c0",
+        "right to both" => "pan=stereo|c0=c1|c1=c1",
+        "swap" => "pan=stereo|c0=c1|c1=c0",
+        _ => return None,
+    };
+    Some(filter.to_string())
+}
+
+/// `-filter_complex` graph that cuts `segments` (seconds from the seek
+/// point) out of the first video stream and `audio_map`, joins them in
+/// order, and runs the usual filters on the result. Outputs `[vout]` and,
+/// with audio, `[aout]`.
+pub fn segments_filter_graph(
+    segments: &[(f64, f64)],
+    audio_map: Option<&str>,
+    video_filters: &[String],
+    audio_filters: &[String],
+) -> String {
+    let count = segments.len();
+    let split_pads = |prefix: &str| {
+        (0..count)
+            .map(|index| format!("[{prefix}{index}]"))
+            .collect::<String>()
+    };
+    let mut graph = format!("[0:v:0]split={count}{};", split_pads("sv"));
+    if let Some(audio_map) = audio_map {
+        graph.push_str(&format!("[{audio_map}]asplit={count}{};", split_pads("sa")));
+    }
+    let mut concat_inputs = String::new();
+    for (index, (start, end)) in segments.iter().enumerate() {
+        graph.push_str(&format!(
+            "[sv{index}]trim=start={start:.3}:end={end:.3},setpts=PTS-STARTPTS[v{index}];"
+        ));
+        concat_inputs.push_str(&format!("[v{index}]"));
+        if audio_map.is_some() {
+            graph.push_str(&format!(
+                "[sa{index}]atrim=start={start:.3}:end={end:.3},asetpts=PTS-STARTPTS[a{index}];"
+            ));
+            concat_inputs.push_str(&format!("[a{index}]"));
+        }
+    }
+    let video_chain = if video_filters.is_empty() {
+        "null".to_string()
+    } else {
+        video_filters.join(",")
+    };
+    if audio_map.is_some() {
+        let audio_chain = if audio_filters.is_empty() {
+            "anull".to_string()
+        } else {
+            audio_filters.join(",")
+        };
+        graph.push_str(&format!(
+            "{concat_inputs}concat=n={count}:v=1:a=1[segv][sega];[segv]{video_chain}[vout];[sega]{audio_chain}[aout]"
+        ));
+    } else {
+        graph.push_str(&format!(
+            "{concat_inputs}concat=n={count}:v=1:a=0[segv];[segv]{video_chain}[vout]"
+        ));
+    }
+    graph
+}
+
+/// Ways to draw an audio track as video, for posting audio to video-only
+/// platforms.
+pub const WAVEFORM_STYLES: [&str; 3] = ["off", "waves", "spectrum"];
+const WAVEFORM_SIZE: &str = "1280x720";
+
+/// Filter that turns one audio stream into a video stream at `fps`.
+pub fn waveform_filter(style: &str, fps: &str) -> Option<String> {
+    let filter = match style {
+        "waves" => format!("showwaves=s={WAVEFORM_SIZE}:mode=cline:rate={fps}:colors=0x4fc3f7"),
+        "spectrum" => format!(
+            "showspectrum=s={WAVEFORM_SIZE}:mode=combined:color=intensity:slide=scroll,fps={fps}"
+        ),
+        _ => return None,
+    };
+    Some(format!("{filter},format=yuv420p"))
+}
+
+/// Values for the `eq` filter, each of which ffmpeg treats as neutral at
+/// its default.
+pub struct ColorAdjust {
+    pub brightness: f64,
+    pub contrast: f64,
+    pub saturation: f64,
+    pub gamma: f64,
+}
+
+impl ColorAdjust {
+    /// `None` when every value is neutral, so no filter is added.
+    pub fn eq_filter(&self) -> Option<String> {
+        let options: Vec<String> = [
+            ("brightness", self.brightness, 0.0),
+            ("contrast", self.contrast, 1.0),
+            ("saturation", self.saturation, 1.0),
+            ("gamma", self.gamma, 1.0),
+        ]
+        .into_iter()
+        .filter(|(_, value, neutral)| value != neutral)
+        .map(|(name, value, _)| format!("{name}={value}"))
+        .collect();
+        (!options.is_empty()).then(|| format!("eq={}", options.join(":")))
+    }
+}
+
+pub fn parse_color_adjust(
+    brightness: &str,
+    contrast: &str,
+    saturation: &str,
+    gamma: &str,
+) -> Result<ColorAdjust, String> {
+    let parse = |label: &str, value: &str, min: f64, max: f64| {
+        value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|parsed| (min..=max).contains(parsed))
+            .ok_or_else(|| format!("{label} must be a number between {min} and {max}."))
+    };
+    Ok(ColorAdjust {
+        brightness: parse("Brightness", brightness, -1.0, 1.0)?,
+        contrast: parse("Contrast", contrast, 0.0, 3.0)?,
+        saturation: parse("Saturation", saturation, 0.0, 3.0)?,
+        gamma: parse("Gamma", gamma, 0.1, 10.0)?,
+    })
+}
+
+/// Caption placements offered in the editor.
+pub const CAPTION_POSITIONS: [&str; 3] = ["bottom", "top", "center"];
+pub const MIN_CAPTION_SIZE: u32 = 8;
+pub const MAX_CAPTION_SIZE: u32 = 400;
+
+/// Builds a `drawtext` filter that burns `text` in white, centered
+/// horizontally at `position`, with an optional black outline.
+pub fn caption_filter(text: &str, position: &str, font_size: u32, outline: bool) -> String {
+    let y = match position {
+        "top" => "h*0.05",
+        "center" => "(h-text_h)/2",
+        _ => "h-text_h-h*0.05",
+    };
+    let mut filter = format!(
+        "drawtext=expansion=none:text={}:x=(w-text_w)/2:y={y}:fontsize={font_size}:fontcolor=white",
+        escape_drawtext_text(text)
+    );
+    if outline {
+        let border = (font_size / 16).max(2);
+        filter.push_str(&format!(":borderw={border}:bordercolor=black"));
+    }
+    filter
+}
+
+/// Escapes caption text for both the drawtext option parser and the
+/// filtergraph parser that runs before it.
+fn escape_drawtext_text(text: &str) -> String {
+    let escape = |input: &str, special: &[char]| {
+        let mut escaped = String::with_capacity(input.len());
+        for ch in input.chars() {
+            if special.contains(&ch) {
+                escaped.push('\\');
+            }
+            escaped.push(ch);
+        }
+        escaped
+    };
+    let option_level = escape(text, &['\\', '\'', ':']);
+    escape(&option_level, &['\\', '\'', '[', ']', ',', ';'])
+}
+
 pub fn summarize_ffmpeg_error(stderr: &str) -> String {
     let lines = stderr
         .lines()
@@ -302,6 +1126,30 @@ pub fn summarize_ffmpeg_error(stderr: &str) -> String {
     "unknown ffmpeg error".to_string()
 }
 
+/// Extracts the encoded position from an ffmpeg stats line
+/// (`... time=00:01:02.50 bitrate=...`) in seconds.
+pub fn parse_ffmpeg_progress_seconds(line: &str) -> Option<f64> {
+    let (_, rest) = line.split_once("time=")?;
+    let timestamp = rest.split_whitespace().next()?;
+    if timestamp.starts_with('-') {
+        return Some(0.0);
+    }
+
+    let mut parts = timestamp.split(':');
+    let hours = parts.next()?.parse::<f64>().ok()?;
+    let minutes = parts.next()?.parse::<f64>().ok()?;
+    let seconds = parts.next()?.parse::<f64>().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Whole-number percentage of `done` over `total`, clamped to 0..=100.
+pub fn progress_percent(done: f64, total: f64) -> Option<u8> {
+    if !total.is_finite() || total <= 0.0 || !done.is_finite() {
+        return None;
+    }
+    Some(((done / total) * 100.0).clamp(0.0, 100.0).floor() as u8)
+}
+
 pub fn shell_quote(value: &str) -> String {
     if value.is_empty() {
         "''".to_string()
@@ -315,6 +1163,69 @@ pub fn shell_quote(value: &str) -> String {
     }
 }
 
+/// Splits `value` into arguments the way a POSIX shell would for plain
+/// words: whitespace separates, single quotes are literal, double quotes
+/// allow `\"` and `\\`, and a backslash escapes the next character.
+pub fn split_shell_words(value: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            ch if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => word.push(ch),
+                        None => return Err("unclosed single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\')) => word.push(ch),
+                            Some(ch) => {
+                                word.push('\\');
+                                word.push(ch);
+                            }
+                            None => return Err("unclosed double quote".to_string()),
+                        },
+                        Some(ch) => word.push(ch),
+                        None => return Err("unclosed double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(ch) => word.push(ch),
+                    None => return Err("trailing backslash".to_string()),
+                }
+            }
+            ch => {
+                in_word = true;
+                word.push(ch);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
 pub fn normalize_output_format(value: &str) -> &'static str {
     OUTPUT_FORMATS
         .iter()
@@ -385,7 +1296,7 @@ fn parse_fraction(value: &str) -> Option<f64> {
     Some(num / den)
 }
 
-fn format_bytes(bytes: u64) -> String {
+pub fn format_bytes(bytes: u64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
     const GB: f64 = MB * 1024.0;
@@ -427,6 +1338,36 @@ fn scaled_dimension_for_percent(dimension: u32, percent: u32) -> u32 {
     scaled.max(2)
 }
 
+fn centered_aspect_crop(
+    source_width: u32,
+    source_height: u32,
+    ratio_width: u32,
+    ratio_height: u32,
+) -> CropRect {
+    let source_width_u64 = source_width as u64;
+    let source_height_u64 = source_height as u64;
+    let (width, height) =
+        if source_width_u64 * ratio_height as u64 > source_height_u64 * ratio_width as u64 {
+            // Source is wider than the target ratio: keep full height.
+            let width = source_height_u64 * ratio_width as u64 / ratio_height as u64;
+            (width as u32, source_height)
+        } else {
+            let height = source_width_u64 * ratio_height as u64 / ratio_width as u64;
+            (source_width, height as u32)
+        };
+
+    // Keep dimensions even so yuv420p encoders accept the cropped frame.
+    let width = (width - width % 2).max(2).min(source_width);
+    let height = (height - height % 2).max(2).min(source_height);
+
+    CropRect {
+        width,
+        height,
+        x: (source_width - width) / 2,
+        y: (source_height - height) / 2,
+    }
+}
+
 fn bitrate_kbps_from_bits_per_second(bits_per_second: u64) -> Option<u32> {
     if bits_per_second == 0 {
         return None;
@@ -446,6 +1387,7 @@ fn probe_audio_codec(path: &Path) -> io::Result<String> {
         .arg("stream=codec_name")
         .arg("-of")
         .arg("default=noprint_wrappers=1:nokey=1")
+        .args(stream_input_args(path))
         .arg(path)
         .output()?;
 
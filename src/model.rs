@@ -2,7 +2,10 @@
 // - Defines app enums (focus targets, tabs, and active input fields).
 // - Defines core value types like file entries and structured time input.
 // - Keeps common types decoupled from module-specific logic.
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
 
 #[derive(Debug, Clone)]
 pub struct FileEntry {
@@ -10,6 +13,7 @@ pub struct FileEntry {
     pub path: PathBuf,
     pub is_dir: bool,
     pub size_bytes: Option<u64>,
+    pub modified: Option<SystemTime>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,15 +45,17 @@ impl Focus {
 pub enum RightTab {
     Editor,
     Downloader,
+    Concat,
 }
 
 impl RightTab {
-    pub const ALL: [Self; 2] = [Self::Editor, Self::Downloader];
+    pub const ALL: [Self; 3] = [Self::Editor, Self::Downloader, Self::Concat];
 
     pub fn next(self) -> Self {
         match self {
             Self::Editor => Self::Downloader,
-            Self::Downloader => Self::Editor,
+            Self::Downloader => Self::Concat,
+            Self::Concat => Self::Editor,
         }
     }
 
@@ -57,6 +63,7 @@ impl RightTab {
         match self {
             Self::Editor => 1,
             Self::Downloader => 2,
+            Self::Concat => 3,
         }
     }
 
@@ -64,6 +71,7 @@ impl RightTab {
         match self {
             Self::Editor => "Editor",
             Self::Downloader => "Downloader",
+            Self::Concat => "Concat",
         }
     }
 
@@ -71,6 +79,7 @@ impl RightTab {
         match number {
             1 => Some(Self::Editor),
             2 => Some(Self::Downloader),
+            3 => Some(Self::Concat),
             _ => None,
         }
     }
@@ -82,23 +91,272 @@ pub enum DownloaderStep {
     QualitySelect,
 }
 
+/// What the editor does after an export finishes successfully.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AfterExport {
+    /// Keep the current selection and form values.
+    Keep,
+    /// Restore the form defaults for the same media file.
+    Reset,
+    /// Load the next media file in the directory.
+    Next,
+}
+
+impl AfterExport {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "keep" => Some(Self::Keep),
+            "reset" => Some(Self::Reset),
+            "next" => Some(Self::Next),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Reset => "reset",
+            Self::Next => "next",
+        }
+    }
+}
+
+/// Event-loop pacing. The `low-power` profile trades latency for fewer
+/// wakeups over SSH; `snappy` polls faster for local terminals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerfSettings {
+    /// How long the loop waits for input before ticking and redrawing.
+    pub poll_interval: Duration,
+    /// Minimum time between spinner frames.
+    pub spinner_interval: Duration,
+    /// Most output chunks taken from a running job per tick; the rest wait
+    /// for the next one.
+    pub pump_batch: usize,
+    /// Time between checks of the browser's folder for changes.
+    pub dir_poll_interval: Duration,
+}
+
+impl PerfSettings {
+    pub const PROFILE_NAMES: [&str; 3] = ["low-power", "balanced", "snappy"];
+
+    pub fn from_profile(name: &str) -> Option<Self> {
+        let (poll_ms, spinner_ms, pump_batch, dir_poll_ms) = match name {
+            "low-power" => (250, 500, 64, 5000),
+            "balanced" => (100, 100, 256, 1000),
+            "snappy" => (33, 80, 1024, 500),
+            _ => return None,
+        };
+        Some(Self {
+            poll_interval: Duration::from_millis(poll_ms),
+            spinner_interval: Duration::from_millis(spinner_ms),
+            pump_batch,
+            dir_poll_interval: Duration::from_millis(dir_poll_ms),
+        })
+    }
+
+    /// Replaces the values that are set and keeps the rest.
+    pub fn with_overrides(
+        mut self,
+        poll_ms: Option<u64>,
+        spinner_ms: Option<u64>,
+        pump_batch: Option<usize>,
+    ) -> Self {
+        if let Some(ms) = poll_ms {
+            self.poll_interval = Duration::from_millis(ms);
+        }
+        if let Some(ms) = spinner_ms {
+            self.spinner_interval = Duration::from_millis(ms);
+        }
+        if let Some(batch) = pump_batch {
+            self.pump_batch = batch;
+        }
+        self
+    }
+}
+
+impl Default for PerfSettings {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(100),
+            spinner_interval: Duration::from_millis(100),
+            pump_batch: 256,
+            dir_poll_interval: Duration::from_millis(1000),
+        }
+    }
+}
+
+/// Triage tag attached to a file from the browser.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileTag {
+    /// Star rating, 0 (unrated) to `FileTag::MAX_RATING`.
+    pub rating: u8,
+    pub note: String,
+}
+
+impl FileTag {
+    pub const MAX_RATING: u8 = 5;
+
+    pub fn is_empty(&self) -> bool {
+        self.rating == 0 && self.note.is_empty()
+    }
+}
+
+/// Which files the browser lists, based on their tags. Directories are
+/// always shown so navigation keeps working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagFilter {
+    All,
+    /// Any rating or note.
+    Tagged,
+    MinRating(u8),
+}
+
+impl TagFilter {
+    /// Cycles All -> Tagged -> 1+ stars -> ... -> 5 stars -> All.
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Tagged,
+            Self::Tagged => Self::MinRating(1),
+            Self::MinRating(rating) if rating < FileTag::MAX_RATING => Self::MinRating(rating + 1),
+            Self::MinRating(_) => Self::All,
+        }
+    }
+
+    pub fn matches(self, tag: Option<&FileTag>) -> bool {
+        match self {
+            Self::All => true,
+            Self::Tagged => tag.is_some_and(|tag| !tag.is_empty()),
+            Self::MinRating(min) => tag.is_some_and(|tag| tag.rating >= min),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Self::All => "all".to_string(),
+            Self::Tagged => "tagged".to_string(),
+            Self::MinRating(rating) if rating >= FileTag::MAX_RATING => {
+                format!("{rating} stars")
+            }
+            Self::MinRating(rating) => format!("{rating}+ stars"),
+        }
+    }
+}
+
+/// Which audio ends up in the export when the input has several tracks
+/// (e.g. separate mic and desktop audio from OBS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioTrack {
+    /// Ticked streams for video outputs, the first track for audio-only.
+    Auto,
+    /// One track, as a 0-based index among the audio streams.
+    Track(usize),
+    /// All tracks merged into one stereo track.
+    Mix,
+}
+
+impl AudioTrack {
+    /// Cycles Auto -> track 1 -> ... -> track N -> Mix for `track_count` tracks.
+    pub fn cycle(self, track_count: usize, forward: bool) -> Self {
+        let count = track_count + 2;
+        let position = match self {
+            Self::Auto => 0,
+            Self::Track(index) => (index + 1).min(track_count),
+            Self::Mix => track_count + 1,
+        };
+        let next = if forward {
+            (position + 1) % count
+        } else {
+            (position + count - 1) % count
+        };
+        match next {
+            0 => Self::Auto,
+            n if n > track_count => Self::Mix,
+            n => Self::Track(n - 1),
+        }
+    }
+}
+
+/// Paging distance for scrollable panels: Ctrl+u/d move half a page,
+/// PgUp/PgDn a full one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageSize {
+    Half,
+    Full,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputField {
     Start,
     End,
+    Timeline,
     Format,
+    StreamCopy,
+    Waveform,
+    Codec,
+    Gpu,
     Fps,
+    ConstantFps,
     Bitrate,
+    TargetSize,
     ScalePercent,
+    Crop,
+    Deinterlace,
+    Denoise,
+    Sharpen,
+    Color,
+    Brightness,
+    Contrast,
+    Saturation,
+    Gamma,
+    Speed,
+    Caption,
+    CaptionPosition,
+    CaptionSize,
+    CaptionOutline,
     RemoveAudio,
+    Streams,
+    AudioTrack,
+    Channels,
+    Normalize,
+    TwoPass,
+    GifPalette,
+    MaxWidth,
+    FrameSequence,
+    KeepMetadata,
+    FileTime,
+    LogLevel,
+    StatsPeriod,
+    ProgressOnly,
+    ExtraArgs,
     Output,
 }
 
+impl InputField {
+    /// Choice fields cycle with Left/Right (and h/l) instead of taking text.
+    pub fn is_choice(self) -> bool {
+        matches!(
+            self,
+            Self::Timeline
+                | Self::Format
+                | Self::Waveform
+                | Self::Codec
+                | Self::Denoise
+                | Self::Sharpen
+                | Self::CaptionPosition
+                | Self::AudioTrack
+                | Self::Channels
+                | Self::FileTime
+                | Self::LogLevel
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TimeInput {
     hours: String,
     minutes: String,
     seconds: String,
+    millis: String,
 }
 
 impl TimeInput {
@@ -107,11 +365,14 @@ impl TimeInput {
             hours: "00".to_string(),
             minutes: "00".to_string(),
             seconds: "00".to_string(),
+            millis: "000".to_string(),
         }
     }
 
+    /// Rounds to the millisecond the field can hold.
     pub fn from_seconds(seconds: f64) -> Self {
-        let total = seconds.max(0.0).round() as u64;
+        let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+        let total = total_millis / 1000;
         let hours = (total / 3600).min(99);
         let minutes = (total % 3600) / 60;
         let secs = total % 60;
@@ -120,13 +381,59 @@ impl TimeInput {
             hours: format!("{hours:02}"),
             minutes: format!("{minutes:02}"),
             seconds: format!("{secs:02}"),
+            millis: format!("{:03}", total_millis % 1000),
         }
     }
 
+    /// Start of frame `frame` (counted from 0) at `fps`.
+    pub fn from_frame(frame: u64, fps: f64) -> Option<Self> {
+        (fps > 0.0).then(|| Self::from_seconds(frame as f64 / fps))
+    }
+
+    /// Parses `HH:MM:SS`, `MM:SS`, or plain seconds as copied from a
+    /// player, with an optional fraction such as `.25` or `,250`. Digits
+    /// past milliseconds are dropped and `75:00` rolls over to `01:15:00`.
+    pub fn parse_timestamp(text: &str) -> Option<Self> {
+        let parts: Vec<&str> = text.trim().split(':').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+        let (last, leading) = parts.split_last()?;
+        let (whole_seconds, fraction) = last.split_once(['.', ',']).unwrap_or((*last, ""));
+        let mut total = 0_u64;
+        for part in leading.iter().chain(std::iter::once(&whole_seconds)) {
+            if part.is_empty() || !part.chars().all(|ch| ch.is_ascii_digit()) {
+                return None;
+            }
+            total = total
+                .checked_mul(60)?
+                .checked_add(part.parse::<u64>().ok()?)?;
+        }
+        if !fraction.chars().all(|ch| ch.is_ascii_digit()) {
+            return None;
+        }
+        let millis = format!("{fraction:0<3}")[..3].parse::<u64>().ok()?;
+        Some(Self::from_seconds(total as f64 + millis as f64 / 1000.0))
+    }
+
+    /// Parses a `start - end` range such as `1:23:45 - 1:25:10`. En and em
+    /// dashes work too, since that is what chat apps often turn `-` into.
+    pub fn parse_timestamp_range(text: &str) -> Option<(Self, Self)> {
+        let (start, end) = text.split_once(['-', '\u{2013}', '\u{2014}'])?;
+        Some((Self::parse_timestamp(start)?, Self::parse_timestamp(end)?))
+    }
+
+    /// `HH:MM:SS`, with `.mmm` only when the time isn't a whole second.
     pub fn to_ffmpeg_timestamp(&self) -> String {
-        format!("{}:{}:{}", self.hours, self.minutes, self.seconds)
+        let whole = format!("{}:{}:{}", self.hours, self.minutes, self.seconds);
+        if self.millis.parse::<u32>().unwrap_or(0) == 0 {
+            whole
+        } else {
+            format!("{whole}.{}", self.millis)
+        }
     }
 
+    /// Whole seconds; the milliseconds are dropped.
     pub fn to_seconds(&self) -> u32 {
         let hours = self.hours.parse::<u32>().unwrap_or(0);
         let minutes = self.minutes.parse::<u32>().unwrap_or(0);
@@ -134,6 +441,14 @@ impl TimeInput {
         hours * 3600 + minutes * 60 + seconds
     }
 
+    pub fn to_millis(&self) -> u64 {
+        u64::from(self.to_seconds()) * 1000 + self.millis.parse::<u64>().unwrap_or(0)
+    }
+
+    pub fn to_seconds_f64(&self) -> f64 {
+        self.to_millis() as f64 / 1000.0
+    }
+
     pub fn has_valid_minute_second_range(&self) -> bool {
         let minutes = self.minutes.parse::<u32>().unwrap_or(99);
         let seconds = self.seconds.parse::<u32>().unwrap_or(99);
@@ -145,30 +460,32 @@ impl TimeInput {
             0 => &self.hours,
             1 => &self.minutes,
             2 => &self.seconds,
+            3 => &self.millis,
             _ => "00",
         }
     }
 
+    /// Shifts `digit` in from the right, keeping the part's width.
     pub fn push_digit_to_part(&mut self, part_index: usize, digit: char) {
         if !digit.is_ascii_digit() {
             return;
         }
 
-        self.ensure_two_digit_parts();
+        self.ensure_padded_parts();
 
         if let Some(part) = self.part_mut(part_index) {
-            let ones = part.chars().nth(1).unwrap_or('0');
-            *part = format!("{ones}{digit}");
+            let kept = part.chars().skip(1).collect::<String>();
+            *part = format!("{kept}{digit}");
         }
     }
 
     pub fn clear_part(&mut self, part_index: usize) {
         if let Some(part) = self.part_mut(part_index) {
-            *part = "00".to_string();
+            *part = "0".repeat(part.len().max(2));
         }
     }
 
-    fn ensure_two_digit_parts(&mut self) {
+    fn ensure_padded_parts(&mut self) {
         if self.hours.len() != 2 || !self.hours.chars().all(|ch| ch.is_ascii_digit()) {
             self.hours = "00".to_string();
         }
@@ -178,6 +495,9 @@ impl TimeInput {
         if self.seconds.len() != 2 || !self.seconds.chars().all(|ch| ch.is_ascii_digit()) {
             self.seconds = "00".to_string();
         }
+        if self.millis.len() != 3 || !self.millis.chars().all(|ch| ch.is_ascii_digit()) {
+            self.millis = "000".to_string();
+        }
     }
 
     fn part_mut(&mut self, part_index: usize) -> Option<&mut String> {
@@ -185,6 +505,7 @@ impl TimeInput {
             0 => Some(&mut self.hours),
             1 => Some(&mut self.minutes),
             2 => Some(&mut self.seconds),
+            3 => Some(&mut self.millis),
             _ => None,
         }
     }
@@ -192,6 +513,144 @@ impl TimeInput {
 
 #[derive(Debug, Clone, Copy)]
 pub struct VideoBounds {
-    pub start_seconds: u32,
-    pub end_seconds: u32,
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+}
+
+/// Single-line editable text value with a character-based cursor.
+/// Used by editor form fields so new inputs don't need their own
+/// cursor/overwrite bookkeeping.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    overwrite_on_next_type: bool,
+}
+
+impl TextInput {
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+            overwrite_on_next_type: true,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn set(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = self.value.chars().count();
+        self.overwrite_on_next_type = true;
+    }
+
+    /// Moves the cursor to the end and arms overwrite so the first typed
+    /// character replaces the current value (same as the FPS/bitrate fields).
+    pub fn focus(&mut self) {
+        self.cursor = self.value.chars().count();
+        self.overwrite_on_next_type = true;
+    }
+
+    /// Moves the cursor to the end without arming overwrite, so typing
+    /// extends the current value.
+    pub fn focus_append(&mut self) {
+        self.cursor = self.value.chars().count();
+        self.overwrite_on_next_type = false;
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+        self.overwrite_on_next_type = false;
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+        self.overwrite_on_next_type = false;
+    }
+
+    pub fn insert(&mut self, ch: char) {
+        if self.overwrite_on_next_type {
+            self.value.clear();
+            self.cursor = 0;
+        }
+        self.overwrite_on_next_type = false;
+        let byte_index = text_byte_index(&self.value, self.cursor);
+        self.value.insert(byte_index, ch);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        self.overwrite_on_next_type = false;
+        if self.cursor == 0 {
+            return;
+        }
+        let start = text_byte_index(&self.value, self.cursor - 1);
+        let end = text_byte_index(&self.value, self.cursor);
+        self.value.replace_range(start..end, "");
+        self.cursor -= 1;
+    }
+}
+
+fn text_byte_index(input: &str, char_index: usize) -> usize {
+    input
+        .char_indices()
+        .nth(char_index)
+        .map(|(index, _)| index)
+        .unwrap_or(input.len())
+}
+
+/// Vim-style numeric prefix typed before a motion key (e.g. `5j`).
+#[derive(Debug, Default)]
+pub struct CountPrefix {
+    value: Option<usize>,
+    started_at: Option<Instant>,
+}
+
+impl CountPrefix {
+    const MAX: usize = 9_999;
+
+    /// Appends a digit. A leading `0` is rejected so it stays free as a key.
+    pub fn push_digit(&mut self, ch: char) -> bool {
+        let Some(digit) = ch.to_digit(10) else {
+            return false;
+        };
+        if digit == 0 && self.value.is_none() {
+            return false;
+        }
+
+        let value = self.value.unwrap_or(0);
+        self.value = Some((value * 10 + digit as usize).min(Self::MAX));
+        self.started_at.get_or_insert_with(Instant::now);
+        true
+    }
+
+    pub fn pending(&self) -> Option<usize> {
+        self.value
+    }
+
+    /// Consumes the prefix as a repeat count, defaulting to 1.
+    pub fn take(&mut self) -> usize {
+        self.started_at = None;
+        self.value.take().unwrap_or(1)
+    }
+
+    /// Consumes a prefix that sat unused for longer than `timeout`.
+    pub fn take_expired(&mut self, timeout: Duration) -> Option<usize> {
+        if self.started_at?.elapsed() < timeout {
+            return None;
+        }
+        self.started_at = None;
+        self.value.take()
+    }
+
+    pub fn clear(&mut self) {
+        self.value = None;
+        self.started_at = None;
+    }
 }
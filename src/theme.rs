@@ -0,0 +1,187 @@
+// Color schemes for the UI.
+// - `Theme` names the role of every color the panes use (labels, accents,
+//   selection, warnings, ...) instead of hard-coding terminal colors.
+// - `[theme]` in `config.toml` picks `dark`, `light`, or `solarized` and can
+//   override single roles with a color name or `#rrggbb`.
+use ratatui::style::Color;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// Values and other regular text.
+    pub text: Color,
+    /// Secondary text such as inactive tab names.
+    pub muted: Color,
+    /// Hints, separators, and unfocused borders.
+    pub dim: Color,
+    /// Text drawn on the selection, cursor, or another highlight.
+    pub on_highlight: Color,
+    /// Background of selected rows and the focused input.
+    pub selection: Color,
+    /// Background of the text cursor.
+    pub cursor: Color,
+    /// Section titles and timeline markers.
+    pub heading: Color,
+    /// Row labels and keys in the keybinds popup.
+    pub label: Color,
+    /// Editor input labels and the editor's tool output.
+    pub input_label: Color,
+    /// The tab bar and the right pane's border.
+    pub tabs: Color,
+    /// The file browser, popups, and the downloader's tool output.
+    pub accent: Color,
+    /// Tab form borders and marked files.
+    pub panel: Color,
+    /// The selected range on the timeline.
+    pub value: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+fn hex(value: u32) -> Color {
+    Color::Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8)
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            muted: Color::Gray,
+            dim: Color::DarkGray,
+            on_highlight: Color::Black,
+            selection: Color::Gray,
+            cursor: Color::White,
+            heading: Color::Yellow,
+            label: Color::Cyan,
+            input_label: Color::LightMagenta,
+            tabs: Color::Cyan,
+            accent: Color::LightBlue,
+            panel: Color::LightYellow,
+            value: Color::LightCyan,
+            success: Color::LightGreen,
+            warning: Color::Yellow,
+            error: Color::LightRed,
+        }
+    }
+
+    /// For terminals with a light background.
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            muted: Color::DarkGray,
+            dim: Color::Gray,
+            on_highlight: Color::White,
+            selection: Color::DarkGray,
+            cursor: Color::Black,
+            heading: hex(0x875f00),
+            label: hex(0x005f87),
+            input_label: hex(0x870087),
+            tabs: hex(0x005f87),
+            accent: hex(0x005fd7),
+            panel: hex(0x875f00),
+            value: hex(0x008787),
+            success: hex(0x008700),
+            warning: hex(0xaf5f00),
+            error: hex(0xd70000),
+        }
+    }
+
+    /// Solarized dark.
+    pub fn solarized() -> Self {
+        Self {
+            text: hex(0x93a1a1),
+            muted: hex(0x839496),
+            dim: hex(0x586e75),
+            on_highlight: hex(0x002b36),
+            selection: hex(0x93a1a1),
+            cursor: hex(0xeee8d5),
+            heading: hex(0xb58900),
+            label: hex(0x2aa198),
+            input_label: hex(0xd33682),
+            tabs: hex(0x268bd2),
+            accent: hex(0x268bd2),
+            panel: hex(0xb58900),
+            value: hex(0x2aa198),
+            success: hex(0x859900),
+            warning: hex(0xcb4b16),
+            error: hex(0xdc322f),
+        }
+    }
+
+    fn named(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            _ => None,
+        }
+    }
+
+    /// Every role and its color, in `[theme]` order.
+    pub fn roles(&self) -> [(&'static str, Color); 16] {
+        [
+            ("text", self.text),
+            ("muted", self.muted),
+            ("dim", self.dim),
+            ("on_highlight", self.on_highlight),
+            ("selection", self.selection),
+            ("cursor", self.cursor),
+            ("heading", self.heading),
+            ("label", self.label),
+            ("input_label", self.input_label),
+            ("tabs", self.tabs),
+            ("accent", self.accent),
+            ("panel", self.panel),
+            ("value", self.value),
+            ("success", self.success),
+            ("warning", self.warning),
+            ("error", self.error),
+        ]
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "dim" => &mut self.dim,
+            "on_highlight" => &mut self.on_highlight,
+            "selection" => &mut self.selection,
+            "cursor" => &mut self.cursor,
+            "heading" => &mut self.heading,
+            "label" => &mut self.label,
+            "input_label" => &mut self.input_label,
+            "tabs" => &mut self.tabs,
+            "accent" => &mut self.accent,
+            "panel" => &mut self.panel,
+            "value" => &mut self.value,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            _ => return None,
+        })
+    }
+
+    /// `name` picks the base scheme, the other entries replace single roles.
+    pub fn from_entries<'a>(
+        entries: impl IntoIterator<Item = (&'a str, String)>,
+    ) -> Result<Self, String> {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+        let mut theme = match entries.iter().find(|(key, _)| *key == "name") {
+            Some((_, name)) => {
+                Self::named(name.trim()).ok_or_else(|| format!("unknown theme `{name}`"))?
+            }
+            None => Self::dark(),
+        };
+        for (role, value) in entries.iter().filter(|(key, _)| *key != "name") {
+            let color = value
+                .trim()
+                .parse::<Color>()
+                .map_err(|_| format!("{role}: unknown color `{value}`"))?;
+            let slot = theme
+                .role_mut(role)
+                .ok_or_else(|| format!("unknown color role `{role}`"))?;
+            *slot = color;
+        }
+        Ok(theme)
+    }
+}
@@ -1,13 +1,17 @@
 // Root UI composition and shared visual components.
 // - Builds the global layout (left browser + right tab area + footer).
-// - Renders shared chrome: tab bar, keybind popup, and delete-confirm modal.
+// - Renders shared chrome: tab bar, keybind popup, confirm modals, and note prompt.
+// - Lays out browser rows as name, duration badge, age, and size columns.
 // - Delegates tab-specific rendering to ui::tabs submodules.
+// - Reports where each pane was drawn so mouse clicks can be hit-tested.
 mod output_panel;
 mod tabs;
 
+use std::time::{Duration, SystemTime};
+
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Layout},
+    layout::{Alignment, Constraint, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap},
@@ -15,30 +19,87 @@ use ratatui::{
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::{
-    app::App,
-    media::is_editable_media_file,
-    model::{Focus, RightTab},
+    app::{App, FileOpKind, HealthLevel, breadcrumb_segments, stars},
+    keymap::Action,
+    media::format_bytes,
+    model::{FileTag, Focus, RightTab, TagFilter, TextInput, TimeInput},
+    theme::Theme,
 };
 
-pub fn render(frame: &mut Frame, app: &App, focus: Focus) {
+/// Where the last frame drew each pane, for mouse hit tests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaneLayout {
+    pub files: Rect,
+    /// The file list inside the border.
+    pub files_list: Rect,
+    /// Entry index shown on the first row of `files_list`.
+    pub files_offset: usize,
+    pub tabs: Rect,
+    pub right_top: Rect,
+    pub right_bottom: Rect,
+}
+
+impl PaneLayout {
+    /// The file entry under a click, if the row holds one.
+    pub fn entry_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.files_list
+            .contains(Position::new(column, row))
+            .then(|| self.files_offset + usize::from(row - self.files_list.y))
+    }
+
+    /// The tab whose label is under a click in the tab bar.
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<RightTab> {
+        if !self.tabs.contains(Position::new(column, row)) {
+            return None;
+        }
+        // Labels sit inside the border, each padded by a space on both
+        // sides and followed by a one-cell divider.
+        let mut start = self.tabs.x + 1;
+        for tab in RightTab::ALL {
+            let end = start + display_width(&tab_label(tab)) as u16 + 2;
+            if (start..end).contains(&column) {
+                return Some(tab);
+            }
+            start = end + 1;
+        }
+        None
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &App, focus: Focus) -> PaneLayout {
     let [content, footer] =
         Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
-    let [left, right] =
-        Layout::horizontal([Constraint::Percentage(34), Constraint::Percentage(66)]).areas(content);
+    let [left, right] = Layout::horizontal([
+        Constraint::Percentage(app.files_pane_percent()),
+        Constraint::Min(0),
+    ])
+    .areas(content);
     let [tabs_area, right_content] =
         Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(right);
 
-    render_files_pane(frame, app, focus, left);
+    let (files_list, files_offset) = render_files_pane(frame, app, focus, left);
     render_right_tabs(frame, app, focus, tabs_area);
 
-    match app.right_tab() {
+    let [right_top, right_bottom] = match app.right_tab() {
         RightTab::Editor => tabs::editor::render_editor_tab(frame, app, focus, right_content),
         RightTab::Downloader => {
             tabs::downloader::render_downloader_tab(frame, app, focus, right_content)
         }
-    }
+        RightTab::Concat => tabs::concat::render_concat_tab(frame, app, focus, right_content),
+    };
+    let layout = PaneLayout {
+        files: left,
+        files_list,
+        files_offset,
+        tabs: tabs_area,
+        right_top,
+        right_bottom,
+    };
 
-    render_footer_hint(frame, footer);
+    render_footer(frame, app, focus, footer);
+    if app.screen_reader() {
+        place_reader_cursor(frame, app, focus, &layout, footer);
+    }
     if app.show_keybinds {
         render_keybinds_popup(frame, app);
     }
@@ -46,52 +107,118 @@ pub fn render(frame: &mut Frame, app: &App, focus: Focus) {
         render_delete_confirm_modal(frame, app);
     } else if app.has_pending_cancel() {
         render_cancel_confirm_modal(frame, app);
+    } else if app.has_pending_export() {
+        render_export_confirm_modal(frame, app);
+    } else if app.has_note_prompt() {
+        render_note_prompt(frame, app);
+    } else if app.has_stream_prompt() {
+        render_stream_prompt(frame, app);
+    } else if app.has_path_jump() {
+        render_path_jump(frame, app);
+    } else if app.has_file_op_prompt() {
+        render_file_op_prompt(frame, app);
+    } else if app.has_batch_prompt() {
+        render_batch_prompt(frame, app);
+    } else if app.has_batch_review() {
+        render_batch_review(frame, app);
+    } else if app.has_cleanup_prompt() {
+        render_cleanup_prompt(frame, app);
+    } else if app.has_cleanup_review() {
+        render_cleanup_review(frame, app);
+    } else if app.has_preset_picker() {
+        render_preset_picker(frame, app);
+    } else if app.has_bookmark_picker() {
+        render_bookmark_picker(frame, app);
+    } else if app.health_check().is_some() {
+        render_health_check(frame, app);
+    } else if app.changelog().is_some() {
+        render_changelog(frame, app);
+    } else if app.job_stats().is_some() {
+        render_job_stats(frame, app);
     }
+    layout
 }
 
 fn render_right_tabs(frame: &mut Frame, app: &App, focus: Focus, area: ratatui::layout::Rect) {
+    let theme = app.theme();
     let selected = RightTab::ALL
         .iter()
         .position(|tab| *tab == app.right_tab())
         .unwrap_or(0);
     let labels = RightTab::ALL
         .iter()
-        .map(|tab| Line::from(format!(" {} {} ", tab.number(), tab.label())))
+        .map(|tab| {
+            if app.screen_reader() && *tab == app.right_tab() {
+                Line::from(format!("[{}]", tab_label(*tab).trim()))
+            } else {
+                Line::from(tab_label(*tab))
+            }
+        })
         .collect::<Vec<_>>();
 
     let tabs = Tabs::new(labels)
         .select(selected)
-        .divider(Span::styled("|", Style::default().fg(Color::DarkGray)))
-        .style(Style::default().fg(Color::Gray))
+        .divider(Span::styled("|", Style::default().fg(theme.dim)))
+        .style(Style::default().fg(theme.muted))
         .highlight_style(
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
+                .fg(theme.on_highlight)
+                .bg(theme.tabs)
                 .add_modifier(Modifier::BOLD),
         )
         .block(
             Block::default()
-                .borders(Borders::ALL)
+                .borders(pane_borders(app))
                 .title_top(Line::from("Tabs").left_aligned())
-                .title_top(
-                    Line::styled("(ctrl+n)", Style::default().fg(Color::DarkGray)).right_aligned(),
-                )
-                .border_style(pane_border_style(focus != Focus::Left, Color::Cyan)),
+                .title_top(Line::styled("(ctrl+n)", Style::default().fg(theme.dim)).right_aligned())
+                .border_style(pane_border_style(theme, focus != Focus::Left, theme.tabs)),
         );
 
     frame.render_widget(tabs, area);
 }
 
-fn render_files_pane(frame: &mut Frame, app: &App, focus: Focus, area: ratatui::layout::Rect) {
+fn tab_label(tab: RightTab) -> String {
+    format!(" {} {} ", tab.number(), tab.label())
+}
+
+/// Returns the list area inside the border and the index of its first row.
+fn render_files_pane(
+    frame: &mut Frame,
+    app: &App,
+    focus: Focus,
+    area: ratatui::layout::Rect,
+) -> (Rect, usize) {
+    let theme = app.theme();
     // Account for borders and highlight symbol so selected rows stay aligned.
     let content_width = area.width.saturating_sub(4) as usize;
+    let now = SystemTime::now();
+    // The marker column only takes room while something is marked.
+    let mark_column = app.marked_count() > 0;
     let file_items = app
         .entries
         .iter()
         .map(|entry| {
-            let line = format_file_row(entry, content_width);
-            if is_editable_media_file(&entry.path) {
-                ListItem::new(Line::styled(line, Style::default().fg(Color::LightGreen)))
+            let mark = app.mark_number(&entry.path);
+            let (icon, color) = app.file_type_label(entry);
+            let columns = FileColumns {
+                duration: app.entry_duration(entry),
+                age: entry
+                    .modified
+                    .and_then(|modified| now.duration_since(modified).ok()),
+            };
+            let line = format_file_row(
+                entry,
+                icon,
+                mark,
+                mark_column,
+                app.file_tag(&entry.path),
+                columns,
+                content_width,
+            );
+            if mark.is_some() {
+                ListItem::new(Line::styled(line, Style::default().fg(theme.panel)))
+            } else if let Ok(color) = color.parse::<Color>() {
+                ListItem::new(Line::styled(line, Style::default().fg(color)))
             } else {
                 ListItem::new(line)
             }
@@ -99,26 +226,51 @@ fn render_files_pane(frame: &mut Frame, app: &App, focus: Focus, area: ratatui::
         .collect::<Vec<_>>();
 
     let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(pane_border_style(focus == Focus::Left, Color::LightBlue))
-        .title_top(Line::from(format!("Files: {}", app.cwd.display())).left_aligned())
-        .title_top(Line::styled("(esc)", Style::default().fg(Color::DarkGray)).right_aligned());
+        .borders(pane_borders(app))
+        .border_style(pane_border_style(theme, focus == Focus::Left, theme.accent))
+        .title_top(files_pane_title(app).left_aligned())
+        .title_top(Line::styled("(esc)", Style::default().fg(theme.dim)).right_aligned());
+    let block = match app.type_ahead_prefix() {
+        Some(prefix) => block.title_bottom(Line::from(vec![
+            Span::styled(" find: ", Style::default().fg(theme.dim)),
+            Span::styled(
+                format!("{prefix}_ "),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])),
+        None => block,
+    };
+    let block = match app.fuzzy_filter() {
+        Some((query, matches)) => block.title_bottom(Line::from(vec![
+            Span::styled(" filter: ", Style::default().fg(theme.dim)),
+            Span::styled(
+                format!("{query}_"),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" {matches} match(es) "),
+                Style::default().fg(theme.dim),
+            ),
+        ])),
+        None => block,
+    };
     let inner = block.inner(area);
     let visible_rows = inner.height as usize;
     app.set_file_browser_visible_rows(visible_rows);
 
     let mut list_state = ListState::default();
+    let mut offset = 0;
     if !app.entries.is_empty() {
         let selected = app.selected.min(app.entries.len().saturating_sub(1));
-        let centered_offset = if visible_rows == 0 {
-            0
-        } else {
+        if visible_rows > 0 {
             let max_offset = app.entries.len().saturating_sub(visible_rows);
-            selected.saturating_sub(visible_rows / 2).min(max_offset)
-        };
-        list_state = list_state
-            .with_offset(centered_offset)
-            .with_selected(Some(selected));
+            offset = selected.saturating_sub(visible_rows / 2).min(max_offset);
+        }
+        list_state = list_state.with_offset(offset).with_selected(Some(selected));
     }
 
     let files = List::new(file_items)
@@ -127,9 +279,11 @@ fn render_files_pane(frame: &mut Frame, app: &App, focus: Focus, area: ratatui::
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(files, area, &mut list_state);
+    (inner, offset)
 }
 
 fn render_keybinds_popup(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
     let outer = frame.area();
     let [vertical] = Layout::vertical([Constraint::Percentage(70)])
         .flex(ratatui::layout::Flex::Center)
@@ -140,37 +294,219 @@ fn render_keybinds_popup(frame: &mut Frame, app: &App) {
 
     frame.render_widget(Clear, popup);
 
-    let block = Block::default().borders(Borders::ALL).title("Keybinds");
+    let block = Block::default()
+        .borders(pane_borders(app))
+        .title("Keybinds");
     let inner = block.inner(popup);
+    let keys = app.keymap();
 
     let lines = vec![
         Line::from("Press ? to close this window. Press Esc to close and focus file browser."),
         Line::from(""),
-        keybind_section("GLOBAL"),
-        keybind_row("?", "toggle keybinds popup"),
-        keybind_row("Esc", "close modal/popup + focus file browser"),
-        keybind_row("Ctrl+c", "quit app"),
-        keybind_row("Up/Down or j/k", "scroll keybinds"),
-        keybind_row("PgUp/PgDn or Ctrl+u/d", "page keybinds"),
-        keybind_row("Tab / Shift+Tab", "move through inputs"),
-        keybind_row("Space", "toggle checkbox"),
+        keybind_section(theme, "GLOBAL"),
+        keybind_row(theme, "?", "toggle keybinds popup"),
+        keybind_row(theme, "Esc", "close modal/popup + focus file browser"),
+        keybind_row(theme, "Ctrl+c", "quit app"),
+        keybind_row(theme, "Up/Down or j/k", "scroll keybinds"),
+        keybind_row(
+            theme,
+            "<count> + motion",
+            "repeat j/k/PgUp/PgDn/Ctrl+u/d (e.g. 5j)",
+        ),
+        keybind_row(theme, "PgUp/PgDn or Ctrl+u/d", "page keybinds"),
+        keybind_row(
+            theme,
+            "g/G or Home/End",
+            "jump to first/last entry or log line",
+        ),
+        keybind_row(
+            theme,
+            "Ctrl+u/d, PgUp/PgDn",
+            "half/full page in tool output",
+        ),
+        keybind_row(theme, "Tab / Shift+Tab", "move through inputs"),
+        keybind_row(theme, "Space", "toggle checkbox"),
+        Line::from(""),
+        keybind_section(theme, "WINDOW FOCUS"),
+        keybind_row(theme, "Ctrl+Left/h/Right/l/Up/k/Down/j", "focus panels"),
+        keybind_row(theme, "Ctrl+o", "focus tool output"),
+        keybind_row(theme, "Ctrl+n", "toggle tabs"),
+        keybind_row(theme, "Alt+Left/Right", "resize file browser"),
+        keybind_row(theme, "Alt+Up/Down", "resize focused tool output"),
+        keybind_row(theme, "1/2/3", "switch tab (after a short pause)"),
+        Line::from(""),
+        keybind_section(theme, "FILE BROWSER"),
+        browser_keybind_row(app, &[Action::Open], "select"),
+        browser_keybind_row(app, &[Action::Parent], "parent directory"),
+        browser_keybind_row(app, &[Action::InitialDir], "initial directory"),
+        browser_keybind_row(
+            app,
+            &[Action::Newest, Action::Largest],
+            "select newest / largest file",
+        ),
+        keybind_row(
+            theme,
+            &format!("{} + d/h/v", keys.keys_label(Action::Go)),
+            "Downloads / home / Videos directory",
+        ),
+        keybind_row(
+            theme,
+            &format!("{0}, [N]{0}", keys.keys_label(Action::Breadcrumb)),
+            "pick an ancestor in the title (h/l, Enter)",
+        ),
+        keybind_row(
+            theme,
+            &format!("{} <path>", keys.keys_label(Action::PathJump)),
+            "go to a typed path (Tab completes folders)",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::Bookmark],
+            "bookmark this directory (again removes it)",
+        ),
+        keybind_row(
+            theme,
+            &format!(
+                "{}, {} + 1-9",
+                keys.keys_label(Action::Bookmarks),
+                keys.keys_label(Action::Go)
+            ),
+            "pick a bookmark / open bookmark N",
+        ),
+        keybind_row(
+            theme,
+            &format!("{} <letters>", keys.keys_label(Action::Find)),
+            "jump to the next name with that prefix (Tab next)",
+        ),
+        keybind_row(
+            theme,
+            &format!("{} <letters>", keys.keys_label(Action::Filter)),
+            "fuzzy filter the listing (Up/Down, Enter jumps, Esc)",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::OpenExternal],
+            "open selected file in system default app",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::OpenLastOutput],
+            "open last export/download (also in tool output)",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::Delete],
+            "move marked files (or selected file) to the trash",
+        ),
+        browser_keybind_row(app, &[Action::UndoDelete], "undo the last delete"),
+        browser_keybind_row(app, &[Action::Rename], "rename selected file or folder"),
+        browser_keybind_row(
+            app,
+            &[Action::Move],
+            "move marked (or selected) to a folder",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::Copy],
+            "copy marked (or selected) to a folder",
+        ),
+        browser_keybind_row(app, &[Action::NewDir], "create a directory here"),
+        browser_keybind_row(app, &[Action::Rate], "cycle star rating (0-5)"),
+        browser_keybind_row(app, &[Action::Note], "edit note for selected file"),
+        browser_keybind_row(app, &[Action::TagFilter], "filter: all/tagged/min stars"),
+        browser_keybind_row(app, &[Action::ToggleHidden], "show/hide dotfiles"),
+        browser_keybind_row(
+            app,
+            &[Action::MediaOnly],
+            "list only folders and media files",
+        ),
+        browser_keybind_row(app, &[Action::Mark], "mark/unmark file"),
+        browser_keybind_row(app, &[Action::ClearMarks], "clear all marks"),
+        browser_keybind_row(
+            app,
+            &[Action::Playlist],
+            "write m3u8 playlist of marked media",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::ContactSheet],
+            "create 4x4 contact sheet of selected video",
+        ),
+        browser_keybind_row(app, &[Action::Verify], "verify file decodes without errors"),
+        browser_keybind_row(
+            app,
+            &[Action::Remux],
+            "repair: remux into a fresh container",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::AddToConcat],
+            "add marked (or selected) videos to concat list",
+        ),
+        browser_keybind_row(app, &[Action::Stats], "export and download statistics"),
+        browser_keybind_row(
+            app,
+            &[Action::Batch],
+            "convert marked (or all) media with current settings",
+        ),
+        browser_keybind_row(
+            app,
+            &[Action::Cleanup],
+            "clean up old numbered exports or files matching a glob",
+        ),
+        browser_keybind_row(app, &[Action::Health], "health check of tools and folders"),
+        browser_keybind_row(
+            app,
+            &[Action::ExportConfig],
+            "write current settings and keys to rt-config.toml",
+        ),
+        browser_keybind_row(app, &[Action::Changelog], "version history"),
+        browser_keybind_row(app, &[Action::Reload], "refresh listing"),
         Line::from(""),
-        keybind_section("WINDOW FOCUS"),
-        keybind_row("Ctrl+Left/h/Right/l/Up/k/Down/j", "focus panels"),
-        keybind_row("Ctrl+o", "focus tool output"),
-        keybind_row("Ctrl+n", "toggle tabs"),
+        keybind_section(theme, "EDITOR PANEL"),
+        keybind_row(theme, "Backspace", "back to URL step"),
+        keybind_row(
+            theme,
+            "Left/Right or h/l",
+            "cycle choice fields (format, codec, caption position, audio track, log level)",
+        ),
+        keybind_row(theme, "Enter", "run editor export"),
+        keybind_row(
+            theme,
+            "Timeline: Left/Right, H/L",
+            "move marker 1s / 10s (Space: start or end)",
+        ),
+        keybind_row(theme, "Ctrl+b", "analyze bitrate over time"),
+        keybind_row(
+            theme,
+            "Ctrl+s",
+            "snap start to previous keyframe (end: to nearest)",
+        ),
+        keybind_row(
+            theme,
+            "Ctrl+r",
+            "play Start-End in mpv/ffplay (again: stop)",
+        ),
+        keybind_row(theme, "Ctrl+t", "toggle frame preview at the focused time"),
+        keybind_row(theme, "Ctrl+a", "add Start-End as a segment to join"),
+        keybind_row(theme, "Ctrl+x", "remove the last segment"),
+        keybind_row(theme, "Ctrl+p", "export presets: apply, save, delete"),
+        keybind_row(theme, "Ctrl+y", "copy ffmpeg command to clipboard"),
+        keybind_row(theme, "Ctrl+g", "open an http(s)/rtmp stream URL"),
         Line::from(""),
-        keybind_section("FILE BROWSER"),
-        keybind_row("Enter", "select"),
-        keybind_row("h/-", "parent directory"),
-        keybind_row("_", "initial directory"),
-        keybind_row("x", "open selected file in system default app"),
-        keybind_row("d", "delete file"),
-        keybind_row("r", "refresh listing"),
+        keybind_section(theme, "DOWNLOADER PANEL"),
+        keybind_row(
+            theme,
+            "Ctrl+r",
+            "probe the URL again (skips the session cache)",
+        ),
         Line::from(""),
-        keybind_section("EDITOR PANEL"),
-        keybind_row("Backspace", "back to URL step"),
-        keybind_row("Enter", "run editor export"),
+        keybind_section(theme, "CONCAT PANEL"),
+        keybind_row(theme, "j/k or Up/Down", "select clip"),
+        keybind_row(theme, "J/K", "move clip down/up"),
+        keybind_row(theme, "d", "remove clip"),
+        keybind_row(theme, "D", "clear list"),
+        keybind_row(theme, "Enter", "join clips"),
     ];
 
     let visible_line_count = inner.height.max(1) as usize;
@@ -184,8 +520,12 @@ fn render_keybinds_popup(frame: &mut Frame, app: &App) {
     frame.render_widget(popup_widget, popup);
 }
 
+/// Marked files listed in the delete confirmation before `... and N more`.
+const DELETE_PREVIEW_PATHS: usize = 8;
+
 fn render_delete_confirm_modal(frame: &mut Frame, app: &App) {
-    let Some((name, path)) = app.pending_delete_target() else {
+    let theme = app.theme();
+    let Some((name, paths)) = app.pending_delete_target() else {
         return;
     };
 
@@ -199,29 +539,68 @@ fn render_delete_confirm_modal(frame: &mut Frame, app: &App) {
 
     frame.render_widget(Clear, popup);
 
-    let lines = vec![
+    let dir_delete = app.pending_dir_delete();
+    let mut lines = vec![
         Line::styled(
-            "Delete this file?",
+            if dir_delete.is_some() {
+                "Delete this folder and everything in it?".to_string()
+            } else if paths.len() == 1 {
+                "Delete this file?".to_string()
+            } else {
+                format!("Delete {name}?")
+            },
             Style::default()
-                .fg(Color::LightRed)
+                .fg(theme.error)
                 .add_modifier(Modifier::BOLD),
         ),
         Line::from(""),
-        Line::from(format!("Name: {name}")),
-        Line::from(format!("Path: {}", path.display())),
+    ];
+    match paths {
+        [path] => {
+            lines.push(Line::from(format!("Name: {name}")));
+            lines.push(Line::from(format!("Path: {}", path.display())));
+            if let Some((contents, _)) = dir_delete.as_ref() {
+                lines.push(Line::from(format!("Contains: {contents}")));
+            }
+        }
+        _ => {
+            for path in paths.iter().take(DELETE_PREVIEW_PATHS) {
+                lines.push(Line::from(format!("  {}", path.display())));
+            }
+            if paths.len() > DELETE_PREVIEW_PATHS {
+                lines.push(Line::from(format!(
+                    "  ... and {} more",
+                    paths.len() - DELETE_PREVIEW_PATHS
+                )));
+            }
+        }
+    }
+    lines.extend([
         Line::from(""),
-        Line::from("This cannot be undone."),
+        Line::from(if app.pending_delete_empties_rt_trash() {
+            "Files in rt-trash are removed for good; this can't be undone."
+        } else {
+            "Files go to the trash; u undoes the delete for 10 minutes."
+        }),
         Line::from(""),
-        Line::from("Press y or Enter to confirm."),
-        Line::from("Press n or Esc to cancel."),
-    ];
+    ]);
+    match dir_delete {
+        Some((_, input)) => lines.extend([
+            Line::from(format!("Type {name} or yes, then Enter. Esc cancels.")),
+            note_input_line(theme, input),
+        ]),
+        None => lines.extend([
+            Line::from("Press y or Enter to confirm."),
+            Line::from("Press n or Esc to cancel."),
+        ]),
+    }
 
     let popup_widget = Paragraph::new(lines)
         .block(
             Block::default()
-                .borders(Borders::ALL)
+                .borders(pane_borders(app))
                 .title("Confirm Delete")
-                .border_style(pane_border_style(true, Color::LightRed)),
+                .border_style(pane_border_style(theme, true, theme.error)),
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
@@ -230,6 +609,7 @@ fn render_delete_confirm_modal(frame: &mut Frame, app: &App) {
 }
 
 fn render_cancel_confirm_modal(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
     let Some(label) = app.pending_cancel_label() else {
         return;
     };
@@ -248,7 +628,7 @@ fn render_cancel_confirm_modal(frame: &mut Frame, app: &App) {
         Line::styled(
             "Cancel running tool?",
             Style::default()
-                .fg(Color::LightRed)
+                .fg(theme.error)
                 .add_modifier(Modifier::BOLD),
         ),
         Line::from(""),
@@ -261,9 +641,9 @@ fn render_cancel_confirm_modal(frame: &mut Frame, app: &App) {
     let popup_widget = Paragraph::new(lines)
         .block(
             Block::default()
-                .borders(Borders::ALL)
+                .borders(pane_borders(app))
                 .title("Confirm Cancel")
-                .border_style(pane_border_style(true, Color::LightRed)),
+                .border_style(pane_border_style(theme, true, theme.error)),
         )
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
@@ -271,96 +651,1285 @@ fn render_cancel_confirm_modal(frame: &mut Frame, app: &App) {
     frame.render_widget(popup_widget, popup);
 }
 
-fn keybind_section(title: &str) -> Line<'static> {
-    Line::styled(
-        title.to_string(),
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    )
-}
+fn render_export_confirm_modal(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(summary) = app.pending_export_summary() else {
+        return;
+    };
 
-fn keybind_row(keys: &str, action: &str) -> Line<'static> {
-    const KEY_COL_WIDTH: usize = 32;
-    let keys_padded = format!("{keys:<KEY_COL_WIDTH$}");
-    Line::from(vec![
-        Span::styled(
-            keys_padded,
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Percentage(38)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let low_space = app.pending_export_low_space();
+    let mut lines = vec![
+        Line::styled(
+            if low_space.is_some() {
+                "Start this export with low disk space?"
+            } else {
+                "Start this large export?"
+            },
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.panel)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("  "),
-        Span::raw(action.to_string()),
-    ])
+        Line::from(""),
+        Line::from(summary.to_string()),
+        Line::from(""),
+    ];
+    if let Some(warning) = low_space {
+        lines.extend([
+            Line::styled(format!("{warning}."), Style::default().fg(theme.warning)),
+            Line::from(""),
+        ]);
+    }
+    lines.extend([
+        Line::from("Press y or Enter to start."),
+        Line::from("Press n or Esc to go back."),
+    ]);
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Confirm Export")
+                .border_style(pane_border_style(theme, true, theme.panel)),
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(popup_widget, popup);
 }
 
-fn render_footer_hint(frame: &mut Frame, area: ratatui::layout::Rect) {
-    let hint = Paragraph::new(Line::styled(
-        "Press ? to see keyboard shortcuts",
-        Style::default().fg(Color::DarkGray),
-    ))
-    .alignment(Alignment::Left);
-    frame.render_widget(hint, area);
+fn render_note_prompt(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((name, input)) = app.note_prompt() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(8)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let lines = vec![
+        Line::from(format!("File: {name}")),
+        Line::from(""),
+        note_input_line(theme, input),
+        Line::from(""),
+        Line::styled(
+            "Enter saves (empty clears), Esc cancels.",
+            Style::default().fg(theme.dim),
+        ),
+    ];
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Note")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
 }
 
-pub(super) fn pane_border_style(is_focused: bool, focused_color: Color) -> Style {
-    if is_focused {
-        Style::default()
-            .fg(focused_color)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    }
+fn render_stream_prompt(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((input, error, probing)) = app.stream_prompt() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(8)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let hint = match error {
+        Some(error) => Line::styled(error.to_string(), Style::default().fg(theme.error)),
+        None if probing => Line::styled(
+            "Probing over the network (up to 15s per step)... Esc cancels.",
+            Style::default().fg(theme.warning),
+        ),
+        None => Line::styled(
+            "Enter probes it over the network (15s timeout), Esc cancels.",
+            Style::default().fg(theme.dim),
+        ),
+    };
+    let lines = vec![
+        Line::from("http(s) or rtmp(s) URL to trim without downloading:"),
+        Line::from(""),
+        note_input_line(theme, input),
+        Line::from(""),
+        hint,
+    ];
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Open Stream")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
 }
 
-fn file_size_label(entry: &crate::model::FileEntry) -> String {
-    if entry.is_dir {
-        "<DIR>".to_string()
-    } else if let Some(bytes) = entry.size_bytes {
-        format_size(bytes)
-    } else {
-        "?".to_string()
+fn render_path_jump(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(jump) = app.path_jump() else {
+        return;
+    };
+
+    let match_rows = jump.matches.len() as u16 + u16::from(jump.more > 0);
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(8 + match_rows)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let dim = Style::default().fg(theme.dim);
+    let mut lines = vec![
+        Line::from("Folder or file to open (absolute, ~, or relative):"),
+        Line::from(""),
+        note_input_line(theme, jump.input),
+    ];
+    for name in jump.matches {
+        lines.push(Line::styled(
+            format!("  {name}/"),
+            Style::default().fg(theme.accent),
+        ));
     }
+    if jump.more > 0 {
+        lines.push(Line::styled(format!("  ... and {} more", jump.more), dim));
+    }
+    lines.push(Line::from(""));
+    lines.push(match jump.error {
+        Some(error) => Line::styled(error.to_string(), Style::default().fg(theme.error)),
+        None => Line::styled("Tab completes, Enter opens, Esc cancels.", dim),
+    });
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Go to Path")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
 }
 
-fn format_file_row(entry: &crate::model::FileEntry, content_width: usize) -> String {
-    let prefix = format!("{} ", file_type_icon(entry));
-    let size = file_size_label(entry);
-    let prefix_len = display_width(&prefix);
-    let size_len = display_width(&size);
+fn render_file_op_prompt(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(view) = app.file_op_prompt() else {
+        return;
+    };
 
-    let available_name_width = content_width.saturating_sub(prefix_len + size_len + 1);
-    let name = truncate_middle_with_ellipsis(&entry.name, available_name_width);
-    let left = format!("{prefix}{name}");
-    let left_len = display_width(&left);
-    let spaces = content_width.saturating_sub(left_len + size_len).max(1);
-    let row = format!("{left}{}{}", " ".repeat(spaces), size);
-    truncate_to_width(&row, content_width)
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(8)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let heading = match view.kind {
+        FileOpKind::Rename => format!("New name for {}:", view.subject),
+        FileOpKind::Move => format!("Move {} to (folder or new name):", view.subject),
+        FileOpKind::Copy => format!("Copy {} to (folder or new name):", view.subject),
+        FileOpKind::NewDir => format!("New directory in {}:", view.subject),
+    };
+    let hint = match (view.overwrites, view.error) {
+        (Some(count), _) => Line::styled(
+            format!("{count} file(s) already exist. Overwrite? y/Enter replaces, n edits."),
+            Style::default().fg(theme.warning),
+        ),
+        (None, Some(error)) => Line::styled(error.to_string(), Style::default().fg(theme.error)),
+        (None, None) => Line::styled(
+            "Enter applies, Esc cancels.",
+            Style::default().fg(theme.dim),
+        ),
+    };
+    let lines = vec![
+        Line::from(heading),
+        Line::from(""),
+        note_input_line(theme, view.input),
+        Line::from(""),
+        hint,
+    ];
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title(view.kind.title())
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
 }
 
-fn file_type_icon(entry: &crate::model::FileEntry) -> &'static str {
-    if entry.is_dir {
-        return "";
-    }
+fn render_batch_prompt(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(input) = app.batch_prompt_input() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(10)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
 
-    let ext = entry
-        .path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.to_ascii_lowercase());
+    frame.render_widget(Clear, popup);
 
-    match ext.as_deref() {
-        Some("mp4" | "mov" | "mkv" | "avi" | "webm" | "m4v" | "mpeg" | "mpg" | "wmv" | "flv") => {
-            ""
-        }
-        Some("mp3" | "wav" | "flac" | "aac" | "ogg" | "m4a") => "",
-        Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" | "svg") => "",
-        Some("zip" | "tar" | "gz" | "bz2" | "xz" | "7z" | "rar") => "",
-        Some("pdf") => "",
-        Some("md" | "txt" | "rtf") => "",
-        Some("rs" | "toml" | "json" | "yaml" | "yml" | "ts" | "js" | "py" | "go" | "java") => "󰈙",
-        _ => "",
+    let (queued, skipped) = app.batch_prompt_match_counts();
+    let mut matches = format!("{queued} file(s) to convert");
+    if skipped > 0 {
+        matches.push_str(&format!(", {skipped} already converted"));
+    }
+    let skip_existing = if app.batch_prompt_skips_existing() {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    let scope = if app.marked_count() > 0 {
+        "Export the marked media files with the current editor settings."
+    } else {
+        "Export every media file here with the current editor settings."
+    };
+    let lines = vec![
+        Line::from(scope),
+        Line::from(format!(
+            "Extensions (e.g. mp4 mkv, empty for all): {matches}"
+        )),
+        Line::from(""),
+        note_input_line(theme, input),
+        Line::from(format!(
+            "{skip_existing} Skip files whose output already exists (Tab)"
+        )),
+        Line::from(""),
+        Line::styled(
+            "Enter reviews the planned exports, Esc cancels.",
+            Style::default().fg(theme.dim),
+        ),
+    ];
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Convert all")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+/// Planned batch exports with include ticks, plus the highlighted file's
+/// output and commands.
+fn render_batch_review(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((cursor, rows)) = app.batch_review() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let included = rows.iter().filter(|row| row.included).count();
+    let list_height = rows.len().clamp(1, 10);
+    let mut lines = vec![
+        Line::from(format!(
+            "{included} of {} file(s) included. Nothing runs until Enter.",
+            rows.len()
+        )),
+        Line::from(""),
+    ];
+    // Keep the highlighted file visible when there are more than fit.
+    let first = cursor.saturating_sub(list_height - 1);
+    for (index, row) in rows.iter().enumerate().skip(first).take(list_height) {
+        let tick = match (row.error, row.included) {
+            (Some(_), _) => "[-]",
+            (None, true) => "[x]",
+            (None, false) if row.probing => "[.]",
+            (None, false) => "[ ]",
+        };
+        let name_style = if index == cursor {
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let detail = match (row.error, row.output) {
+            (Some(error), _) => format!("  {error}"),
+            (None, Some(output)) => format!(
+                "  -> {}",
+                output
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| output.display().to_string())
+            ),
+            (None, None) if row.probing => "  probing...".to_string(),
+            (None, None) => String::new(),
+        };
+        let detail_color = if row.error.is_some() {
+            theme.warning
+        } else {
+            theme.dim
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{tick} ")),
+            Span::styled(row.name.to_string(), name_style),
+            Span::styled(detail, Style::default().fg(detail_color)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(row) = rows.get(cursor) {
+        if let Some(output) = row.output {
+            lines.push(Line::from(format!("Output: {}", output.display())));
+        }
+        for (pass, command) in row.commands.iter().enumerate() {
+            let label = if row.commands.len() > 1 {
+                format!("Pass {}: ", pass + 1)
+            } else {
+                "Command: ".to_string()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.label)),
+                Span::raw(command.clone()),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Space toggles, a toggles all, Enter starts the included files, Esc cancels.",
+        Style::default().fg(theme.dim),
+    ));
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Review batch")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_cleanup_prompt(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(input) = app.cleanup_prompt_input() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Length(9)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let (count, size) = app.cleanup_prompt_matches();
+    let lines = vec![
+        Line::from("Days: numbered exports (clip(2).mp4, ...) older than that."),
+        Line::from(format!(
+            "Anything else is a glob (e.g. *.webm): {count} file(s), {}",
+            format_bytes(size)
+        )),
+        Line::from(""),
+        note_input_line(theme, input),
+        Line::from(""),
+        Line::styled(
+            "Enter lists the matches for review, Esc cancels.",
+            Style::default().fg(theme.dim),
+        ),
+    ];
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Clean up")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+/// Cleanup matches with include ticks, sizes, and the reclaimable total.
+fn render_cleanup_review(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((cursor, rows)) = app.cleanup_review() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let included = rows.iter().filter(|row| row.included);
+    let reclaimable = included.clone().map(|row| row.size).sum::<u64>();
+    // Leave room for the summary, the hint, and the borders.
+    let list_height = (popup.height.saturating_sub(6) as usize).max(1);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(format!(
+                "{} of {} file(s) included, ",
+                included.count(),
+                rows.len()
+            )),
+            Span::styled(
+                format!("{} reclaimable", format_bytes(reclaimable)),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(". Nothing moves to the trash until Enter."),
+        ]),
+        Line::from(""),
+    ];
+    let first = cursor.saturating_sub(list_height - 1);
+    for (index, row) in rows.iter().enumerate().skip(first).take(list_height) {
+        let tick = if row.included { "[x]" } else { "[ ]" };
+        let name_style = if index == cursor {
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{tick} ")),
+            Span::styled(row.name.to_string(), name_style),
+            Span::styled(
+                format!("  {}", format_bytes(row.size)),
+                Style::default().fg(theme.dim),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Space toggles, a toggles all, Enter trashes the included files, Esc cancels.",
+        Style::default().fg(theme.dim),
+    ));
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Review cleanup")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_bookmark_picker(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((cursor, bookmarks)) = app.bookmark_picker() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let list_height = bookmarks.len().clamp(1, 12) as u16;
+    let [vertical] = Layout::vertical([Constraint::Length(list_height + 4)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let mut lines = Vec::new();
+    if bookmarks.is_empty() {
+        lines.push(Line::styled(
+            format!(
+                "No bookmarks yet. Press {} in a directory to add it.",
+                app.keymap().keys_label(Action::Bookmark)
+            ),
+            Style::default().fg(theme.dim),
+        ));
+    }
+    // Keep the highlighted bookmark visible when there are more than fit.
+    let first = cursor.saturating_sub(list_height as usize - 1);
+    for (index, bookmark) in bookmarks
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(list_height as usize)
+    {
+        let number = if index < 9 {
+            format!("{} ", index + 1)
+        } else {
+            "  ".to_string()
+        };
+        let path_style = if index == cursor {
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD)
+        } else if bookmark.missing {
+            Style::default().fg(theme.dim)
+        } else {
+            Style::default()
+        };
+        let marker = if index == cursor {
+            selection_symbol(app)
+        } else {
+            ""
+        };
+        let mut spans = vec![
+            Span::raw(marker),
+            Span::styled(number, Style::default().fg(theme.dim)),
+            Span::styled(bookmark.path.clone(), path_style),
+        ];
+        if bookmark.missing {
+            spans.push(Span::styled(
+                "  (missing)",
+                Style::default().fg(theme.warning),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Enter or 1-9 open, J/K reorder, d delete, Esc closes.",
+        Style::default().fg(theme.dim),
+    ));
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Bookmarks")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_preset_picker(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some((cursor, naming)) = app.preset_picker() else {
+        return;
+    };
+    let presets = app.presets();
+
+    let outer = frame.area();
+    let list_height = presets.len().clamp(1, 9) as u16;
+    let [vertical] = Layout::vertical([Constraint::Length(list_height + 6)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(68)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let mut lines = Vec::new();
+    if presets.is_empty() {
+        lines.push(Line::styled(
+            "No presets yet. Press s to save the current settings.",
+            Style::default().fg(theme.dim),
+        ));
+    }
+    // Keep the highlighted preset visible when there are more than fit.
+    let first = cursor.saturating_sub(list_height as usize - 1);
+    for (index, preset) in presets
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(list_height as usize)
+    {
+        let number = if index < 9 {
+            format!("{} ", index + 1)
+        } else {
+            "  ".to_string()
+        };
+        let selected = naming.is_none() && index == cursor;
+        let marker = if selected { selection_symbol(app) } else { "" };
+        let name_style = if selected {
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::raw(marker),
+            Span::styled(number, Style::default().fg(theme.dim)),
+            Span::styled(preset.name.clone(), name_style),
+            Span::styled(
+                format!("  {}", preset.summary()),
+                Style::default().fg(theme.dim),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    match naming {
+        Some(input) => {
+            lines.push(Line::from("Save current settings as:"));
+            lines.push(note_input_line(theme, input));
+            lines.push(Line::styled(
+                "Enter saves (same name replaces), Esc cancels.",
+                Style::default().fg(theme.dim),
+            ));
+        }
+        None => {
+            lines.push(Line::styled(
+                "Enter or 1-9 apply, s save current, d delete, Esc closes.",
+                Style::default().fg(theme.dim),
+            ));
+        }
+    }
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Export presets")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_health_check(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(health) = app.health_check() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(72)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let dim = Style::default().fg(theme.dim);
+    let mut lines = Vec::new();
+    if health.running {
+        lines.push(Line::styled(
+            "Checking tools, encoders, folders, and the clipboard...",
+            dim,
+        ));
+    }
+    let mut section = "";
+    for check in health.checks.iter().skip(health.scroll) {
+        if check.section != section {
+            if !section.is_empty() {
+                lines.push(Line::from(""));
+            }
+            section = check.section;
+            lines.push(Line::styled(
+                section,
+                Style::default()
+                    .fg(theme.heading)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        let (mark, color) = match check.level {
+            HealthLevel::Ok => ("ok  ", theme.success),
+            HealthLevel::Warn => ("warn", theme.warning),
+            HealthLevel::Fail => ("FAIL", theme.error),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{mark} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{:<12}", check.label),
+                Style::default().fg(theme.label),
+            ),
+            Span::raw(check.detail.clone()),
+        ]));
+        if let Some(fix) = check.fix.as_deref() {
+            lines.push(Line::styled(format!("     -> {fix}"), dim));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Up/Down scroll. Enter, q, or Esc closes.",
+        dim,
+    ));
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Health Check")
+                .border_style(pane_border_style(theme, true, theme.panel)),
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_changelog(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(changelog) = app.changelog() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let [vertical] = Layout::vertical([Constraint::Percentage(80)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(72)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let dim = Style::default().fg(theme.dim);
+    let mut notes = Vec::new();
+    for release in changelog.releases {
+        notes.push(Line::styled(
+            release.version,
+            Style::default()
+                .fg(theme.heading)
+                .add_modifier(Modifier::BOLD),
+        ));
+        notes.extend(release.notes.iter().map(|note| Line::raw(*note)));
+        notes.push(Line::from(""));
+    }
+    let mut lines = notes.into_iter().skip(changelog.scroll).collect::<Vec<_>>();
+    lines.push(Line::styled(
+        if changelog.whats_new {
+            "Up/Down scroll, V shows every release. Enter, q, or Esc closes."
+        } else {
+            "Up/Down scroll. Enter, q, or Esc closes."
+        },
+        dim,
+    ));
+
+    let title = if changelog.whats_new {
+        format!("What's new in rt {}", env!("CARGO_PKG_VERSION"))
+    } else {
+        "Changelog".to_string()
+    };
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title(title)
+                .border_style(pane_border_style(theme, true, theme.panel)),
+        )
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn render_job_stats(frame: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let Some(stats) = app.job_stats() else {
+        return;
+    };
+
+    let outer = frame.area();
+    let encoder_rows = stats.encoders.len().clamp(1, 12) as u16;
+    let [vertical] = Layout::vertical([Constraint::Length(encoder_rows + 9)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(outer);
+    let [popup] = Layout::horizontal([Constraint::Percentage(60)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(vertical);
+
+    frame.render_widget(Clear, popup);
+
+    let label_style = Style::default().fg(theme.label);
+    let dim = Style::default().fg(theme.dim);
+    let duration = |seconds: f64| TimeInput::from_seconds(seconds).to_ffmpeg_timestamp();
+    let speed = |speed: Option<f64>| speed.map_or("-".to_string(), |speed| format!("{speed:.2}x"));
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<11}", "Exports"), label_style),
+            Span::raw(format!(
+                "{} | {} | {} of media in {} | avg {}",
+                stats.exports,
+                format_bytes(stats.export_bytes),
+                duration(stats.media_seconds),
+                duration(stats.encode_seconds),
+                speed(stats.average_speed())
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:<11}", "Downloads"), label_style),
+            Span::raw(format!(
+                "{} | {} | {}",
+                stats.downloads,
+                format_bytes(stats.download_bytes),
+                duration(stats.download_seconds)
+            )),
+        ]),
+        Line::from(""),
+        Line::styled(
+            "SPEED BY ENCODER",
+            Style::default()
+                .fg(theme.heading)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if stats.encoders.is_empty() {
+        lines.push(Line::styled("No exports yet.", dim));
+    }
+    for encoder in stats.encoders.iter().take(encoder_rows as usize) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<16}", encoder.name), label_style),
+            Span::raw(format!("{:>8}", speed(encoder.speed()))),
+            Span::styled(
+                format!(
+                    "  {} exports, {} in {}",
+                    encoder.exports,
+                    duration(encoder.media_seconds),
+                    duration(encoder.encode_seconds)
+                ),
+                dim,
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Totals from every rt instance. Enter, q, or Esc closes.",
+        dim,
+    ));
+
+    let popup_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(pane_borders(app))
+                .title("Statistics")
+                .border_style(pane_border_style(theme, true, theme.accent)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(popup_widget, popup);
+}
+
+fn note_input_line(theme: &Theme, input: &TextInput) -> Line<'static> {
+    let chars = input.value().chars().collect::<Vec<_>>();
+    let cursor = input.cursor().min(chars.len());
+    let cursor_style = Style::default().fg(theme.on_highlight).bg(theme.accent);
+    let before = chars[..cursor].iter().collect::<String>();
+    let at = chars.get(cursor).map_or(" ".to_string(), char::to_string);
+    let after = chars
+        .get(cursor + 1..)
+        .unwrap_or_default()
+        .iter()
+        .collect::<String>();
+
+    Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.dim)),
+        Span::raw(before),
+        Span::styled(at, cursor_style),
+        Span::raw(after),
+    ])
+}
+
+fn keybind_section(theme: &Theme, title: &str) -> Line<'static> {
+    Line::styled(
+        title.to_string(),
+        Style::default()
+            .fg(theme.heading)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// A browser row with the keys currently bound to `actions`.
+fn browser_keybind_row(app: &App, actions: &[Action], description: &str) -> Line<'static> {
+    let theme = app.theme();
+    let keys = actions
+        .iter()
+        .map(|action| app.keymap().keys_label(*action))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    keybind_row(theme, &keys, description)
+}
+
+fn keybind_row(theme: &Theme, keys: &str, action: &str) -> Line<'static> {
+    const KEY_COL_WIDTH: usize = 32;
+    let keys_padded = format!("{keys:<KEY_COL_WIDTH$}");
+    Line::from(vec![
+        Span::styled(
+            keys_padded,
+            Style::default()
+                .fg(theme.label)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+        Span::raw(action.to_string()),
+    ])
+}
+
+/// Puts the terminal cursor where a screen reader should read: the status
+/// line once after it changes, else the selected file or the footer.
+fn place_reader_cursor(
+    frame: &mut Frame,
+    app: &App,
+    focus: Focus,
+    layout: &PaneLayout,
+    footer: Rect,
+) {
+    let list = layout.files_list;
+    let row = app.selected.saturating_sub(layout.files_offset) as u16;
+    let on_file = focus == Focus::Left && !app.entries.is_empty() && row < list.height;
+    if app.take_status_announcement() || !on_file {
+        frame.set_cursor_position((footer.x, footer.y));
+    } else {
+        frame.set_cursor_position((list.x, list.y + row));
+    }
+}
+
+/// What has focus, in words, for the screen reader footer.
+fn focus_label(app: &App, focus: Focus) -> String {
+    let tab = app.right_tab().label();
+    match focus {
+        Focus::Left => "Files".to_string(),
+        Focus::RightTop => format!("{tab} form"),
+        Focus::RightBottom => format!("{tab} output"),
+    }
+}
+
+fn render_footer(frame: &mut Frame, app: &App, focus: Focus, area: ratatui::layout::Rect) {
+    let theme = app.theme();
+    if !app.screen_reader() {
+        let hint = Paragraph::new(Line::styled(
+            "Press ? to see keyboard shortcuts",
+            Style::default().fg(theme.dim),
+        ))
+        .alignment(Alignment::Left);
+        frame.render_widget(hint, area);
+    }
+
+    // Pending count prefix, then progress for every running job, visible
+    // regardless of the active tab. A job writing outside the browser's
+    // folder names where its output goes.
+    let mut parts = Vec::new();
+    if let Some(count) = app.count_prefix.pending() {
+        parts.push(count.to_string());
+    }
+    let editor_progress = if let Some(batch) = app.editor_batch_progress() {
+        Some(footer_job_progress(
+            &format!("E {}/{}", batch.finished + 1, batch.total),
+            app.editor_batch_percent(),
+        ))
+    } else if app.editor_is_running() {
+        Some(footer_job_progress("E", app.editor_progress_percent()))
+    } else {
+        None
+    };
+    if let Some(progress) = editor_progress {
+        parts.push(footer_job_target(progress, app.editor_writes_elsewhere()));
+    }
+    if app.downloader_is_running() {
+        parts.push(footer_job_target(
+            footer_job_progress("D", app.downloader_progress_percent()),
+            app.downloader_writes_elsewhere(),
+        ));
+    }
+    if app.file_op_is_running() {
+        parts.push(footer_job_progress("F", app.file_op_progress_percent()));
+    }
+    if app.screen_reader() {
+        // One plain line: focus, then the status, then job progress.
+        let status = match app.status_message() {
+            "" => "? lists the keys",
+            status => status,
+        };
+        let mut text = format!("{}: {status}", focus_label(app, focus));
+        if !parts.is_empty() {
+            text.push_str(&format!(" | {}", parts.join(" ")));
+        }
+        frame.render_widget(Paragraph::new(text), area);
+        return;
+    }
+    if parts.is_empty() {
+        return;
+    }
+
+    let progress = Paragraph::new(Line::styled(
+        parts.join(" "),
+        Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .alignment(Alignment::Right);
+    frame.render_widget(progress, area);
+}
+
+fn footer_job_target(progress: String, elsewhere: Option<String>) -> String {
+    match elsewhere {
+        Some(dir) => format!("{progress} → {dir}"),
+        None => progress,
+    }
+}
+
+fn footer_job_progress(label: &str, percent: Option<u8>) -> String {
+    match percent {
+        Some(percent) => format!("{label}:{percent}%"),
+        None => format!("{label}:--"),
+    }
+}
+
+/// Box-drawing borders, left off for screen readers.
+pub(super) fn pane_borders(app: &App) -> Borders {
+    if app.screen_reader() {
+        Borders::NONE
+    } else {
+        Borders::ALL
+    }
+}
+
+/// Marks the highlighted row for screen readers, which can't see its color.
+pub(super) fn selection_symbol(app: &App) -> &'static str {
+    if app.screen_reader() { "> " } else { "" }
+}
+
+pub(super) fn pane_border_style(theme: &Theme, is_focused: bool, focused_color: Color) -> Style {
+    if is_focused {
+        Style::default()
+            .fg(focused_color)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.dim)
+    }
+}
+
+fn file_size_label(entry: &crate::model::FileEntry) -> String {
+    if entry.is_dir {
+        "<DIR>".to_string()
+    } else if let Some(bytes) = entry.size_bytes {
+        format_size(bytes)
+    } else {
+        "?".to_string()
+    }
+}
+
+fn files_pane_title(app: &App) -> Line<'static> {
+    let mut label = "Files".to_string();
+    if app.tag_filter() != TagFilter::All {
+        label.push_str(&format!(" [{}]", app.tag_filter().label()));
+    }
+    if !app.show_hidden_files() {
+        label.push_str(" [no dotfiles]");
+    }
+    if app.media_only() {
+        label.push_str(" [media]");
+    }
+    if let Some(number) = app.bookmark_number(&app.cwd) {
+        label.push_str(&format!(" [bookmark {number}]"));
+    }
+    if app.marked_count() > 0 {
+        label.push_str(&format!(" ({} marked)", app.marked_count()));
+    }
+    if app.showing_stdin_listing() {
+        return Line::from(format!("{label}: [stdin] {} entries", app.entries.len()));
+    }
+    let mut spans = vec![Span::raw(format!("{label}: "))];
+    spans.extend(breadcrumb_spans(app));
+    Line::from(spans)
+}
+
+/// The cwd one segment per span. While `b` picks an ancestor it is
+/// highlighted and the segments below it are dimmed.
+fn breadcrumb_spans(app: &App) -> Vec<Span<'static>> {
+    let theme = app.theme();
+    let segments = breadcrumb_segments(&app.cwd);
+    let picked = app
+        .breadcrumb_levels_up()
+        .map(|levels_up| segments.len().saturating_sub(levels_up + 1));
+    let mut spans = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        if index > 0 && !segments[index - 1].ends_with(std::path::MAIN_SEPARATOR) {
+            spans.push(Span::raw(std::path::MAIN_SEPARATOR.to_string()));
+        }
+        let style = match picked {
+            Some(picked) if index == picked => Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+            Some(picked) if index > picked => Style::default().fg(theme.dim),
+            _ => Style::default(),
+        };
+        spans.push(Span::styled(segment.clone(), style));
+    }
+    if picked.is_some() {
+        spans.push(Span::styled(
+            "  h/l pick, Enter open",
+            Style::default().fg(theme.dim),
+        ));
+    }
+    spans
+}
+
+/// Width the file name keeps before the optional columns are dropped.
+const MIN_FILE_NAME_WIDTH: usize = 16;
+const DURATION_COLUMN_WIDTH: usize = 7;
+const AGE_COLUMN_WIDTH: usize = 3;
+/// Leading column on marked rows; the mark order stays on the right.
+const MARKER: char = '●';
+
+/// Optional file row columns; each is left out when unknown or when the
+/// pane is too narrow for it.
+#[derive(Clone, Copy)]
+struct FileColumns {
+    duration: Option<f64>,
+    age: Option<Duration>,
+}
+
+/// `4:05` or `1:02:03`.
+fn duration_badge(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Compact time since the last change: `now`, `12m`, `5h`, `3d`, `8w`, `2y`.
+fn age_label(age: Duration) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    const YEAR: u64 = 365 * DAY;
+    let secs = age.as_secs();
+    match secs {
+        0..MINUTE => "now".to_string(),
+        MINUTE..HOUR => format!("{}m", secs / MINUTE),
+        HOUR..DAY => format!("{}h", secs / HOUR),
+        DAY..WEEK => format!("{}d", secs / DAY),
+        WEEK..YEAR => format!("{}w", secs / WEEK),
+        _ => format!("{}y", secs / YEAR),
+    }
+}
+
+fn format_file_row(
+    entry: &crate::model::FileEntry,
+    icon: &str,
+    mark: Option<usize>,
+    mark_column: bool,
+    tag: Option<&FileTag>,
+    columns: FileColumns,
+    content_width: usize,
+) -> String {
+    let prefix = match (mark_column, mark) {
+        (false, _) => format!("{icon} "),
+        (true, Some(_)) => format!("{MARKER} {icon} "),
+        (true, None) => format!("  {icon} "),
+    };
+    let mut size = format!("{:>7}", file_size_label(entry));
+    // Age goes first when the name needs the room, then the duration.
+    let mut spare = content_width
+        .saturating_sub(display_width(&prefix) + display_width(&size) + 1 + MIN_FILE_NAME_WIDTH);
+    if spare > AGE_COLUMN_WIDTH + DURATION_COLUMN_WIDTH + 2 {
+        let age = columns.age.map(age_label).unwrap_or_default();
+        size = format!("{age:>AGE_COLUMN_WIDTH$} {size}");
+        spare -= AGE_COLUMN_WIDTH + 1;
+    }
+    if spare > DURATION_COLUMN_WIDTH + 1 {
+        let duration = columns.duration.map(duration_badge).unwrap_or_default();
+        size = format!("{duration:>DURATION_COLUMN_WIDTH$} {size}");
+    }
+    if let Some(label) = tag.map(|tag| file_tag_label(tag, content_width))
+        && !label.is_empty()
+    {
+        size = format!("{label} {size}");
+    }
+    if let Some(mark) = mark {
+        size = format!("#{mark} {size}");
+    }
+    let prefix_len = display_width(&prefix);
+    let size_len = display_width(&size);
+
+    let available_name_width = content_width.saturating_sub(prefix_len + size_len + 1);
+    let name = truncate_middle_with_ellipsis(&entry.name, available_name_width);
+    let left = format!("{prefix}{name}");
+    let left_len = display_width(&left);
+    let spaces = content_width.saturating_sub(left_len + size_len).max(1);
+    let row = format!("{left}{}{}", " ".repeat(spaces), size);
+    truncate_to_width(&row, content_width)
+}
+
+/// Stars plus a note snippet, capped so the name keeps most of the row.
+fn file_tag_label(tag: &FileTag, content_width: usize) -> String {
+    let stars = stars(tag.rating);
+    if tag.note.is_empty() {
+        return stars;
+    }
+
+    let note_width = (content_width / 4).clamp(4, 24);
+    let note = truncate_middle_with_ellipsis(&tag.note, note_width);
+    if stars.is_empty() {
+        format!("✎ {note}")
+    } else {
+        format!("{stars} ✎ {note}")
     }
 }
 
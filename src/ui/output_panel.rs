@@ -9,6 +9,8 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use crate::theme::Theme;
+
 pub struct LogPanelStateView<'a> {
     pub title: &'a str,
     pub lines: &'a [String],
@@ -17,16 +19,27 @@ pub struct LogPanelStateView<'a> {
     pub accent_color: Color,
     pub trim_wrapped_lines: bool,
     pub title_hint_right: Option<&'a str>,
+    /// `Borders::NONE` in screen reader mode.
+    pub borders: Borders,
 }
 
-pub fn render_log_panel(frame: &mut Frame, area: Rect, panel: LogPanelStateView<'_>) {
+pub fn render_log_panel(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    panel: LogPanelStateView<'_>,
+) {
     const OVERSCAN_MULTIPLIER: usize = 4;
     const MIN_WINDOW_LINES: usize = 64;
     const FOCUS_HINT: &str = "(ctrl+o)";
 
     let mut block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(log_panel_border_style(panel.focused, panel.accent_color))
+        .borders(panel.borders)
+        .border_style(log_panel_border_style(
+            theme,
+            panel.focused,
+            panel.accent_color,
+        ))
         .title_top(Line::from(panel.title).left_aligned())
         .title_top(Line::from(FOCUS_HINT).right_aligned());
     if let Some(hint) = panel.title_hint_right {
@@ -71,10 +84,10 @@ pub fn render_log_panel(frame: &mut Frame, area: Rect, panel: LogPanelStateView<
     frame.render_widget(widget, inner);
 }
 
-fn log_panel_border_style(is_focused: bool, accent: Color) -> Style {
+fn log_panel_border_style(theme: &Theme, is_focused: bool, accent: Color) -> Style {
     if is_focused {
         Style::default().fg(accent).add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.dim)
     }
 }
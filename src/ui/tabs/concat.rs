@@ -0,0 +1,143 @@
+// Concat tab rendering.
+// - Lists the clips to join in order, with each clip's codecs and size.
+// - Shows whether the join will stream copy or re-encode.
+// - Shares the ffmpeg tool-output panel with the editor tab.
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    app::{App, ConcatMode},
+    model::{Focus, TimeInput},
+};
+
+use super::{
+    super::{pane_border_style, pane_borders, selection_symbol},
+    editor::render_ffmpeg_output_pane,
+};
+
+pub fn render_concat_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect) -> [Rect; 2] {
+    let right_constraints = if focus == Focus::RightBottom {
+        [
+            Constraint::Min(0),
+            Constraint::Percentage(app.output_pane_percent()),
+        ]
+    } else {
+        [Constraint::Min(0), Constraint::Length(8)]
+    };
+    let [top, bottom] = Layout::vertical(right_constraints).areas(area);
+
+    render_concat_list(frame, app, focus, top);
+    render_ffmpeg_output_pane(frame, app, focus, bottom);
+    [top, bottom]
+}
+
+fn render_concat_list(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+    let theme = app.theme();
+    let panel = Block::default()
+        .borders(pane_borders(app))
+        .border_style(pane_border_style(
+            theme,
+            focus == Focus::RightTop,
+            theme.panel,
+        ))
+        .title_top(Line::from("Concat").left_aligned())
+        .title_top(
+            Line::styled(
+                "(J/K reorder, d remove, Enter join)",
+                Style::default().fg(theme.dim),
+            )
+            .right_aligned(),
+        );
+    let inner = panel.inner(area);
+    frame.render_widget(panel, area);
+
+    if inner.width < 4 || inner.height < 3 {
+        return;
+    }
+
+    let items = app.concat_items();
+    if items.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from("No clips yet."),
+            Line::from(""),
+            Line::from("Mark videos in the file browser with Space, then press a"),
+            Line::from("to add them here (or press a on a single video)."),
+        ]);
+        frame.render_widget(hint, inner);
+        return;
+    }
+
+    let [summary_area, rows_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(inner);
+    let mode = match app.concat_mode() {
+        ConcatMode::StreamCopy => Span::styled(
+            "stream copy (clips match)",
+            Style::default().fg(theme.success),
+        ),
+        ConcatMode::Reencode => Span::styled(
+            "re-encode to h264/aac mp4 (clips differ)",
+            Style::default().fg(theme.panel),
+        ),
+    };
+    let summary = Paragraph::new(vec![Line::from(vec![
+        Span::styled("Join: ", Style::default().fg(theme.muted)),
+        mode,
+        Span::styled(
+            format!(
+                "  {} clips, {}",
+                items.len(),
+                TimeInput::from_seconds(app.concat_total_seconds()).to_ffmpeg_timestamp()
+            ),
+            Style::default().fg(theme.dim),
+        ),
+    ])]);
+    frame.render_widget(summary, summary_area);
+
+    let rows = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let details = item
+                .stats
+                .as_ref()
+                .map(|stats| {
+                    format!(
+                        "{} {} / {}",
+                        stats.resolution, stats.video_codec, stats.audio_codec
+                    )
+                })
+                .unwrap_or_else(|| "probe failed".to_string());
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:>2}. ", index + 1),
+                    Style::default().fg(theme.dim),
+                ),
+                Span::raw(item.name.clone()),
+                Span::styled(
+                    format!(
+                        "  {}  {details}",
+                        TimeInput::from_seconds(item.duration_seconds).to_ffmpeg_timestamp()
+                    ),
+                    Style::default().fg(theme.dim),
+                ),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let mut state = ListState::default();
+    state.select(Some(app.concat_cursor().min(rows.len().saturating_sub(1))));
+    let list = List::new(rows)
+        .highlight_symbol(selection_symbol(app))
+        .highlight_style(
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_stateful_widget(list, rows_area, &mut state);
+}
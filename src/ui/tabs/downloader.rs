@@ -7,27 +7,31 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
 };
 
 use crate::{
     app::App,
     model::{DownloaderStep, Focus},
+    theme::Theme,
 };
 
 use super::super::{
     output_panel::{LogPanelStateView, render_log_panel},
-    pane_border_style,
+    pane_border_style, pane_borders, selection_symbol,
 };
 
 const INPUT_LABEL_COL_WIDTH: usize = 12;
 const MAX_QUALITY_ROWS: usize = 8;
 
-pub fn render_downloader_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+pub fn render_downloader_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect) -> [Rect; 2] {
     let right_constraints = if focus == Focus::RightBottom {
-        [Constraint::Percentage(30), Constraint::Percentage(70)]
+        [
+            Constraint::Min(0),
+            Constraint::Percentage(app.output_pane_percent()),
+        ]
     } else {
         [Constraint::Min(0), Constraint::Length(8)]
     };
@@ -35,13 +39,15 @@ pub fn render_downloader_tab(frame: &mut Frame, app: &App, focus: Focus, area: R
 
     render_downloader_form(frame, app, focus, top);
     render_downloader_output(frame, app, focus, bottom);
+    [top, bottom]
 }
 
 fn render_downloader_form(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+    let theme = app.theme();
     let form_focused = focus == Focus::RightTop;
     let panel = Block::default()
-        .borders(Borders::ALL)
-        .border_style(pane_border_style(form_focused, Color::LightYellow))
+        .borders(pane_borders(app))
+        .border_style(pane_border_style(theme, form_focused, theme.panel))
         .title("Downloader");
     let inner = panel.inner(area);
     frame.render_widget(panel, area);
@@ -52,8 +58,8 @@ fn render_downloader_form(frame: &mut Frame, app: &App, focus: Focus, area: Rect
 
     if !app.downloader_available() {
         let warning_panel = Paragraph::new(vec![
-            warning("WARNING: Downloader requires yt-dlp in PATH."),
-            warning("Install yt-dlp, then restart this app."),
+            warning(theme, "WARNING: Downloader requires yt-dlp in PATH."),
+            warning(theme, "Install yt-dlp, then restart this app."),
         ])
         .alignment(Alignment::Left);
         frame.render_widget(warning_panel, inner);
@@ -67,51 +73,59 @@ fn render_downloader_form(frame: &mut Frame, app: &App, focus: Focus, area: Rect
 }
 
 fn render_downloader_output(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+    let theme = app.theme();
     let title = "TOOL OUTPUT";
     let visible_line_count = area.height.saturating_sub(2).max(1) as usize;
 
     render_log_panel(
         frame,
         area,
+        theme,
         LogPanelStateView {
             title,
             lines: app.downloader_output_lines(),
             scroll: app.clamped_downloader_output_scroll(visible_line_count),
             focused: focus == Focus::RightBottom,
-            accent_color: Color::LightBlue,
+            accent_color: theme.accent,
             trim_wrapped_lines: false,
             title_hint_right: Some("(press x to cancel)"),
+            borders: pane_borders(app),
         },
     );
 }
 
 fn render_url_step(frame: &mut Frame, app: &App, form_focused: bool, area: Rect) {
+    let theme = app.theme();
     let url_cursor =
         (form_focused && app.downloader_accepts_text_input()).then_some(app.downloader_url_cursor);
 
     let step_line = if app.downloader_is_fetching_qualities() {
         format!(
-            "Step 1/2: Fetching video qualities {}",
-            spinner_glyph(app.downloader_spinner_frame)
+            "Step 1/2: Fetching video qualities {} (Esc cancels)",
+            spinner(app)
         )
     } else {
         "Step 1/2: Enter URL".to_string()
     };
 
+    let hint = match app.downloader_url_error() {
+        Some(error) => Line::styled(error.to_string(), Style::default().fg(theme.error)),
+        None => Line::styled(
+            "Enter: fetch video qualities (a local path opens in the editor)",
+            Style::default().fg(theme.dim),
+        ),
+    };
     let lines = vec![
         Line::styled(
             step_line,
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.heading)
                 .add_modifier(Modifier::BOLD),
         ),
         Line::from(""),
-        input_line("URL", &app.downloader_url, url_cursor),
+        input_line(theme, "URL", &app.downloader_url, url_cursor),
         Line::from(""),
-        Line::styled(
-            "Enter: fetch video qualities",
-            Style::default().fg(Color::DarkGray),
-        ),
+        hint,
     ];
 
     let panel = Paragraph::new(lines).alignment(Alignment::Left);
@@ -119,51 +133,70 @@ fn render_url_step(frame: &mut Frame, app: &App, form_focused: bool, area: Rect)
 }
 
 fn render_quality_step(frame: &mut Frame, app: &App, form_focused: bool, area: Rect) {
+    let theme = app.theme();
     let show_playlist_option = app.downloader_playlist_available();
     let header_height = if show_playlist_option { 8 } else { 7 };
     let [header_area, list_region] =
         Layout::vertical([Constraint::Length(header_height), Constraint::Min(0)]).areas(area);
     let (selected, total) = app.downloader_quality_position();
     let selector = app.downloader_selected_quality_selector();
-    let pick_row = if app.downloader_audio_only_enabled() {
+    let pick_row = if app.downloader_audio_only_enabled() && !app.downloader_quality_list_is_audio()
+    {
         format!("audio-only  ({selector})")
     } else {
         format!("{selected}/{total}  ({selector})")
     };
     let option_focus = app.downloader_option_focus_index();
     let list_focused = app.downloader_quality_list_focused();
-    let title_or_url = app.downloader_video_title().unwrap_or(app.downloader_url.trim());
+    let title_or_url = app
+        .downloader_video_title()
+        .unwrap_or(app.downloader_url.trim());
+
+    let kind = if app.downloader_quality_list_is_audio() {
+        "audio"
+    } else {
+        "video"
+    };
+    let step_line = if app.downloader_is_refreshing_qualities() {
+        format!(
+            "Step 2/2: Refreshing {kind} qualities {} (Esc cancels)",
+            spinner(app)
+        )
+    } else {
+        format!("Step 2/2: Select {kind} quality")
+    };
 
     let mut header_lines = vec![
         Line::styled(
-            "Step 2/2: Select video quality",
+            step_line,
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.heading)
                 .add_modifier(Modifier::BOLD),
         ),
         Line::styled(
             "Backspace: return to URL input",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ),
         row(
+            theme,
             "Title",
-            truncate_middle(
-                title_or_url,
-                area.width.saturating_sub(14) as usize,
-            ),
+            truncate_middle(title_or_url, area.width.saturating_sub(14) as usize),
         ),
-        row("Pick", pick_row),
+        row(theme, "Pick", pick_row),
         checkbox_line(
+            theme,
             "Audio only",
             app.downloader_audio_only_enabled(),
             form_focused && option_focus == Some(0),
         ),
         checkbox_line(
+            theme,
             "Sponsorblock",
             app.downloader_sponsorblock_enabled(),
             form_focused && option_focus == Some(1),
         ),
         checkbox_line(
+            theme,
             "Subtitles",
             app.downloader_subtitles_enabled(),
             form_focused && option_focus == Some(2),
@@ -171,6 +204,7 @@ fn render_quality_step(frame: &mut Frame, app: &App, form_focused: bool, area: R
     ];
     if show_playlist_option {
         header_lines.push(checkbox_line_with_hint(
+            theme,
             "Playlist",
             app.downloader_playlist_enabled(),
             form_focused && option_focus == Some(3),
@@ -183,19 +217,18 @@ fn render_quality_step(frame: &mut Frame, app: &App, form_focused: bool, area: R
         return;
     }
 
-    let max_rows = (list_region.height.saturating_sub(3) as usize)
-        .max(1)
-        .min(MAX_QUALITY_ROWS);
+    let max_rows = (list_region.height.saturating_sub(3) as usize).clamp(1, MAX_QUALITY_ROWS);
     let list_height = (max_rows as u16 + 3).min(list_region.height);
     let [list_area, _] =
         Layout::vertical([Constraint::Length(list_height), Constraint::Min(0)]).areas(list_region);
 
     let list_block = Block::default()
-        .borders(Borders::ALL)
+        .borders(pane_borders(app))
         .title("QUALITY")
         .border_style(pane_border_style(
+            theme,
             form_focused && list_focused,
-            Color::LightYellow,
+            theme.panel,
         ));
     let inner = list_block.inner(list_area);
     frame.render_widget(list_block, list_area);
@@ -209,14 +242,12 @@ fn render_quality_step(frame: &mut Frame, app: &App, form_focused: bool, area: R
     frame.render_widget(
         Paragraph::new(Line::styled(
             app.downloader_quality_header_row(),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         )),
         columns_area,
     );
 
-    let visible_rows = (rows_area.height as usize).max(1).min(MAX_QUALITY_ROWS);
+    let visible_rows = (rows_area.height as usize).clamp(1, MAX_QUALITY_ROWS);
     let (rows, selected_in_view) = app.downloader_visible_quality_rows(visible_rows);
     let items = rows
         .iter()
@@ -227,32 +258,34 @@ fn render_quality_step(frame: &mut Frame, app: &App, form_focused: bool, area: R
         state.select(Some(selected_in_view.min(items.len().saturating_sub(1))));
     }
 
-    let list = List::new(items).highlight_style(
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
-            .add_modifier(Modifier::BOLD),
-    );
+    let list = List::new(items)
+        .highlight_symbol(selection_symbol(app))
+        .highlight_style(
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
+                .add_modifier(Modifier::BOLD),
+        );
     frame.render_stateful_widget(list, rows_area, &mut state);
 }
 
-fn warning(message: &str) -> Line<'static> {
+fn warning(theme: &Theme, message: &str) -> Line<'static> {
     Line::styled(
         message.to_string(),
         Style::default()
-            .fg(Color::LightRed)
+            .fg(theme.error)
             .add_modifier(Modifier::BOLD),
     )
 }
 
-fn row(label: &str, value: String) -> Line<'static> {
+fn row(theme: &Theme, label: &str, value: String) -> Line<'static> {
     const LABEL_COL_WIDTH: usize = INPUT_LABEL_COL_WIDTH;
     let label_cell = format!("{label:<LABEL_COL_WIDTH$}");
     Line::from(vec![
         Span::styled(
             label_cell,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.label)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("  "),
@@ -260,27 +293,27 @@ fn row(label: &str, value: String) -> Line<'static> {
     ])
 }
 
-fn checkbox_line(label: &str, checked: bool, focused: bool) -> Line<'static> {
+fn checkbox_line(theme: &Theme, label: &str, checked: bool, focused: bool) -> Line<'static> {
     const LABEL_COL_WIDTH: usize = INPUT_LABEL_COL_WIDTH;
     let label_cell = format!("{label:<LABEL_COL_WIDTH$}");
     let box_text = if checked { "[x]" } else { "[ ]" };
     let (label_style, value_style) = if focused {
         (
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Gray)
+                .fg(theme.on_highlight)
+                .bg(theme.selection)
                 .add_modifier(Modifier::BOLD),
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::White)
+                .fg(theme.on_highlight)
+                .bg(theme.cursor)
                 .add_modifier(Modifier::BOLD),
         )
     } else {
         (
             Style::default()
-                .fg(Color::LightMagenta)
+                .fg(theme.input_label)
                 .add_modifier(Modifier::BOLD),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         )
     };
 
@@ -291,17 +324,28 @@ fn checkbox_line(label: &str, checked: bool, focused: bool) -> Line<'static> {
     ])
 }
 
-fn checkbox_line_with_hint(label: &str, checked: bool, focused: bool, hint: &str) -> Line<'static> {
-    let mut line = checkbox_line(label, checked, focused);
+fn checkbox_line_with_hint(
+    theme: &Theme,
+    label: &str,
+    checked: bool,
+    focused: bool,
+    hint: &str,
+) -> Line<'static> {
+    let mut line = checkbox_line(theme, label, checked, focused);
     line.spans.push(Span::raw("  "));
     line.spans.push(Span::styled(
         hint.to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.dim),
     ));
     line
 }
 
-fn input_line(label: &str, value: &str, active_cursor: Option<usize>) -> Line<'static> {
+fn input_line(
+    theme: &Theme,
+    label: &str,
+    value: &str,
+    active_cursor: Option<usize>,
+) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     let active = active_cursor.is_some();
 
@@ -310,12 +354,12 @@ fn input_line(label: &str, value: &str, active_cursor: Option<usize>) -> Line<'s
             label_cell,
             if active {
                 Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Gray)
+                    .fg(theme.on_highlight)
+                    .bg(theme.selection)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
-                    .fg(Color::LightMagenta)
+                    .fg(theme.input_label)
                     .add_modifier(Modifier::BOLD)
             },
         ),
@@ -326,15 +370,15 @@ fn input_line(label: &str, value: &str, active_cursor: Option<usize>) -> Line<'s
     let cursor = active_cursor.unwrap_or(0).min(chars.len());
     let value_style = if active {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
+            .fg(theme.on_highlight)
+            .bg(theme.selection)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     };
     let cursor_style = Style::default()
-        .fg(Color::Black)
-        .bg(Color::White)
+        .fg(theme.on_highlight)
+        .bg(theme.cursor)
         .add_modifier(Modifier::BOLD);
 
     for (index, ch) in chars.iter().enumerate() {
@@ -371,7 +415,11 @@ fn truncate_middle(value: &str, max_chars: usize) -> String {
     format!("{start}...{end}")
 }
 
-fn spinner_glyph(frame: usize) -> char {
+/// The probe spinner; screen readers get a still `...` instead.
+fn spinner(app: &App) -> String {
     const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
-    FRAMES[frame % FRAMES.len()]
+    if app.screen_reader() {
+        return "...".to_string();
+    }
+    FRAMES[app.downloader_spinner_frame % FRAMES.len()].to_string()
 }
@@ -7,25 +7,34 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Paragraph, Wrap},
 };
 
 use crate::{
-    app::App,
-    media::scaled_resolution_for_percent,
-    model::{Focus, InputField, TimeInput},
+    app::{App, TimelineMarker},
+    media::{
+        StreamKind, codec_fits_container, is_video_file, parse_crop_input,
+        scaled_resolution_for_percent,
+    },
+    model::{AudioTrack, Focus, InputField, TimeInput},
+    theme::Theme,
 };
 
 use super::super::{
     output_panel::{LogPanelStateView, render_log_panel},
-    pane_border_style,
+    pane_border_style, pane_borders, selection_symbol,
 };
 
-const INPUT_LABEL_COL_WIDTH: usize = 11;
+const INPUT_LABEL_COL_WIDTH: usize = 13;
+/// Room after the timeline bar for the selection length.
+const TIMELINE_SUFFIX_WIDTH: usize = 20;
 
-pub fn render_editor_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+pub fn render_editor_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect) -> [Rect; 2] {
     let right_constraints = if focus == Focus::RightBottom {
-        [Constraint::Percentage(30), Constraint::Percentage(70)]
+        [
+            Constraint::Min(0),
+            Constraint::Percentage(app.output_pane_percent()),
+        ]
     } else {
         [Constraint::Min(0), Constraint::Length(8)]
     };
@@ -33,149 +42,506 @@ pub fn render_editor_tab(frame: &mut Frame, app: &App, focus: Focus, area: Rect)
 
     render_editor_pane(frame, app, focus, top);
     render_ffmpeg_output_pane(frame, app, focus, bottom);
+    [top, bottom]
 }
 
 fn render_editor_pane(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+    let theme = app.theme();
     let mut lines = Vec::new();
     let mut focused_line_index = None;
     if !app.ffmpeg_available() {
         lines.push(ffmpeg_warning_line(
+            theme,
             "WARNING: ffmpeg not found in PATH. Editor export is disabled.",
         ));
         lines.push(ffmpeg_warning_line(
+            theme,
             "Install ffmpeg, then restart this app.",
         ));
         lines.push(Line::from(""));
     }
 
-    if let Some(video) = &app.selected_video {
-        let start_active_part = (focus == Focus::RightTop && app.active_input == InputField::Start)
-            .then_some(app.start_part);
-        let end_active_part = (focus == Focus::RightTop && app.active_input == InputField::End)
-            .then_some(app.end_part);
-        let format_active = focus == Focus::RightTop && app.active_input == InputField::Format;
-        let fps_active_cursor = (focus == Focus::RightTop && app.active_input == InputField::Fps)
-            .then_some(app.output_fps_cursor);
-        let bitrate_active_cursor = (app.bitrate_enabled()
-            && focus == Focus::RightTop
-            && app.active_input == InputField::Bitrate)
-            .then_some(app.output_bitrate_cursor);
-        let scale_percent_active_cursor = (focus == Focus::RightTop
-            && app.active_input == InputField::ScalePercent)
-            .then_some(app.output_scale_percent_cursor);
-        let remove_audio_active =
-            focus == Focus::RightTop && app.active_input == InputField::RemoveAudio;
-        let output_active_cursor = (focus == Focus::RightTop
-            && app.active_input == InputField::Output)
-            .then_some(app.output_cursor);
-
-        lines.push(editor_section("TIME RANGE"));
-        lines.push(input_hint_line("", "HH:MM:SS"));
-        if start_active_part.is_some() {
-            focused_line_index = Some(lines.len());
-        }
-        lines.push(time_input_line(
-            "Start time",
-            &app.start_time,
-            start_active_part,
-        ));
-        if end_active_part.is_some() {
-            focused_line_index = Some(lines.len());
+    if let Some(batch) = app.editor_batch_progress() {
+        lines.push(editor_section(theme, "BATCH"));
+        let mut progress = format!(
+            "{}/{} done, {}% overall",
+            batch.finished,
+            batch.total,
+            app.editor_batch_percent().unwrap_or(0)
+        );
+        if batch.failed > 0 {
+            progress.push_str(&format!(", {} failed", batch.failed));
         }
-        lines.push(time_input_line("End time", &app.end_time, end_active_part));
-        lines.push(editor_section("OUTPUT"));
-        if format_active {
-            focused_line_index = Some(lines.len());
+        lines.push(editor_row(theme, "Progress", progress));
+        if let Some(current) = batch.current {
+            lines.push(editor_row(theme, "Current", current.to_string()));
         }
-        lines.push(choice_input_line(
-            "Format",
-            app.output_format,
-            format_active,
+        lines.push(editor_separator(theme));
+    }
+
+    if let Some(video) = &app.selected_video {
+        let mut form = EditorForm {
+            app,
+            focused: focus == Focus::RightTop,
+            lines: std::mem::take(&mut lines),
+            focused_line_index: None,
+        };
+
+        form.push(editor_section(theme, "TIME RANGE"));
+        form.push(input_hint_line(
+            theme,
+            "",
+            "HH:MM:SS.mmm, fN for frame N, or -N from end / +N from start",
         ));
-        if app.video_options_enabled() {
-            if fps_active_cursor.is_some() {
-                focused_line_index = Some(lines.len());
-            }
-            lines.push(input_line("FPS", &app.output_fps, fps_active_cursor));
-            if app.bitrate_enabled() {
-                if bitrate_active_cursor.is_some() {
-                    focused_line_index = Some(lines.len());
-                }
-                lines.push(input_line(
-                    "Bitrate",
-                    &app.output_bitrate_kbps,
-                    bitrate_active_cursor,
-                ));
-            } else {
-                lines.push(disabled_input_line("Bitrate", "n/a for GIF"));
+        form.field(InputField::Start, "Start time", |active| {
+            match app.time_offset_entry(InputField::Start) {
+                Some(entry) => time_offset_line(theme, "Start time", entry),
+                None => time_input_line(
+                    theme,
+                    "Start time",
+                    &app.start_time,
+                    active.then_some(app.start_part),
+                ),
             }
-            if scale_percent_active_cursor.is_some() {
-                focused_line_index = Some(lines.len());
+        });
+        form.field(InputField::End, "End time", |active| {
+            match app.time_offset_entry(InputField::End) {
+                Some(entry) => time_offset_line(theme, "End time", entry),
+                None => time_input_line(
+                    theme,
+                    "End time",
+                    &app.end_time,
+                    active.then_some(app.end_part),
+                ),
             }
-            lines.push(input_line_with_suffix(
+        });
+        let timeline_width = usize::from(area.width.saturating_sub(2))
+            .saturating_sub(INPUT_LABEL_COL_WIDTH + 2 + TIMELINE_SUFFIX_WIDTH)
+            .max(10);
+        form.field(InputField::Timeline, "Timeline", |active| {
+            timeline_line(app, active, timeline_width)
+        });
+        if is_video_file(video) {
+            form.push(editor_row(
+                theme,
+                "Start keys",
+                keyframe_summary(app, InputField::Start),
+            ));
+            form.push(editor_row(
+                theme,
+                "End keys",
+                keyframe_summary(app, InputField::End),
+            ));
+            form.push(editor_row(theme, "Segments", segments_summary(app)));
+        }
+        let playback = if app.range_playback_running() {
+            "playing, Ctrl+r stops"
+        } else {
+            "Ctrl+r plays Start-End"
+        };
+        form.push(editor_row(theme, "Playback", playback.to_string()));
+        form.push(editor_section(theme, "OUTPUT"));
+        form.field(InputField::Format, "Format", |active| {
+            choice_input_line(theme, "Format", app.output_format, active)
+        });
+        form.field(InputField::StreamCopy, "Stream copy", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Stream copy", app.stream_copy, active),
+                "no re-encode, start snaps to a keyframe",
+            )
+        });
+        form.field(InputField::Waveform, "Waveform", |active| {
+            let hint = if app.waveform_style == "off" {
+                "draw the audio as the video"
+            } else {
+                "1280x720, video filters don't apply"
+            };
+            with_hint(
+                theme,
+                choice_input_line(theme, "Waveform", app.waveform_style, active),
+                hint,
+            )
+        });
+        form.field(InputField::Codec, "Codec", |active| {
+            with_hint(
+                theme,
+                choice_input_line(theme, "Codec", app.output_video_codec, active),
+                &preview_video_encoder(app),
+            )
+        });
+        form.field(InputField::Gpu, "GPU encode", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "GPU encode", app.use_gpu_encoding(), active),
+                "NVENC, CPU often looks better at low bitrates",
+            )
+        });
+        form.field(InputField::Fps, "FPS", |active| {
+            input_line(
+                theme,
+                "FPS",
+                &app.output_fps,
+                active.then_some(app.output_fps_cursor),
+            )
+        });
+        form.field(InputField::ConstantFps, "CFR convert", |active| {
+            let hint = if app
+                .selected_video_stats
+                .as_ref()
+                .is_some_and(|stats| stats.variable_frame_rate)
+            {
+                "source has a variable frame rate"
+            } else {
+                "source looks constant"
+            };
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "CFR convert", app.constant_fps, active),
+                hint,
+            )
+        });
+        form.field(InputField::Bitrate, "Bitrate", |active| {
+            input_line(
+                theme,
+                "Bitrate",
+                &app.output_bitrate_kbps,
+                active.then_some(app.output_bitrate_cursor),
+            )
+        });
+        form.field(InputField::TargetSize, "Target size", |active| {
+            input_line_with_suffix(
+                theme,
+                "Target size",
+                app.output_target_size_mb.value(),
+                active.then_some(app.output_target_size_mb.cursor()),
+                &preview_target_size(app),
+            )
+        });
+        form.field(InputField::ScalePercent, "Scale %", |active| {
+            input_line_with_suffix(
+                theme,
                 "Scale %",
                 &app.output_scale_percent,
-                scale_percent_active_cursor,
+                active.then_some(app.output_scale_percent_cursor),
                 &preview_scaled_resolution(app),
-            ));
-            if remove_audio_active {
-                focused_line_index = Some(lines.len());
+            )
+        });
+        form.field(InputField::Crop, "Crop", |active| {
+            input_line_with_suffix(
+                theme,
+                "Crop",
+                app.output_crop.value(),
+                active.then_some(app.output_crop.cursor()),
+                &preview_crop(app),
+            )
+        });
+        form.field(InputField::Deinterlace, "Deinterlace", |active| {
+            let hint = if app
+                .selected_video_stats
+                .as_ref()
+                .is_some_and(|stats| stats.interlaced)
+            {
+                "bwdif, source is interlaced"
+            } else {
+                "bwdif, source looks progressive"
+            };
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Deinterlace", app.deinterlace, active),
+                hint,
+            )
+        });
+        form.field(InputField::Denoise, "Denoise", |active| {
+            let hint = match app.denoise_level {
+                "off" => "",
+                "strong" => "nlmeans, slow",
+                _ => "hqdn3d",
+            };
+            with_hint(
+                theme,
+                choice_input_line(theme, "Denoise", app.denoise_level, active),
+                hint,
+            )
+        });
+        form.field(InputField::Sharpen, "Sharpen", |active| {
+            let hint = if app.sharpen_level == "off" {
+                ""
+            } else {
+                "unsharp, luma only"
+            };
+            with_hint(
+                theme,
+                choice_input_line(theme, "Sharpen", app.sharpen_level, active),
+                hint,
+            )
+        });
+        form.field(InputField::Color, "Color", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Color", app.color_adjust, active),
+                "brightness, contrast, saturation, gamma",
+            )
+        });
+        if app.color_adjust {
+            for (field, label, input, range) in [
+                (
+                    InputField::Brightness,
+                    "Brightness",
+                    &app.color_brightness,
+                    "-1 to 1, 0 = unchanged",
+                ),
+                (
+                    InputField::Contrast,
+                    "Contrast",
+                    &app.color_contrast,
+                    "0 to 3, 1 = unchanged",
+                ),
+                (
+                    InputField::Saturation,
+                    "Saturation",
+                    &app.color_saturation,
+                    "0 to 3, 1 = unchanged",
+                ),
+                (
+                    InputField::Gamma,
+                    "Gamma",
+                    &app.color_gamma,
+                    "0.1 to 10, 1 = unchanged",
+                ),
+            ] {
+                form.field(field, label, |active| {
+                    input_line_with_suffix(
+                        theme,
+                        label,
+                        input.value(),
+                        active.then_some(input.cursor()),
+                        range,
+                    )
+                });
             }
-            lines.push(checkbox_input_line(
-                "Remove audio",
-                app.remove_audio,
-                remove_audio_active,
-            ));
-        } else {
-            lines.push(disabled_input_line("FPS", "n/a for audio-only"));
-            lines.push(disabled_input_line("Bitrate", "n/a for audio-only"));
-            lines.push(disabled_input_line("Scale %", "n/a for audio-only"));
-            lines.push(disabled_input_line("Remove audio", "n/a for audio-only"));
         }
-        if output_active_cursor.is_some() {
-            focused_line_index = Some(lines.len());
+        form.field(InputField::Speed, "Speed", |active| {
+            input_line_with_suffix(
+                theme,
+                "Speed",
+                app.output_speed.value(),
+                active.then_some(app.output_speed.cursor()),
+                "x (0.25-4)",
+            )
+        });
+        form.field(InputField::Caption, "Caption", |active| {
+            input_line_with_suffix(
+                theme,
+                "Caption",
+                app.output_caption.value(),
+                active.then_some(app.output_caption.cursor()),
+                "burned in, empty = none",
+            )
+        });
+        form.field(InputField::CaptionPosition, "Position", |active| {
+            choice_input_line(theme, "Position", app.caption_position, active)
+        });
+        form.field(InputField::CaptionSize, "Font size", |active| {
+            input_line_with_suffix(
+                theme,
+                "Font size",
+                app.caption_size.value(),
+                active.then_some(app.caption_size.cursor()),
+                "px",
+            )
+        });
+        form.field(InputField::CaptionOutline, "Outline", |active| {
+            checkbox_input_line(theme, "Outline", app.caption_outline, active)
+        });
+        form.field(InputField::RemoveAudio, "Remove audio", |active| {
+            checkbox_input_line(theme, "Remove audio", app.remove_audio, active)
+        });
+        form.multi_line_field(
+            InputField::Streams,
+            "Streams",
+            app.stream_cursor,
+            |active| stream_lines(app, active),
+        );
+        form.field(InputField::AudioTrack, "Audio track", |active| {
+            let (value, hint) = audio_track_choice(app);
+            with_hint(
+                theme,
+                choice_input_line(theme, "Audio track", &value, active),
+                &hint,
+            )
+        });
+        form.field(InputField::Channels, "Channels", |active| {
+            with_hint(
+                theme,
+                choice_input_line(theme, "Channels", app.audio_channels, active),
+                "fix a mic recorded on one side",
+            )
+        });
+        form.field(InputField::Normalize, "Normalize", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Normalize", app.normalize_audio, active),
+                "EBU R128 loudnorm to -16 LUFS",
+            )
+        });
+        form.field(InputField::TwoPass, "Two-pass", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Two-pass", app.normalize_two_pass, active),
+                "measure first, then normalize linearly",
+            )
+        });
+        form.field(InputField::GifPalette, "HQ palette", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "HQ palette", app.gif_palette, active),
+                "palettegen + paletteuse, smaller and cleaner",
+            )
+        });
+        form.field(InputField::MaxWidth, "Max width", |active| {
+            input_line_with_suffix(
+                theme,
+                "Max width",
+                app.output_max_width.value(),
+                active.then_some(app.output_max_width.cursor()),
+                "px, empty = no limit",
+            )
+        });
+        form.field(InputField::FrameSequence, "Sequence", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Sequence", app.frame_sequence, active),
+                "numbered frames at FPS, off = one frame at start",
+            )
+        });
+        form.field(InputField::KeepMetadata, "Metadata", |active| {
+            with_hint(
+                theme,
+                checkbox_input_line(theme, "Metadata", app.keep_metadata, active),
+                if app.keep_metadata {
+                    "keep source titles and dates"
+                } else {
+                    "stripped before sharing"
+                },
+            )
+        });
+        form.field(InputField::FileTime, "File time", |active| {
+            let hint = match app.file_time_mode {
+                "source" => "same as the source",
+                "clip start" => "source time + start",
+                _ => "time of export",
+            };
+            with_hint(
+                theme,
+                choice_input_line(theme, "File time", app.file_time_mode, active),
+                hint,
+            )
+        });
+        form.field(InputField::LogLevel, "Log level", |active| {
+            choice_input_line(theme, "Log level", app.ffmpeg_log_level, active)
+        });
+        form.field(InputField::StatsPeriod, "Stats every", |active| {
+            input_line_with_suffix(
+                theme,
+                "Stats every",
+                app.ffmpeg_stats_period.value(),
+                active.then_some(app.ffmpeg_stats_period.cursor()),
+                "seconds",
+            )
+        });
+        form.field(InputField::ProgressOnly, "Progress only", |active| {
+            checkbox_input_line(theme, "Progress only", app.ffmpeg_progress_only, active)
+        });
+        form.field(InputField::ExtraArgs, "Extra args", |active| {
+            input_line_with_suffix(
+                theme,
+                "Extra args",
+                app.ffmpeg_extra_args.value(),
+                active.then_some(app.ffmpeg_extra_args.cursor()),
+                "added before the output, e.g. -tune film",
+            )
+        });
+        form.field(InputField::Output, "Output", |active| {
+            input_line(
+                theme,
+                "Output",
+                &app.output_name,
+                active.then_some(app.output_cursor),
+            )
+        });
+        lines = form.lines;
+        focused_line_index = form.focused_line_index;
+        if app.frame_preview_enabled() {
+            lines.push(editor_separator(theme));
+            lines.push(editor_section(theme, "PREVIEW"));
+            let preview_width = usize::from(area.width.saturating_sub(2)).max(1);
+            lines.extend(frame_preview_lines(app, preview_width));
         }
-        lines.push(input_line("Output", &app.output_name, output_active_cursor));
-        lines.push(editor_separator());
-        lines.push(editor_section("VIDEO DETAILS"));
+        lines.push(editor_separator(theme));
+        lines.push(editor_section(theme, "COMMAND"));
+        let command_width = usize::from(area.width.saturating_sub(2)).max(1);
+        lines.extend(command_preview_lines(app, command_width));
+        lines.push(editor_separator(theme));
+        lines.push(editor_section(theme, "VIDEO DETAILS"));
         let filename = video
             .file_name()
             .map(|name| name.to_string_lossy().into_owned())
             .unwrap_or_else(|| video.display().to_string());
-        lines.push(editor_row("Video", filename));
-        lines.push(editor_row("Path", video.display().to_string()));
+        lines.push(editor_row(theme, "Video", filename));
+        lines.push(editor_row(theme, "Path", video.display().to_string()));
 
         if let Some(stats) = &app.selected_video_stats {
-            lines.push(editor_row("Duration", stats.duration.clone()));
-            lines.push(editor_row("Resolution", stats.resolution.clone()));
-            lines.push(editor_row("FPS", stats.fps.clone()));
-            lines.push(editor_row("Video", stats.video_codec.clone()));
-            lines.push(editor_row("Audio", stats.audio_codec.clone()));
-            lines.push(editor_row("Size", stats.size.clone()));
-            lines.push(editor_row("Bitrate", stats.bitrate.clone()));
+            lines.push(editor_row(theme, "Duration", stats.duration.clone()));
+            lines.push(editor_row(theme, "Resolution", stats.resolution.clone()));
+            lines.push(editor_row(theme, "FPS", stats.fps.clone()));
+            if stats.interlaced {
+                lines.push(editor_row(theme, "Scan", "interlaced".to_string()));
+            }
+            if stats.variable_frame_rate {
+                let warning = editor_row(
+                    theme,
+                    "Frame rate",
+                    "variable, may drift out of sync in other editors".to_string(),
+                );
+                lines.push(warning.patch_style(Style::default().fg(theme.warning)));
+            }
+            lines.push(editor_row(theme, "Video", stats.video_codec.clone()));
+            lines.push(editor_row(theme, "Audio", stats.audio_codec.clone()));
+            lines.push(editor_row(theme, "Size", stats.size.clone()));
+            lines.push(editor_row(theme, "Bitrate", stats.bitrate.clone()));
         } else {
-            lines.push(editor_row("Stats", "unavailable".to_string()));
+            lines.push(editor_row(theme, "Stats", "unavailable".to_string()));
         }
+
+        lines.push(editor_separator(theme));
+        lines.push(editor_section(theme, "BITRATE OVER TIME"));
+        let graph_width = usize::from(area.width.saturating_sub(2)).max(1);
+        lines.extend(bitrate_lines(app, graph_width));
     } else {
-        lines.push(editor_section("NO VIDEO SELECTED"));
+        lines.push(editor_section(theme, "NO VIDEO SELECTED"));
         lines.push(Line::from(""));
         lines.push(Line::from(
             "Select a video in the left pane and press Enter.",
         ));
+        lines.push(Line::from(
+            "Or press Ctrl+g to trim an http(s)/rtmp stream without downloading it.",
+        ));
         lines.push(Line::from(
             "Supported: mp4, mov, mkv, avi, webm, m4v, mpeg, mpg, wmv, flv",
         ));
     }
 
     let panel = Block::default()
-        .borders(Borders::ALL)
+        .borders(pane_borders(app))
         .border_style(pane_border_style(
+            theme,
             focus == Focus::RightTop,
-            Color::LightYellow,
+            theme.panel,
         ))
         .title_top(Line::from("Editor").left_aligned())
         .title_top(
-            Line::styled("(Up/Down scroll)", Style::default().fg(Color::DarkGray)).right_aligned(),
+            Line::styled("(Up/Down scroll)", Style::default().fg(theme.dim)).right_aligned(),
         );
     let inner = panel.inner(area);
     let visible_line_count = inner.height as usize;
@@ -216,51 +582,394 @@ fn render_editor_pane(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
     frame.render_widget(details, area);
 }
 
-fn render_ffmpeg_output_pane(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+/// Accumulates editor form rows and remembers which row holds the focused
+/// field so the pane can keep it scrolled into view.
+struct EditorForm<'a> {
+    app: &'a App,
+    focused: bool,
+    lines: Vec<Line<'static>>,
+    focused_line_index: Option<usize>,
+}
+
+impl EditorForm<'_> {
+    fn push(&mut self, line: Line<'static>) {
+        self.lines.push(line);
+    }
+
+    /// Pushes the row for `field`, or a greyed-out placeholder with the
+    /// reason when the field doesn't apply to the current form state.
+    fn field(&mut self, field: InputField, label: &str, build: impl FnOnce(bool) -> Line<'static>) {
+        if let Some(reason) = self.app.editor_input_disabled_reason(field) {
+            self.lines
+                .push(disabled_input_line(self.app.theme(), label, reason));
+            return;
+        }
+
+        let active = self.focused && self.app.active_input == field;
+        if active {
+            self.focused_line_index = Some(self.lines.len());
+        }
+        let mut line = build(active);
+        if active {
+            line.spans.insert(0, Span::raw(selection_symbol(self.app)));
+        }
+        self.lines.push(line);
+    }
+
+    /// Like `field`, for a field spanning several rows; `active_row` is the
+    /// row kept in view while it has focus.
+    fn multi_line_field(
+        &mut self,
+        field: InputField,
+        label: &str,
+        active_row: usize,
+        build: impl FnOnce(bool) -> Vec<Line<'static>>,
+    ) {
+        if let Some(reason) = self.app.editor_input_disabled_reason(field) {
+            self.lines
+                .push(disabled_input_line(self.app.theme(), label, reason));
+            return;
+        }
+
+        let active = self.focused && self.app.active_input == field;
+        if active {
+            self.focused_line_index = Some(self.lines.len() + active_row);
+        }
+        let mut lines = build(active);
+        if active && let Some(line) = lines.get_mut(active_row) {
+            line.spans.insert(0, Span::raw(selection_symbol(self.app)));
+        }
+        self.lines.extend(lines);
+    }
+}
+
+fn audio_track_choice(app: &App) -> (String, String) {
+    match app.audio_track {
+        AudioTrack::Auto if app.video_options_enabled() => {
+            ("auto".to_string(), "ticked streams".to_string())
+        }
+        AudioTrack::Auto => ("auto".to_string(), "first track".to_string()),
+        AudioTrack::Track(index) => (
+            format!("track {}", index + 1),
+            app.audio_streams()
+                .get(index)
+                .map(|stream| stream.label())
+                .unwrap_or_default(),
+        ),
+        AudioTrack::Mix => (
+            "mix all".to_string(),
+            "amerge every track into stereo".to_string(),
+        ),
+    }
+}
+
+/// One checkbox row per probed stream; Tab walks the rows, Space toggles.
+fn stream_lines(app: &App, active: bool) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    app.media_streams
+        .iter()
+        .zip(&app.stream_selected)
+        .enumerate()
+        .map(|(row, (stream, selected))| {
+            let label = if row == 0 { "Streams" } else { "" };
+            let row_active = active && row == app.stream_cursor;
+            let mut line = checkbox_input_line(theme, label, *selected, row_active);
+            line.spans.push(Span::raw(" "));
+            line.spans.push(Span::styled(
+                stream.label(),
+                input_value_style(theme, row_active),
+            ));
+            let audio_note = if stream.kind != StreamKind::Audio {
+                None
+            } else if app.remove_audio {
+                Some("  (audio removed)")
+            } else if app.audio_track != AudioTrack::Auto
+                && app.editor_input_enabled(InputField::AudioTrack)
+            {
+                Some("  (set by Audio track)")
+            } else {
+                None
+            };
+            if let Some(note) = audio_note {
+                line.spans
+                    .push(Span::styled(note, Style::default().fg(theme.dim)));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Keyframes around the start or end time; Ctrl+s snaps the focused one.
+fn keyframe_summary(app: &App, field: InputField) -> String {
+    let hint = if field == InputField::End {
+        app.end_keyframe_hint()
+    } else {
+        app.keyframe_hint()
+    };
+    let Some(hint) = hint else {
+        return "scanning...".to_string();
+    };
+    if hint.keyframes.is_none() {
+        return "scan failed".to_string();
+    }
+    let format_keyframe = |keyframe: Option<f64>| match keyframe {
+        Some(seconds) => format_seconds_millis(seconds),
+        None => "none".to_string(),
+    };
+    let marker = if hint.on_keyframe() {
+        "on keyframe"
+    } else {
+        "between keyframes"
+    };
+    format!(
+        "{marker}: prev {} | next {} (Ctrl+s snaps)",
+        format_keyframe(hint.previous()),
+        format_keyframe(hint.next())
+    )
+}
+
+/// Listed segments and their joined length, or how to start a list.
+fn segments_summary(app: &App) -> String {
+    let segments = app.segments();
+    if segments.is_empty() {
+        return "none (Ctrl+a adds Start-End to join several ranges)".to_string();
+    }
+    let timestamp = |seconds: f64| TimeInput::from_seconds(seconds).to_ffmpeg_timestamp();
+    let listed = segments
+        .iter()
+        .enumerate()
+        .map(|(index, (start, end))| {
+            format!("{}: {}-{}", index + 1, timestamp(*start), timestamp(*end))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let total = segments.iter().map(|(start, end)| end - start).sum::<f64>();
+    format!(
+        "{listed} | total {} (Ctrl+x removes last)",
+        timestamp(total)
+    )
+}
+
+fn format_seconds_millis(seconds: f64) -> String {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let millis = total_millis % 1000;
+    let total_seconds = total_millis / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{millis:03}",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60
+    )
+}
+
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The command Enter would run, wrapped by hand so the scroll math, which
+/// counts lines, stays right.
+fn command_preview_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    let commands = match app.editor_command_preview() {
+        Ok(commands) => commands,
+        Err(message) => {
+            return vec![Line::styled(message, Style::default().fg(theme.warning))];
+        }
+    };
+    let style = Style::default().fg(theme.dim);
+    let two_pass = commands.len() > 1;
+    let mut lines = Vec::new();
+    for (index, command) in commands.iter().enumerate() {
+        if two_pass {
+            lines.push(Line::styled(format!("pass {}:", index + 1), style));
+        }
+        lines.extend(
+            wrap_words(command, width)
+                .into_iter()
+                .map(|line| Line::styled(line, style)),
+        );
+    }
+    lines.push(Line::styled("Ctrl+y copies it.".to_string(), style));
+    lines
+}
+
+/// Greedy wrap at spaces; words longer than `width` are split.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for word in text.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        if current_width > 0 && current_width + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        if current_width > 0 {
+            current.push(' ');
+            current_width += 1;
+        }
+        while current_width + word.len() > width {
+            let split = width - current_width;
+            current.extend(word.drain(..split));
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current_width += word.len();
+        current.extend(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Draws the preview with `▀`: the top pixel is the foreground and the
+/// bottom pixel the background of each cell.
+fn frame_preview_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    let Some(preview) = app.frame_preview() else {
+        return vec![editor_row(theme, "Frame", "rendering...".to_string())];
+    };
+    let time = TimeInput::from_seconds(preview.time_seconds()).to_ffmpeg_timestamp();
+    let status = if app.frame_preview_pending() {
+        format!("{time} (updating...)")
+    } else {
+        time
+    };
+    if let Err(err) = &preview.rgb {
+        return vec![editor_row(theme, "Frame", format!("{status}: {err}"))];
+    }
+
+    let columns = (preview.width as usize).min(width) as u32;
+    let mut lines = (0..preview.height / 2)
+        .map(|row| {
+            let spans = (0..columns)
+                .map(|x| {
+                    let color = |y| {
+                        preview
+                            .pixel(x, y)
+                            .map(|(r, g, b)| Color::Rgb(r, g, b))
+                            .unwrap_or(Color::Reset)
+                    };
+                    Span::styled(
+                        "▀",
+                        Style::default().fg(color(row * 2)).bg(color(row * 2 + 1)),
+                    )
+                })
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+    lines.push(editor_row(theme, "Frame", status));
+    lines
+}
+
+fn bitrate_lines(app: &App, width: usize) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    if app.bitrate_analysis_running() {
+        return vec![editor_row(
+            theme,
+            "Graph",
+            "analyzing packets...".to_string(),
+        )];
+    }
+    let Some(graph) = app.bitrate_graph() else {
+        return vec![editor_row(theme, "Graph", "Ctrl+b to analyze".to_string())];
+    };
+
+    let mut lines = vec![Line::styled(
+        bitrate_sparkline(&graph.kbps_per_second, width),
+        Style::default().fg(theme.value),
+    )];
+    lines.push(editor_row(
+        theme,
+        "Average",
+        format!("{} kbps", graph.average_kbps()),
+    ));
+    if let Some((second, kbps)) = graph.peak() {
+        lines.push(editor_row(
+            theme,
+            "Peak",
+            format!(
+                "{kbps} kbps at {}",
+                TimeInput::from_seconds(second as f64).to_ffmpeg_timestamp()
+            ),
+        ));
+    }
+    lines
+}
+
+/// Squeezes the per-second series into `width` columns. Each column shows
+/// the highest second it covers so short spikes stay visible.
+fn bitrate_sparkline(kbps_per_second: &[u32], width: usize) -> String {
+    let columns = width.min(kbps_per_second.len()).max(1);
+    let peaks = (0..columns)
+        .map(|column| {
+            let start = column * kbps_per_second.len() / columns;
+            let end = ((column + 1) * kbps_per_second.len() / columns).max(start + 1);
+            kbps_per_second[start..end.min(kbps_per_second.len())]
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let max = peaks.iter().copied().max().unwrap_or(0).max(1);
+    peaks
+        .iter()
+        .map(|kbps| {
+            let level = (*kbps as usize * (SPARKLINE_LEVELS.len() - 1)) / max as usize;
+            SPARKLINE_LEVELS[level]
+        })
+        .collect()
+}
+
+pub(super) fn render_ffmpeg_output_pane(frame: &mut Frame, app: &App, focus: Focus, area: Rect) {
+    let theme = app.theme();
     let title = "TOOL OUTPUT";
     let visible_line_count = area.height.saturating_sub(2).max(1) as usize;
 
     render_log_panel(
         frame,
         area,
+        theme,
         LogPanelStateView {
             title,
             lines: app.ffmpeg_output_lines(),
             scroll: app.clamped_ffmpeg_output_scroll(visible_line_count),
             focused: focus == Focus::RightBottom,
-            accent_color: Color::LightMagenta,
+            accent_color: theme.input_label,
             trim_wrapped_lines: false,
             title_hint_right: Some("(press x to cancel)"),
+            borders: pane_borders(app),
         },
     );
 }
 
-fn editor_section(title: &str) -> Line<'static> {
+fn editor_section(theme: &Theme, title: &str) -> Line<'static> {
     Line::styled(
         title.to_string(),
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.heading)
             .add_modifier(Modifier::BOLD),
     )
 }
 
-fn ffmpeg_warning_line(message: &str) -> Line<'static> {
+fn ffmpeg_warning_line(theme: &Theme, message: &str) -> Line<'static> {
     Line::styled(
         message.to_string(),
         Style::default()
-            .fg(Color::LightRed)
+            .fg(theme.error)
             .add_modifier(Modifier::BOLD),
     )
 }
 
-fn editor_separator() -> Line<'static> {
+fn editor_separator(theme: &Theme) -> Line<'static> {
     Line::styled(
         "------------------------------------------------".to_string(),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.dim),
     )
 }
 
-fn editor_row(label: &str, value: String) -> Line<'static> {
+fn editor_row(theme: &Theme, label: &str, value: String) -> Line<'static> {
     const LABEL_COL_WIDTH: usize = 10;
     const VALUE_MAX_CHARS: usize = 64;
     let label_cell = format!("{label:<LABEL_COL_WIDTH$}");
@@ -268,7 +977,7 @@ fn editor_row(label: &str, value: String) -> Line<'static> {
         Span::styled(
             label_cell,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.label)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("  "),
@@ -276,17 +985,22 @@ fn editor_row(label: &str, value: String) -> Line<'static> {
     ])
 }
 
-fn input_line(label: &str, value: &str, active_cursor: Option<usize>) -> Line<'static> {
+fn input_line(
+    theme: &Theme,
+    label: &str,
+    value: &str,
+    active_cursor: Option<usize>,
+) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     let active = active_cursor.is_some();
-    let value_style = input_value_style(active);
+    let value_style = input_value_style(theme, active);
     let cursor_style = Style::default()
-        .fg(Color::Black)
-        .bg(Color::White)
+        .fg(theme.on_highlight)
+        .bg(theme.cursor)
         .add_modifier(Modifier::BOLD);
 
     let mut spans = vec![
-        Span::styled(label_cell, input_label_style(active)),
+        Span::styled(label_cell, input_label_style(theme, active)),
         Span::raw("  "),
     ];
 
@@ -310,123 +1024,208 @@ fn input_line(label: &str, value: &str, active_cursor: Option<usize>) -> Line<'s
 }
 
 fn input_line_with_suffix(
+    theme: &Theme,
     label: &str,
     value: &str,
     active_cursor: Option<usize>,
     suffix: &str,
 ) -> Line<'static> {
-    let mut line = input_line(label, value, active_cursor);
+    let mut line = input_line(theme, label, value, active_cursor);
     if !suffix.is_empty() {
         line.spans.push(Span::raw("  "));
         line.spans.push(Span::styled(
             suffix.to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.dim),
         ));
     }
     line
 }
 
-fn input_hint_line(label: &str, value: &str) -> Line<'static> {
+fn with_hint(theme: &Theme, mut line: Line<'static>, hint: &str) -> Line<'static> {
+    line.spans.push(Span::raw("  "));
+    line.spans.push(Span::styled(
+        hint.to_string(),
+        Style::default().fg(theme.dim),
+    ));
+    line
+}
+
+fn input_hint_line(theme: &Theme, label: &str, value: &str) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     Line::from(vec![
         Span::styled(
             label_cell,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::raw("  "),
-        Span::styled(value.to_string(), Style::default().fg(Color::DarkGray)),
+        Span::styled(value.to_string(), Style::default().fg(theme.dim)),
     ])
 }
 
-fn choice_input_line(label: &str, value: &str, active: bool) -> Line<'static> {
+fn choice_input_line(theme: &Theme, label: &str, value: &str, active: bool) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
 
     Line::from(vec![
-        Span::styled(label_cell, input_label_style(active)),
+        Span::styled(label_cell, input_label_style(theme, active)),
         Span::raw("  "),
-        Span::styled(value.to_string(), input_value_style(active)),
+        Span::styled(value.to_string(), input_value_style(theme, active)),
     ])
 }
 
-fn disabled_input_line(label: &str, value: &str) -> Line<'static> {
+fn disabled_input_line(theme: &Theme, label: &str, value: &str) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     Line::from(vec![
         Span::styled(
             label_cell,
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::raw("  "),
-        Span::styled(value.to_string(), Style::default().fg(Color::DarkGray)),
+        Span::styled(value.to_string(), Style::default().fg(theme.dim)),
     ])
 }
 
-fn checkbox_input_line(label: &str, checked: bool, active: bool) -> Line<'static> {
+fn checkbox_input_line(theme: &Theme, label: &str, checked: bool, active: bool) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     let mark = if checked { "[x]" } else { "[ ]" };
 
     Line::from(vec![
-        Span::styled(label_cell, input_label_style(active)),
+        Span::styled(label_cell, input_label_style(theme, active)),
         Span::raw("  "),
-        Span::styled(mark.to_string(), input_value_style(active)),
+        Span::styled(mark.to_string(), input_value_style(theme, active)),
     ])
 }
 
-fn time_input_line(label: &str, value: &TimeInput, active_part: Option<usize>) -> Line<'static> {
+fn time_input_line(
+    theme: &Theme,
+    label: &str,
+    value: &TimeInput,
+    active_part: Option<usize>,
+) -> Line<'static> {
     let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
     let mut spans = vec![
-        Span::styled(label_cell, input_label_style(false)),
+        Span::styled(label_cell, input_label_style(theme, false)),
         Span::raw("  "),
     ];
 
-    for part in 0..3 {
+    for part in 0..4 {
         spans.push(Span::styled(
             value.part(part).to_string(),
-            time_part_style(active_part == Some(part)),
+            time_part_style(theme, active_part == Some(part)),
+        ));
+        let separator = match part {
+            0 | 1 => ":",
+            2 => ".",
+            _ => continue,
+        };
+        spans.push(Span::styled(
+            separator.to_string(),
+            input_value_style(theme, false),
         ));
-        if part < 2 {
-            spans.push(Span::styled(":".to_string(), input_value_style(false)));
-        }
     }
 
     Line::from(spans)
 }
 
-fn input_label_style(active: bool) -> Style {
+/// The file's duration as a bar of `width` cells with `[` and `]` at the
+/// start and end times. The marker Left/Right moves is highlighted while
+/// the field has focus.
+fn timeline_line(app: &App, active: bool, width: usize) -> Line<'static> {
+    let theme = app.theme();
+    let label_cell = format!("{:<INPUT_LABEL_COL_WIDTH$}", "Timeline");
+    let mut spans = vec![
+        Span::styled(label_cell, input_label_style(theme, active)),
+        Span::raw("  "),
+    ];
+    let Some((first, last)) = app.timeline_bounds() else {
+        return Line::from(spans);
+    };
+    let start = app.start_time.to_seconds_f64();
+    let end = app.end_time.to_seconds_f64().min(last);
+    let column = |seconds: f64| {
+        let fraction = ((seconds - first) / (last - first)).clamp(0.0, 1.0);
+        (fraction * (width - 1) as f64).round() as usize
+    };
+    let (start_column, end_column) = (column(start), column(end));
+    let marker_style = |marker| {
+        if active && app.timeline_marker() == marker {
+            Style::default()
+                .fg(theme.on_highlight)
+                .bg(theme.heading)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(theme.heading)
+                .add_modifier(Modifier::BOLD)
+        }
+    };
+
+    for index in 0..width {
+        let (cell, style) = if index == start_column {
+            ("[", marker_style(TimelineMarker::Start))
+        } else if index == end_column {
+            ("]", marker_style(TimelineMarker::End))
+        } else if index > start_column && index < end_column {
+            ("━", Style::default().fg(theme.value))
+        } else {
+            ("─", Style::default().fg(theme.dim))
+        };
+        spans.push(Span::styled(cell, style));
+    }
+    spans.push(Span::styled(
+        format!(
+            "  {}",
+            TimeInput::from_seconds((end - start).max(0.0)).to_ffmpeg_timestamp()
+        ),
+        Style::default().fg(theme.dim),
+    ));
+    Line::from(spans)
+}
+
+fn time_offset_line(theme: &Theme, label: &str, entry: &str) -> Line<'static> {
+    let label_cell = format!("{label:<INPUT_LABEL_COL_WIDTH$}");
+    with_hint(
+        theme,
+        Line::from(vec![
+            Span::styled(label_cell, input_label_style(theme, false)),
+            Span::raw("  "),
+            Span::styled(entry.to_string(), time_part_style(theme, true)),
+        ]),
+        "Tab applies, Esc cancels",
+    )
+}
+
+fn input_label_style(theme: &Theme, active: bool) -> Style {
     if active {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
+            .fg(theme.on_highlight)
+            .bg(theme.selection)
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
-            .fg(Color::LightMagenta)
+            .fg(theme.input_label)
             .add_modifier(Modifier::BOLD)
     }
 }
 
-fn input_value_style(active: bool) -> Style {
+fn input_value_style(theme: &Theme, active: bool) -> Style {
     if active {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
+            .fg(theme.on_highlight)
+            .bg(theme.selection)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     }
 }
 
-fn time_part_style(active: bool) -> Style {
+fn time_part_style(theme: &Theme, active: bool) -> Style {
     if active {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
+            .fg(theme.on_highlight)
+            .bg(theme.selection)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     }
 }
 
@@ -452,11 +1251,48 @@ fn truncate_tail(value: &str, max_chars: usize) -> String {
     format!("…{tail}")
 }
 
+fn preview_video_encoder(app: &App) -> String {
+    let (encoder, fell_back) = app.resolved_video_encoder();
+    if !codec_fits_container(app.output_video_codec, app.output_format) {
+        format!("not supported in {}, use mp4 or mkv", app.output_format)
+    } else if fell_back {
+        format!("unavailable, falls back to {encoder}")
+    } else {
+        format!("-> {encoder}")
+    }
+}
+
+fn source_resolution(app: &App) -> Option<(u32, u32)> {
+    app.selected_video_stats
+        .as_ref()
+        .and_then(|stats| stats.width.zip(stats.height))
+}
+
+fn preview_crop(app: &App) -> String {
+    match parse_crop_input(app.output_crop.value(), source_resolution(app)) {
+        Ok(Some(crop)) => format!("{}x{} at {},{}", crop.width, crop.height, crop.x, crop.y),
+        Ok(None) => "off (W:H aspect or W:H:X:Y)".to_string(),
+        Err(message) => message,
+    }
+}
+
+fn preview_target_size(app: &App) -> String {
+    if !app.target_size_set() {
+        return "MB, empty = use Bitrate".to_string();
+    }
+    match app.target_size_video_kbps() {
+        Some(kbps) => format!("MB -> {kbps} kbps video, approximate"),
+        None => "MB, too small for this clip".to_string(),
+    }
+}
+
 fn preview_scaled_resolution(app: &App) -> String {
-    let Some(stats) = app.selected_video_stats.as_ref() else {
-        return "n/a".to_string();
-    };
-    let (Some(width), Some(height)) = (stats.width, stats.height) else {
+    // Scale applies to the cropped frame when a valid crop is set.
+    let cropped = parse_crop_input(app.output_crop.value(), source_resolution(app))
+        .ok()
+        .flatten()
+        .map(|crop| (crop.width, crop.height));
+    let Some((width, height)) = cropped.or_else(|| source_resolution(app)) else {
         return "n/a".to_string();
     };
     let Some(percent) = parse_scale_percent_for_preview(&app.output_scale_percent) else {
@@ -1,5 +1,6 @@
 // Right-column tab module registry.
 // - Each tab module owns only its own rendering behavior.
 // - Keeping tabs separate makes it easier to add new tools over time.
+pub mod concat;
 pub mod downloader;
 pub mod editor;